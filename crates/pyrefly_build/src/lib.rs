@@ -30,6 +30,7 @@ use std::sync::Arc;
 use std::sync::LazyLock;
 
 use dupe::Dupe as _;
+use pyrefly_python::sys_info::SysInfo;
 use pyrefly_util::absolutize::Absolutize as _;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::arc_id::WeakArcId;
@@ -48,6 +49,8 @@ use tracing::info;
 use which::which;
 
 use crate::query::SourceDbQuerier;
+pub use crate::query::bazel::BazelArgs;
+use crate::query::bazel::BazelQuerier;
 use crate::query::buck::BxlArgs;
 use crate::query::buck::BxlQuerier;
 use crate::query::custom::CustomQuerier;
@@ -60,7 +63,7 @@ use crate::source_db::query_source_db::QuerySourceDatabase;
 static BUILD_SYSTEM_CACHE: LazyLock<
     Mutex<
         SmallMap<
-            (PathBuf, BuildSystemArgs, Vec<Target>, bool),
+            (PathBuf, BuildSystemArgs, Vec<Target>, bool, Option<SysInfo>),
             WeakArcId<Box<dyn source_db::SourceDatabase + 'static>>,
         >,
     >,
@@ -69,6 +72,7 @@ static BUILD_SYSTEM_CACHE: LazyLock<
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "kebab-case", tag = "type")]
 pub enum BuildSystemArgs {
+    Bazel(BazelArgs),
     Buck(BxlArgs),
     Custom(CustomQueryArgs),
 }
@@ -78,6 +82,7 @@ impl BuildSystemArgs {
         #[cfg(not(target_arch = "wasm32"))]
         {
             let cmd = match self {
+                Self::Bazel(args) => args.program(),
                 Self::Buck(_) => "buck2",
                 Self::Custom(args) => args.command.first(),
             };
@@ -89,6 +94,7 @@ impl BuildSystemArgs {
 
     fn get_repo_root(&self, cwd: &Path) -> anyhow::Result<PathBuf> {
         match self {
+            Self::Bazel(args) => args.get_repo_root(cwd),
             Self::Buck(args) => args.get_repo_root(cwd),
             Self::Custom(args) => args.get_repo_root(cwd),
         }
@@ -98,6 +104,7 @@ impl BuildSystemArgs {
 impl Display for BuildSystemArgs {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Bazel(args) => write!(f, "Bazel({})", args),
             Self::Buck(args) => write!(f, "Buck({})", args),
             Self::Custom(args) => write!(f, "Custom({})", args),
         }
@@ -145,9 +152,12 @@ impl BuildSystem {
         }
     }
 
+    /// The source database for this build system. Build systems that don't report the Python
+    /// version and platform of their targets use `sys_info`, the config's.
     pub fn get_source_db(
         &mut self,
         config_root: PathBuf,
+        sys_info: SysInfo,
     ) -> Option<anyhow::Result<ArcId<Box<dyn source_db::SourceDatabase + 'static>>>> {
         let build_system_available = self.args.is_build_system_available();
         if !build_system_available {
@@ -175,6 +185,8 @@ impl BuildSystem {
             self.args.clone(),
             self.catch_all_targets.clone(),
             self.catch_all_targets_only,
+            // Only Bazel manifests take their `SysInfo` from the config.
+            matches!(self.args, BuildSystemArgs::Bazel(_)).then_some(sys_info),
         );
         if let Some(maybe_result) = cache.get(&key)
             && let Some(result) = maybe_result.upgrade()
@@ -189,6 +201,7 @@ impl BuildSystem {
         );
 
         let querier: Arc<dyn SourceDbQuerier> = match &self.args {
+            BuildSystemArgs::Bazel(args) => Arc::new(BazelQuerier::new(args.clone(), sys_info)),
             BuildSystemArgs::Buck(args) => Arc::new(BxlQuerier::new(args.clone())),
             BuildSystemArgs::Custom(args) => Arc::new(CustomQuerier::new(args.clone())),
         };
@@ -229,7 +242,9 @@ mod tests {
 
         let root = Path::new("/root");
 
-        bs.get_source_db(root.to_path_buf()).unwrap().unwrap();
+        bs.get_source_db(root.to_path_buf(), SysInfo::default())
+            .unwrap()
+            .unwrap();
         assert_eq!(
             &bs.search_path_prefix,
             &[
@@ -237,7 +252,9 @@ mod tests {
                 PathBuf::from("/absolute/path/to/project")
             ]
         );
-        bs2.get_source_db(root.to_path_buf()).unwrap().unwrap();
+        bs2.get_source_db(root.to_path_buf(), SysInfo::default())
+            .unwrap()
+            .unwrap();
         assert_eq!(
             &bs2.search_path_prefix,
             &[
@@ -248,7 +265,9 @@ mod tests {
 
         // double check that configuring twice doesn't corrupt path, even though it should
         // never be called twice
-        bs2.get_source_db(root.to_path_buf()).unwrap().unwrap();
+        bs2.get_source_db(root.to_path_buf(), SysInfo::default())
+            .unwrap()
+            .unwrap();
         assert_eq!(
             &bs2.search_path_prefix,
             &[
@@ -272,7 +291,9 @@ mod tests {
         };
         let root = Path::new("/root");
 
-        bs.get_source_db(root.to_path_buf()).unwrap().unwrap_err();
+        bs.get_source_db(root.to_path_buf(), SysInfo::default())
+            .unwrap()
+            .unwrap_err();
 
         let mut bs = BuildSystem {
             args: BuildSystemArgs::Custom(CustomQueryArgs {
@@ -284,6 +305,9 @@ mod tests {
             catch_all_targets: vec![],
             catch_all_targets_only: false,
         };
-        assert!(bs.get_source_db(root.to_path_buf()).is_none());
+        assert!(
+            bs.get_source_db(root.to_path_buf(), SysInfo::default())
+                .is_none()
+        );
     }
 }
@@ -36,6 +36,7 @@ use vec1::vec1;
 
 use crate::source_db::Target;
 
+pub mod bazel;
 pub mod buck;
 pub mod custom;
 
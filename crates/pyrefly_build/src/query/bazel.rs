@@ -0,0 +1,717 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A querier that builds the source DB from `bazel cquery`.
+//!
+//! Files are mapped to the `py_library`, `py_binary` and `py_test` rules in their own package that
+//! list them as sources, and we query those rules and their transitive dependencies with
+//! `--output=jsonproto`. Each rule becomes a [`PythonLibraryManifest`], with the module names of
+//! its sources relative to the longest matching `imports` entry, or the workspace root otherwise.
+//! Only rules in the main repository are included: external repositories (like pip hubs) are
+//! left to the site package path. Bazel doesn't tell us which Python version a rule targets, so
+//! every manifest uses the `SysInfo` of the config the querier was created for.
+//!
+//! Results are cached per target, and a target is queried again once the `BUILD` file it was
+//! read from, or a main repository `.bzl` file it loads, changes.
+
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use itertools::Itertools as _;
+use pyrefly_python::PYTHON_EXTENSIONS;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::sys_info::SysInfo;
+use pyrefly_util::absolutize::Absolutize as _;
+use pyrefly_util::interned_path::InternedPath;
+use pyrefly_util::lock::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+use vec1::Vec1;
+use vec1::vec1;
+
+use crate::query::Include;
+use crate::query::PythonLibraryManifest;
+use crate::query::QueryResult;
+use crate::query::SourceDbQuerier;
+use crate::query::TargetManifest;
+use crate::query::TargetManifestDatabase;
+use crate::source_db::Target;
+
+const BUILD_FILE_NAMES: &[&str] = &["BUILD.bazel", "BUILD"];
+
+/// The rule kinds we read Python sources from, as a `kind()` query pattern.
+const PYTHON_RULE_KINDS: &str = "^py_(library|binary|test) rule$";
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub struct BazelArgs {
+    /// The command used to run Bazel, e.g. `["bazelisk"]`. Defaults to `["bazel"]`.
+    #[serde(default)]
+    pub command: Option<Vec1<String>>,
+    /// Extra flags passed to `bazel cquery`, e.g. `--config=linux`.
+    #[serde(default)]
+    pub extras: Option<Vec<String>>,
+    /// The root of the Bazel workspace. If unset, we ask `bazel info workspace`.
+    #[serde(default)]
+    pub repo_root: Option<PathBuf>,
+}
+
+impl BazelArgs {
+    /// The Bazel executable to run.
+    pub fn program(&self) -> &str {
+        self.command.as_ref().map_or("bazel", |c| c.first())
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(self.program());
+        if let Some(command) = &self.command {
+            cmd.args(command.iter().skip(1));
+        }
+        cmd
+    }
+
+    pub fn get_repo_root(&self, cwd: &Path) -> anyhow::Result<PathBuf> {
+        if let Some(repo_root) = &self.repo_root {
+            return Ok(repo_root.clone());
+        }
+        let output = self
+            .command()
+            .arg("info")
+            .arg("workspace")
+            .current_dir(cwd)
+            .output()
+            .context("Querying for Bazel workspace root")?;
+        let stdout = String::from_utf8(output.stdout).with_context(|| {
+            let stderr =
+                String::from_utf8(output.stderr).unwrap_or("<Could not decode STDERR>".to_owned());
+            format!("Failed to parse stdout while querying Bazel workspace root, STDERR: {stderr}")
+        })?;
+        Ok(PathBuf::from(stdout.trim()))
+    }
+}
+
+impl Display for BazelArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "command: {:?}, extras: {:?}, repo_root: {:?}",
+            self.command, self.extras, self.repo_root
+        )
+    }
+}
+
+/// The identity of a file when we read it, so we can tell when it changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: Option<u64>,
+}
+
+impl FileStamp {
+    fn new(path: PathBuf) -> Self {
+        let metadata = fs::metadata(&path).ok();
+        Self {
+            modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+            len: metadata.map(|m| m.len()),
+            path,
+        }
+    }
+}
+
+/// The identity of a `BUILD` file and the `.bzl` files it loads when we read targets from it,
+/// so we can tell when any of them changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stamp {
+    files: Vec<FileStamp>,
+}
+
+impl Stamp {
+    fn new(root: &Path, buildfile: PathBuf) -> Self {
+        let mut files = Vec::new();
+        let mut seen = SmallSet::new();
+        let mut stack = vec![buildfile];
+        while let Some(file) = stack.pop() {
+            if !seen.insert(file.clone()) {
+                continue;
+            }
+            stack.extend(loads(root, &file));
+            files.push(FileStamp::new(file));
+        }
+        Self { files }
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.files
+            .iter()
+            .all(|file| FileStamp::new(file.path.clone()) == *file)
+    }
+}
+
+/// The `.bzl` files in the main repository that `file` loads, e.g. `<root>/tools/defs.bzl` for
+/// `load("//tools:defs.bzl", "py_lib")`, or `<package>/defs.bzl` for `load(":defs.bzl", ...)`.
+fn loads(root: &Path, file: &Path) -> Vec<PathBuf> {
+    let Ok(contents) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    contents
+        .match_indices("load(")
+        .filter(|(i, _)| {
+            !contents[..*i]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        })
+        .filter_map(|(i, call)| {
+            let rest = contents[i + call.len()..].trim_start();
+            let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+            let label = rest[1..].split(quote).next()?;
+            match label.strip_prefix(':') {
+                Some(name) => Some(file.parent()?.join(name)),
+                None => {
+                    let (package, name) = split_label(label)?;
+                    Some(root.join(package).join(name))
+                }
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct CachedTarget {
+    manifest: PythonLibraryManifest,
+    stamp: Stamp,
+}
+
+#[derive(Debug)]
+struct CachedOwners {
+    targets: Vec<Target>,
+    stamp: Stamp,
+}
+
+#[derive(Debug, Default)]
+struct BazelCache {
+    /// Libraries by label.
+    targets: SmallMap<Target, CachedTarget>,
+    /// For each queried file, the libraries in its package that list it as a source.
+    owners: SmallMap<InternedPath, CachedOwners>,
+}
+
+/// The package and name of a label in the main repository, e.g. `("a/b", "c.py")` for
+/// `//a/b:c.py`, `@//a/b:c.py` or `@@//a/b:c.py`.
+fn split_label(label: &str) -> Option<(&str, &str)> {
+    let label = label.trim_start_matches('@').strip_prefix("//")?;
+    match label.split_once(':') {
+        Some(x) => Some(x),
+        None => Some((label, label.rsplit('/').next()?)),
+    }
+}
+
+/// The label of a source file, and the `BUILD` file of the package containing it.
+fn source_label(root: &Path, file: &Path) -> Option<(String, Stamp)> {
+    let (package_dir, buildfile) = file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .find_map(|dir| {
+            BUILD_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|buildfile| buildfile.is_file())
+                .map(|buildfile| (dir, buildfile))
+        })?;
+    let package = package_dir.strip_prefix(root).ok()?;
+    let name = file.strip_prefix(package_dir).ok()?;
+    let slashes = |p: &Path| {
+        p.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .join("/")
+    };
+    Some((
+        format!("//{}:{}", slashes(package), slashes(name)),
+        Stamp::new(root, buildfile),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+struct CqueryOutput {
+    #[serde(default)]
+    results: Vec<CqueryResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CqueryResult {
+    target: CqueryTarget,
+}
+
+#[derive(Debug, Deserialize)]
+struct CqueryTarget {
+    rule: Option<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    name: String,
+    location: Option<String>,
+    #[serde(default)]
+    attribute: Vec<Attribute>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Attribute {
+    name: String,
+    #[serde(default)]
+    string_list_value: Vec<String>,
+}
+
+impl Rule {
+    fn strings(&self, name: &str) -> impl Iterator<Item = &str> {
+        self.attribute
+            .iter()
+            .filter(move |a| a.name == name)
+            .flat_map(|a| a.string_list_value.iter().map(String::as_str))
+    }
+
+    /// The `BUILD` file the rule is defined in. `location` has the form `<path>:<line>:<column>`.
+    fn buildfile(&self) -> Option<PathBuf> {
+        let location = self.location.as_deref()?;
+        let mut parts = location.rsplitn(3, ':');
+        let (_column, _line, path) = (parts.next()?, parts.next()?, parts.next()?);
+        Some(PathBuf::from(path))
+    }
+
+    /// Convert the rule to a manifest, with absolute paths. Returns `None` for rules
+    /// outside the main repository.
+    fn to_manifest(
+        &self,
+        root: &Path,
+        sys_info: SysInfo,
+    ) -> Option<(Target, PythonLibraryManifest)> {
+        let (package, _) = split_label(&self.name)?;
+        let package_dir = root.join(package);
+        // Each `imports` entry is a directory, relative to the package, that is added to
+        // the import path.
+        let import_roots = self
+            .strings("imports")
+            .map(|import| package_dir.join(import).absolutize())
+            .collect::<Vec<_>>();
+        let mut srcs: SmallMap<ModuleName, Vec1<InternedPath>> = SmallMap::new();
+        for src in self.strings("srcs").chain(self.strings("pyi_srcs")) {
+            let Some((src_package, name)) = split_label(src) else {
+                continue;
+            };
+            let path = root.join(src_package).join(name);
+            if !path
+                .extension()
+                .is_some_and(|e| PYTHON_EXTENSIONS.iter().any(|x| e == *x))
+            {
+                continue;
+            }
+            let import_root = import_roots
+                .iter()
+                .filter(|r| path.starts_with(r))
+                .max_by_key(|r| r.components().count())
+                .map_or(root, |r| r.as_path());
+            let Some(module) = path
+                .strip_prefix(import_root)
+                .ok()
+                .and_then(|p| ModuleName::from_relative_path(p).ok())
+            else {
+                continue;
+            };
+            let path = InternedPath::new(path);
+            match srcs.get_mut(&module) {
+                Some(paths) => paths.push(path),
+                None => {
+                    srcs.insert(module, vec1![path]);
+                }
+            }
+        }
+        let deps = self
+            .strings("deps")
+            .filter(|dep| split_label(dep).is_some())
+            .map(|dep| Target::from_string(dep.trim_start_matches('@').to_owned()))
+            .collect();
+        let buildfile_path = self
+            .buildfile()
+            .unwrap_or_else(|| package_dir.join(BUILD_FILE_NAMES[0]));
+        let target = Target::from_string(self.name.trim_start_matches('@').to_owned());
+        Some((
+            target,
+            PythonLibraryManifest {
+                deps,
+                srcs,
+                relative_to: None,
+                sys_info,
+                buildfile_path,
+                packages: SmallMap::new(),
+            },
+        ))
+    }
+}
+
+/// A querier that asks Bazel for the Python rules owning a file and their dependencies.
+#[derive(Debug)]
+pub struct BazelQuerier {
+    args: BazelArgs,
+    /// The Python version and platform of the config this querier was created for.
+    sys_info: SysInfo,
+    cache: Mutex<BazelCache>,
+}
+
+impl BazelQuerier {
+    pub fn new(args: BazelArgs, sys_info: SysInfo) -> Self {
+        Self {
+            args,
+            sys_info,
+            cache: Mutex::new(BazelCache::default()),
+        }
+    }
+
+    fn cquery(&self, expression: &str, cwd: &Path) -> anyhow::Result<Vec<Rule>> {
+        let mut cmd = self.construct_command(None);
+        cmd.arg(expression);
+        cmd.current_dir(cwd);
+        let result = cmd.output().context("Running `bazel cquery`")?;
+        if !result.status.success() {
+            let stdout = String::from_utf8_lossy(&result.stdout);
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            return Err(anyhow::anyhow!(
+                "Source DB query failed...\nSTDOUT: {stdout}\nSTDERR: {stderr}"
+            ));
+        }
+        let output: CqueryOutput = serde_json::from_slice(&result.stdout).with_context(|| {
+            format!(
+                "Failed to parse `bazel cquery` output. Command run: {} {}",
+                cmd.get_program().display(),
+                cmd.get_args().map(|a| a.to_string_lossy()).join(" "),
+            )
+        })?;
+        Ok(output
+            .results
+            .into_iter()
+            .filter_map(|r| r.target.rule)
+            .collect())
+    }
+
+    fn query(
+        &self,
+        includes: &SmallSet<Include>,
+        cwd: &Path,
+    ) -> anyhow::Result<TargetManifestDatabase> {
+        let cache = self.cache.lock();
+        let mut roots: SmallSet<Target> = SmallSet::new();
+        let mut stale_files: Vec<(InternedPath, String, Stamp)> = Vec::new();
+        let mut stale_targets: SmallSet<Target> = SmallSet::new();
+        for include in includes {
+            match include {
+                Include::Path(path) => match cache.owners.get(path) {
+                    Some(owners) if owners.stamp.is_fresh() => {
+                        roots.extend(owners.targets.iter().copied())
+                    }
+                    _ => {
+                        if let Some((label, stamp)) = source_label(cwd, path) {
+                            stale_files.push((*path, label, stamp));
+                        }
+                    }
+                },
+                Include::Target(target) => {
+                    roots.insert(*target);
+                }
+            }
+        }
+        // Anything reachable from a root whose `BUILD` file changed needs to be queried again.
+        let mut stack = roots.iter().copied().collect::<Vec<_>>();
+        let mut seen: SmallSet<Target> = SmallSet::new();
+        while let Some(target) = stack.pop() {
+            if !seen.insert(target) {
+                continue;
+            }
+            match cache.targets.get(&target) {
+                Some(cached) if cached.stamp.is_fresh() => {
+                    stack.extend(cached.manifest.deps.iter().copied())
+                }
+                _ => {
+                    stale_targets.insert(target);
+                }
+            }
+        }
+        // Don't hold the cache while Bazel runs, which can take a long time.
+        drop(cache);
+
+        if !stale_files.is_empty() || !stale_targets.is_empty() {
+            let mut universe = Vec::new();
+            if !stale_files.is_empty() {
+                universe.push(format!(
+                    "same_pkg_direct_rdeps(set({}))",
+                    stale_files.iter().map(|(_, label, _)| label).join(" ")
+                ));
+            }
+            if !stale_targets.is_empty() {
+                universe.push(format!("set({})", stale_targets.iter().join(" ")));
+            }
+            let expression = format!(
+                "kind(\"{PYTHON_RULE_KINDS}\", deps({}))",
+                universe.join(" + ")
+            );
+            let targets = self
+                .cquery(&expression, cwd)?
+                .iter()
+                .filter_map(|rule| rule.to_manifest(cwd, self.sys_info))
+                .map(|(target, manifest)| {
+                    let stamp = Stamp::new(cwd, manifest.buildfile_path.clone());
+                    (target, CachedTarget { manifest, stamp })
+                })
+                .collect::<Vec<_>>();
+            let mut cache = self.cache.lock();
+            for (target, cached) in targets {
+                cache.targets.insert(target, cached);
+            }
+            for (path, _, stamp) in stale_files {
+                let targets = cache
+                    .targets
+                    .iter()
+                    .filter(|(_, cached)| cached.manifest.srcs.values().flatten().contains(&path))
+                    .map(|(target, _)| *target)
+                    .collect::<Vec<_>>();
+                roots.extend(targets.iter().copied());
+                cache.owners.insert(path, CachedOwners { targets, stamp });
+            }
+        }
+
+        let cache = self.cache.lock();
+        let mut db = SmallMap::new();
+        let mut stack = roots.into_iter().collect::<Vec<_>>();
+        while let Some(target) = stack.pop() {
+            if db.contains_key(&target) {
+                continue;
+            }
+            if let Some(cached) = cache.targets.get(&target) {
+                stack.extend(cached.manifest.deps.iter().copied());
+                db.insert(target, TargetManifest::Library(cached.manifest.clone()));
+            }
+        }
+        Ok(TargetManifestDatabase {
+            db,
+            root: cwd.to_path_buf(),
+            extra_filetypes: SmallSet::new(),
+        })
+    }
+}
+
+impl SourceDbQuerier for BazelQuerier {
+    fn query_source_db(&self, includes: &SmallSet<Include>, cwd: &Path) -> QueryResult {
+        let start = Instant::now();
+        let db = self.query(includes, cwd);
+        QueryResult {
+            db,
+            build_id: None,
+            build_duration: Some(start.elapsed()),
+            parse_duration: None,
+            stdout_size: None,
+            exit_reason: None,
+        }
+    }
+
+    fn construct_command(&self, _: Option<&Path>) -> Command {
+        let mut cmd = self.args.command();
+        cmd.arg("cquery");
+        if let Some(extras) = &self.args.extras {
+            cmd.args(extras);
+        }
+        cmd.arg("--output=jsonproto");
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use pyrefly_python::sys_info::PythonPlatform;
+    use pyrefly_python::sys_info::PythonVersion;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_split_label() {
+        assert_eq!(split_label("//a/b:c.py"), Some(("a/b", "c.py")));
+        assert_eq!(split_label("@@//a/b:c.py"), Some(("a/b", "c.py")));
+        assert_eq!(split_label("//a/b"), Some(("a/b", "b")));
+        assert_eq!(split_label("@@rules_python~//python:defs.bzl"), None);
+    }
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_bazel_querier() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path().canonicalize().unwrap();
+        write(
+            &root,
+            "app/BUILD",
+            "load(\"//tools:defs.bzl\", \"py_app\")\npy_app(name = \"main\")",
+        );
+        write(
+            &root,
+            "tools/defs.bzl",
+            "load(\":common.bzl\", \"py_binary\")",
+        );
+        write(&root, "tools/common.bzl", "");
+        write(&root, "app/main.py", "");
+        write(&root, "lib/BUILD.bazel", "py_library(name = \"lib\")");
+        write(&root, "lib/src/pkg/mod.py", "");
+        let output = serde_json::json!({
+            "results": [
+                {"target": {"type": "RULE", "rule": {
+                    "name": "//app:main",
+                    "ruleClass": "py_binary",
+                    "location": format!("{}:1:10", root.join("app/BUILD").display()),
+                    "attribute": [
+                        {"name": "srcs", "stringListValue": ["//app:main.py"]},
+                        {"name": "deps", "stringListValue": ["//lib:lib", "@@rules_python~//python/runfiles:runfiles"]},
+                    ],
+                }}},
+                {"target": {"type": "RULE", "rule": {
+                    "name": "@@//lib:lib",
+                    "ruleClass": "py_library",
+                    "location": format!("{}:1:11", root.join("lib/BUILD.bazel").display()),
+                    "attribute": [
+                        {"name": "srcs", "stringListValue": ["//lib:src/pkg/mod.py", "//lib:README.md"]},
+                        {"name": "imports", "stringListValue": ["src"]},
+                    ],
+                }}},
+            ]
+        });
+        write(&root, "output.json", &output.to_string());
+        let log = root.join("log");
+        let stub = root.join("bazel");
+        write(
+            &root,
+            "bazel",
+            &format!(
+                "#!/bin/sh\necho \"$@\" >> {}\ncat {}\n",
+                log.display(),
+                root.join("output.json").display()
+            ),
+        );
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let sys_info = SysInfo::new(PythonVersion::new(3, 11, 0), PythonPlatform::linux());
+        let querier = BazelQuerier::new(
+            BazelArgs {
+                command: Some(vec1![stub.display().to_string()]),
+                extras: Some(vec!["--config=ci".to_owned()]),
+                repo_root: None,
+            },
+            sys_info,
+        );
+        let includes: SmallSet<Include> =
+            [Include::path(InternedPath::new(root.join("app/main.py")))]
+                .into_iter()
+                .collect();
+        let query = || {
+            querier
+                .query_source_db(&includes, &root)
+                .db
+                .unwrap()
+                .produce_map()
+                .0
+        };
+        let invocations = || fs::read_to_string(&log).unwrap().lines().count();
+
+        let db = query();
+        let app = db
+            .get(&Target::from_string("//app:main".to_owned()))
+            .unwrap();
+        assert_eq!(
+            app.deps,
+            [Target::from_string("//lib:lib".to_owned())]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            app.srcs
+                .get(&ModuleName::from_str("app.main"))
+                .unwrap()
+                .first()
+                .to_path_buf(),
+            root.join("app/main.py")
+        );
+        assert_eq!(app.sys_info, sys_info);
+        let lib = db
+            .get(&Target::from_string("//lib:lib".to_owned()))
+            .unwrap();
+        assert_eq!(
+            lib.srcs.keys().collect::<Vec<_>>(),
+            vec![&ModuleName::from_str("pkg.mod")]
+        );
+        assert_eq!(
+            fs::read_to_string(&log).unwrap().trim(),
+            "cquery --config=ci --output=jsonproto kind(\"^py_(library|binary|test) rule$\", deps(same_pkg_direct_rdeps(set(//app:main.py))))"
+        );
+
+        // Unchanged `BUILD` files are served from the cache.
+        assert_eq!(query(), db);
+        assert_eq!(invocations(), 1);
+
+        // Changing a `BUILD` file queries the targets read from it again.
+        write(
+            &root,
+            "lib/BUILD.bazel",
+            "py_library(name = \"lib\", srcs = [])",
+        );
+        assert_eq!(query(), db);
+        assert_eq!(invocations(), 2);
+        assert!(
+            fs::read_to_string(&log)
+                .unwrap()
+                .lines()
+                .last()
+                .unwrap()
+                .ends_with("deps(set(//lib:lib)))")
+        );
+
+        // So does changing a `.bzl` file loaded by a `BUILD` file, even indirectly.
+        write(&root, "tools/common.bzl", "# changed");
+        assert_eq!(query(), db);
+        assert_eq!(invocations(), 3);
+    }
+
+    #[test]
+    fn test_loads() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        write(
+            root,
+            "a/BUILD",
+            "load(\"//tools:defs.bzl\", \"x\")\nload(':local.bzl', 'y')\nload(\"@rules_python//python:defs.bzl\", \"py_library\")\npreload(\"//no:t.bzl\")",
+        );
+        assert_eq!(
+            loads(root, &root.join("a/BUILD")),
+            vec![root.join("tools/defs.bzl"), root.join("a/local.bzl")]
+        );
+    }
+}
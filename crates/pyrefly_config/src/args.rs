@@ -371,6 +371,10 @@ pub struct ConfigOverrideArgs {
         num_args = 0..=1
     )]
     treat_all_caps_as_final: Option<bool>,
+    /// The maximum number of missing or incompatible members to report when a type is not
+    /// assignable to a protocol.
+    #[arg(long)]
+    max_protocol_mismatches: Option<usize>,
 }
 
 impl ConfigOverrideArgs {
@@ -528,6 +532,9 @@ impl ConfigOverrideArgs {
         if let Some(x) = &self.treat_all_caps_as_final {
             config.root.treat_all_caps_as_final = Some(*x);
         }
+        if let Some(x) = &self.max_protocol_mismatches {
+            config.root.max_protocol_mismatches = Some(*x);
+        }
         let apply_error_settings = |error_config: &mut ErrorDisplayConfig| {
             for error_kind in &self.error {
                 error_config.set_error_severity(*error_kind, Severity::Error);
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! APIs a project forbids, configured with `[[banned-apis]]`.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use dupe::Dupe;
+use serde::Deserialize;
+use serde::Serialize;
+use starlark_map::small_set::SmallSet;
+
+/// A function, method, class or module that must not be used.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct BannedApi {
+    /// The qualified name of the API, e.g. `pickle.loads` or `datetime.datetime.utcnow`.
+    pub name: String,
+    /// Explains why the API is banned, or what to use instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// When set, the API may be used, but only in calls that pass all of these keyword
+    /// arguments (e.g. `timeout` for `requests.get`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_keywords: Vec<String>,
+}
+
+/// The `[[banned-apis]]` of a config, cheap to share with every module it checks.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Dupe, Default)]
+#[serde(from = "Vec<BannedApi>", into = "Vec<BannedApi>")]
+pub struct BannedApis {
+    apis: Arc<[BannedApi]>,
+    /// The last component of each name in `apis`, e.g. `loads` for `pickle.loads`.
+    short_names: Arc<SmallSet<String>>,
+}
+
+impl From<Vec<BannedApi>> for BannedApis {
+    fn from(apis: Vec<BannedApi>) -> Self {
+        Self::new(apis)
+    }
+}
+
+impl From<BannedApis> for Vec<BannedApi> {
+    fn from(apis: BannedApis) -> Self {
+        apis.apis.to_vec()
+    }
+}
+
+impl BannedApis {
+    pub fn new(apis: Vec<BannedApi>) -> Self {
+        let short_names = apis
+            .iter()
+            .filter_map(|api| api.name.rsplit('.').next())
+            .map(str::to_owned)
+            .collect::<SmallSet<_>>();
+        Self {
+            apis: apis.into(),
+            short_names: Arc::new(short_names),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.apis.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BannedApi> {
+        self.apis.iter()
+    }
+
+    /// Whether any banned API's name ends with the component `short_name`. Every name that
+    /// refers to a banned API does too, so this cheaply rules out most names.
+    pub fn has_short_name(&self, short_name: &str) -> bool {
+        self.short_names.contains(short_name)
+    }
+
+    pub(crate) fn validate(&self) -> Vec<anyhow::Error> {
+        self.apis
+            .iter()
+            .filter(|api| api.name.split('.').any(|part| part.trim().is_empty()))
+            .map(|api| {
+                anyhow!(
+                    "Invalid name `{}` in `banned-apis`, expected a qualified name like `pickle.loads`",
+                    api.name
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_banned_apis() {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct Wrapper {
+            banned_apis: BannedApis,
+        }
+        let wrapper: Wrapper = toml::from_str(
+            r#"
+[[banned-apis]]
+name = "pickle.loads"
+message = "Use json instead"
+
+[[banned-apis]]
+name = "requests.get"
+required-keywords = ["timeout"]
+
+[[banned-apis]]
+name = "a..b"
+"#,
+        )
+        .unwrap();
+        let apis = wrapper.banned_apis.iter().collect::<Vec<_>>();
+        assert_eq!(apis.len(), 3);
+        assert_eq!(apis[0].message.as_deref(), Some("Use json instead"));
+        assert!(apis[0].required_keywords.is_empty());
+        assert_eq!(apis[1].name, "requests.get");
+        assert_eq!(apis[1].required_keywords, vec!["timeout".to_owned()]);
+        assert_eq!(wrapper.banned_apis.validate().len(), 1);
+        assert!(wrapper.banned_apis.has_short_name("loads"));
+        assert!(!wrapper.banned_apis.has_short_name("pickle"));
+    }
+}
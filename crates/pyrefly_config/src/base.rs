@@ -317,6 +317,11 @@ pub struct ConfigBase {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub treat_all_caps_as_final: Option<bool>,
 
+    /// The maximum number of missing or incompatible members to report when a type is not
+    /// assignable to a protocol. Defaults to 5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_protocol_mismatches: Option<usize>,
+
     /// Any unknown config items
     #[serde(flatten)]
     pub(crate) extras: ExtraConfigs,
@@ -437,6 +442,10 @@ impl ConfigBase {
     pub fn get_treat_all_caps_as_final(base: &Self) -> Option<bool> {
         base.treat_all_caps_as_final
     }
+
+    pub fn get_max_protocol_mismatches(base: &Self) -> Option<usize> {
+        base.max_protocol_mismatches
+    }
 }
 
 #[cfg(test)]
@@ -21,7 +21,7 @@ use anyhow::Result;
 use anyhow::anyhow;
 use clap::ValueEnum;
 use derivative::Derivative;
-use dupe::Dupe as _;
+use dupe::Dupe;
 use itertools::Itertools;
 use pyrefly_build::BuildSystem;
 use pyrefly_build::handle::Handle;
@@ -60,6 +60,7 @@ use starlark_map::small_set::SmallSet;
 use tracing::debug;
 use tracing::error;
 
+use crate::banned_api::BannedApis;
 use crate::base::ConfigBase;
 use crate::base::ExtraConfigs;
 use crate::base::InferReturnTypes;
@@ -75,6 +76,8 @@ use crate::finder::ConfigError;
 use crate::migration::run::MigratedFromKind;
 use crate::module_wildcard::Match;
 use crate::pyproject::PyProject;
+use crate::workspace::Workspace;
+use crate::workspace::WorkspaceMember;
 
 pub static GENERATED_FILE_CONFIG_OVERRIDE: LazyLock<
     RwLock<SmallMap<InternedPath, ArcId<ConfigFile>>>,
@@ -121,6 +124,22 @@ impl CoverageConfig {
     }
 }
 
+/// Settings for the lint checks that run alongside type checking a module. They never change
+/// the types Pyrefly infers, so they are kept apart from the solver's settings.
+#[derive(Debug, Clone, Dupe, Default)]
+pub struct LintConfig {
+    /// APIs reported with `banned-api`. Empty when the check is disabled.
+    pub banned_apis: BannedApis,
+    /// Set when calls that block the event loop should be reported in `async def` bodies.
+    /// Holds the configured names on top of the built-in ones.
+    pub extra_blocking_calls: Option<Arc<[String]>>,
+    /// Set when resources that may be left open should be reported.
+    /// Holds the configured types on top of the built-in ones.
+    pub extra_closeable_types: Option<Arc<[String]>>,
+    /// Whether raised exceptions should be checked against the `Raises` section of docstrings.
+    pub undocumented_raise: bool,
+}
+
 /// Which scope of the config a command reads its settings from.
 /// Currently only affects file-glob selection.
 #[derive(Debug, Clone, Copy)]
@@ -266,7 +285,7 @@ impl ProjectLayout {
         Self::Flat
     }
 
-    fn get_import_root(&self, project_root: &Path) -> PathBuf {
+    pub(crate) fn get_import_root(&self, project_root: &Path) -> PathBuf {
         match self {
             Self::Flat => project_root.to_path_buf(),
             Self::Src => project_root.join("src"),
@@ -678,6 +697,41 @@ pub struct ConfigFile {
     #[serde(skip)]
     #[derivative(PartialEq = "ignore")]
     pub synthesized_preset_reason: Option<SynthesizedPresetReason>,
+
+    /// Don't split a uv, Poetry or PDM workspace rooted at this config into one
+    /// project per member.
+    #[serde(default, skip_serializing_if = "crate::util::skip_default_false")]
+    pub disable_workspace_discovery: bool,
+
+    /// Functions, methods, classes and modules that are reported with `banned-api` wherever
+    /// they are used.
+    #[serde(default, skip_serializing_if = "BannedApis::is_empty")]
+    pub banned_apis: BannedApis,
+
+    /// Qualified names of functions and methods that block the event loop, reported with
+    /// `blocking-call-in-async` in addition to Pyrefly's default catalog.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_blocking_calls: Vec<String>,
+
+    /// Qualified names of classes whose instances must be closed, reported with
+    /// `unclosed-resource` in addition to Pyrefly's default catalog.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_closeable_types: Vec<String>,
+
+    /// The workspace rooted at this config, discovered by `configure()`.
+    #[serde(skip)]
+    #[derivative(PartialEq = "ignore")]
+    pub workspace: Option<Arc<Workspace>>,
+
+    /// For the config of a single workspace member, the member it checks.
+    #[serde(skip)]
+    #[derivative(PartialEq = "ignore")]
+    pub workspace_member: Option<WorkspaceMember>,
+
+    /// One config per member of `workspace`, in the same order.
+    #[serde(skip)]
+    #[derivative(PartialEq = "ignore")]
+    pub workspace_member_configs: Vec<ArcId<ConfigFile>>,
 }
 
 impl Default for ConfigFile {
@@ -715,6 +769,13 @@ impl Default for ConfigFile {
             skip_lsp_config_indexing: false,
             extra_file_extensions: Vec::new(),
             synthesized_preset_reason: None,
+            disable_workspace_discovery: false,
+            banned_apis: Default::default(),
+            extra_blocking_calls: Vec::new(),
+            extra_closeable_types: Vec::new(),
+            workspace: None,
+            workspace_member: None,
+            workspace_member_configs: Vec::new(),
         }
     }
 }
@@ -1081,6 +1142,14 @@ impl ConfigFile {
                  self.root.treat_all_caps_as_final.unwrap())
     }
 
+    pub fn max_protocol_mismatches(&self, path: &Path) -> usize {
+        self.get_from_sub_configs(ConfigBase::get_max_protocol_mismatches, path)
+            .unwrap_or_else(||
+                 // we can use unwrap here, because the value in the root config must
+                 // be set in `ConfigFile::configure()`.
+                 self.root.max_protocol_mismatches.unwrap())
+    }
+
     pub fn enabled_ignores(&self, path: &Path) -> &SmallSet<Tool> {
         self.get_from_sub_configs(ConfigBase::get_enabled_ignores, path)
             .unwrap_or_else(||
@@ -1089,6 +1158,25 @@ impl ConfigFile {
                  self.root.enabled_ignores.as_ref().unwrap())
     }
 
+    /// The lint settings for `path`. Checks whose error kind is disabled for `path` are left
+    /// unset, so they cost nothing.
+    pub fn lint_config(&self, path: &Path) -> LintConfig {
+        let errors = self.errors(path);
+        let enabled = |kind: ErrorKind| errors.severity(kind).is_enabled();
+        LintConfig {
+            banned_apis: if enabled(ErrorKind::BannedApi) {
+                self.banned_apis.dupe()
+            } else {
+                BannedApis::default()
+            },
+            extra_blocking_calls: enabled(ErrorKind::BlockingCallInAsync)
+                .then(|| self.extra_blocking_calls.as_slice().into()),
+            extra_closeable_types: enabled(ErrorKind::UnclosedResource)
+                .then(|| self.extra_closeable_types.as_slice().into()),
+            undocumented_raise: enabled(ErrorKind::UndocumentedRaise),
+        }
+    }
+
     /// Get the recursion limit configuration.
     /// Returns None if not set (disabled).
     pub fn recursion_limit_config(&self) -> Option<RecursionLimitConfig> {
@@ -1310,6 +1398,9 @@ impl ConfigFile {
         // Whether the user explicitly configured `site_package_path` (via config
         // file or CLI flag). If not, we auto-discover a `typings/` directory below.
         let site_package_path_set = self.python_environment.site_package_path.is_some();
+        // Likewise, workspace members only take their Python version from
+        // `requires-python` if the user didn't configure one.
+        let python_version_set = self.python_environment.python_version.is_some();
 
         if self.interpreters.skip_interpreter_query {
             self.python_environment.set_empty_to_default();
@@ -1491,6 +1582,10 @@ impl ConfigFile {
             self.root.treat_all_caps_as_final = Some(false);
         }
 
+        if self.root.max_protocol_mismatches.is_none() {
+            self.root.max_protocol_mismatches = Some(5);
+        }
+
         let tools_from_permissive_ignores = match self.root.permissive_ignores {
             Some(true) => Some(Tool::all()),
             Some(false) => Some(Tool::default_enabled()),
@@ -1510,6 +1605,7 @@ impl ConfigFile {
         };
         self.root.enabled_ignores = Some(enabled_ignores);
 
+        let sys_info = self.get_sys_info();
         let mut configure_source_db = |build_system: &mut BuildSystem| {
             let root = match &self.source {
                 ConfigSource::File(path) => {
@@ -1524,7 +1620,7 @@ impl ConfigFile {
                 }
             };
 
-            match build_system.get_source_db(root.to_path_buf())? {
+            match build_system.get_source_db(root.to_path_buf(), sys_info)? {
                 Ok(source_db) => {
                     self.source_db = Some(source_db);
                     None
@@ -1579,6 +1675,19 @@ impl ConfigFile {
              ));
         }
 
+        configure_errors.extend(self.banned_apis.validate());
+
+        if !self.disable_workspace_discovery
+            && self.workspace_member.is_none()
+            && let Some(root) = self.source.root()
+        {
+            match Workspace::discover(root) {
+                Ok(Some(workspace)) => self.configure_workspace(workspace, python_version_set),
+                Ok(None) => {}
+                Err(e) => configure_errors.push(e.context("While discovering workspace members")),
+            }
+        }
+
         if let ConfigSource::File(path) = &self.source {
             configure_errors
                 .into_map(|e| ConfigError::warn(e.context(format!("{}", path.display()))))
@@ -1587,6 +1696,62 @@ impl ConfigFile {
         }
     }
 
+    /// Build one config per member of `workspace`. Each member gets its own import root, the
+    /// import roots of the members it depends on, and the Python version from its
+    /// `requires-python`. Editable installs put every member on the interpreter's path, so we
+    /// drop the members a member doesn't depend on from its site package path.
+    fn configure_workspace(&mut self, workspace: Workspace, python_version_set: bool) {
+        self.workspace_member_configs = workspace
+            .members
+            .iter()
+            .map(|member| {
+                let mut config = self.clone();
+                config.workspace_member_configs = Vec::new();
+                config.import_root = Some(member.import_root.clone());
+                config.search_path_from_file.extend(
+                    workspace
+                        .dependencies_of(member)
+                        .map(|m| m.import_root.clone()),
+                );
+                let undeclared = workspace
+                    .members
+                    .iter()
+                    .filter(|m| {
+                        m.name != member.name && !member.workspace_dependencies.contains(&m.name)
+                    })
+                    .collect::<Vec<_>>();
+                let declared = |path: &PathBuf| {
+                    !undeclared
+                        .iter()
+                        .any(|m| *path == m.root || *path == m.import_root)
+                };
+                if let Some(site_package_path) = &mut config.python_environment.site_package_path {
+                    site_package_path.retain(declared);
+                }
+                config
+                    .python_environment
+                    .interpreter_site_package_path
+                    .retain(declared);
+                if !python_version_set && let Some(version) = member.python_version {
+                    config.python_environment.python_version = Some(version);
+                }
+                config.workspace_member = Some(member.clone());
+                ArcId::new(config)
+            })
+            .collect();
+        self.workspace = Some(Arc::new(workspace));
+    }
+
+    /// The config of the workspace member containing `path`, if this config is the root of a
+    /// workspace. Nested members take precedence over the members containing them.
+    pub fn workspace_member_config(&self, path: &Path) -> Option<ArcId<ConfigFile>> {
+        let member = self.workspace.as_ref()?.member_for_path(path)?;
+        self.workspace_member_configs
+            .iter()
+            .find(|c| c.workspace_member.as_ref() == Some(member))
+            .map(|c| c.dupe())
+    }
+
     /// Rewrites any config values that must be updated *before* applying CLI flag values, namely
     /// rewriting any `PathBuf`s and `Globs` to be relative to `config_root`.
     /// We do this as a step separate from `configure()` because CLI args may override some of these
@@ -2008,6 +2173,7 @@ mod tests {
                     spec_compliant_overloads: None,
                     legacy_overload_expansion: None,
                     treat_all_caps_as_final: None,
+                    max_protocol_mismatches: None,
                 },
                 source_db: Default::default(),
                 sub_configs: vec![SubConfig {
@@ -2036,6 +2202,7 @@ mod tests {
                         spec_compliant_overloads: None,
                         legacy_overload_expansion: None,
                         treat_all_caps_as_final: None,
+                        max_protocol_mismatches: None,
                     }
                 }],
                 coverage: CoverageConfig {
@@ -2051,6 +2218,13 @@ mod tests {
                 skip_lsp_config_indexing: false,
                 extra_file_extensions: Vec::new(),
                 synthesized_preset_reason: None,
+                disable_workspace_discovery: false,
+                banned_apis: Default::default(),
+                extra_blocking_calls: Vec::new(),
+                extra_closeable_types: Vec::new(),
+                workspace: None,
+                workspace_member: None,
+                workspace_member_configs: Vec::new(),
             }
         );
     }
@@ -2375,6 +2549,13 @@ mod tests {
             skip_lsp_config_indexing: false,
             extra_file_extensions: Vec::new(),
             synthesized_preset_reason: None,
+            disable_workspace_discovery: false,
+            banned_apis: Default::default(),
+            extra_blocking_calls: Vec::new(),
+            extra_closeable_types: Vec::new(),
+            workspace: None,
+            workspace_member: None,
+            workspace_member_configs: Vec::new(),
         };
 
         let current_dir = std::env::current_dir().unwrap();
@@ -2446,6 +2627,13 @@ mod tests {
             skip_lsp_config_indexing: false,
             extra_file_extensions: Vec::new(),
             synthesized_preset_reason: None,
+            disable_workspace_discovery: false,
+            banned_apis: Default::default(),
+            extra_blocking_calls: Vec::new(),
+            extra_closeable_types: Vec::new(),
+            workspace: None,
+            workspace_member: None,
+            workspace_member_configs: Vec::new(),
         };
         assert_eq!(config, expected_config);
     }
@@ -2623,6 +2811,7 @@ output-format = "omit-errors"
                 spec_compliant_overloads: None,
                 legacy_overload_expansion: None,
                 treat_all_caps_as_final: None,
+                max_protocol_mismatches: None,
             },
             sub_configs: vec![
                 SubConfig {
@@ -3494,6 +3683,7 @@ output-format = "omit-errors"
                 spec_compliant_overloads: None,
                 legacy_overload_expansion: None,
                 treat_all_caps_as_final: None,
+                max_protocol_mismatches: None,
             },
             sub_configs: vec![],
             ..Default::default()
@@ -3536,6 +3726,7 @@ output-format = "omit-errors"
                 spec_compliant_overloads: None,
                 legacy_overload_expansion: None,
                 treat_all_caps_as_final: None,
+                max_protocol_mismatches: None,
             },
             sub_configs: vec![],
             ..Default::default()
@@ -4083,4 +4274,58 @@ output-format = "omit-errors"
             "without pytorch-efficiency-lints flag, lints should default to Ignore"
         );
     }
+
+    #[test]
+    fn test_workspace_member_configs() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        let write = |path: &str, contents: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+        write(
+            "pyproject.toml",
+            "[tool.uv.workspace]\nmembers = [\"packages/*\"]\n",
+        );
+        write(
+            "packages/a/pyproject.toml",
+            "[project]\nname = \"a\"\nrequires-python = \">=3.9\"\ndependencies = [\"b\"]\n",
+        );
+        write("packages/a/src/a/__init__.py", "");
+        write("packages/b/pyproject.toml", "[project]\nname = \"b\"\n");
+
+        let (mut config, _errors) = ConfigFile::from_file(&root.join("pyproject.toml"));
+        config.interpreters.skip_interpreter_query = true;
+        config.configure();
+        assert!(config.workspace.is_some());
+        assert!(
+            config
+                .workspace_member_config(&root.join("other"))
+                .is_none()
+        );
+
+        let a = config
+            .workspace_member_config(&root.join("packages/a/src/a"))
+            .unwrap();
+        assert_eq!(a.workspace_member.as_ref().unwrap().name, "a");
+        assert_eq!(a.python_version(), PythonVersion::new(3, 9, 0));
+        let search_path = a.search_path().cloned().collect::<Vec<_>>();
+        assert!(search_path.contains(&root.join("packages/a/src")));
+        assert!(search_path.contains(&root.join("packages/b")));
+
+        // `b` doesn't depend on `a`, so it can't import it.
+        let b = config
+            .workspace_member_config(&root.join("packages/b"))
+            .unwrap();
+        assert!(
+            !b.search_path()
+                .any(|p| p.starts_with(root.join("packages/a")))
+        );
+
+        config.disable_workspace_discovery = true;
+        config.workspace = None;
+        config.configure();
+        assert!(config.workspace.is_none());
+    }
 }
@@ -96,6 +96,9 @@ pub enum ErrorKind {
     /// e.g. multiple fields with the same name.
     /// Errors related specifically to inheritance should use InvalidInheritance.
     BadClassDefinition,
+    /// The parameters declared by `click` decorators, or the defaults of `typer` parameters,
+    /// don't match the signature of the command function.
+    BadClickCommand,
     /// Attempting to use a type that cannot be used as a contextmanager in a `with` statement.
     BadContextManager,
     /// A dataclass field is typed as a descriptor whose read-back type does not match
@@ -103,6 +106,9 @@ pub enum ErrorKind {
     BadDataclassDescriptor,
     /// An entry in user-defined `__all__` does not exist in the module.
     BadDunderAll,
+    /// A FastAPI endpoint or dependency is set up in a way that fails when a request is served,
+    /// e.g. a dependency whose value doesn't match the parameter it is injected into.
+    BadFastapiEndpoint,
     /// A function definition has some typing-related error.
     /// e.g. putting a non-default argument after a default argument.
     BadFunctionDefinition,
@@ -152,6 +158,11 @@ pub enum ErrorKind {
     /// An error caused by unpacking.
     /// e.g. attempting to unpack an iterable into the wrong number of variables.
     BadUnpacking,
+    /// Using a function, method, class or module listed in the `banned-apis` config option.
+    BannedApi,
+    /// Calling a function that blocks the event loop, such as `time.sleep`, directly in the
+    /// body of an `async def`.
+    BlockingCallInAsync,
     /// A Polars DataFrame's data columns do not match the column set declared by its `schema=`.
     ColumnSchemaMismatch,
     /// A Polars DataFrame column literal has an element that does not fit the column's first-element dtype.
@@ -399,6 +410,14 @@ pub enum ErrorKind {
     UnannotatedReturn,
     /// Attempting to use a name that may be unbound or uninitialized
     UnboundName,
+    /// A closeable resource, such as an open file, is created in a function but may not be
+    /// closed before the function returns.
+    UnclosedResource,
+    /// Importing a third-party module whose distribution is not declared as a dependency
+    /// in the project's `pyproject.toml`.
+    UndeclaredDependency,
+    /// A function raises an exception that the `Raises` section of its docstring doesn't list.
+    UndocumentedRaise,
     /// An error caused by a keyword argument used in the wrong place.
     UnexpectedKeyword,
     /// An error caused by passing a positional argument for a keyword-only parameter.
@@ -425,6 +444,9 @@ pub enum ErrorKind {
     /// This occurs when a return/yield follows a statement that always exits,
     /// such as return, raise, break, or continue.
     Unreachable,
+    /// An `except` clause that can never run, because an earlier clause of the same `try`
+    /// catches all of its exceptions.
+    UnreachableExcept,
     /// A match case whose pattern can never match the subject type.
     UnreachableMatchCase,
     /// `__all__` is defined but cannot be statically analyzed.
@@ -543,6 +565,7 @@ impl ErrorKind {
     pub fn default_severity(self) -> Severity {
         // IMPORTANT: When updating these, also update error-kinds.mdx in the docs
         match self {
+            ErrorKind::BlockingCallInAsync => Severity::Ignore,
             ErrorKind::CoverageMissing => Severity::Warn,
             ErrorKind::CoveragePartial => Severity::Warn,
             ErrorKind::Deprecated => Severity::Warn,
@@ -593,9 +616,13 @@ impl ErrorKind {
             ErrorKind::ImplicitAnyLambda => Severity::Ignore,
             ErrorKind::UnknownAttributeType => Severity::Ignore,
             ErrorKind::UnknownVariableType => Severity::Ignore,
+            ErrorKind::UnclosedResource => Severity::Ignore,
+            ErrorKind::UndeclaredDependency => Severity::Ignore,
+            ErrorKind::UndocumentedRaise => Severity::Ignore,
             ErrorKind::UnnecessaryComparison => Severity::Warn,
             ErrorKind::UnnecessaryTypeConversion => Severity::Warn,
             ErrorKind::Unreachable => Severity::Warn,
+            ErrorKind::UnreachableExcept => Severity::Ignore,
             ErrorKind::UnreachableMatchCase => Severity::Warn,
             ErrorKind::UnresolvableDunderAll => Severity::Warn,
             ErrorKind::UnsupportedDynamicBase => Severity::Ignore,
@@ -637,7 +664,8 @@ impl ErrorKind {
         self.default_severity() == Severity::Ignore
             || matches!(
                 self,
-                ErrorKind::Deprecated
+                ErrorKind::BannedApi
+                    | ErrorKind::Deprecated
                     | ErrorKind::RedundantCast
                     | ErrorKind::UnnecessaryTypeConversion
             )
@@ -659,8 +687,55 @@ impl ErrorKind {
             self.to_name()
         )
     }
+
+    /// Looks up an error kind by any of the ways Pyrefly's output identifies one: its name
+    /// (`bad-return`), the bracketed code after a message (`[bad-return]`), the Code Climate
+    /// check name (`pyrefly/bad-return`), the GitHub annotation title (`Pyrefly bad-return`),
+    /// or the `docs_url`. The name may also be spelled `bad_return`, `BAD_RETURN` or `BadReturn`.
+    pub fn from_error_id(id: &str) -> Option<Self> {
+        let id = id.trim();
+        let id = id
+            .strip_prefix("https://pyrefly.org/en/docs/error-kinds/#")
+            .or_else(|| id.strip_prefix("pyrefly/"))
+            .or_else(|| id.strip_prefix("Pyrefly "))
+            .unwrap_or(id);
+        let id = id.trim_start_matches('[').trim_end_matches(']');
+        let id = if id.chars().any(|c| c.is_ascii_lowercase()) {
+            id.to_owned()
+        } else {
+            id.to_ascii_lowercase()
+        };
+        let mut name = String::with_capacity(id.len());
+        for (i, c) in id.chars().enumerate() {
+            if c == '_' {
+                name.push('-');
+            } else if c.is_ascii_uppercase() {
+                if i > 0 {
+                    name.push('-');
+                }
+                name.push(c.to_ascii_lowercase());
+            } else {
+                name.push(c);
+            }
+        }
+        name.parse().ok()
+    }
+
+    /// Returns the long-form documentation for this error kind, i.e. the body of its
+    /// section on the page that `docs_url` points to. The sections are compiled into the
+    /// binary so that they are available offline.
+    pub fn docs(self) -> Option<&'static str> {
+        let header = format!("\n## {}\n", self.to_name());
+        let start = ERROR_KINDS_DOC.find(&header)? + header.len();
+        let section = &ERROR_KINDS_DOC[start..];
+        let end = section.find("\n## ").unwrap_or(section.len());
+        Some(section[..end].trim())
+    }
 }
 
+/// The sections of `website/docs/error-kinds.mdx` that document each error kind.
+static ERROR_KINDS_DOC: &str = include_str!("error_kinds.md");
+
 #[cfg(test)]
 mod tests {
     use enum_iterator::all;
@@ -700,6 +775,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_docs() {
+        for kind in all::<ErrorKind>().filter(|k| !k.is_coverage()) {
+            let docs = kind.docs();
+            assert!(
+                docs.is_some_and(|d| !d.is_empty()),
+                "Missing docs for `{}`",
+                kind.to_name()
+            );
+        }
+        assert!(
+            ErrorKind::AbstractMethodCall
+                .docs()
+                .unwrap()
+                .starts_with("This error is raised when code attempts to invoke")
+        );
+        assert!(!ErrorKind::AssertType.docs().unwrap().contains("## "));
+    }
+
+    #[test]
+    fn test_docs_match_website() {
+        let doc_path = std::env::var("ERROR_KINDS_DOC_PATH").expect(
+            "ERROR_KINDS_DOC_PATH env var not set: cargo or buck should set this automatically",
+        );
+        let doc_contents = std::fs::read_to_string(&doc_path)
+            .unwrap_or_else(|e| panic!("Failed to read {doc_path}: {e}"));
+        let sections = &doc_contents[doc_contents.find("\n## ").unwrap()..];
+        let bundled = &ERROR_KINDS_DOC[ERROR_KINDS_DOC.find("\n## ").unwrap()..];
+        assert!(
+            sections == bundled,
+            "The error kind sections of {doc_path} and src/error_kinds.md differ: copy them over"
+        );
+    }
+
+    #[test]
+    fn test_from_error_id() {
+        for id in [
+            "bad-return",
+            "[bad-return]",
+            " bad_return ",
+            "BAD_RETURN",
+            "BadReturn",
+            "pyrefly/bad-return",
+            "Pyrefly bad-return",
+            "https://pyrefly.org/en/docs/error-kinds/#bad-return",
+        ] {
+            assert_eq!(
+                ErrorKind::from_error_id(id),
+                Some(ErrorKind::BadReturn),
+                "{id}"
+            );
+        }
+        assert_eq!(ErrorKind::from_error_id("not-a-kind"), None);
+    }
+
     #[test]
     fn test_doc_headers() {
         // Verifies that the secondary headers in error-kinds.mdx contain the same variants as the ErrorKind enum and are sorted lexicographically.
@@ -166,6 +166,8 @@ impl ConfigFinder {
                     // For `pyproject.toml`, this requires a `[tool.pyrefly]` section
                     // (even if empty). A real pyrefly config always takes highest
                     // priority, even over Python tool markers in closer directories.
+                    // The root of a uv, Poetry or PDM workspace counts too, since it
+                    // knows how to configure the members beneath it.
                     FileGroup::new(
                         ConfigFile::CONFIG_FILE_NAMES
                             .iter()
@@ -175,7 +177,7 @@ impl ConfigFinder {
                             matches!(
                                 c.source,
                                 ConfigSource::File(_) | ConfigSource::FailedParse(_)
-                            )
+                            ) || c.workspace.is_some()
                         },
                     ),
                     // Group 2: pyproject.toml files with Python tool sections
@@ -248,7 +250,9 @@ impl ConfigFinder {
     /// Get the config file associated with a directory. If no config exists on disk,
     /// returns `None`.
     pub fn directory(&self, dir: &Path) -> Option<ArcId<ConfigFile>> {
-        self.search.directory(dir)
+        let dir = dir.absolutize();
+        let config = self.search.directory_absolute(&dir)?;
+        Some(config.workspace_member_config(&dir).unwrap_or(config))
     }
 
     /// Get the config file given a Python file. If no config exists on disk, one will be
@@ -264,10 +268,10 @@ impl ConfigFinder {
         }
 
         let f = |dir: Option<&Path>| match dir {
-            Some(parent) => self
-                .search
-                .directory_absolute(parent)
-                .unwrap_or_else(|| (self.fallback)(name, path)),
+            Some(parent) => match self.search.directory_absolute(parent) {
+                Some(config) => config.workspace_member_config(parent).unwrap_or(config),
+                None => (self.fallback)(name, path),
+            },
             None => (self.fallback)(name, path),
         };
 
@@ -6,6 +6,7 @@
  */
 
 pub mod args;
+pub mod banned_api;
 pub mod base;
 pub mod config;
 pub mod environment;
@@ -18,3 +19,4 @@ pub(crate) mod module_wildcard;
 pub mod pyproject;
 pub mod resolve_unconfigured;
 pub(crate) mod util;
+pub mod workspace;
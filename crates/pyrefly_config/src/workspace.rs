@@ -0,0 +1,633 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Discover the members of a uv, Poetry or PDM workspace from the root `pyproject.toml`,
+//! so each member can be checked as its own project.
+//!
+//! - uv lists members as globs in `[tool.uv.workspace] members` (minus `exclude`).
+//! - Poetry has no workspace table, so members are the `path` dependencies of the root that
+//!   are installed in development mode, e.g. `a = { path = "libs/a", develop = true }`.
+//! - PDM monorepos reference members as `name @ file:///${PROJECT_ROOT}/path` requirements.
+//!
+//! A member may only import another member it declares as a dependency.
+//!
+//! Discovery walks the workspace and reads every member. It runs when the root config is loaded,
+//! so the result lives as long as the config finder caches that config, which is until any
+//! config file, including a member's `pyproject.toml`, changes.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use pyrefly_python::sys_info::PythonVersion;
+use pyrefly_util::absolutize::Absolutize as _;
+use pyrefly_util::globs::Glob;
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::config::ConfigFile;
+use crate::config::ProjectLayout;
+
+/// Normalize a distribution name as described in PEP 503, so `PyYAML`, `pyyaml` and
+/// `py_yaml` compare equal to their spellings in requirements.
+pub fn normalize_distribution_name(name: &str) -> String {
+    let mut res = String::with_capacity(name.len());
+    let mut separator = false;
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            separator = true;
+        } else {
+            if separator && !res.is_empty() {
+                res.push('-');
+            }
+            separator = false;
+            res.push(c.to_ascii_lowercase());
+        }
+    }
+    res
+}
+
+/// The distribution name at the start of a PEP 508 requirement, e.g. `requests` in
+/// `requests[socks] >= 2.0; python_version < "3.13"`.
+pub fn requirement_name(requirement: &str) -> Option<String> {
+    let name: String = requirement
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if name.is_empty() {
+        None
+    } else {
+        Some(normalize_distribution_name(&name))
+    }
+}
+
+/// The lowest Python version allowed by a `requires-python` specifier such as `>=3.10, <4`.
+fn requires_python_lower_bound(specifier: &str) -> Option<PythonVersion> {
+    specifier.split(',').find_map(|clause| {
+        let clause = clause.trim();
+        let version = clause
+            .strip_prefix(">=")
+            .or_else(|| clause.strip_prefix("~="))
+            .or_else(|| clause.strip_prefix("=="))?;
+        PythonVersion::from_str(version.trim().trim_end_matches(".*")).ok()
+    })
+}
+
+/// The path in a PDM `name @ file:///${PROJECT_ROOT}/path` requirement, relative to the project root.
+fn pdm_member_path(requirement: &str) -> Option<&str> {
+    let (_, url) = requirement.split_once('@')?;
+    let url = url.trim().strip_prefix("file:///${PROJECT_ROOT}")?;
+    let url = url.split(';').next()?.trim();
+    Some(url.trim_start_matches('/'))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PyProject {
+    project: Option<Project>,
+    #[serde(rename = "dependency-groups", default)]
+    dependency_groups: BTreeMap<String, Vec<toml::Value>>,
+    #[serde(default)]
+    tool: Tool,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: Option<String>,
+    #[serde(rename = "requires-python")]
+    requires_python: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(rename = "optional-dependencies", default)]
+    optional_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Tool {
+    uv: Option<Uv>,
+    poetry: Option<Poetry>,
+    pdm: Option<Pdm>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Uv {
+    workspace: Option<WorkspaceTable>,
+}
+
+/// The `members` and `exclude` globs of `[tool.uv.workspace]`.
+#[derive(Debug, Deserialize)]
+struct WorkspaceTable {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Poetry {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(rename = "dev-dependencies", default)]
+    dev_dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    group: BTreeMap<String, PoetryGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryGroup {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pdm {
+    #[serde(rename = "dev-dependencies", default)]
+    dev_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl PyProject {
+    fn read(path: &Path) -> anyhow::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(toml::from_str(&contents)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn name(&self) -> Option<String> {
+        self.project
+            .as_ref()
+            .and_then(|p| p.name.as_deref())
+            .or_else(|| self.tool.poetry.as_ref().and_then(|p| p.name.as_deref()))
+            .map(normalize_distribution_name)
+    }
+
+    fn poetry_dependencies(&self) -> impl Iterator<Item = (&String, &toml::Value)> {
+        self.tool.poetry.iter().flat_map(|poetry| {
+            poetry
+                .dependencies
+                .iter()
+                .chain(poetry.dev_dependencies.iter())
+                .chain(poetry.group.values().flat_map(|g| g.dependencies.iter()))
+        })
+    }
+
+    /// Every PEP 508 requirement in the project, its optional dependencies, dependency groups
+    /// and PDM dev dependencies.
+    fn requirements(&self) -> impl Iterator<Item = &str> {
+        let project = self.project.iter().flat_map(|project| {
+            project
+                .dependencies
+                .iter()
+                .chain(project.optional_dependencies.values().flatten())
+                .map(String::as_str)
+        });
+        // Entries may also be `{include-group = "..."}` tables, whose requirements
+        // are declared by the group they include.
+        let groups = self
+            .dependency_groups
+            .values()
+            .flatten()
+            .filter_map(|x| x.as_str());
+        let pdm = self
+            .tool
+            .pdm
+            .iter()
+            .flat_map(|pdm| pdm.dev_dependencies.values().flatten().map(String::as_str));
+        project.chain(groups).chain(pdm)
+    }
+
+    /// The normalized names of every distribution this project depends on.
+    fn dependency_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .requirements()
+            .filter_map(requirement_name)
+            .chain(
+                self.poetry_dependencies()
+                    .map(|(name, _)| normalize_distribution_name(name)),
+            )
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// The tool whose configuration defined a [`Workspace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    Uv,
+    Poetry,
+    Pdm,
+}
+
+impl Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uv => write!(f, "uv"),
+            Self::Poetry => write!(f, "Poetry"),
+            Self::Pdm => write!(f, "PDM"),
+        }
+    }
+}
+
+/// A single package in a [`Workspace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The normalized distribution name of the member.
+    pub name: String,
+    /// The directory containing the member's `pyproject.toml`.
+    pub root: PathBuf,
+    /// Where the member's importable packages live, e.g. `<root>/src` for a src layout.
+    pub import_root: PathBuf,
+    /// The lowest Python version allowed by the member's `requires-python`.
+    pub python_version: Option<PythonVersion>,
+    /// The normalized names of the other members this member declares as dependencies.
+    pub workspace_dependencies: Vec<String>,
+}
+
+/// A set of projects that share a root `pyproject.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub kind: WorkspaceKind,
+    /// The directory containing the workspace's root `pyproject.toml`.
+    pub root: PathBuf,
+    pub members: Vec<WorkspaceMember>,
+}
+
+impl Workspace {
+    /// Look for a workspace defined by the `pyproject.toml` in `root`. Returns `None` if there
+    /// is no `pyproject.toml`, or it doesn't define a workspace with at least one member.
+    pub fn discover(root: &Path) -> anyhow::Result<Option<Self>> {
+        let read = |dir: &Path| PyProject::read(&dir.join(ConfigFile::PYPROJECT_FILE_NAME));
+        let Some(pyproject) = read(root)? else {
+            return Ok(None);
+        };
+        let poetry_dirs = Self::poetry_member_dirs(root, &pyproject);
+        let (kind, mut dirs) = if let Some(workspace) = pyproject
+            .tool
+            .uv
+            .as_ref()
+            .and_then(|uv| uv.workspace.as_ref())
+        {
+            (WorkspaceKind::Uv, Self::member_dirs(root, workspace)?)
+        } else if !poetry_dirs.is_empty() {
+            (WorkspaceKind::Poetry, poetry_dirs)
+        } else {
+            let dirs = pyproject
+                .requirements()
+                .filter_map(pdm_member_path)
+                .map(|path| Path::new(path).absolutize_from(root))
+                .collect();
+            (WorkspaceKind::Pdm, dirs)
+        };
+        dirs.retain(|dir| dir != root);
+        dirs.sort();
+        dirs.dedup();
+        if dirs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut members = Vec::new();
+        // The root is a member too, if it is a project rather than just a workspace definition.
+        if let Some(name) = pyproject.name() {
+            members.push((
+                Self::member(root, name, &pyproject),
+                pyproject.dependency_names(),
+            ));
+        }
+        for dir in dirs {
+            let Some(member) = read(&dir)? else {
+                continue;
+            };
+            if let Some(name) = member.name() {
+                members.push((Self::member(&dir, name, &member), member.dependency_names()));
+            }
+        }
+        let names: Vec<String> = members.iter().map(|(m, _)| m.name.clone()).collect();
+        let members = members
+            .into_iter()
+            .map(|(mut member, dependencies)| {
+                member.workspace_dependencies = dependencies
+                    .into_iter()
+                    .filter(|dep| *dep != member.name && names.contains(dep))
+                    .collect();
+                member
+            })
+            .collect();
+        Ok(Some(Self {
+            kind,
+            root: root.to_path_buf(),
+            members,
+        }))
+    }
+
+    fn member(root: &Path, name: String, pyproject: &PyProject) -> WorkspaceMember {
+        WorkspaceMember {
+            name,
+            root: root.to_path_buf(),
+            import_root: ProjectLayout::new(root).get_import_root(root),
+            python_version: pyproject
+                .project
+                .as_ref()
+                .and_then(|p| p.requires_python.as_deref())
+                .and_then(requires_python_lower_bound),
+            workspace_dependencies: Vec::new(),
+        }
+    }
+
+    /// The `path` dependencies of a Poetry project that are installed in development mode.
+    /// Other `path` dependencies are built and installed like any other package.
+    fn poetry_member_dirs(root: &Path, pyproject: &PyProject) -> Vec<PathBuf> {
+        pyproject
+            .poetry_dependencies()
+            .filter_map(|(_, spec)| {
+                let spec = spec.as_table()?;
+                if spec.get("develop")?.as_bool()? {
+                    spec.get("path")?.as_str()
+                } else {
+                    None
+                }
+            })
+            .map(|path| Path::new(path).absolutize_from(root))
+            .filter(|dir| dir.is_dir())
+            .collect()
+    }
+
+    /// Expand the `members` globs of a workspace to the directories containing a `pyproject.toml`.
+    fn member_dirs(root: &Path, workspace: &WorkspaceTable) -> anyhow::Result<Vec<PathBuf>> {
+        let globs = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Glob::new_with_root(root, p.trim_end_matches('/').to_owned()))
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        let members = globs(&workspace.members)?;
+        let exclude = globs(&workspace.exclude)?;
+        // Without a `**`, a pattern can only match directories at its own depth.
+        let max_depth = if workspace.members.iter().any(|p| p.contains("**")) {
+            usize::MAX
+        } else {
+            workspace
+                .members
+                .iter()
+                .map(|p| p.trim_end_matches('/').split('/').count())
+                .max()
+                .unwrap_or(0)
+        };
+        Ok(WalkDir::new(root)
+            .min_depth(1)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(|e| {
+                e.file_type().is_dir()
+                    && !e.file_name().to_str().is_some_and(|n| n.starts_with('.'))
+                    && e.file_name() != "node_modules"
+            })
+            .filter_map(Result::ok)
+            .map(|e| e.into_path())
+            .filter(|dir| {
+                members.iter().any(|g| g.matches(dir))
+                    && !exclude.iter().any(|g| g.matches(dir))
+                    && dir.join(ConfigFile::PYPROJECT_FILE_NAME).exists()
+            })
+            .collect())
+    }
+
+    /// The member whose root most closely contains `path`.
+    pub fn member_for_path(&self, path: &Path) -> Option<&WorkspaceMember> {
+        self.members
+            .iter()
+            .filter(|m| path.starts_with(&m.root))
+            .max_by_key(|m| m.root.components().count())
+    }
+
+    /// The members that `member` declares as dependencies.
+    pub fn dependencies_of<'a>(
+        &'a self,
+        member: &'a WorkspaceMember,
+    ) -> impl Iterator<Item = &'a WorkspaceMember> {
+        self.members
+            .iter()
+            .filter(|m| member.workspace_dependencies.contains(&m.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_normalize_distribution_name() {
+        assert_eq!(normalize_distribution_name("PyYAML"), "pyyaml");
+        assert_eq!(
+            normalize_distribution_name("typing_extensions"),
+            "typing-extensions"
+        );
+        assert_eq!(
+            normalize_distribution_name("zope.Interface"),
+            "zope-interface"
+        );
+        assert_eq!(normalize_distribution_name("a--_b"), "a-b");
+    }
+
+    #[test]
+    fn test_requirement_name() {
+        assert_eq!(
+            requirement_name("requests[socks] >= 2.0; python_version < '3.13'"),
+            Some("requests".to_owned())
+        );
+        assert_eq!(requirement_name("Django>=4"), Some("django".to_owned()));
+        assert_eq!(requirement_name(">=1"), None);
+    }
+
+    #[test]
+    fn test_requires_python_lower_bound() {
+        assert_eq!(
+            requires_python_lower_bound(">=3.10, <4"),
+            Some(PythonVersion::new(3, 10, 0))
+        );
+        assert_eq!(
+            requires_python_lower_bound("<4,~=3.9"),
+            Some(PythonVersion::new(3, 9, 0))
+        );
+        assert_eq!(requires_python_lower_bound("<3.12"), None);
+    }
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_discover_uv_workspace() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        write(
+            root,
+            "pyproject.toml",
+            r#"
+[project]
+name = "root"
+dependencies = ["A"]
+
+[tool.uv.workspace]
+members = ["packages/*"]
+exclude = ["packages/skip"]
+"#,
+        );
+        write(
+            root,
+            "packages/a/pyproject.toml",
+            r#"
+[project]
+name = "A"
+requires-python = ">=3.9"
+dependencies = ["b", "requests"]
+"#,
+        );
+        write(root, "packages/a/src/a/__init__.py", "");
+        write(
+            root,
+            "packages/b/pyproject.toml",
+            "[project]\nname = \"b\"\n",
+        );
+        write(
+            root,
+            "packages/skip/pyproject.toml",
+            "[project]\nname = \"skip\"\n",
+        );
+        write(root, "packages/not_a_project/x.py", "");
+
+        let workspace = Workspace::discover(root).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Uv);
+        assert_eq!(
+            workspace.members,
+            vec![
+                WorkspaceMember {
+                    name: "root".to_owned(),
+                    root: root.to_path_buf(),
+                    import_root: root.to_path_buf(),
+                    python_version: None,
+                    workspace_dependencies: vec!["a".to_owned()],
+                },
+                WorkspaceMember {
+                    name: "a".to_owned(),
+                    root: root.join("packages/a"),
+                    import_root: root.join("packages/a/src"),
+                    python_version: Some(PythonVersion::new(3, 9, 0)),
+                    workspace_dependencies: vec!["b".to_owned()],
+                },
+                WorkspaceMember {
+                    name: "b".to_owned(),
+                    root: root.join("packages/b"),
+                    import_root: root.join("packages/b"),
+                    python_version: None,
+                    workspace_dependencies: Vec::new(),
+                },
+            ]
+        );
+        assert_eq!(
+            workspace
+                .member_for_path(&root.join("packages/a/src/a/__init__.py"))
+                .map(|m| m.name.as_str()),
+            Some("a")
+        );
+        assert_eq!(
+            workspace
+                .member_for_path(&root.join("scripts/x.py"))
+                .map(|m| m.name.as_str()),
+            Some("root")
+        );
+    }
+
+    #[test]
+    fn test_discover_poetry_and_pdm_workspaces() {
+        let tempdir = TempDir::new().unwrap();
+        let root = tempdir.path();
+        write(root, "libs/a/pyproject.toml", "[project]\nname = \"a\"\n");
+
+        write(root, "libs/b/pyproject.toml", "[project]\nname = \"b\"\n");
+
+        // A `path` dependency that isn't installed in development mode doesn't make a workspace.
+        write(
+            root,
+            "pyproject.toml",
+            r#"
+[tool.poetry.dependencies]
+python = "^3.12"
+a = { path = "libs/a" }
+"#,
+        );
+        assert_eq!(Workspace::discover(root).unwrap(), None);
+
+        write(
+            root,
+            "pyproject.toml",
+            r#"
+[tool.poetry]
+name = "app"
+
+[tool.poetry.dependencies]
+python = "^3.12"
+a = { path = "libs/a", develop = true }
+b = { path = "libs/b" }
+missing = { path = "libs/missing", develop = true }
+
+[tool.poetry.group.dev.dependencies]
+b = { path = "libs/b", develop = true }
+"#,
+        );
+        let workspace = Workspace::discover(root).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Poetry);
+        assert_eq!(
+            workspace
+                .members
+                .iter()
+                .map(|m| (m.root.clone(), m.workspace_dependencies.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (root.to_path_buf(), vec!["a".to_owned(), "b".to_owned()]),
+                (root.join("libs/a"), Vec::new()),
+                (root.join("libs/b"), Vec::new()),
+            ]
+        );
+
+        write(
+            root,
+            "pyproject.toml",
+            r#"
+[project]
+name = "app"
+dependencies = ["a @ file:///${PROJECT_ROOT}/libs/a"]
+"#,
+        );
+        let workspace = Workspace::discover(root).unwrap().unwrap();
+        assert_eq!(workspace.kind, WorkspaceKind::Pdm);
+        assert_eq!(
+            workspace
+                .members
+                .iter()
+                .map(|m| (m.name.as_str(), m.workspace_dependencies.clone()))
+                .collect::<Vec<_>>(),
+            vec![("app", vec!["a".to_owned()]), ("a", Vec::new())]
+        );
+
+        write(root, "pyproject.toml", "[project]\nname = \"app\"\n");
+        assert_eq!(Workspace::discover(root).unwrap(), None);
+    }
+}
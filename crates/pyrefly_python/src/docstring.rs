@@ -606,10 +606,101 @@ pub fn parse_parameter_documentation(docstring: &str) -> HashMap<String, String>
     docs
 }
 
+/// Split an entry of a `Raises` section, like `ValueError` or `KeyError, IndexError`, into
+/// exception names.
+fn push_raised_names(entry: &str, names: &mut Vec<String>) {
+    for name in entry.split([',', ' ']) {
+        let name = name.trim().trim_matches('`');
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        {
+            names.push(name.to_owned());
+        }
+    }
+}
+
+/// Parse Sphinx `:raises ValueError: description` (or `:raise ...:`) fields.
+fn parse_sphinx_raises(lines: &[String], names: &mut Vec<String>) -> bool {
+    let mut found = false;
+    for line in lines {
+        let trimmed = line.trim_start();
+        let Some(rest) = trimmed
+            .strip_prefix(":raises")
+            .or_else(|| trimmed.strip_prefix(":raise"))
+        else {
+            continue;
+        };
+        if let Some((entry, _)) = rest.split_once(':') {
+            found = true;
+            push_raised_names(entry, names);
+        }
+    }
+    found
+}
+
+/// Parse Google-style `Raises:` sections, with one `ExceptionName: description` entry per
+/// line, and NumPy-style `Raises` sections underlined with dashes, with one exception name
+/// per line and the descriptions indented below them.
+fn parse_section_raises(lines: &[String], names: &mut Vec<String>) -> bool {
+    let mut found = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        let indent = leading_space_count(line);
+        let trimmed = line.trim();
+        let is_numpy = trimmed == "Raises"
+            && lines.get(i + 1).is_some_and(|next| {
+                next.trim().len() >= 3 && next.trim().chars().all(|c| c == '-')
+            });
+        if trimmed != "Raises:" && !is_numpy {
+            i += 1;
+            continue;
+        }
+        found = true;
+        i += if is_numpy { 2 } else { 1 };
+        let mut entry_indent = None;
+        while i < lines.len() {
+            let line = &lines[i];
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                i += 1;
+                continue;
+            }
+            let line_indent = leading_space_count(line);
+            let is_next_section = lines.get(i + 1).is_some_and(|next| {
+                next.trim().len() >= 3 && next.trim().chars().all(|c| c == '-')
+            });
+            if line_indent < indent || (line_indent == indent && (!is_numpy || is_next_section)) {
+                break;
+            }
+            let entry_indent = *entry_indent.get_or_insert(line_indent);
+            if line_indent == entry_indent {
+                push_raised_names(trimmed.split(':').next().unwrap_or(""), names);
+            }
+            i += 1;
+        }
+    }
+    found
+}
+
+/// Extract the names of the exceptions listed in the `Raises` sections of the supplied
+/// docstring, supporting Sphinx (`:raises ValueError:`), Google-style (`Raises:`) and
+/// NumPy-style formats. Returns `None` if the docstring has no such section.
+pub fn parse_raises_documentation(docstring: &str) -> Option<Vec<String>> {
+    let lines = dedented_lines_for_parsing(docstring);
+    let mut names = Vec::new();
+    let sphinx = parse_sphinx_raises(&lines, &mut names);
+    let section = parse_section_raises(&lines, &mut names);
+    (sphinx || section).then_some(names)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::docstring::Docstring;
     use crate::docstring::parse_parameter_documentation;
+    use crate::docstring::parse_raises_documentation;
 
     #[test]
     fn test_clean_removes_double_multiline_double_quotes() {
@@ -924,6 +1015,57 @@ Raises:
         assert_eq!(docs.len(), 2);
     }
 
+    #[test]
+    fn test_parse_raises_docs() {
+        let google = r#"
+Args:
+    key: the key
+
+Raises:
+    KeyError: when the key is missing
+        and nothing else matches
+    ValueError, TypeError: when the key is invalid
+
+Returns:
+    the value
+"#;
+        assert_eq!(
+            parse_raises_documentation(google),
+            Some(vec![
+                "KeyError".to_owned(),
+                "ValueError".to_owned(),
+                "TypeError".to_owned()
+            ])
+        );
+        let sphinx = r#"
+:param key: the key
+:raises KeyError: when the key is missing
+:raise errors.LookupFailed: when the lookup fails
+"#;
+        assert_eq!(
+            parse_raises_documentation(sphinx),
+            Some(vec![
+                "KeyError".to_owned(),
+                "errors.LookupFailed".to_owned()
+            ])
+        );
+        let numpy = r#"
+Raises
+------
+KeyError
+    When the key is missing.
+
+Returns
+-------
+int
+"#;
+        assert_eq!(
+            parse_raises_documentation(numpy),
+            Some(vec!["KeyError".to_owned()])
+        );
+        assert_eq!(parse_raises_documentation("Returns the value."), None);
+    }
+
     #[test]
     fn test_parse_google_empty_parameter_description() {
         let doc = r#"
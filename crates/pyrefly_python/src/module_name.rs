@@ -237,6 +237,10 @@ impl ModuleName {
         Self::from_str("functools")
     }
 
+    pub fn argparse() -> Self {
+        Self::from_str("argparse")
+    }
+
     pub fn type_checker_internals() -> Self {
         Self::from_str("_typeshed._type_checker_internals")
     }
@@ -309,6 +313,50 @@ impl ModuleName {
         Self::from_str("marshmallow.schema")
     }
 
+    pub fn msgspec() -> Self {
+        Self::from_str("msgspec")
+    }
+
+    pub fn sqlmodel_main() -> Self {
+        Self::from_str("sqlmodel.main")
+    }
+
+    pub fn sqlalchemy_orm() -> Self {
+        Self::from_str("sqlalchemy.orm")
+    }
+
+    pub fn cattrs_converters() -> Self {
+        Self::from_str("cattrs.converters")
+    }
+
+    pub fn fastapi_applications() -> Self {
+        Self::from_str("fastapi.applications")
+    }
+
+    pub fn fastapi_routing() -> Self {
+        Self::from_str("fastapi.routing")
+    }
+
+    pub fn fastapi_param_functions() -> Self {
+        Self::from_str("fastapi.param_functions")
+    }
+
+    pub fn click_core() -> Self {
+        Self::from_str("click.core")
+    }
+
+    pub fn click_decorators() -> Self {
+        Self::from_str("click.decorators")
+    }
+
+    pub fn click_types() -> Self {
+        Self::from_str("click.types")
+    }
+
+    pub fn typer_params() -> Self {
+        Self::from_str("typer.params")
+    }
+
     pub fn pydantic_types() -> Self {
         Self::from_str("pydantic.types")
     }
@@ -17,11 +17,13 @@ import {
   ConfigurationParams,
   ConfigurationRequest,
   DidChangeConfigurationNotification,
+  ExecuteCommandRequest,
   LanguageClient,
   LanguageClientOptions,
   LSPAny,
   ResponseError,
   ServerOptions,
+  TextDocumentPositionParams,
 } from 'vscode-languageclient/node';
 import {
   TYPE_ERROR_DISPLAY_STATUS_VERSION,
@@ -40,6 +42,7 @@ let client: LanguageClient;
 let outputChannel: vscode.OutputChannel;
 let traceOutputChannel: vscode.OutputChannel;
 let inferOutputChannel: vscode.OutputChannel;
+let whyOutputChannel: vscode.OutputChannel;
 
 /// Get a setting at the path, or throw an error if it's not set.
 function requireSetting<T>(path: string): T {
@@ -105,6 +108,9 @@ export async function activate(context: ExtensionContext) {
   if (!inferOutputChannel) {
     inferOutputChannel = vscode.window.createOutputChannel('Pyrefly infer');
   }
+  if (!whyOutputChannel) {
+    whyOutputChannel = vscode.window.createOutputChannel('Pyrefly why');
+  }
 
   const lspPath: string = requireSetting('pyrefly.lspPath');
   // `pyrefly.lspArguments` resolves to an empty array in some environments
@@ -325,6 +331,28 @@ export async function activate(context: ExtensionContext) {
       }
     }),
   );
+  context.subscriptions.push(
+    // Linked from hovers: ask the server how the hovered type was derived.
+    vscode.commands.registerCommand(
+      'pyrefly.showTypeDerivation',
+      async (args: TextDocumentPositionParams) => {
+        try {
+          const derivation: string | null = await client.sendRequest(
+            ExecuteCommandRequest.type,
+            {command: 'pyrefly.why', arguments: [args]},
+          );
+          whyOutputChannel.clear();
+          whyOutputChannel.append(derivation ?? '');
+          whyOutputChannel.show(true);
+        } catch (error) {
+          const message = error instanceof Error ? error.message : String(error);
+          await vscode.window.showErrorMessage(
+            `Pyrefly could not explain this type: ${message}`,
+          );
+        }
+      },
+    ),
+  );
   registerCodeLensCommands(context, pythonEnv);
 
   // When our extension is activated, make sure ms-python knows
@@ -78,6 +78,14 @@ export function registerHoverProvider(
           if (!hover) {
             return undefined;
           }
+          // Allow the "Explain how this type was derived" link, and nothing else.
+          for (const content of hover.contents) {
+            if (content instanceof vscode.MarkdownString) {
+              content.isTrusted = {
+                enabledCommands: ['pyrefly.showTypeDerivation'],
+              };
+            }
+          }
           const verboseHover = new vscode.VerboseHover(
             hover.contents,
             hover.range,
@@ -7,14 +7,23 @@
 
 pub mod answers;
 pub mod answers_solver;
+pub mod api_names;
+pub mod argparse;
 pub mod attr;
+pub mod banned_api;
+pub mod blocking_call;
 pub mod call;
 pub mod callable;
+pub mod cattrs;
 pub mod class;
+pub mod click;
 pub mod debugging;
+pub mod exception_flow;
 pub mod expr;
+pub mod fastapi;
 pub mod function;
 pub mod functools;
+pub mod jax;
 pub mod jaxtyping;
 pub mod narrow;
 pub mod nn_module_specials;
@@ -28,4 +37,5 @@ pub mod specials;
 pub mod subscript;
 pub mod traits;
 pub mod types;
+pub mod unclosed_resource;
 pub mod unwrap;
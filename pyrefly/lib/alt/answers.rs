@@ -67,6 +67,7 @@ use crate::types::class::ClassFields;
 use crate::types::equality::TypeEq;
 use crate::types::equality::TypeEqCtx;
 use crate::types::heap::TypeHeap;
+use crate::types::quantified::Quantified;
 use crate::types::stdlib::Stdlib;
 use crate::types::types::Forall;
 use crate::types::types::Forallable;
@@ -111,11 +112,22 @@ pub enum AttributeReferenceKind {
 pub struct OverloadTrace {
     callable: Callable,
     tparams: Option<Arc<TParams>>,
+    /// Why the overload was rejected, or empty if it was chosen.
+    errors: Vec<String>,
 }
 
 impl OverloadTrace {
     pub(crate) fn new(callable: Callable, tparams: Option<Arc<TParams>>) -> Self {
-        Self { callable, tparams }
+        Self {
+            callable,
+            tparams,
+            errors: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_errors(mut self, errors: Vec<String>) -> Self {
+        self.errors = errors;
+        self
     }
 
     fn as_type(&self) -> Type {
@@ -150,6 +162,8 @@ pub struct Traces {
     invoked_properties: SmallMap<TextRange, Arc<Type>>,
     /// A map from expression range to expected type at that position (for type checking)
     expected_types: SmallMap<TextRange, Arc<Type>>,
+    /// A map from the range of a call's arguments to what the callee's type parameters were solved to
+    call_solutions: SmallMap<TextRange, Arc<[(Quantified, Type)]>>,
 }
 
 impl Traces {
@@ -167,6 +181,9 @@ impl Traces {
         for (k, v) in side_effects.expected_types {
             self.expected_types.insert(k, v);
         }
+        for (k, v) in side_effects.call_solutions {
+            self.call_solutions.insert(k, v);
+        }
     }
 }
 
@@ -178,6 +195,7 @@ pub struct TraceSideEffects {
     pub overloaded_callees: SmallMap<TextRange, OverloadedCallee>,
     pub invoked_properties: SmallMap<TextRange, Arc<Type>>,
     pub expected_types: SmallMap<TextRange, Arc<Type>>,
+    pub call_solutions: SmallMap<TextRange, Arc<[(Quantified, Type)]>>,
 }
 
 /// Invariants:
@@ -1065,6 +1083,22 @@ impl Answers {
         )
     }
 
+    /// What the type parameters of the callee were solved to in the call whose arguments span
+    /// `range`, in the order the callee declares them.
+    pub fn get_call_solutions_for_display(
+        &self,
+        range: TextRange,
+    ) -> Option<Vec<(Quantified, Type)>> {
+        let lock = self.trace.as_ref()?.lock();
+        Some(
+            lock.call_solutions
+                .get(&range)?
+                .iter()
+                .map(|(q, ty)| (q.clone(), self.solver.for_display(ty.clone())))
+                .collect(),
+        )
+    }
+
     pub fn try_get_getter_for_range(&self, range: TextRange) -> Option<Type> {
         let lock = self.trace.as_ref()?.lock();
         Some(self.force_for_export_boundary(lock.invoked_properties.get(&range)?.as_ref().clone()))
@@ -1120,6 +1154,18 @@ impl Answers {
         }
     }
 
+    /// Why each overload of the callee was rejected, in the order of `get_all_overload_trace`.
+    /// The chosen overload has no errors.
+    pub fn get_overload_errors(&self, range: TextRange) -> Option<Vec<Vec<String>>> {
+        let lock = self.trace.as_ref()?.lock();
+        match lock.overloaded_callees.get(&range)? {
+            OverloadedCallee::Resolved { .. } => Some(vec![Vec::new()]),
+            OverloadedCallee::Candidates { all, .. } => {
+                Some(all.iter().map(|trace| trace.errors.clone()).collect())
+            }
+        }
+    }
+
     pub fn add_parent_method_mapping(
         &self,
         child_range: TextRange,
@@ -1172,6 +1218,14 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Record what the type parameters of a callee were solved to in a call.
+    pub(crate) fn record_call_solutions(&self, loc: TextRange, solutions: &[(Quantified, Type)]) {
+        if self.current().trace.is_some() && !solutions.is_empty() {
+            self.trace_state()
+                .record_call_solutions(loc, Arc::from(solutions));
+        }
+    }
+
     /// Record all the overloads and the chosen overload.
     /// The trace will be used to power signature help and hover for overloaded functions.
     pub(crate) fn record_overload_trace(
@@ -60,12 +60,15 @@ use crate::alt::answers::SolutionsTable;
 use crate::alt::answers::TraceSideEffects;
 use crate::alt::traits::Solve;
 use crate::alt::types::class_metadata::DjangoReverseRelationIndex;
+use crate::alt::types::function_raises::FunctionRaises;
 use crate::binding::binding::AnyIdx;
 use crate::binding::binding::Binding;
 use crate::binding::binding::Exported;
 use crate::binding::binding::Key;
 use crate::binding::binding::KeyDjangoRelations;
 use crate::binding::binding::KeyExport;
+use crate::binding::binding::KeyFunctionLocalRaises;
+use crate::binding::binding::KeyFunctionRaises;
 use crate::binding::binding::KeyTypeAlias;
 use crate::binding::binding::LambdaParamId;
 use crate::binding::bindings::BindingEntry;
@@ -94,6 +97,7 @@ use crate::types::class::Class;
 use crate::types::class::ClassFields;
 use crate::types::equality::TypeEq;
 use crate::types::equality::TypeEqCtx;
+use crate::types::function::FuncDefId;
 use crate::types::stdlib::Stdlib;
 use crate::types::type_info::TypeInfo;
 use crate::types::types::Type;
@@ -1594,6 +1598,17 @@ impl ThreadState {
         }
     }
 
+    /// Append the solutions of a call's type parameters to the active sink.
+    pub(crate) fn record_call_solutions(
+        &self,
+        loc: TextRange,
+        solutions: Arc<[(Quantified, Type)]>,
+    ) {
+        if let Some(sink) = self.trace_sink.borrow_mut().as_mut() {
+            sink.call_solutions.insert(loc, solutions);
+        }
+    }
+
     /// Append a resolved callee trace to the active sink.
     pub(crate) fn record_resolved_trace(&self, loc: TextRange, callee: OverloadedCallee) {
         if let Some(sink) = self.trace_sink.borrow_mut().as_mut() {
@@ -3011,6 +3026,27 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.get_from_module(cls.module_name(), Some(cls.module_path()), k)
     }
 
+    /// The exceptions that may escape a call to the function `func`. Returns `None` if the
+    /// function is no longer present in its module.
+    pub fn get_function_raises(&self, func: &FuncDefId) -> Option<Arc<FunctionRaises>> {
+        self.get_from_module(
+            func.qname.module_name(),
+            Some(func.qname.module_path()),
+            &KeyFunctionRaises(func.def_index),
+        )
+    }
+
+    /// The exceptions that may escape a call to the function `func`, following only calls to
+    /// functions in its own module. Returns `None` if the function is no longer present in its
+    /// module.
+    pub fn get_function_local_raises(&self, func: &FuncDefId) -> Option<Arc<FunctionRaises>> {
+        self.get_from_module(
+            func.qname.module_name(),
+            Some(func.qname.module_path()),
+            &KeyFunctionLocalRaises(func.def_index),
+        )
+    }
+
     pub fn get_type_alias(&self, data: &TypeAliasData) -> Arc<TypeAlias> {
         match data {
             TypeAliasData::Ref(r) => {
@@ -3112,18 +3148,24 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         self.record_expected_type_trace(loc, want);
 
         let subset_result = match options.call_context {
-            Some(call_context) => {
-                self.solver()
-                    .is_subset_eq(got, want, self.type_order(), Some(call_context))
-            }
+            Some(call_context) => self.solver().is_subset_eq_with_reason(
+                got,
+                want,
+                self.type_order(),
+                Some(call_context),
+            ),
             None => match (options.context)().kind {
                 TypeCheckKind::CallArgument(..)
                 | TypeCheckKind::CallVarArgs(..)
                 | TypeCheckKind::CallKwArgs(..)
                 | TypeCheckKind::CallUnpackKwArg(..) => {
                     let call_context = CallContext::outside().with_argument_side(ArgumentSide::Got);
-                    self.solver()
-                        .is_subset_eq(got, want, self.type_order(), Some(&call_context))
+                    self.solver().is_subset_eq_with_reason(
+                        got,
+                        want,
+                        self.type_order(),
+                        Some(&call_context),
+                    )
                 }
                 _ => self.is_subset_eq_with_reason(got, want),
             },
@@ -3202,9 +3244,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         tcc: &dyn Fn() -> TypeCheckContext,
         error: SubsetError,
     ) {
+        let implement_members = Self::implement_protocol_members_fix(got, want, &error);
         let mut builder = self
             .solver()
             .error_builder(got, want, errors, loc, tcc, error);
+        if let Some(fix) = implement_members {
+            builder = builder.with_quick_fix(fix);
+        }
         if let Some(replacement) = self.suggest_enum_member_for_value(got, want) {
             builder = builder
                 .with_detail(format!("Did you mean `{replacement}`?"))
@@ -3259,6 +3305,32 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         builder.emit();
     }
 
+    /// If `got` is an instance of a class that doesn't match the protocol `want` because it lacks
+    /// some of the protocol's members, the quick fix that adds them to the class.
+    fn implement_protocol_members_fix(
+        got: &Type,
+        want: &Type,
+        error: &SubsetError,
+    ) -> Option<ErrorQuickFix> {
+        let (Type::ClassType(class), Type::ClassType(protocol)) = (got, want) else {
+            return None;
+        };
+        if !protocol.class_object().is_protocol() {
+            return None;
+        }
+        let is_missing_member = |error: &SubsetError| matches!(error, SubsetError::MissingAttribute(name, _) if name == protocol.name());
+        let missing = match error {
+            SubsetError::ProtocolMembers(inner) => {
+                inner.0 == *protocol.name() && inner.1.iter().any(is_missing_member)
+            }
+            error => is_missing_member(error),
+        };
+        missing.then(|| ErrorQuickFix::ImplementProtocolMembers {
+            class: class.class_object().clone(),
+            protocol: protocol.clone(),
+        })
+    }
+
     /// Returns true if the type is `None` or a union containing `None`.
     fn type_contains_none(ty: &Type) -> bool {
         match ty {
@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Matching the functions, classes and modules used by an expression against qualified names
+//! from the config, such as `pickle.loads` or `datetime.datetime.utcnow`.
+
+use std::iter;
+
+use itertools::Itertools;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::class::Class;
+use ruff_python_ast::name::Name;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::config::banned_api::BannedApis;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// The qualified name of the function, class or module that `ty` refers to.
+    fn api_definition_name(ty: &Type) -> Option<String> {
+        if ty.property_metadata().is_some() {
+            return None;
+        }
+        if let Some(kind) = ty.to_func_kind() {
+            let module = kind.module_name();
+            let name = kind.function_name();
+            return Some(match kind.class() {
+                Some(cls) => format!("{module}.{}.{name}", cls.name()),
+                None => format!("{module}.{name}"),
+            });
+        }
+        match ty {
+            Type::ClassDef(cls) => Some(Self::api_class_name(cls)),
+            Type::Module(m) => Some(m.parts().iter().join(".")),
+            _ => None,
+        }
+    }
+
+    /// The last component of `api_definition_name(ty)`, without building the qualified name.
+    fn api_definition_short_name(ty: &Type) -> Option<Name> {
+        if ty.property_metadata().is_some() {
+            return None;
+        }
+        if let Some(kind) = ty.to_func_kind() {
+            return Some(kind.function_name().into_owned());
+        }
+        match ty {
+            Type::ClassDef(cls) => Some(cls.name().clone()),
+            Type::Module(m) => m.parts().last().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Whether `api_names(ty, access)` could refer to any of `apis`. Each of those names ends
+    /// with the attribute or the definition's own name, so this is cheap to rule out.
+    pub fn may_refer_to_api(apis: &BannedApis, ty: &Type, access: Option<(&Type, &Name)>) -> bool {
+        access.is_some_and(|(_, attr)| apis.has_short_name(attr.as_str()))
+            || Self::api_definition_short_name(ty)
+                .is_some_and(|name| apis.has_short_name(name.as_str()))
+    }
+
+    fn api_class_name(cls: &Class) -> String {
+        format!("{}.{}", cls.module_name(), cls.name())
+    }
+
+    /// The qualified names that `base.attr` spells, following the MRO for classes and
+    /// instances so that attributes reached through a subclass are found too.
+    fn api_access_names(&self, base: &Type, attr: &Name) -> Vec<String> {
+        let cls = match base {
+            Type::Module(m) => return vec![format!("{}.{attr}", m.parts().iter().join("."))],
+            Type::ClassDef(cls) => cls,
+            Type::ClassType(cls) | Type::SelfType(cls) => cls.class_object(),
+            _ => return Vec::new(),
+        };
+        self.api_class_names(cls)
+            .into_iter()
+            .map(|name| format!("{name}.{attr}"))
+            .collect()
+    }
+
+    /// The qualified names of `cls` and of every class in its MRO.
+    pub fn api_class_names(&self, cls: &Class) -> Vec<String> {
+        let mro = self.get_mro_for_class(cls);
+        iter::once(cls)
+            .chain(
+                mro.ancestors(self.stdlib)
+                    .map(|ancestor| ancestor.class_object()),
+            )
+            .map(Self::api_class_name)
+            .collect()
+    }
+
+    /// The qualified names an expression of type `ty` refers to, where `access` is the base
+    /// and attribute name if the expression is an attribute access.
+    pub fn api_names(&self, ty: &Type, access: Option<(&Type, &Name)>) -> Vec<String> {
+        let mut names =
+            access.map_or_else(Vec::new, |(base, attr)| self.api_access_names(base, attr));
+        names.extend(Self::api_definition_name(ty));
+        names
+    }
+
+    /// The qualified names `api` may be defined under, after following the re-exports of each
+    /// module prefix of it (e.g. `pickle.loads` is defined as `_pickle.loads`).
+    fn api_origins(&self, api: &str) -> Vec<String> {
+        let parts = api.split('.').collect::<Vec<_>>();
+        (1..parts.len())
+            .filter_map(|i| {
+                let module = ModuleName::from_str(&parts[..i].join("."));
+                let name = Name::new(parts[i]);
+                let (origin_module, origin_name) = self.exports.export_origin(module, &name).origin;
+                if origin_module == module && origin_name == name {
+                    return None;
+                }
+                Some(
+                    iter::once(format!("{origin_module}.{origin_name}"))
+                        .chain(parts[i + 1..].iter().map(|part| (*part).to_owned()))
+                        .join("."),
+                )
+            })
+            .collect()
+    }
+
+    /// Whether any of `names`, as computed by `api_names`, refers to the qualified name `api`.
+    pub fn refers_to_api(&self, names: &[String], api: &str) -> bool {
+        if names.iter().any(|name| name == api) {
+            return true;
+        }
+        // Only resolve re-exports when the final component could match.
+        let short_name = api.rsplit('.').next();
+        names
+            .iter()
+            .any(|name| name.rsplit('.').next() == short_name)
+            && self
+                .api_origins(api)
+                .iter()
+                .any(|origin| names.contains(origin))
+    }
+}
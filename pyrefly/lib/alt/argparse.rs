@@ -0,0 +1,150 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Types of the fields of namespaces synthesized for `argparse` parsers, see
+//! `binding::argparse`.
+
+use dupe::Dupe;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::call::CallStyle;
+use crate::alt::callable::CallArg;
+use crate::binding::argparse::ArgparseAction;
+use crate::binding::argparse::ArgparseArgument;
+use crate::binding::argparse::ArgparseNargs;
+use crate::error::collector::ErrorCollector;
+use crate::error::style::ErrorStyle;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// The `argparse.Namespace` class that synthesized namespaces inherit from.
+    pub fn argparse_namespace_base(&self) -> Option<Type> {
+        match self
+            .try_get_from_export(ModuleName::argparse(), Name::new_static("Namespace"))
+            .as_deref()
+        {
+            Some(Type::ClassDef(cls)) => Some(
+                self.heap
+                    .mk_class_type(self.promote_nontypeddict_silently_to_classtype(cls)),
+            ),
+            _ => None,
+        }
+    }
+
+    /// The type of a namespace field: the union of what each declaration of the
+    /// destination may store.
+    pub fn argparse_destination_type(
+        &self,
+        arguments: &[ArgparseArgument],
+        errors: &ErrorCollector,
+    ) -> Type {
+        // `set_defaults` replaces the default of every argument sharing the destination.
+        let has_set_default = arguments
+            .iter()
+            .any(|x| x.action == ArgparseAction::SetDefault);
+        self.unions(
+            arguments
+                .iter()
+                .map(|x| self.argparse_argument_type(x, has_set_default, errors))
+                .collect(),
+        )
+    }
+
+    fn argparse_argument_type(
+        &self,
+        x: &ArgparseArgument,
+        has_set_default: bool,
+        errors: &ErrorCollector,
+    ) -> Type {
+        // The expressions were already checked as arguments of the `add_argument` call,
+        // so don't report their errors a second time.
+        let errors = ErrorCollector::new(errors.module().dupe(), ErrorStyle::Never);
+        let value = |e: &Expr| {
+            self.expr_infer(e, &errors)
+                .promote_implicit_literals(self.stdlib)
+        };
+        let converted = || self.argparse_converted_type(x.conversion.as_ref(), x.range, &errors);
+        let constant = || {
+            x.constant
+                .as_ref()
+                .map_or_else(|| self.heap.mk_none(), &value)
+        };
+        let list = |t: Type| self.heap.mk_class_type(self.stdlib.list(t));
+        let bool = || self.heap.mk_class_type(self.stdlib.bool().clone());
+        let stored = match x.action {
+            ArgparseAction::Store => match x.nargs {
+                ArgparseNargs::Single => converted(),
+                ArgparseNargs::Optional => self.union(converted(), constant()),
+                ArgparseNargs::Multiple => list(converted()),
+            },
+            ArgparseAction::StoreConst => constant(),
+            ArgparseAction::StoreTrue
+            | ArgparseAction::StoreFalse
+            | ArgparseAction::BooleanOptional => bool(),
+            ArgparseAction::Count => self.heap.mk_class_type(self.stdlib.int().clone()),
+            ArgparseAction::Append | ArgparseAction::Extend => match x.nargs {
+                ArgparseNargs::Multiple if x.action == ArgparseAction::Append => {
+                    list(list(converted()))
+                }
+                _ => list(converted()),
+            },
+            ArgparseAction::AppendConst => list(constant()),
+            ArgparseAction::Subcommand => self.heap.mk_class_type(self.stdlib.str().clone()),
+            ArgparseAction::SetDefault => {
+                return x
+                    .default
+                    .as_ref()
+                    .map_or_else(|| self.heap.mk_none(), &value);
+            }
+            ArgparseAction::Unknown => return self.heap.mk_any_implicit(),
+        };
+        if x.required || has_set_default {
+            return stored;
+        }
+        // An option that isn't given on the command line keeps its default, which
+        // argparse passes through the `type=` conversion when it is a string.
+        let default = match (&x.default, x.action) {
+            (Some(Expr::StringLiteral(_)), ArgparseAction::Store) if x.conversion.is_some() => {
+                converted()
+            }
+            (Some(default), _) => value(default),
+            (None, ArgparseAction::StoreTrue | ArgparseAction::StoreFalse) => bool(),
+            (None, _) => self.heap.mk_none(),
+        };
+        self.union(stored, default)
+    }
+
+    /// The result of calling the `type=` conversion of an argument with a string.
+    fn argparse_converted_type(
+        &self,
+        conversion: Option<&Expr>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Type {
+        let str_type = self.heap.mk_class_type(self.stdlib.str().clone());
+        let Some(conversion) = conversion else {
+            return str_type;
+        };
+        let callee = self.expr_infer(conversion, errors);
+        self.call_infer(
+            self.as_call_target_or_error(callee, CallStyle::FreeForm, range, errors, None),
+            &[CallArg::ty(&str_type, range)],
+            &[],
+            range,
+            errors,
+            None,
+            None,
+            None,
+        )
+        .promote_implicit_literals(self.stdlib)
+    }
+}
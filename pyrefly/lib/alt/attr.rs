@@ -39,12 +39,15 @@ use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::context::TypeCheckContext;
 use crate::error::context::TypeCheckKind;
+use crate::error::signature_diff::render_signature_diff;
 use crate::error::style::ErrorStyle;
 use crate::solver::solver::SubsetError;
 use crate::state::loader::FindingOrError;
 use crate::types::callable::Params;
 use crate::types::class::Class;
 use crate::types::class::ClassType;
+use crate::types::display::LspDisplayMode;
+use crate::types::display::TypeDisplayContext;
 use crate::types::function::FunctionKind;
 use crate::types::function::PropertyMetadata;
 use crate::types::function::PropertyRole;
@@ -226,6 +229,35 @@ impl AttrSubsetError {
             }
         }
     }
+
+    /// When both sides of a type mismatch are a single signature, render them side by side,
+    /// highlighting where they differ. Both signatures are shown with the name `attr_name`.
+    pub fn signature_diff(&self, attr_name: &Name) -> Option<Vec<String>> {
+        let (AttrSubsetError::Covariant { got, want, .. }
+        | AttrSubsetError::Invariant { got, want, .. }
+        | AttrSubsetError::Contravariant { got, want, .. }) = self
+        else {
+            return None;
+        };
+        if got.callable_signatures().len() != 1 || want.callable_signatures().len() != 1 {
+            return None;
+        }
+        let mut ctx = TypeDisplayContext::new(&[got, want]);
+        ctx.set_lsp_display_mode(LspDisplayMode::SignatureHelp);
+        // Normalize function names so both lines show the attribute name, not the original
+        // function name (e.g., when `method = helper`).
+        let normalize_name = |sig: String| -> String {
+            if let Some(rest) = sig.strip_prefix("def ")
+                && let Some(paren) = rest.find('(')
+            {
+                return format!("def {attr_name}{}", &rest[paren..]);
+            }
+            sig
+        };
+        let got_sig = normalize_name(ctx.display(got).to_string());
+        let want_sig = normalize_name(ctx.display(want).to_string());
+        render_signature_diff(&want_sig, &got_sig)
+    }
 }
 
 /// The result of an attempt to access an attribute (which will eventually be
@@ -1062,6 +1094,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 Attribute::Simple(attr_ty) => {
                     self.check_set_read_write_and_infer_narrow(
                         attr_ty,
+                        None,
                         attr_name,
                         got,
                         range,
@@ -1122,9 +1155,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// Check an assignment of `got` to an attribute of type `attr_ty`. If the attribute is
+    /// looked up on `owner`, errors point at the attribute's declaration.
     pub fn check_set_read_write_and_infer_narrow(
         &self,
         attr_ty: Type,
+        owner: Option<&Class>,
         attr_name: &Name,
         got: TypeOrExpr,
         range: TextRange,
@@ -1133,20 +1169,23 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         should_narrow: bool,
         narrowed_types: &mut Vec<Type>,
     ) {
+        let tcc = || {
+            let tcc = TypeCheckContext::of_kind(TypeCheckKind::Attribute(attr_name.clone()))
+                .with_context(context.map(|ctx| ctx()));
+            match owner
+                .and_then(|cls| self.get_class_member_with_defining_class(cls, attr_name))
+                .and_then(|member| self.field_decl_annotation(&member.defining_class, attr_name))
+            {
+                Some((module, decl_range, label)) => {
+                    tcc.with_related_annotation(module, decl_range, label)
+                }
+                None => tcc,
+            }
+        };
         let ty = match &got {
-            TypeOrExpr::Expr(got) => self.expr_check(
-                got,
-                Some((&attr_ty, &|| {
-                    TypeCheckContext::of_kind(TypeCheckKind::Attribute(attr_name.clone()))
-                        .with_context(context.map(|ctx| ctx()))
-                })),
-                errors,
-            ),
+            TypeOrExpr::Expr(got) => self.expr_check(got, Some((&attr_ty, &tcc)), errors),
             TypeOrExpr::Type(got, _) => {
-                self.check_type(got, &attr_ty, range, errors, &|| {
-                    TypeCheckContext::of_kind(TypeCheckKind::Attribute(attr_name.clone()))
-                        .with_context(context.map(|ctx| ctx()))
-                });
+                self.check_type(got, &attr_ty, range, errors, &tcc);
                 (*got).clone()
             }
         };
@@ -2119,6 +2158,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
         let direct_lookup_not_found = std::mem::take(&mut result.not_found);
         for not_found in direct_lookup_not_found {
+            // A namespace synthesized for an `argparse` parser usually knows all of its
+            // attributes, so `argparse.Namespace.__getattr__` shouldn't hide a misspelled one.
+            if let NotFoundOn::ClassInstance(cls, _) = &not_found
+                && self
+                    .get_metadata_for_class(cls)
+                    .is_closed_argparse_namespace()
+            {
+                result.not_found.push(not_found);
+                continue;
+            }
             let (getattribute_found, getattribute_not_found, getattribute_internal_error) = self
                 .lookup_magic_dunder_attr(
                     not_found.attr_base1().to_attr_base(),
@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Reporting uses of the APIs listed in the `banned-apis` config option.
+
+use std::iter;
+
+use pyrefly_python::module_name::ModuleName;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::config::banned_api::BannedApi;
+use crate::config::banned_api::BannedApis;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::types::types::Type;
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// The APIs banned in this module, which is empty unless `banned-api` is enabled.
+    pub fn banned_apis(&self) -> &BannedApis {
+        &self.bindings().lint_config().banned_apis
+    }
+
+    /// The first banned API accepted by `filter` that any of `names` refers to.
+    fn find_banned_api(
+        &self,
+        names: &[String],
+        filter: impl Fn(&BannedApi) -> bool,
+    ) -> Option<&BannedApi> {
+        if names.is_empty() {
+            return None;
+        }
+        self.banned_apis()
+            .iter()
+            .find(|api| filter(api) && self.refers_to_api(names, &api.name))
+    }
+
+    /// Report a use of any API called `names` that is banned outright.
+    pub fn report_banned_api(&self, names: &[String], range: TextRange, errors: &ErrorCollector) {
+        if let Some(api) = self.find_banned_api(names, |api| api.required_keywords.is_empty()) {
+            let mut builder = errors.error_builder(
+                range,
+                ErrorKind::BannedApi,
+                format!("`{}` is banned", api.name),
+            );
+            if let Some(message) = &api.message {
+                builder = builder.with_detail(message.clone());
+            }
+            builder.emit();
+        }
+    }
+
+    /// Check whether `ty`, reached through the attribute `access` if any, refers to a banned API.
+    pub fn check_for_banned_api(
+        &self,
+        ty: &Type,
+        access: Option<(&Type, &Name)>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        let apis = self.banned_apis();
+        if apis.is_empty() || !Self::may_refer_to_api(apis, ty, access) {
+            return;
+        }
+        self.report_banned_api(&self.api_names(ty, access), range, errors);
+    }
+
+    /// Check whether an `import` statement for module `m` imports a banned module, or a
+    /// module inside a banned package.
+    pub fn check_for_banned_module(
+        &self,
+        m: ModuleName,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        let apis = self.banned_apis();
+        let module = m.as_str();
+        if apis.is_empty() || !module.split('.').any(|part| apis.has_short_name(part)) {
+            return;
+        }
+        let names = module
+            .match_indices('.')
+            .map(|(i, _)| module[..i].to_owned())
+            .chain(iter::once(module.to_owned()))
+            .collect::<Vec<_>>();
+        self.report_banned_api(&names, range, errors);
+    }
+
+    /// Check that a call to an API that is banned unless given certain keyword arguments
+    /// passes all of them.
+    pub fn check_for_banned_api_call(
+        &self,
+        x: &ExprCall,
+        callee_ty: &Type,
+        access: Option<(&Type, &Name)>,
+        errors: &ErrorCollector,
+    ) {
+        let apis = self.banned_apis();
+        if apis.is_empty()
+            || !Self::may_refer_to_api(apis, callee_ty, access)
+            // Anything might be passed through `**kwargs`.
+            || x.arguments.keywords.iter().any(|kw| kw.arg.is_none())
+        {
+            return;
+        }
+        let passed = |keyword: &String| {
+            x.arguments
+                .keywords
+                .iter()
+                .any(|kw| kw.arg.as_ref().is_some_and(|arg| arg.as_str() == keyword))
+        };
+        let names = self.api_names(callee_ty, access);
+        let Some(api) = self.find_banned_api(&names, |api| {
+            !api.required_keywords.iter().all(passed)
+        }) else {
+            return;
+        };
+        let missing = api
+            .required_keywords
+            .iter()
+            .filter(|keyword| !passed(keyword))
+            .map(|keyword| format!("`{keyword}`"))
+            .collect::<Vec<_>>();
+        let header = format!(
+            "`{}` must be called with keyword argument{} {}",
+            api.name,
+            if missing.len() == 1 { "" } else { "s" },
+            missing.join(", ")
+        );
+        let mut builder = errors.error_builder(x.range(), ErrorKind::BannedApi, header);
+        if let Some(message) = &api.message {
+            builder = builder.with_detail(message.clone());
+        }
+        builder.emit();
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Reporting calls that block the event loop when made directly in an `async def`.
+
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::name::Name;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::types::types::Type;
+
+/// Functions, methods and classes that block until I/O or another process completes.
+/// Users can add to these with the `extra-blocking-calls` config option.
+const DEFAULT_BLOCKING_CALLS: &[&str] = &[
+    "time.sleep",
+    "builtins.input",
+    "builtins.open",
+    "io.open",
+    "pathlib.Path.open",
+    "pathlib.Path.read_bytes",
+    "pathlib.Path.read_text",
+    "pathlib.Path.write_bytes",
+    "pathlib.Path.write_text",
+    "shutil.copy",
+    "shutil.copy2",
+    "shutil.copyfile",
+    "shutil.copytree",
+    "shutil.move",
+    "shutil.rmtree",
+    "os.popen",
+    "os.system",
+    "os.wait",
+    "os.waitpid",
+    "subprocess.call",
+    "subprocess.check_call",
+    "subprocess.check_output",
+    "subprocess.getoutput",
+    "subprocess.getstatusoutput",
+    "subprocess.run",
+    "subprocess.Popen.communicate",
+    "subprocess.Popen.wait",
+    "socket.create_connection",
+    "socket.getaddrinfo",
+    "socket.gethostbyname",
+    "socket.socket.accept",
+    "socket.socket.connect",
+    "socket.socket.recv",
+    "socket.socket.recv_into",
+    "socket.socket.recvfrom",
+    "socket.socket.send",
+    "socket.socket.sendall",
+    "urllib.request.urlopen",
+    "http.client.HTTPConnection.getresponse",
+    "http.client.HTTPConnection.request",
+    "requests.delete",
+    "requests.get",
+    "requests.head",
+    "requests.options",
+    "requests.patch",
+    "requests.post",
+    "requests.put",
+    "requests.request",
+    "requests.Session.delete",
+    "requests.Session.get",
+    "requests.Session.head",
+    "requests.Session.options",
+    "requests.Session.patch",
+    "requests.Session.post",
+    "requests.Session.put",
+    "requests.Session.request",
+    "requests.Session.send",
+    "sqlite3.connect",
+    "sqlite3.Connection.commit",
+    "sqlite3.Connection.execute",
+    "sqlite3.Cursor.execute",
+    "sqlite3.Cursor.fetchall",
+    "sqlite3.Cursor.fetchone",
+    "psycopg2.connect",
+    "pymysql.connect",
+];
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Report `x` if it calls a blocking API directly in the body of an `async def`, where
+    /// it stalls every other task on the event loop. Calls in a nested `def` or `lambda`
+    /// (e.g. one passed to `asyncio.to_thread`) are not reported.
+    pub fn check_for_blocking_call_in_async(
+        &self,
+        x: &ExprCall,
+        callee_ty: &Type,
+        access: Option<(&Type, &Name)>,
+        errors: &ErrorCollector,
+    ) {
+        let Some(extra_blocking_calls) = &self.bindings().lint_config().extra_blocking_calls else {
+            return;
+        };
+        if !self.bindings().is_call_in_async_def(x.range) {
+            return;
+        }
+        let names = self.api_names(callee_ty, access);
+        if names.is_empty() {
+            return;
+        }
+        let Some(blocking_call) = DEFAULT_BLOCKING_CALLS
+            .iter()
+            .copied()
+            .chain(extra_blocking_calls.iter().map(String::as_str))
+            .find(|api| self.refers_to_api(&names, api))
+        else {
+            return;
+        };
+        errors
+            .error_builder(
+                x.range,
+                ErrorKind::BlockingCallInAsync,
+                format!("`{blocking_call}` blocks the event loop when called in an async function"),
+            )
+            .with_detail(
+                "Use an async alternative, or run the call in a worker thread with \
+                 `asyncio.to_thread` or `loop.run_in_executor`."
+                    .to_owned(),
+            )
+            .emit();
+    }
+}
@@ -58,6 +58,7 @@ use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::context::TypeCheckContext;
 use crate::error::context::TypeCheckKind;
+use crate::error::error::ErrorQuickFix;
 use crate::solver::solver::QuantifiedHandle;
 use crate::solver::solver::TypeVarSpecializationError;
 use crate::types::callable::Callable;
@@ -1637,21 +1638,25 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     if constructor_kind == ConstructorKind::BareClassName
                         && !unimplemented_abstract_methods.is_empty()
                     {
-                        self.error_with_context(
-                            errors,
-                            arguments_range,
-                            ErrorKind::BadInstantiation,
-                            format!(
-                                "Cannot instantiate `{}` because the following members are abstract: {}",
-                                cls.name(),
-                                unimplemented_abstract_methods
-                                    .iter()
-                                    .map(|x| format!("`{x}`"))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            ),
-                            context,
-                        );
+                        errors
+                            .error_builder(
+                                arguments_range,
+                                ErrorKind::BadInstantiation,
+                                format!(
+                                    "Cannot instantiate `{}` because the following members are abstract: {}",
+                                    cls.name(),
+                                    unimplemented_abstract_methods
+                                        .iter()
+                                        .map(|x| format!("`{x}`"))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            )
+                            .with_context(context)
+                            .with_quick_fix(ErrorQuickFix::ImplementAbstractMembers {
+                                class: cls.class_object().clone(),
+                            })
+                            .emit();
                     } else if constructor_kind == ConstructorKind::BareClassName
                         && metadata.is_explicitly_abstract()
                     {
@@ -1973,7 +1978,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         {
             *targs = chosen_targs;
         }
-        let (ty, specialization_errors, return_type_errors, _expected_types) = chosen_res;
+        let (ty, specialization_errors, return_type_errors, argmap) = chosen_res;
+        self.record_call_solutions(arguments_range, &argmap.type_var_solutions);
         if let Ok(errors) = Vec1::try_from_vec(specialization_errors) {
             self.add_specialization_errors(errors, arguments_range, call_errors, context);
         }
@@ -2332,8 +2338,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             let args;
             let kws;
             let call = CallWithTypes::new();
-            if callee_ty.is_union() {
+            let cattrs_hook_registration = self.cattrs_hook_registration(&callee_ty);
+            if callee_ty.is_union() || cattrs_hook_registration.is_some() {
                 // If we have a union we will distribute over it, and end up duplicating each function call.
+                // A cattrs hook registration checks the argument types again after the call.
                 args = x
                     .arguments
                     .args
@@ -2558,6 +2566,17 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         errors,
                     )
                 }
+                // `jax.vmap(f, ...)` adds the batch dimension to the signature of `f` (see `alt::jax`).
+                _ if Self::is_jax_vmap(ty) => self.call_jax_vmap(
+                    ty,
+                    x,
+                    &args,
+                    &kws,
+                    x.func.range(),
+                    x.arguments.range(),
+                    hint,
+                    errors,
+                ),
                 // Decorators can be applied in two ways:
                 //   - (common, idiomatic) via `@decorator`:
                 //     @staticmethod
@@ -2580,6 +2599,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 ),
                 _ => self.freeform_call_infer(ty.clone(), &args, &kws, x.func.range(), x.arguments.range(), hint, errors),
             }});
+            if let Some(registration) = &cattrs_hook_registration {
+                self.check_cattrs_hook_registration(registration, &args, &kws, errors);
+            }
             // TypeIs and TypeGuard functions return bool at runtime
             match result {
                 Type::TypeIs(_) | Type::TypeGuard(_) => {
@@ -7,6 +7,7 @@
 
 use std::collections::HashMap;
 
+use dupe::Dupe;
 use itertools::Itertools;
 use pyrefly_python::dunder;
 use pyrefly_types::dimension::ShapeError;
@@ -38,6 +39,9 @@ use starlark_map::small_set::SmallSet;
 use crate::alt::answers::LookupAnswer;
 use crate::alt::answers_solver::AnswersSolver;
 use crate::alt::answers_solver::TypeCheckOptions;
+use crate::alt::call::CallTarget;
+use crate::alt::call::CallTargetLookup;
+use crate::alt::call::TargetWithTParams;
 use crate::alt::expr::ExprOptions;
 use crate::alt::expr::TypeOrExpr;
 use crate::alt::solve::Iterable;
@@ -633,6 +637,8 @@ pub struct ArgMap {
     pub range_to_param: HashMap<TextRange, MatchedParam>,
     /// Required parameters that were left unmatched
     pub unmatched_params: SmallSet<Option<Name>>,
+    /// What each of the callee's type parameters was solved to. Only collected when tracing.
+    pub type_var_solutions: Vec<(Quantified, Type)>,
 }
 
 impl ArgMap {
@@ -640,6 +646,7 @@ impl ArgMap {
         Self {
             range_to_param: HashMap::new(),
             unmatched_params: SmallSet::new(),
+            type_var_solutions: Vec::new(),
         }
     }
 
@@ -1529,12 +1536,27 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                     NameOrigin::Param => Some((*name).clone()),
                                     NameOrigin::UnpackedKwargs(name) => name.cloned(),
                                 });
-                                error(
-                                    call_errors,
-                                    arguments_range,
-                                    ErrorKind::MissingArgument,
-                                    format!("Missing argument `{name}`"),
-                                );
+                                let mut builder = call_errors
+                                    .error_builder(
+                                        arguments_range,
+                                        ErrorKind::MissingArgument,
+                                        format!(
+                                            "Missing argument `{name}`{}",
+                                            function_suffix(callable_name, self.module().name())
+                                        ),
+                                    )
+                                    .with_context(context);
+                                // Point at the signature that declares the parameter.
+                                if let Some(def) =
+                                    callable_name.and_then(|kind| kind.as_func_def_id())
+                                {
+                                    builder = builder.with_related_annotation(
+                                        def.qname.module().dupe(),
+                                        def.qname.range(),
+                                        format!("`{}` is defined here", def.qname.id()),
+                                    );
+                                }
+                                builder.emit();
                             }
                         }
                     }
@@ -1761,6 +1783,50 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         ret
     }
 
+    /// Matches the arguments of a call to the parameters of `callee`, the same way checking the
+    /// call does, without reporting any errors. Returns `None` for callees other than a plain
+    /// function, bound method or callable, e.g. overloads and classes.
+    pub fn match_call_arguments(
+        &self,
+        callee: Type,
+        args: &[CallArg],
+        keywords: &[CallKeyword],
+        arguments_range: TextRange,
+    ) -> Option<ArgMap> {
+        let CallTargetLookup::Ok(target) = self.as_call_target(callee) else {
+            return None;
+        };
+        let (self_obj, tparams, callable, kind) = match *target {
+            CallTarget::Function(TargetWithTParams(tparams, func)) => {
+                (None, tparams, func.signature, Some(func.metadata.kind))
+            }
+            CallTarget::BoundMethod(obj, TargetWithTParams(tparams, func)) => {
+                (Some(obj), tparams, func.signature, Some(func.metadata.kind))
+            }
+            CallTarget::Callable(TargetWithTParams(tparams, callable)) => {
+                (None, tparams, callable, None)
+            }
+            _ => return None,
+        };
+        let errors = self.error_swallower();
+        let (_, _, _, argmap) = self.callable_infer(
+            callable,
+            kind.as_ref(),
+            None,
+            tparams.as_deref(),
+            self_obj,
+            args,
+            keywords,
+            arguments_range,
+            &errors,
+            &errors,
+            None,
+            None,
+            None,
+        );
+        Some(argmap)
+    }
+
     // Call a function with the given arguments. The arguments are contextually typed, if possible.
     // We pass two error collectors into this function and return late resolution errors separately:
     // * arg_errors is used to infer the types of arguments, before passing them to the function.
@@ -1872,6 +1938,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         } else {
             (QuantifiedHandle::empty(), callable)
         };
+        // The variable each type parameter was instantiated to, to report its solution.
+        let tparam_vars = match tparams {
+            Some(tparams) if self.current().tracing_enabled() => tparams
+                .iter()
+                .cloned()
+                .zip(callable_qs.vars().iter().copied())
+                .collect(),
+            _ => Vec::new(),
+        };
         let (mut self_qs, remaining_callable_qs) = if self_obj.is_some()
             && let Some(first_param) = callable.get_first_param()
             // TODO(https://github.com/facebook/pyrefly/issues/105): handle nested vars
@@ -1911,7 +1986,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             arguments_range,
         });
         let self_arg = self_obj.as_ref().map(|ty| CallArg::ty(ty, arguments_range));
-        let argmap = match callable.params {
+        let mut argmap = match callable.params {
             Params::List(params) | Params::Partial(params) => self.callable_infer_params(
                 callable_name,
                 &params,
@@ -2036,6 +2111,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 call_boundary,
             )
             .map_or_else(|e| e.to_vec(), |_| Vec::new());
+        argmap.type_var_solutions = tparam_vars.into_map(|(q, v)| {
+            let solution = self.solver().expand(v.to_type(self.heap));
+            (q, solution)
+        });
 
         // Apply meta-shape inference if bound args were collected
         let ret = if let Some(meta_shape_func) = meta_shape_func
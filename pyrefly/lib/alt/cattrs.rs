@@ -0,0 +1,124 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Validation of `cattrs` hook registrations, whose stubs accept any callable as a hook.
+
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::callable::Param;
+use pyrefly_types::callable::Required;
+use pyrefly_types::function::FunctionKind;
+use pyrefly_types::types::Type;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::callable::CallArg;
+use crate::alt::callable::CallKeyword;
+use crate::alt::expr::TypeOrExpr;
+use crate::error::collector::ErrorCollector;
+use crate::error::context::TypeCheckContext;
+use crate::error::context::TypeCheckKind;
+
+const CONVERTERS: &[&str] = &["BaseConverter", "Converter"];
+
+/// A call to `Converter.register_structure_hook` or `Converter.register_unstructure_hook`.
+pub struct CattrsHookRegistration {
+    kind: FunctionKind,
+    is_structure: bool,
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Whether `callee_ty` is one of the hook registration methods of a cattrs converter.
+    pub fn cattrs_hook_registration(&self, callee_ty: &Type) -> Option<CattrsHookRegistration> {
+        let Type::BoundMethod(bm) = callee_ty else {
+            return None;
+        };
+        let kind = &bm.func.metadata().kind;
+        let id = kind.as_func_def_id()?;
+        if !id.cls.as_ref().is_some_and(|cls| {
+            CONVERTERS
+                .iter()
+                .any(|name| cls.has_toplevel_qname(ModuleName::cattrs_converters().as_str(), name))
+        }) {
+            return None;
+        }
+        let is_structure = match id.qname.id().as_str() {
+            "register_structure_hook" => true,
+            "register_unstructure_hook" => false,
+            _ => return None,
+        };
+        Some(CattrsHookRegistration {
+            kind: kind.clone(),
+            is_structure,
+        })
+    }
+
+    /// Check the hook passed to a cattrs hook registration against the class it is registered
+    /// for: a structure hook is called as `hook(value, cl)` and must produce a `cl`, while an
+    /// unstructure hook is called with an instance of `cl`. `args` and `kws` are the arguments
+    /// of the call, already inferred and checked against the stub signature.
+    pub fn check_cattrs_hook_registration(
+        &self,
+        registration: &CattrsHookRegistration,
+        args: &[CallArg],
+        kws: &[CallKeyword],
+        errors: &ErrorCollector,
+    ) {
+        let argument = |position: usize, keyword: &str| -> Option<(&Type, TextRange)> {
+            let value = match args.get(position) {
+                Some(CallArg::Arg(value)) => *value,
+                Some(CallArg::Star(..)) => return None,
+                None => {
+                    kws.iter()
+                        .find(|kw| kw.arg.is_some_and(|arg| arg.id.as_str() == keyword))?
+                        .value
+                }
+            };
+            match value {
+                TypeOrExpr::Type(ty, range) => Some((ty, range)),
+                TypeOrExpr::Expr(_) => None,
+            }
+        };
+        let (Some((cl, _)), Some((hook, hook_range))) = (argument(0, "cl"), argument(1, "func"))
+        else {
+            return;
+        };
+        let instance = match cl {
+            Type::ClassDef(cls) => self.promote_silently(cls),
+            Type::Type(instance) if matches!(**instance, Type::ClassType(_)) => {
+                (**instance).clone()
+            }
+            _ => return,
+        };
+        let any = self.heap.mk_any_implicit();
+        let want = if registration.is_structure {
+            self.heap.mk_callable_from_vec(
+                vec![
+                    Param::PosOnly(None, any, Required::Required),
+                    Param::PosOnly(
+                        None,
+                        self.heap.mk_type_of(instance.clone()),
+                        Required::Required,
+                    ),
+                ],
+                instance,
+            )
+        } else {
+            self.heap.mk_callable_from_vec(
+                vec![Param::PosOnly(None, instance, Required::Required)],
+                any,
+            )
+        };
+        self.check_type(hook, &want, hook_range, errors, &|| {
+            TypeCheckContext::of_kind(TypeCheckKind::CallArgument(
+                Some(Name::new_static("func")),
+                Some(registration.kind.clone()),
+            ))
+        });
+    }
+}
@@ -13,10 +13,12 @@ pub mod dataclass;
 pub mod django;
 pub mod enums;
 pub mod factory_boy;
+pub mod msgspec;
 pub mod named_tuple;
 pub mod new_type;
 pub mod pydantic;
 pub mod pydantic_lax;
+pub mod sqlmodel;
 pub mod synthesized_classes_shared;
 pub mod targs;
 pub mod targs_cursor;
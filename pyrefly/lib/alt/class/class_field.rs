@@ -72,11 +72,12 @@ use crate::binding::binding::KeyClassSynthesizedFields;
 use crate::binding::binding::MethodSelfKind;
 use crate::binding::binding::MethodThatSetsAttr;
 use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorBuilder;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
 use crate::error::context::TypeCheckContext;
 use crate::error::context::TypeCheckKind;
-use crate::error::signature_diff::render_signature_diff;
+use crate::module::module_info::ModuleInfo;
 use crate::solver::solver::SubsetError;
 use crate::types::annotation::Annotation;
 use crate::types::annotation::Qualifier;
@@ -85,8 +86,6 @@ use crate::types::callable::Required;
 use crate::types::class::Class;
 use crate::types::class::ClassKind;
 use crate::types::class::ClassType;
-use crate::types::display::LspDisplayMode;
-use crate::types::display::TypeDisplayContext;
 use crate::types::function::FuncMetadata;
 use crate::types::function::Function;
 use crate::types::function::PropertyMetadata;
@@ -3965,29 +3964,34 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             if want_field.defining_class.is_builtin("object") {
                 parent_attr_is_from_object = true;
             }
+            let defining_class = want_field.defining_class;
+            let override_error = |kind: ErrorKind, msg: String| {
+                self.annotate_field_decl(
+                    errors.error_builder(range, kind, msg),
+                    &defining_class,
+                    field_name,
+                )
+            };
             let want_class_field = Arc::unwrap_or_clone(want_field.value);
             parent_attr_requires_override = parent_attr_requires_override
                 || (!parent_metadata.is_protocol() && !want_class_field.is_abstract());
             if want_class_field.is_final() {
-                self.error(
-                    errors,
-                    range,
+                override_error(
                     ErrorKind::BadOverride,
                     format!(
                         "`{}` is declared as final in parent class `{}`",
                         field_name,
                         parent.name()
                     ),
-                );
+                )
+                .emit();
                 continue;
             }
             if want_class_field.has_explicit_annotation() && class_field.has_explicit_annotation() {
                 let want_is_class_var = want_class_field.is_class_var();
                 let got_is_class_var = class_field.is_class_var();
                 if want_is_class_var && !got_is_class_var {
-                    self.error(
-                            errors,
-                            range,
+                    override_error(
                             ErrorKind::BadOverride,
                             format!(
                                 "Instance variable `{}.{}` overrides ClassVar of the same name in parent class `{}`",
@@ -3995,12 +3999,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                 field_name,
                                 parent.name()
                             ),
-                        );
+                        )
+                        .emit();
                     continue;
                 } else if !want_is_class_var && got_is_class_var {
-                    self.error(
-                            errors,
-                            range,
+                    override_error(
                             ErrorKind::BadOverride,
                             format!(
                                 "ClassVar `{}.{}` overrides instance variable of the same name in parent class `{}`",
@@ -4008,7 +4011,8 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                 field_name,
                                 parent.name()
                             ),
-                        );
+                        )
+                        .emit();
                     continue;
                 }
             }
@@ -4174,7 +4178,6 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     })
                 }
                 Err(error) => {
-                    let mut diff_lines = Vec::new();
                     // Invariant = ReadWrite vs ReadWrite type mismatch.
                     // Contravariant with got_is_property=false = ReadWrite
                     // overriding a Property with setter (narrowed writable type).
@@ -4188,38 +4191,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                 ..
                             }
                     );
-                    if let AttrSubsetError::Covariant { got, want, .. }
-                    | AttrSubsetError::Invariant { got, want, .. }
-                    | AttrSubsetError::Contravariant { got, want, .. } = &*error
-                    {
-                        let got_sigs = got.callable_signatures();
-                        let want_sigs = want.callable_signatures();
-                        if got_sigs.len() == 1 && want_sigs.len() == 1 {
-                            let mut ctx = TypeDisplayContext::new(&[got, want]);
-                            ctx.set_lsp_display_mode(LspDisplayMode::SignatureHelp);
-                            let got_sig = ctx.display(got).to_string();
-                            let want_sig = ctx.display(want).to_string();
-
-                            // Normalize function names so both lines show the
-                            // attribute name, not the original function name
-                            // (e.g., when `method = helper`).
-                            let normalize_name = |sig: &str| -> String {
-                                if let Some(rest) = sig.strip_prefix("def ")
-                                    && let Some(paren) = rest.find('(')
-                                {
-                                    return format!("def {field_name}{}", &rest[paren..]);
-                                }
-                                sig.to_owned()
-                            };
-                            let got_sig = normalize_name(&got_sig);
-                            let want_sig = normalize_name(&want_sig);
-
-                            if let Some(lines) = render_signature_diff(&want_sig, &got_sig) {
-                                diff_lines = lines;
-                            }
-                        }
-                    }
-
+                    let diff_lines = error.signature_diff(field_name).unwrap_or_default();
                     Some(OverrideError {
                         kind: if is_mutable_attribute {
                             ErrorKind::BadOverrideMutableAttribute
@@ -4238,18 +4210,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 diff_lines: extra_lines,
             }) = error
             {
-                let mut builder = errors
-                    .error_builder(
-                        range,
-                        kind,
-                        format!(
-                            "Class member `{}.{}` overrides parent class `{}` in an inconsistent manner",
-                            cls.name(),
-                            field_name,
-                            parent.name()
-                        ),
-                    )
-                    .with_detail(message);
+                let mut builder = override_error(
+                    kind,
+                    format!(
+                        "Class member `{}.{}` overrides parent class `{}` in an inconsistent manner",
+                        cls.name(),
+                        field_name,
+                        parent.name()
+                    ),
+                )
+                .with_detail(message);
                 for line in extra_lines {
                     builder = builder.with_detail(line);
                 }
@@ -4622,6 +4592,35 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
+    /// An annotation pointing at the declaration of `name` in `cls`, which is often in another
+    /// module than the error, as the module, range and label to attach.
+    pub(crate) fn field_decl_annotation(
+        &self,
+        cls: &Class,
+        name: &Name,
+    ) -> Option<(ModuleInfo, TextRange, String)> {
+        let decl_range = self.get_class_fields(cls)?.field_decl_range(name)?;
+        Some((
+            cls.module().dupe(),
+            decl_range,
+            format!("`{}.{name}` is declared here", cls.name()),
+        ))
+    }
+
+    /// Point an error at the declaration of `name` in `cls`, e.g. the parent method that an
+    /// override is checked against.
+    fn annotate_field_decl<'b>(
+        &self,
+        builder: ErrorBuilder<'b>,
+        cls: &Class,
+        name: &Name,
+    ) -> ErrorBuilder<'b> {
+        match self.field_decl_annotation(cls, name) {
+            Some((module, range, label)) => builder.with_related_annotation(module, range, label),
+            None => builder,
+        }
+    }
+
     pub(crate) fn get_class_member(&self, cls: &Class, name: &Name) -> Option<Arc<ClassField>> {
         self.get_class_member_with_defining_class(cls, name)
             .map(|member| member.value)
@@ -4872,8 +4871,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     /// Access is disallowed for instance-only attributes and for attributes whose
     /// type contains a class-scoped type parameter - e.g., `class A[T]: x: T`.
     pub fn get_class_attribute(&self, cls: &ClassBase, name: &Name) -> Option<ClassAttribute> {
-        self.get_class_member(cls.class_object(), name)
-            .map(|field| self.as_class_attribute(name, &field, cls))
+        let field = self.get_class_member(cls.class_object(), name)?;
+        if let Some(column) = self.sqlmodel_column_type(cls.class_object(), name, &field) {
+            return Some(ClassAttribute::read_write(column));
+        }
+        Some(self.as_class_attribute(name, &field, cls))
     }
 
     pub fn get_bounded_quantified_class_attribute(
@@ -5149,6 +5151,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 {
                     self.check_set_read_write_and_infer_narrow(
                         attr_ty,
+                        None,
                         attr_name,
                         got,
                         range,
@@ -5187,6 +5190,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 }
                 self.check_set_read_write_and_infer_narrow(
                     attr_ty,
+                    class_object,
                     attr_name,
                     got,
                     range,
@@ -5202,6 +5206,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     let attr_ty = self.call_property_getter(getter, range, errors, context);
                     self.check_set_read_write_and_infer_narrow(
                         attr_ty,
+                        None,
                         attr_name,
                         got,
                         range,
@@ -5262,6 +5267,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         let attr_ty = self.heap.mk_class_type(x.cls.clone());
                         self.check_set_read_write_and_infer_narrow(
                             attr_ty,
+                            None,
                             attr_name,
                             got,
                             range,
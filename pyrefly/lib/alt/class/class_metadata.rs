@@ -326,6 +326,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         || metadata.is_factory_boy_factory()
                 });
 
+        let sqlmodel_metadata = self.sqlmodel_metadata(&bases_with_metadata, &keyword_annotations);
+
+        let is_closed_argparse_namespace = bases
+            .iter()
+            .any(|base| matches!(base, BaseClass::ArgparseNamespace(_, false)));
+
         let is_metaclass = bases_with_metadata
             .iter()
             .any(|(base_class_object, metadata)| {
@@ -360,7 +366,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             );
         }
 
-        let pydantic_config = self.pydantic_config(
+        let mut pydantic_config = self.pydantic_config(
             &bases_with_metadata,
             pydantic_config_dict,
             &keyword_annotations,
@@ -368,6 +374,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             errors,
             cls.range(),
         );
+        if sqlmodel_metadata.as_ref().is_some_and(|m| m.table)
+            && let Some(config) = &mut pydantic_config
+        {
+            // SQLModel doesn't validate table models on construction, so field values are
+            // stored exactly as given and lax-mode coercion doesn't apply.
+            config.strict = Some(true);
+        }
 
         let is_typed_dict = has_typed_dict_base_class
             || bases_with_metadata
@@ -510,6 +523,13 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 errors,
             );
         }
+        let msgspec_metadata = self.msgspec_struct_metadata(
+            &keyword_annotations,
+            &bases_with_metadata,
+            dataclass_metadata.as_ref(),
+            errors,
+            cls.range(),
+        );
         let extends_abc = self.extends_abc(&bases_with_metadata, metaclass);
 
         // Compute final base class list.
@@ -566,6 +586,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             django_model_metadata,
             is_marshmallow_schema,
             is_factory_boy_factory,
+            msgspec_metadata,
+            sqlmodel_metadata,
+            is_closed_argparse_namespace,
             is_metaclass,
             explicit_slots,
             capture_init.map(|names| names.to_vec()),
@@ -1659,6 +1682,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 self.heap
                     .mk_class_type(self.stdlib.named_tuple_fallback().clone()),
             ),
+            BaseClass::ArgparseNamespace(..) => match self.argparse_namespace_base() {
+                Some(ty) => parse_base_class_type(ty),
+                None => BaseClassParseResult::Ignored,
+            },
             BaseClass::SynthesizedBase(class_idx, _) => {
                 match &self.get_idx(*class_idx).as_ref().0 {
                     Some(cls) => {
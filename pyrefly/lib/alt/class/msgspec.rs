@@ -0,0 +1,341 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! `msgspec.Struct` subclasses. Their fields, `__init__`, `frozen` and `kw_only` come from
+//! the `dataclass_transform` on `msgspec.Struct`. Here we validate the class keywords, and
+//! record the ones that change how a struct is encoded (`rename`, `tag`, `tag_field` and
+//! `array_like`) to synthesize the class attributes describing the encoding.
+//! `omit_defaults` and the other keywords don't affect any type, so we only check them
+//! against the signature of `Struct.__init_subclass__`.
+
+use std::sync::Arc;
+
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::annotation::Annotation;
+use pyrefly_types::class::Class;
+use pyrefly_types::lit_int::LitInt;
+use pyrefly_types::literal::Lit;
+use pyrefly_types::types::Type;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::ClassSynthesizedField;
+use crate::alt::types::class_metadata::ClassSynthesizedFields;
+use crate::alt::types::class_metadata::DataclassMetadata;
+use crate::alt::types::class_metadata::MsgspecRename;
+use crate::alt::types::class_metadata::MsgspecStructMetadata;
+use crate::alt::types::class_metadata::MsgspecTag;
+use crate::error::collector::ErrorCollector;
+
+const STRUCT_FIELDS: Name = Name::new_static("__struct_fields__");
+const STRUCT_ENCODE_FIELDS: Name = Name::new_static("__struct_encode_fields__");
+const STRUCT_TAG_FIELD: Name = Name::new_static("__struct_tag_field__");
+const STRUCT_TAG: Name = Name::new_static("__struct_tag__");
+const FROZEN: Name = Name::new_static("frozen");
+const CACHE_HASH: Name = Name::new_static("cache_hash");
+const RENAME: Name = Name::new_static("rename");
+
+/// The field holding the tag of a tagged struct that doesn't set `tag_field`.
+const DEFAULT_TAG_FIELD: &str = "type";
+
+/// Class keywords accepted by `msgspec.Struct` subclasses.
+const STRUCT_OPTIONS: &[&str] = &[
+    "tag",
+    "tag_field",
+    "rename",
+    "omit_defaults",
+    "forbid_unknown_fields",
+    "frozen",
+    "eq",
+    "order",
+    "kw_only",
+    "repr_omit_defaults",
+    "array_like",
+    "gc",
+    "weakref",
+    "dict",
+    "cache_hash",
+];
+
+/// Naming conventions accepted as a string value of `rename=`.
+const RENAME_CONVENTIONS: &[&str] = &["lower", "upper", "camel", "pascal", "kebab"];
+
+fn is_true(ty: &Type) -> bool {
+    matches!(ty, Type::Literal(lit) if matches!(lit.value, Lit::Bool(true)))
+}
+
+fn str_literal(ty: &Type) -> Option<&str> {
+    match ty {
+        Type::Literal(lit) => match &lit.value {
+            Lit::Str(s) => Some(s.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The encoded name of the field `name` under a `rename=` naming convention. Leading
+/// underscores are kept, and the rest of the name is split into words at underscores.
+fn rename_field(convention: &str, name: &str) -> String {
+    let body = name.trim_start_matches('_');
+    let prefix = &name[..name.len() - body.len()];
+    let words = body.split('_').filter(|word| !word.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+    };
+    match convention {
+        "lower" => name.to_lowercase(),
+        "upper" => name.to_uppercase(),
+        "camel" => {
+            let mut renamed = prefix.to_owned();
+            for (i, word) in words.enumerate() {
+                if i == 0 {
+                    renamed.push_str(word);
+                } else {
+                    renamed.push_str(&capitalize(word));
+                }
+            }
+            renamed
+        }
+        "pascal" => prefix.to_owned() + &words.map(capitalize).collect::<String>(),
+        "kebab" => words.collect::<Vec<_>>().join("-"),
+        _ => name.to_owned(),
+    }
+}
+
+impl MsgspecStructMetadata {
+    /// The encoded names of `fields`, or `None` if they are renamed in a way we don't follow.
+    fn encoded_names<'b>(&self, fields: impl Iterator<Item = &'b Name>) -> Option<Vec<String>> {
+        match &self.rename {
+            MsgspecRename::Unchanged => Some(fields.map(|name| name.to_string()).collect()),
+            MsgspecRename::Convention(convention) => Some(
+                fields
+                    .map(|name| rename_field(convention, name.as_str()))
+                    .collect(),
+            ),
+            MsgspecRename::Unknown => None,
+        }
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// The encoding options of a `msgspec.Struct` subclass, starting from the ones it
+    /// inherits and applying its class keywords. Returns `None` if `cls` is not a struct.
+    pub fn msgspec_struct_metadata(
+        &self,
+        keywords: &[(Name, Annotation)],
+        bases_with_metadata: &[(Class, Arc<ClassMetadata>)],
+        dataclass: Option<&DataclassMetadata>,
+        errors: &ErrorCollector,
+        range: TextRange,
+    ) -> Option<MsgspecStructMetadata> {
+        let mut is_struct = false;
+        let mut inherited = None;
+        for (base, metadata) in bases_with_metadata {
+            if let Some(base_metadata) = metadata.msgspec_metadata() {
+                is_struct = true;
+                inherited.get_or_insert_with(|| base_metadata.clone());
+            } else if base.has_toplevel_qname(ModuleName::msgspec().as_str(), "Struct") {
+                is_struct = true;
+            }
+        }
+        if !is_struct {
+            return None;
+        }
+        self.check_msgspec_struct_options(keywords, bases_with_metadata, errors, range);
+        let mut metadata = inherited.unwrap_or_default();
+        let mut sets_tag = false;
+        let mut sets_tag_field = false;
+        for (name, ann) in keywords {
+            let ty = ann.get_type();
+            match name.as_str() {
+                "rename" => {
+                    metadata.rename = match str_literal(ty) {
+                        _ if ty.is_none() => MsgspecRename::Unchanged,
+                        Some(convention) if RENAME_CONVENTIONS.contains(&convention) => {
+                            MsgspecRename::Convention(convention.to_owned())
+                        }
+                        _ => MsgspecRename::Unknown,
+                    };
+                }
+                "tag" => {
+                    sets_tag = true;
+                    metadata.tag = match ty {
+                        _ if ty.is_none() => None,
+                        Type::Literal(lit) => match &lit.value {
+                            Lit::Bool(true) => Some(MsgspecTag::ClassName),
+                            Lit::Bool(false) => None,
+                            Lit::Str(s) => Some(MsgspecTag::Str(s.to_string())),
+                            Lit::Int(i) => {
+                                Some(i.as_i64().map_or(MsgspecTag::Unknown, MsgspecTag::Int))
+                            }
+                            _ => Some(MsgspecTag::Unknown),
+                        },
+                        _ => Some(MsgspecTag::Unknown),
+                    };
+                }
+                "tag_field" => {
+                    sets_tag_field = true;
+                    metadata.tag_field = str_literal(ty).map(str::to_owned);
+                }
+                "array_like" => metadata.array_like = is_true(ty),
+                _ => {}
+            }
+        }
+        // Setting a `tag_field` alone tags the struct with its class name.
+        if sets_tag_field && !sets_tag && metadata.tag.is_none() && metadata.tag_field.is_some() {
+            metadata.tag = Some(MsgspecTag::ClassName);
+        }
+        if metadata.tag.is_some() && metadata.tag_field.is_none() {
+            metadata.tag_field = Some(DEFAULT_TAG_FIELD.to_owned());
+        }
+        if metadata.tag.is_some()
+            && !metadata.array_like
+            && let Some(tag_field) = &metadata.tag_field
+            && let Some(dataclass) = dataclass
+            && metadata
+                .encoded_names(dataclass.instance_fields())
+                .is_some_and(|names| names.contains(tag_field))
+        {
+            self.error(
+                errors,
+                range,
+                ErrorKind::BadClassDefinition,
+                format!("`tag_field` `{tag_field}` conflicts with an existing field of that name"),
+            );
+        }
+        Some(metadata)
+    }
+
+    /// Validate the class keywords of a `msgspec.Struct` subclass. `frozen` and `kw_only` are
+    /// also understood through the `dataclass_transform` on `msgspec.Struct`, but since
+    /// `Struct` has a custom metaclass we otherwise wouldn't look at the keywords at all.
+    fn check_msgspec_struct_options(
+        &self,
+        keywords: &[(Name, Annotation)],
+        bases_with_metadata: &[(Class, Arc<ClassMetadata>)],
+        errors: &ErrorCollector,
+        range: TextRange,
+    ) {
+        for (name, ann) in keywords {
+            if !STRUCT_OPTIONS.contains(&name.as_str()) {
+                self.error(
+                    errors,
+                    range,
+                    ErrorKind::UnexpectedKeyword,
+                    format!("Unexpected keyword `{name}` for `msgspec.Struct` subclass"),
+                );
+            } else if name == &RENAME
+                && let Some(s) = str_literal(ann.get_type())
+                && !RENAME_CONVENTIONS.contains(&s)
+            {
+                self.error(
+                    errors,
+                    range,
+                    ErrorKind::InvalidLiteral,
+                    format!(
+                        "Invalid value for `rename`. Expected one of {}",
+                        RENAME_CONVENTIONS
+                            .iter()
+                            .map(|x| format!("'{x}'"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                );
+            }
+        }
+        let keyword = |name: &Name| {
+            keywords
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, ann)| is_true(ann.get_type()))
+        };
+        if keyword(&CACHE_HASH) == Some(true) {
+            let frozen = keyword(&FROZEN).unwrap_or_else(|| {
+                bases_with_metadata.iter().any(|(_, metadata)| {
+                    metadata
+                        .dataclass_metadata()
+                        .is_some_and(|dm| dm.kws.frozen)
+                })
+            });
+            if !frozen {
+                self.error(
+                    errors,
+                    range,
+                    ErrorKind::BadClassDefinition,
+                    "`cache_hash=True` requires `frozen=True`".to_owned(),
+                );
+            }
+        }
+    }
+
+    /// Synthesize the class attributes describing how a `msgspec.Struct` subclass is encoded:
+    /// `__struct_fields__`, the names of its fields, `__struct_encode_fields__`, their encoded
+    /// names, and `__struct_tag_field__` and `__struct_tag__`, which are `None` unless the
+    /// struct is tagged.
+    pub fn get_msgspec_synthesized_fields(&self, cls: &Class) -> Option<ClassSynthesizedFields> {
+        let metadata = self.get_metadata_for_class(cls);
+        let msgspec = metadata.msgspec_metadata()?;
+        let dataclass = metadata.dataclass_metadata()?;
+        let str_tuple = |names: Vec<String>| {
+            self.heap.mk_concrete_tuple(
+                names
+                    .into_iter()
+                    .map(|name| Lit::Str(name.into()).to_implicit_type())
+                    .collect(),
+            )
+        };
+        let fields = str_tuple(
+            dataclass
+                .instance_fields()
+                .map(|name| name.to_string())
+                .collect(),
+        );
+        let encode_fields = match msgspec.encoded_names(dataclass.instance_fields()) {
+            Some(names) => str_tuple(names),
+            None => self
+                .heap
+                .mk_unbounded_tuple(self.heap.mk_class_type(self.stdlib.str().clone())),
+        };
+        let tag_field = match &msgspec.tag_field {
+            Some(tag_field) if msgspec.tag.is_some() => {
+                Lit::Str(tag_field.as_str().into()).to_implicit_type()
+            }
+            _ => self.heap.mk_none(),
+        };
+        let tag = match &msgspec.tag {
+            None => self.heap.mk_none(),
+            Some(MsgspecTag::ClassName) => Lit::Str(cls.name().as_str().into()).to_implicit_type(),
+            Some(MsgspecTag::Str(s)) => Lit::Str(s.as_str().into()).to_implicit_type(),
+            Some(MsgspecTag::Int(i)) => Lit::Int(LitInt::new(*i)).to_implicit_type(),
+            Some(MsgspecTag::Unknown) => self.unions(vec![
+                self.heap.mk_class_type(self.stdlib.str().clone()),
+                self.heap.mk_class_type(self.stdlib.int().clone()),
+            ]),
+        };
+        Some(ClassSynthesizedFields::new(SmallMap::from_iter([
+            (STRUCT_FIELDS, ClassSynthesizedField::new_classvar(fields)),
+            (
+                STRUCT_ENCODE_FIELDS,
+                ClassSynthesizedField::new_classvar(encode_fields),
+            ),
+            (
+                STRUCT_TAG_FIELD,
+                ClassSynthesizedField::new_classvar(tag_field),
+            ),
+            (STRUCT_TAG, ClassSynthesizedField::new_classvar(tag)),
+        ])))
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::annotation::Annotation;
+use pyrefly_types::class::Class;
+use pyrefly_types::literal::Lit;
+use pyrefly_types::types::Type;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::class::class_field::ClassField;
+use crate::alt::types::class_metadata::ClassMetadata;
+use crate::alt::types::class_metadata::SqlModelMetadata;
+
+const TABLE: Name = Name::new_static("table");
+const INSTRUMENTED_ATTRIBUTE: Name = Name::new_static("InstrumentedAttribute");
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    pub fn sqlmodel_metadata(
+        &self,
+        bases_with_metadata: &[(Class, Arc<ClassMetadata>)],
+        keywords: &[(Name, Annotation)],
+    ) -> Option<SqlModelMetadata> {
+        let is_sqlmodel = bases_with_metadata.iter().any(|(base, metadata)| {
+            base.has_toplevel_qname(ModuleName::sqlmodel_main().as_str(), "SQLModel")
+                || metadata.sqlmodel_metadata().is_some()
+        });
+        if !is_sqlmodel {
+            return None;
+        }
+        let table = keywords.iter().any(|(name, ann)| {
+            name == &TABLE
+                && matches!(ann.get_type(), Type::Literal(lit) if matches!(lit.value, Lit::Bool(true)))
+        });
+        Some(SqlModelMetadata { table })
+    }
+
+    /// On the class object of a SQLModel table model, fields are SQLAlchemy column
+    /// descriptors rather than values, so that `select(Hero).where(Hero.age > 30)` works.
+    pub fn sqlmodel_column_type(
+        &self,
+        cls: &Class,
+        name: &Name,
+        field: &ClassField,
+    ) -> Option<Type> {
+        let metadata = self.get_metadata_for_class(cls);
+        if !metadata.sqlmodel_metadata()?.table
+            || !metadata.dataclass_metadata()?.is_instance_field(name)
+        {
+            return None;
+        }
+        let attribute =
+            self.try_get_from_export(ModuleName::sqlalchemy_orm(), INSTRUMENTED_ATTRIBUTE)?;
+        let Type::ClassDef(attribute) = attribute.as_ref() else {
+            return None;
+        };
+        Some(self.specialize(
+            attribute,
+            vec![field.ty()],
+            TextRange::default(),
+            &self.error_swallower(),
+        ))
+    }
+}
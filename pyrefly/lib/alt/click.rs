@@ -0,0 +1,393 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Checks that the parameters declared by `click` decorators, and the defaults of `typer`
+//! parameters, match the signature of the command function they are passed to.
+
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_graph::index::Idx;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_name::is_python_identifier;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ParameterWithDefault;
+use ruff_python_ast::UnaryOp;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::types::decorated_function::UndecoratedFunction;
+use crate::binding::binding::FunctionDefData;
+use crate::binding::binding::KeyDecorator;
+use crate::error::collector::ErrorCollector;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ClickDecorator {
+    Option,
+    Argument,
+    Command,
+    /// `@pass_context` or `@pass_obj`, which pass an extra first argument.
+    Pass,
+    /// `@version_option`, `@help_option` or `@confirmation_option`, which don't pass a value.
+    NoValue,
+}
+
+/// A parameter declared by `@click.option` or `@click.argument`.
+struct ClickParameter {
+    /// The first declaration, e.g. `--dry-run`, for error messages.
+    declaration: String,
+    /// The name of the function parameter click passes the value as.
+    name: Name,
+    /// The type of the value, if we can tell.
+    ty: Option<Type>,
+    /// Whether the value is passed to the function, i.e. `expose_value` isn't `False`.
+    exposed: bool,
+    range: TextRange,
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    pub fn check_click_command(
+        &self,
+        def: &UndecoratedFunction,
+        stmt: &FunctionDefData,
+        decorators: &[Idx<KeyDecorator>],
+        errors: &ErrorCollector,
+    ) {
+        let parameters = stmt
+            .parameters
+            .posonlyargs
+            .iter()
+            .chain(&stmt.parameters.args)
+            .chain(&stmt.parameters.kwonlyargs)
+            .collect::<Vec<_>>();
+        for param in &parameters {
+            self.check_typer_default(param, def, errors);
+        }
+
+        let mut declared = Vec::new();
+        let mut is_command = false;
+        let mut passes_first_argument = false;
+        // Whether we know the parameters every decorator passes to the function. Other
+        // decorators, like `@click.password_option()` or one returned by
+        // `click.make_pass_decorator`, may supply any parameter.
+        let mut knows_all_decorators = true;
+        for idx in decorators {
+            match self.click_decorator(*idx) {
+                Some((ClickDecorator::Option | ClickDecorator::Argument, None)) | None => {
+                    knows_all_decorators = false;
+                }
+                Some((kind @ (ClickDecorator::Option | ClickDecorator::Argument), Some(call))) => {
+                    match self.click_parameter(call, kind == ClickDecorator::Option) {
+                        Some(parameter) => declared.push(parameter),
+                        // A declaration we don't understand, so we can't tell which parameters
+                        // are accounted for.
+                        None => return,
+                    }
+                }
+                Some((ClickDecorator::Command, _)) => is_command = true,
+                Some((ClickDecorator::Pass, _)) => passes_first_argument = true,
+                Some((ClickDecorator::NoValue, _)) => {}
+            }
+        }
+        if declared.is_empty() && !is_command {
+            return;
+        }
+        declared.retain(|x| x.exposed);
+        let has_kwargs = stmt.parameters.kwarg.is_some();
+        let candidates = parameters
+            .iter()
+            .skip(if passes_first_argument { 1 } else { 0 })
+            .collect::<Vec<_>>();
+        for parameter in &declared {
+            if !candidates
+                .iter()
+                .any(|p| p.parameter.name.id == parameter.name)
+            {
+                if !has_kwargs {
+                    self.error(
+                        errors,
+                        parameter.range,
+                        ErrorKind::BadClickCommand,
+                        format!(
+                            "`{}` is passed as `{}`, which is not a parameter of `{}`",
+                            parameter.declaration, parameter.name, stmt.name
+                        ),
+                    );
+                }
+                continue;
+            }
+            if let Some(got) = &parameter.ty
+                && let Some(want) = def.resolved_param_types.get(&parameter.name)
+                && !self.is_subset_eq(got, want)
+            {
+                self.error(
+                    errors,
+                    parameter.range,
+                    ErrorKind::BadClickCommand,
+                    format!(
+                        "`{}` provides `{}`, which is not assignable to parameter `{}` with type `{}`",
+                        parameter.declaration,
+                        self.for_display(got.clone()),
+                        parameter.name,
+                        self.for_display(want.clone()),
+                    ),
+                );
+            }
+        }
+        if is_command && knows_all_decorators {
+            for param in candidates {
+                let name = &param.parameter.name.id;
+                // Parameters with a default can be left to their default by click.
+                if param.default.is_none() && !declared.iter().any(|x| &x.name == name) {
+                    self.error(
+                        errors,
+                        param.parameter.name.range(),
+                        ErrorKind::BadClickCommand,
+                        format!(
+                            "Parameter `{name}` of command `{}` is not declared by any click option or argument",
+                            stmt.name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Classify the decorator `idx`, returning the call expression if it was called.
+    fn click_decorator(
+        &self,
+        idx: Idx<KeyDecorator>,
+    ) -> Option<(ClickDecorator, Option<&ExprCall>)> {
+        let call = match &self.bindings().get(idx).expr {
+            Expr::Call(call) => Some(call),
+            _ => None,
+        };
+        let decorator = self.get_idx(idx);
+        let ty = match &decorator.callee {
+            Some(callee) => callee,
+            None => &decorator.ty,
+        };
+        let id = ty.to_func_kind()?.as_func_def_id()?;
+        let is_click = match &id.cls {
+            Some(cls) => cls.has_toplevel_qname(ModuleName::click_core().as_str(), "Group"),
+            None => id.qname.module_name() == ModuleName::click_decorators(),
+        };
+        if !is_click {
+            return None;
+        }
+        let kind = match id.qname.id().as_str() {
+            "option" => ClickDecorator::Option,
+            "argument" => ClickDecorator::Argument,
+            "command" | "group" => ClickDecorator::Command,
+            "pass_context" | "pass_obj" => ClickDecorator::Pass,
+            "version_option" | "help_option" | "confirmation_option" => ClickDecorator::NoValue,
+            _ => return None,
+        };
+        Some((kind, call))
+    }
+
+    /// The parameter declared by a `@click.option(...)` or `@click.argument(...)` call.
+    fn click_parameter(&self, call: &ExprCall, is_option: bool) -> Option<ClickParameter> {
+        let declarations = call
+            .arguments
+            .args
+            .iter()
+            .map(|x| match x {
+                Expr::StringLiteral(x) => Some(x.value.to_str().to_owned()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let keyword = |name: &str| call.arguments.find_keyword(name).map(|k| &k.value);
+        let is_true =
+            |name: &str| matches!(keyword(name), Some(Expr::BooleanLiteral(x)) if x.value);
+        let name = if is_option {
+            click_option_name(&declarations)?
+        } else {
+            declarations.first()?.replace('-', "_").to_lowercase()
+        };
+        let is_flag =
+            is_true("is_flag") || (is_option && declarations.iter().any(|x| x.contains('/')));
+        let ty = if is_option && is_true("count") {
+            Some(self.heap.mk_class_type(self.stdlib.int().clone()))
+        } else {
+            self.click_value_type(call, is_option, is_flag)
+        };
+        Some(ClickParameter {
+            declaration: declarations.first()?.clone(),
+            name: Name::new(name),
+            ty,
+            exposed: !matches!(keyword("expose_value"), Some(Expr::BooleanLiteral(x)) if !x.value),
+            range: call.range,
+        })
+    }
+
+    fn click_value_type(&self, call: &ExprCall, is_option: bool, is_flag: bool) -> Option<Type> {
+        let swallower = self.error_swallower();
+        let keyword = |name: &str| call.arguments.find_keyword(name).map(|k| &k.value);
+        let default = keyword("default");
+        let value = if is_flag {
+            self.heap.mk_class_type(self.stdlib.bool().clone())
+        } else if let Some(ty) = keyword("type") {
+            self.click_converted_type(&self.expr_infer(ty, &swallower))?
+        } else if let Some(default) = default {
+            if keyword("multiple").is_some() {
+                return None;
+            }
+            self.expr_infer(default, &swallower)
+                .promote_implicit_literals(self.stdlib)
+        } else {
+            self.heap.mk_class_type(self.stdlib.str().clone())
+        };
+        let multiple = matches!(keyword("multiple"), Some(Expr::BooleanLiteral(x)) if x.value);
+        let nargs = match keyword("nargs") {
+            Some(Expr::NumberLiteral(x)) => x.value.as_int().and_then(|x| x.as_i64()),
+            Some(Expr::UnaryOp(x))
+                if x.op == UnaryOp::USub && matches!(&*x.operand, Expr::NumberLiteral(_)) =>
+            {
+                Some(-1)
+            }
+            Some(_) => return None,
+            None => None,
+        };
+        let value = match nargs {
+            Some(n) if n > 1 => self.heap.mk_concrete_tuple(vec![value; n as usize]),
+            Some(n) if n < 0 => self.heap.mk_unbounded_tuple(value),
+            _ => value,
+        };
+        if multiple {
+            return Some(self.heap.mk_unbounded_tuple(value));
+        }
+        let required = match keyword("required") {
+            Some(Expr::BooleanLiteral(x)) => x.value,
+            Some(_) => return Some(value),
+            None => !is_option && nargs.is_none_or(|n| n >= 0),
+        };
+        if required || is_flag || default.is_some() || nargs.is_some_and(|n| n < 0) {
+            Some(value)
+        } else {
+            Some(self.union(value, self.heap.mk_none()))
+        }
+    }
+
+    /// The type a click `type=` converts values to, if we can tell.
+    fn click_converted_type(&self, ty: &Type) -> Option<Type> {
+        match ty {
+            Type::ClassDef(cls)
+                if ["int", "float", "str", "bool"]
+                    .iter()
+                    .any(|x| cls.is_builtin(x)) =>
+            {
+                Some(self.instantiate(cls))
+            }
+            Type::ClassType(cls) => {
+                let module = ModuleName::click_types();
+                if cls.has_qname(module.as_str(), "Choice") {
+                    Some(self.heap.mk_class_type(self.stdlib.str().clone()))
+                } else if cls.has_qname(module.as_str(), "IntRange") {
+                    Some(self.heap.mk_class_type(self.stdlib.int().clone()))
+                } else if cls.has_qname(module.as_str(), "FloatRange") {
+                    Some(self.heap.mk_class_type(self.stdlib.float().clone()))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// `typer.Option(default, ...)` and `typer.Argument(default, ...)` give the default of the
+    /// parameter, which must match its annotation.
+    fn check_typer_default(
+        &self,
+        param: &ParameterWithDefault,
+        def: &UndecoratedFunction,
+        errors: &ErrorCollector,
+    ) {
+        let Some(Expr::Call(call)) = param.default.as_deref() else {
+            return;
+        };
+        let callee_name = match &*call.func {
+            Expr::Name(x) => &x.id,
+            Expr::Attribute(x) => &x.attr.id,
+            _ => return,
+        };
+        if !matches!(callee_name.as_str(), "Option" | "Argument") {
+            return;
+        }
+        let default = match call.arguments.args.first() {
+            Some(x) => Some(x),
+            None => call.arguments.find_keyword("default").map(|k| &k.value),
+        };
+        let Some(default) = default else {
+            return;
+        };
+        // `...` marks the parameter as required, and a string starting with `-` is an
+        // old-style parameter declaration rather than a default.
+        match default {
+            Expr::EllipsisLiteral(_) | Expr::NoneLiteral(_) => return,
+            Expr::StringLiteral(x) if x.value.to_str().starts_with('-') => return,
+            _ => {}
+        }
+        let Some(callee) = self.solved_expr_type(&call.func) else {
+            return;
+        };
+        let Some(id) = callee.to_func_kind().and_then(|k| k.as_func_def_id()) else {
+            return;
+        };
+        if id.qname.module_name() != ModuleName::typer_params() {
+            return;
+        }
+        let name = &param.parameter.name.id;
+        let Some(want) = def.resolved_param_types.get(name) else {
+            return;
+        };
+        let Some(got) = self.solved_expr_type(default) else {
+            return;
+        };
+        if !self.is_subset_eq(&got, want) {
+            self.error(
+                errors,
+                default.range(),
+                ErrorKind::BadClickCommand,
+                format!(
+                    "Default `{}` is not assignable to parameter `{name}` with type `{}`",
+                    self.for_display(got),
+                    self.for_display(want.clone()),
+                ),
+            );
+        }
+    }
+}
+
+/// The parameter name click derives for an option: an explicit identifier if one is given,
+/// otherwise the declaration with the longest prefix, with dashes replaced by underscores.
+fn click_option_name(declarations: &[String]) -> Option<String> {
+    let mut best: Option<(usize, &str)> = None;
+    for declaration in declarations {
+        if is_python_identifier(declaration) {
+            return Some(declaration.clone());
+        }
+        // `--shout/--no-shout` declares a flag with a secondary name.
+        let declaration = declaration.split('/').next()?.trim();
+        let mut chars = declaration.chars();
+        let first = chars.next()?;
+        let prefix_len = if first.is_alphanumeric() {
+            0
+        } else if chars.next() == Some(first) {
+            2
+        } else {
+            1
+        };
+        if best.is_none_or(|(len, _)| prefix_len > len) {
+            best = Some((prefix_len, &declaration[prefix_len..]));
+        }
+    }
+    best.map(|(_, name)| name.replace('-', "_").to_lowercase())
+}
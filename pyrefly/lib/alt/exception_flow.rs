@@ -0,0 +1,220 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Reporting `except` clauses shadowed by earlier ones, and computing the exceptions that may
+//! escape a function, which are checked against the `Raises` section of its docstring and shown
+//! on hover. See `binding::exception_flow` for what is recorded.
+
+use std::sync::Arc;
+
+use dupe::Dupe;
+use pyrefly_graph::index::Idx;
+use ruff_python_ast::Expr;
+use ruff_text_size::Ranged;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::types::function_raises::FunctionRaises;
+use crate::binding::binding::BindingFunctionLocalRaises;
+use crate::binding::binding::BindingFunctionRaises;
+use crate::binding::binding::Key;
+use crate::binding::binding::RaiseSite;
+use crate::binding::binding::RaiseSiteKind;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::types::class::Class;
+use crate::types::function::FuncDefId;
+use crate::types::function::FunctionKind;
+use crate::types::tuple::Tuple;
+use crate::types::types::Type;
+
+/// The exception classes in `ty`, which is either the type caught by an `except` clause
+/// or the type of a raised expression. Returns `None` if any part of `ty` is not a class
+/// or an instance of one, since then we can't tell what it matches.
+pub fn exception_classes(ty: &Type) -> Option<Vec<Class>> {
+    match ty {
+        Type::ClassType(cls) => Some(vec![cls.class_object().dupe()]),
+        Type::ClassDef(cls) => Some(vec![cls.dupe()]),
+        Type::Type(inner) => match &**inner {
+            Type::ClassType(cls) => Some(vec![cls.class_object().dupe()]),
+            _ => None,
+        },
+        Type::Union(union) => exception_classes_of_all(&union.members),
+        Type::Tuple(Tuple::Concrete(elts)) => exception_classes_of_all(elts),
+        _ => None,
+    }
+}
+
+/// The exception classes in all of `tys`, or `None` if we can't tell for any of them.
+pub fn exception_classes_of_all(tys: &[Type]) -> Option<Vec<Class>> {
+    let mut classes = Vec::new();
+    for ty in tys {
+        classes.extend(exception_classes(ty)?);
+    }
+    Some(classes)
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Report the `except` clause catching `exceptions` if an earlier clause of the same `try`
+    /// already catches everything it does.
+    pub fn check_for_unreachable_except(
+        &self,
+        ann: &Expr,
+        exceptions: &Type,
+        errors: &ErrorCollector,
+    ) {
+        let earlier_handlers = self.bindings().earlier_except_handlers(ann.range());
+        if earlier_handlers.is_empty() {
+            return;
+        }
+        let Some(caught) = exception_classes(exceptions) else {
+            return;
+        };
+        for idx in earlier_handlers {
+            let earlier = self.get_idx(*idx);
+            let Some(earlier_caught) = exception_classes(earlier.ty()) else {
+                continue;
+            };
+            let is_caught_earlier = |cls: &Class| {
+                earlier_caught
+                    .iter()
+                    .find(|earlier| self.has_superclass(cls, earlier))
+            };
+            if caught.iter().all(|cls| is_caught_earlier(cls).is_some())
+                && let Some(shadowing) = caught.first().and_then(is_caught_earlier)
+            {
+                self.error(
+                    errors,
+                    ann.range(),
+                    ErrorKind::UnreachableExcept,
+                    format!(
+                        "`except {}` is unreachable because an earlier `except` clause catches `{}`",
+                        self.module().display(ann),
+                        shadowing.name(),
+                    ),
+                );
+                return;
+            }
+        }
+    }
+
+    /// The exception classes caught by `handlers`, or `None` if one of them might catch anything.
+    fn caught_exceptions(&self, handlers: &[Idx<Key>]) -> Option<Vec<Class>> {
+        let mut caught = Vec::new();
+        for idx in handlers {
+            caught.extend(exception_classes(self.get_idx(*idx).ty())?);
+        }
+        Some(caught)
+    }
+
+    fn is_documented_raise(&self, cls: &Class, documented: &[String]) -> bool {
+        let mro = self.get_mro_for_class(cls);
+        documented.iter().any(|name| {
+            let name = name.rsplit('.').next().unwrap_or(name);
+            cls.name().as_str() == name
+                || mro
+                    .ancestors(self.stdlib)
+                    .any(|ancestor| ancestor.name().as_str() == name)
+        })
+    }
+
+    /// The exception classes that may escape `site`, where `callee_raises` gives the exceptions
+    /// that may escape a call to a function.
+    fn raise_site_exceptions(
+        &self,
+        site: &RaiseSite,
+        callee_raises: impl Fn(&FuncDefId) -> Option<Arc<FunctionRaises>>,
+    ) -> Vec<Class> {
+        let Some(caught) = self.caught_exceptions(&site.handlers) else {
+            return Vec::new();
+        };
+        // The sites were already checked as part of the statements they appear in.
+        let errors = self.error_swallower();
+        let raised = match &site.kind {
+            RaiseSiteKind::Raise(exc) => exception_classes(&self.expr_infer(exc, &errors)),
+            RaiseSiteKind::Call(callee) => match self.expr_infer(callee, &errors).to_func_kind() {
+                Some(FunctionKind::Def(func)) => {
+                    callee_raises(func).map(|raises| raises.0.to_vec())
+                }
+                _ => None,
+            },
+        };
+        raised
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|cls| {
+                !caught
+                    .iter()
+                    .any(|handler| self.has_superclass(cls, handler))
+            })
+            .collect()
+    }
+
+    fn is_in_this_module(&self, func: &FuncDefId) -> bool {
+        func.qname.module_name() == self.module().name()
+            && func.qname.module_path() == self.module().path()
+    }
+
+    /// Compute the exceptions that may escape a call to a function, following only calls to
+    /// functions in this module.
+    pub fn solve_function_local_raises(
+        &self,
+        binding: &BindingFunctionLocalRaises,
+    ) -> Arc<FunctionRaises> {
+        let mut raises = SmallSet::new();
+        for site in &self.bindings().get(binding.0).sites {
+            raises.extend(self.raise_site_exceptions(site, |func| {
+                if self.is_in_this_module(func) {
+                    self.get_function_local_raises(func)
+                } else {
+                    None
+                }
+            }));
+        }
+        Arc::new(FunctionRaises(raises.into_iter().collect()))
+    }
+
+    /// Compute the exceptions that may escape a call to a function, and report the ones missing
+    /// from the `Raises` section of its docstring.
+    pub fn solve_function_raises(
+        &self,
+        binding: &BindingFunctionRaises,
+        errors: &ErrorCollector,
+    ) -> Arc<FunctionRaises> {
+        let mut raises = SmallSet::new();
+        for site in &binding.sites {
+            let site_raises = self.raise_site_exceptions(site, |func| {
+                if self.is_in_this_module(func) {
+                    self.get_function_raises(func)
+                } else {
+                    // Only follow calls into other modules one level deep.
+                    self.get_function_local_raises(func)
+                }
+            });
+            for cls in site_raises {
+                if let Some(documented) = &binding.documented
+                    && !self.is_documented_raise(&cls, documented)
+                {
+                    let msg = match &site.kind {
+                        RaiseSiteKind::Raise(_) => format!(
+                            "`{}` is raised but not listed in the `Raises` section of the docstring",
+                            cls.name(),
+                        ),
+                        RaiseSiteKind::Call(_) => format!(
+                            "`{}` may be raised by this call but is not listed in the `Raises` section of the docstring",
+                            cls.name(),
+                        ),
+                    };
+                    self.error(errors, site.range(), ErrorKind::UndocumentedRaise, msg);
+                }
+                raises.insert(cls);
+            }
+        }
+        Arc::new(FunctionRaises(raises.into_iter().collect()))
+    }
+}
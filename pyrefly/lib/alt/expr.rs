@@ -408,6 +408,16 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             .then(|| self.get_hashed(Hashed::new(&anon_key)).ty().clone())
     }
 
+    /// The namespace synthesized for a `parse_args()` call on a parser followed at binding time.
+    fn argparse_namespace_call_type(&self, call: &ExprCall) -> Option<Type> {
+        let anon_key = Key::Anon(call.range);
+        let idx = self
+            .bindings()
+            .key_to_idx_hashed_opt(Hashed::new(&anon_key))?;
+        matches!(self.bindings().get(idx), Binding::ArgparseNamespace(..))
+            .then(|| self.get_hashed(Hashed::new(&anon_key)).ty().clone())
+    }
+
     /// Infer a type for an expression, with an optional type hint that influences the inferred type.
     /// The inferred type is also checked against the hint.
     /// Convenience wrapper around `expr_with_options`.
@@ -517,6 +527,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         };
         // Check for deprecation
         self.check_for_deprecated_call(res.ty(), x.range(), errors);
+        if let Expr::Name(_) = x {
+            self.check_for_banned_api(res.ty(), None, x.range(), errors);
+        }
         self.record_type_trace(x.range(), res.ty());
         res
     }
@@ -976,9 +989,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             // and deprecation check here as that path would for any other expression.
             self.check_for_deprecated_call(attr.ty(), func.range(), errors);
             self.record_type_trace(func.range(), attr.ty());
+            let access = Some((base.ty(), &func.attr.id));
+            self.check_for_banned_api_call(x, attr.ty(), access, errors);
+            self.check_for_blocking_call_in_async(x, attr.ty(), access, errors);
             attr.into_ty()
         } else {
-            self.expr_infer(&x.func, errors)
+            let callee_ty = self.expr_infer(&x.func, errors);
+            self.check_for_banned_api_call(x, &callee_ty, None, errors);
+            self.check_for_blocking_call_in_async(x, &callee_ty, None, errors);
+            callee_ty
         };
         PreparedExprCall::Callee(callee_ty)
     }
@@ -1049,7 +1068,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     .into_ty()
             }
         } else {
-            self.expr_call_infer(x, callee_ty, hint, errors)
+            let ty = self.expr_call_infer(x, callee_ty, hint, errors);
+            // The call is still checked against `parse_args`, but its result is the
+            // namespace we synthesized from the parser's arguments.
+            self.argparse_namespace_call_type(x).unwrap_or(ty)
         }
     }
 
@@ -2100,6 +2122,36 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         )
     }
 
+    /// The type of an expression that is part of a definition already solved, like a parameter
+    /// default or decorator argument, without inferring it again: names are read from their
+    /// bindings and literals are typed directly. `None` for other expressions.
+    pub(crate) fn solved_expr_type(&self, x: &Expr) -> Option<Type> {
+        match x {
+            Expr::Name(name) => Some(
+                self.get_hashed_opt(Hashed::new(&Key::BoundName(ShortIdentifier::expr_name(
+                    name,
+                ))))?
+                .arc_clone_ty(),
+            ),
+            Expr::Attribute(attr) => {
+                let base = self.solved_expr_type(&attr.value)?;
+                Some(self.attr_infer_for_type(
+                    &base,
+                    &attr.attr.id,
+                    attr.range,
+                    &self.error_swallower(),
+                    None,
+                ))
+            }
+            Expr::StringLiteral(_)
+            | Expr::BytesLiteral(_)
+            | Expr::NumberLiteral(_)
+            | Expr::BooleanLiteral(_)
+            | Expr::NoneLiteral(_) => Some(self.expr_infer(x, &self.error_swallower())),
+            _ => None,
+        }
+    }
+
     /// Infer an attribute access from its already-inferred base. Factored from the
     /// `Expr::Attribute` arm so a method call can infer its receiver once and reuse it.
     fn attr_access_infer(
@@ -2115,6 +2167,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             AttributeReferenceKind::Textual,
         );
         let attr_type = self.attr_infer(base, &x.attr.id, x.range, errors, None);
+        self.check_for_banned_api(
+            attr_type.ty(),
+            Some((base.ty(), &x.attr.id)),
+            x.range,
+            errors,
+        );
         if base.ty().is_literal_string() {
             match attr_type.ty() {
                 Type::BoundMethod(method) => attr_type
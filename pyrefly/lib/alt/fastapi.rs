@@ -0,0 +1,424 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Checks for FastAPI endpoints and dependencies. FastAPI injects parameters based on their
+//! defaults and `Annotated` metadata, so the mistakes these checks look for otherwise only
+//! show up when a request is served.
+
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_graph::index::Idx;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_types::callable::Params;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::types::decorated_function::UndecoratedFunction;
+use crate::binding::binding::FunctionDefData;
+use crate::binding::binding::KeyDecorator;
+use crate::error::collector::ErrorCollector;
+
+/// Methods of `FastAPI` and `APIRouter` that register the decorated function as an endpoint.
+const ROUTE_METHODS: &[&str] = &[
+    "get",
+    "put",
+    "post",
+    "delete",
+    "options",
+    "head",
+    "patch",
+    "trace",
+    "api_route",
+    "websocket",
+];
+
+/// Functions in `fastapi.param_functions` whose argument is a dependency to call.
+const DEPENDENCY_MARKERS: &[&str] = &["Depends", "Security"];
+
+/// Functions in `fastapi.param_functions` whose first argument is the default of the parameter.
+const PARAMETER_MARKERS: &[&str] = &["Path", "Query", "Header", "Cookie", "Body", "Form", "File"];
+
+/// Return types of dependencies that are iterated once to produce the injected value.
+const GENERATOR_CLASSES: &[&str] = &["Generator", "Iterator", "AsyncGenerator", "AsyncIterator"];
+
+/// A `@app.get(...)`-style decorator on an endpoint.
+struct FastApiRoute<'e> {
+    path: Option<&'e Expr>,
+    response_model: Option<&'e Expr>,
+    has_dependencies: bool,
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Check the parameters of a function that uses FastAPI dependency injection, and, if it is
+    /// decorated as an endpoint, its route path and `response_model`.
+    pub fn check_fastapi_function(
+        &self,
+        def: &UndecoratedFunction,
+        stmt: &FunctionDefData,
+        decorators: &[Idx<KeyDecorator>],
+        errors: &ErrorCollector,
+    ) {
+        let routes = decorators
+            .iter()
+            .filter_map(|idx| self.fastapi_route(*idx))
+            .collect::<Vec<_>>();
+        let parameters = stmt
+            .parameters
+            .posonlyargs
+            .iter()
+            .chain(&stmt.parameters.args)
+            .chain(&stmt.parameters.kwonlyargs);
+        // The names a request may supply to this endpoint, including through the parameters
+        // of its dependencies. `None` when a dependency's parameters can't be determined.
+        let mut injected_names = Some(SmallSet::new());
+        for param in parameters {
+            let name = &param.parameter.name.id;
+            if let Some(names) = &mut injected_names {
+                names.insert(name.clone());
+            }
+            if let Some(default) = param.default.as_deref()
+                && let Some(want) = def.resolved_param_types.get(name)
+            {
+                self.check_fastapi_parameter_default(default, want, name, errors);
+            }
+            let annotation_metadata = match param.parameter.annotation.as_deref() {
+                Some(Expr::Subscript(subscript)) => match &*subscript.slice {
+                    Expr::Tuple(tuple) => tuple.elts.get(1..).unwrap_or_default(),
+                    _ => &[],
+                },
+                _ => &[],
+            };
+            for marker in param
+                .default
+                .as_deref()
+                .into_iter()
+                .chain(annotation_metadata)
+            {
+                let Some(dependency) = self.fastapi_dependency(marker) else {
+                    continue;
+                };
+                // `Depends()` with no argument calls the annotated class itself.
+                let Some(dependency) = dependency else {
+                    continue;
+                };
+                let dependency_ty = self.solved_expr_type(dependency);
+                match (
+                    &mut injected_names,
+                    dependency_ty.clone().and_then(|ty| ty.to_callable()),
+                ) {
+                    (Some(names), Some(callable)) => {
+                        if let Params::List(params) = &callable.params {
+                            names.extend(params.items().iter().filter_map(|p| p.name().cloned()));
+                        }
+                    }
+                    _ => injected_names = None,
+                }
+                if let Some(dependency_ty) = &dependency_ty
+                    && let Some(want) = def.resolved_param_types.get(name)
+                {
+                    self.check_fastapi_dependency(dependency_ty, want, name, dependency, errors);
+                }
+            }
+        }
+        for route in routes {
+            if let Some(path) = route.path
+                && let Some(names) = &injected_names
+                && !route.has_dependencies
+            {
+                self.check_fastapi_route_path(path, names, &stmt.name.id, errors);
+            }
+            if let Some(response_model) = route.response_model {
+                self.check_fastapi_response_model(response_model, stmt, errors);
+            }
+        }
+    }
+
+    /// If the decorator `idx` registers an endpoint on a `FastAPI` app or an `APIRouter`, the
+    /// relevant arguments of the decorator.
+    fn fastapi_route(&self, idx: Idx<KeyDecorator>) -> Option<FastApiRoute<'_>> {
+        let Expr::Call(ExprCall {
+            func, arguments, ..
+        }) = &self.bindings().get(idx).expr
+        else {
+            return None;
+        };
+        let Expr::Attribute(attr) = &**func else {
+            return None;
+        };
+        if !ROUTE_METHODS.contains(&attr.attr.id.as_str()) {
+            return None;
+        }
+        let decorator = self.get_idx(idx);
+        let Some(Type::BoundMethod(bm)) = &decorator.callee else {
+            return None;
+        };
+        let cls = bm.func.metadata().kind.as_func_def_id()?.cls.as_ref()?;
+        if !cls.has_toplevel_qname(ModuleName::fastapi_applications().as_str(), "FastAPI")
+            && !cls.has_toplevel_qname(ModuleName::fastapi_routing().as_str(), "APIRouter")
+        {
+            return None;
+        }
+        Some(FastApiRoute {
+            path: arguments.args.first(),
+            response_model: arguments.find_keyword("response_model").map(|k| &k.value),
+            has_dependencies: arguments.find_keyword("dependencies").is_some(),
+        })
+    }
+
+    /// If `marker` is a call to one of the `markers` in `fastapi.param_functions`, the call.
+    fn fastapi_marker<'e>(&self, marker: &'e Expr, markers: &[&str]) -> Option<&'e ExprCall> {
+        let Expr::Call(call) = marker else {
+            return None;
+        };
+        let callee_name = match &*call.func {
+            Expr::Name(x) => &x.id,
+            Expr::Attribute(x) => &x.attr.id,
+            _ => return None,
+        };
+        if !markers.contains(&callee_name.as_str()) {
+            return None;
+        }
+        let callee = self.solved_expr_type(&call.func)?;
+        let id = callee.to_func_kind()?.as_func_def_id()?;
+        if id.qname.module_name() != ModuleName::fastapi_param_functions() {
+            return None;
+        }
+        Some(call)
+    }
+
+    /// If `marker` is a `Depends(...)` or `Security(...)` call, its dependency argument.
+    fn fastapi_dependency<'e>(&self, marker: &'e Expr) -> Option<Option<&'e Expr>> {
+        let call = self.fastapi_marker(marker, DEPENDENCY_MARKERS)?;
+        Some(
+            call.arguments
+                .args
+                .first()
+                .or_else(|| call.arguments.find_keyword("dependency").map(|k| &k.value)),
+        )
+    }
+
+    /// The value FastAPI injects for a dependency: its return value, awaited if it is a
+    /// coroutine and with the single yielded value taken if it is a generator.
+    fn fastapi_dependency_value(&self, dependency: &Type) -> Option<Type> {
+        let ret = match dependency {
+            Type::ClassDef(cls) => return Some(self.instantiate(cls)),
+            _ => dependency.clone().to_callable()?.ret,
+        };
+        let ret = match &ret {
+            Type::ClassType(ct) if ct.has_qname("typing", "Coroutine") => {
+                ct.targs().as_slice().get(2)?.clone()
+            }
+            _ => ret,
+        };
+        let ret = match &ret {
+            Type::ClassType(ct) if GENERATOR_CLASSES.iter().any(|x| ct.has_qname("typing", x)) => {
+                ct.targs().as_slice().first()?.clone()
+            }
+            _ => ret,
+        };
+        // A generic dependency is solved against the parameter, which we don't attempt.
+        if ret.contains_type_variable() {
+            None
+        } else {
+            Some(ret)
+        }
+    }
+
+    fn check_fastapi_dependency(
+        &self,
+        dependency_ty: &Type,
+        want: &Type,
+        name: &Name,
+        dependency: &Expr,
+        errors: &ErrorCollector,
+    ) {
+        let Some(got) = self.fastapi_dependency_value(dependency_ty) else {
+            return;
+        };
+        if !self.is_subset_eq(&got, want) {
+            self.error(
+                errors,
+                dependency.range(),
+                ErrorKind::BadFastapiEndpoint,
+                format!(
+                    "Dependency provides `{}`, which is not assignable to parameter `{name}` with type `{}`",
+                    self.for_display(got),
+                    self.for_display(want.clone()),
+                ),
+            );
+        }
+    }
+
+    /// A parameter whose default is `Query(default)`, `Path(default)` and so on gets `default`
+    /// when the request doesn't supply it, so the default must match the parameter type. A
+    /// default of `...` marks the parameter as required.
+    fn check_fastapi_parameter_default(
+        &self,
+        default: &Expr,
+        want: &Type,
+        name: &Name,
+        errors: &ErrorCollector,
+    ) {
+        let Some(call) = self.fastapi_marker(default, PARAMETER_MARKERS) else {
+            return;
+        };
+        let Some(value) = call
+            .arguments
+            .args
+            .first()
+            .or_else(|| call.arguments.find_keyword("default").map(|k| &k.value))
+        else {
+            return;
+        };
+        if matches!(value, Expr::EllipsisLiteral(_)) {
+            return;
+        }
+        let Some(got) = self.solved_expr_type(value) else {
+            return;
+        };
+        if !self.is_subset_eq(&got, want) {
+            self.error(
+                errors,
+                value.range(),
+                ErrorKind::BadFastapiEndpoint,
+                format!(
+                    "Default `{}` is not assignable to parameter `{name}` with type `{}`",
+                    self.for_display(got),
+                    self.for_display(want.clone()),
+                ),
+            );
+        }
+    }
+
+    /// Every `{name}` in the path template must be supplied to the endpoint or one of its
+    /// dependencies.
+    fn check_fastapi_route_path(
+        &self,
+        path: &Expr,
+        names: &SmallSet<Name>,
+        function_name: &Name,
+        errors: &ErrorCollector,
+    ) {
+        let Expr::StringLiteral(path) = path else {
+            return;
+        };
+        let template = path.value.to_str();
+        for segment in template.split('{').skip(1) {
+            let Some((placeholder, _)) = segment.split_once('}') else {
+                continue;
+            };
+            // `{file_path:path}` uses a Starlette converter.
+            let placeholder = placeholder.split(':').next().unwrap_or(placeholder);
+            if !names.contains(&Name::new(placeholder)) {
+                self.error(
+                    errors,
+                    path.range(),
+                    ErrorKind::BadFastapiEndpoint,
+                    format!(
+                        "Path parameter `{placeholder}` in `{template}` is not a parameter of `{function_name}`"
+                    ),
+                );
+            }
+        }
+    }
+
+    /// FastAPI validates the value returned by an endpoint against its `response_model`. Model
+    /// types are validated field by field, so returning a different model with the required
+    /// fields is fine. Only an annotated return type is checked, since the inferred one
+    /// depends on the body of the function rather than its signature.
+    fn check_fastapi_response_model(
+        &self,
+        response_model: &Expr,
+        stmt: &FunctionDefData,
+        errors: &ErrorCollector,
+    ) {
+        let swallower = self.error_swallower();
+        let Some(model) = self.untype_opt(
+            self.expr_infer(response_model, &swallower),
+            response_model.range(),
+            &swallower,
+        ) else {
+            return;
+        };
+        let Some(annotation) = self.bindings().function_return_annotation(&stmt.name) else {
+            return;
+        };
+        let ret = self.get_idx(annotation).annotation.get_type().clone();
+        if ret.is_any() || model.is_none() || self.is_subset_eq(&ret, &model) {
+            return;
+        }
+        let missing = match (&ret, &model) {
+            (Type::ClassType(got), Type::ClassType(want)) => {
+                let want_metadata = self.get_metadata_for_class(want.class_object());
+                let Some(dataclass) = want_metadata.dataclass_metadata() else {
+                    return self.response_model_error(&ret, &model, response_model, errors);
+                };
+                if got.is_builtin("dict") {
+                    // Dictionaries are validated key by key at runtime.
+                    return;
+                }
+                let mut missing = Vec::new();
+                for (name, field, keywords) in
+                    self.iter_fields(want.class_object(), dataclass, false, &SmallMap::new())
+                {
+                    if keywords.default.is_some() {
+                        continue;
+                    }
+                    match self.get_class_member(got.class_object(), &name) {
+                        Some(provided) if self.is_subset_eq(&provided.ty(), &field.ty()) => {}
+                        _ => missing.push(name),
+                    }
+                }
+                missing
+            }
+            _ => return self.response_model_error(&ret, &model, response_model, errors),
+        };
+        if !missing.is_empty() {
+            self.error(
+                errors,
+                response_model.range(),
+                ErrorKind::BadReturn,
+                format!(
+                    "Returned `{}` does not provide field{} {} required by `response_model` `{}`",
+                    self.for_display(ret),
+                    if missing.len() == 1 { "" } else { "s" },
+                    missing
+                        .iter()
+                        .map(|x| format!("`{x}`"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    self.for_display(model),
+                ),
+            );
+        }
+    }
+
+    fn response_model_error(
+        &self,
+        ret: &Type,
+        model: &Type,
+        response_model: &Expr,
+        errors: &ErrorCollector,
+    ) {
+        self.error(
+            errors,
+            response_model.range(),
+            ErrorKind::BadReturn,
+            format!(
+                "Returned `{}` is not compatible with `response_model` `{}`",
+                self.for_display(ret.clone()),
+                self.for_display(model.clone()),
+            ),
+        );
+    }
+}
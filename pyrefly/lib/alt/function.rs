@@ -99,6 +99,9 @@ use crate::types::types::Overload;
 use crate::types::types::OverloadType;
 use crate::types::types::Type;
 
+/// Label for the annotation pointing at an overload's implementation when it is inconsistent.
+const IMPLEMENTATION_LABEL: &str = "implementation defined here";
+
 /// Extract a display string for numeric default values whose types don't preserve
 /// the source spelling. This preserves both values that lose precision in the
 /// type, like floats, and integer literal spelling, like `0o777`.
@@ -1929,6 +1932,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         {
             return decoratee;
         }
+        // Bare `@jax.vmap` maps every argument and the result over their leading axis.
+        if Self::is_jax_vmap(&decorator)
+            && let Some(vmapped) = self.jax_vmap(&decoratee, None, None, range, errors)
+        {
+            return vmapped;
+        }
         let application = self.prepare_decorator_application(decorator, decoratee, range, errors);
         // Run a decorator call, buffering errors so we can decide between the primary
         // and Self-rewritten fallback without double-reporting.
@@ -2308,21 +2317,24 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         original_overload_func.signature.clone(),
                         impl_sig.clone(),
                     ))
+                    .with_annotation(Some(def.id_range()), IMPLEMENTATION_LABEL.to_owned())
                 },
             );
             if let Err(specialization_errors) = self.finish_quantified(vs, false) {
-                let mut builder = errors.error_builder(
-                    *range,
-                    ErrorKind::InconsistentOverload,
-                    format!(
-                        "Overload signature `{}` is not consistent with implementation signature `{}`",
-                        self.for_display(
-                            self.heap
-                                .mk_callable_from(original_overload_func.signature.clone())
+                let mut builder = errors
+                    .error_builder(
+                        *range,
+                        ErrorKind::InconsistentOverload,
+                        format!(
+                            "Overload signature `{}` is not consistent with implementation signature `{}`",
+                            self.for_display(
+                                self.heap
+                                    .mk_callable_from(original_overload_func.signature.clone())
+                            ),
+                            self.for_display(self.heap.mk_callable_from(impl_sig.clone())),
                         ),
-                        self.for_display(self.heap.mk_callable_from(impl_sig.clone())),
-                    ),
-                );
+                    )
+                    .with_annotation(def.id_range(), IMPLEMENTATION_LABEL.to_owned());
                 for e in specialization_errors {
                     builder = builder.with_detail(e.to_error_msg(self));
                 }
@@ -2334,7 +2346,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 &impl_ret.promote_implicit_literals(self.stdlib),
                 *range,
                 errors,
-                &|| TypeCheckContext::of_kind(TypeCheckKind::OverloadReturn),
+                &|| {
+                    TypeCheckContext::of_kind(TypeCheckKind::OverloadReturn)
+                        .with_annotation(Some(def.id_range()), IMPLEMENTATION_LABEL.to_owned())
+                },
             );
             match &overload_func.signature.params {
                 Params::List(params) => {
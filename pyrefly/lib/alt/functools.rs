@@ -31,6 +31,7 @@ use crate::alt::callable::CallKeyword;
 use crate::alt::unwrap::HintRef;
 use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
+use crate::error::error::ErrorQuickFix;
 use crate::types::callable::Callable;
 use crate::types::callable::Param;
 use crate::types::callable::ParamList;
@@ -95,20 +96,24 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     let abstract_members = self.get_abstract_members_for_class(&cls);
                     let unimplemented = abstract_members.unimplemented_abstract_methods();
                     if !unimplemented.is_empty() {
-                        self.error(
-                            errors,
-                            callee_range,
-                            ErrorKind::BadInstantiation,
-                            format!(
-                                "Cannot instantiate `{}` because the following members are abstract: {}",
-                                cls.name(),
-                                unimplemented
-                                    .iter()
-                                    .map(|x| format!("`{x}`"))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            ),
-                        );
+                        errors
+                            .error_builder(
+                                callee_range,
+                                ErrorKind::BadInstantiation,
+                                format!(
+                                    "Cannot instantiate `{}` because the following members are abstract: {}",
+                                    cls.name(),
+                                    unimplemented
+                                        .iter()
+                                        .map(|x| format!("`{x}`"))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            )
+                            .with_quick_fix(ErrorQuickFix::ImplementAbstractMembers {
+                                class: cls.clone(),
+                            })
+                            .emit();
                     } else if metadata.is_explicitly_abstract() {
                         self.error(
                             errors,
@@ -277,10 +282,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             },
             _ => return fallback(self),
         };
-        // Only plain type variables are re-scoped correctly; a `ParamSpec` or `TypeVarTuple` target
-        // needs structural residual handling we don't do, so defer it to the stub.
+        // Only plain type variables and shape `IntVar`s are re-scoped correctly; a `ParamSpec` or
+        // `TypeVarTuple` target needs structural residual handling we don't do, so defer it to the stub.
         if let Some(tparams) = &tparams
-            && !tparams.iter().all(|q| q.kind() == QuantifiedKind::TypeVar)
+            && !tparams
+                .iter()
+                .all(|q| matches!(q.kind(), QuantifiedKind::TypeVar | QuantifiedKind::IntVar))
         {
             return fallback(self);
         }
@@ -0,0 +1,391 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Shape propagation through `jax.vmap`. The stub can't express that the mapped function gains
+//! a batch dimension on every mapped argument and result, so we synthesize the batched signature
+//! from the signature of the mapped function and the `in_axes`/`out_axes` literals.
+
+use std::sync::Arc;
+
+use dupe::Dupe;
+use pyrefly_types::callable::Callable;
+use pyrefly_types::callable::Param;
+use pyrefly_types::callable::ParamList;
+use pyrefly_types::callable::Params;
+use pyrefly_types::callable::Required;
+use pyrefly_types::dimension::Int;
+use pyrefly_types::function::FunctionKind;
+use pyrefly_types::quantified::AnchorIndex;
+use pyrefly_types::quantified::Quantified;
+use pyrefly_types::quantified::QuantifiedIdentity;
+use pyrefly_types::quantified::QuantifiedKind;
+use pyrefly_types::quantified::QuantifiedOrigin;
+use pyrefly_types::shaped_array::IntTuple;
+use pyrefly_types::shaped_array::IntTupleView;
+use pyrefly_types::tuple::Tuple;
+use pyrefly_types::type_var::PreInferenceVariance;
+use pyrefly_types::type_var::Restriction;
+use pyrefly_types::types::CalleeKind;
+use pyrefly_types::types::Forallable;
+use pyrefly_types::types::TParams;
+use pyrefly_types::types::Type;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Number;
+use ruff_python_ast::UnaryOp;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::alt::callable::CallArg;
+use crate::alt::callable::CallKeyword;
+use crate::alt::expr::TypeOrExpr;
+use crate::alt::unwrap::HintRef;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+
+/// The `in_axes` or `out_axes` of a `vmap`: `None` marks an argument or result that isn't mapped.
+enum VmapAxes {
+    /// A single axis that applies to every argument or result.
+    All(Option<i64>),
+    /// One axis per positional argument, or per element of a returned tuple.
+    PerPosition(Vec<Option<i64>>),
+}
+
+impl VmapAxes {
+    /// Parse the axes, defaulting to mapping over the leading axis. Returns `None` for axes we
+    /// don't model, such as pytree prefixes or computed values.
+    fn from_expr(x: Option<&Expr>) -> Option<Self> {
+        match x {
+            None => Some(Self::All(Some(0))),
+            Some(Expr::Tuple(tuple)) => Some(Self::PerPosition(
+                tuple.elts.iter().map(vmap_axis).collect::<Option<_>>()?,
+            )),
+            Some(x) => Some(Self::All(vmap_axis(x)?)),
+        }
+    }
+
+    fn get(&self, position: usize) -> Option<i64> {
+        match self {
+            Self::All(axis) => *axis,
+            Self::PerPosition(axes) => axes.get(position).copied().flatten(),
+        }
+    }
+}
+
+/// A single axis literal: an integer, or `None` for an unmapped value.
+fn vmap_axis(x: &Expr) -> Option<Option<i64>> {
+    match x {
+        Expr::NoneLiteral(_) => Some(None),
+        Expr::NumberLiteral(x) if let Number::Int(i) = &x.value => Some(Some(i.as_i64()?)),
+        Expr::UnaryOp(x) if x.op == UnaryOp::USub => Some(Some(-vmap_axis(&x.operand)??)),
+        _ => None,
+    }
+}
+
+/// `shape` with `dim` inserted at `axis`, counting from the end when negative. Returns `None` if
+/// the axis is out of bounds for the batched rank.
+fn insert_axis(shape: &IntTuple, axis: i64, dim: Int) -> Option<IntTuple> {
+    match shape.view() {
+        IntTupleView::Concrete(dims) => {
+            // The batched array has one more dimension than `shape`.
+            let rank = dims.len() as i64 + 1;
+            let index = if axis < 0 { axis + rank } else { axis };
+            if !(0..rank).contains(&index) {
+                return None;
+            }
+            let mut dims = dims.to_vec();
+            dims.insert(index as usize, dim);
+            Some(IntTuple::new(dims))
+        }
+        IntTupleView::Gradual => Some(shape.clone()),
+        IntTupleView::Unpacked {
+            prefix,
+            middle,
+            suffix,
+        } => {
+            let mut prefix = prefix.to_vec();
+            let mut suffix = suffix.to_vec();
+            if axis >= 0 && axis as usize <= prefix.len() {
+                prefix.insert(axis as usize, dim);
+            } else if axis < 0 && (-axis - 1) as usize <= suffix.len() {
+                suffix.insert(suffix.len() - (-axis - 1) as usize, dim);
+            } else {
+                // The axis falls inside the unpacked middle, so its position is unknown.
+                return Some(IntTuple::shapeless());
+            }
+            Some(IntTuple::unpacked(prefix, middle.clone(), suffix))
+        }
+    }
+}
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Is this `jax.vmap`, either as re-exported from `jax` or from its defining module?
+    pub fn is_jax_vmap(ty: &Type) -> bool {
+        matches!(
+            ty.callee_kind(),
+            Some(CalleeKind::Function(FunctionKind::Def(func)))
+                if func.has_toplevel_qname("jax", "vmap")
+                    || func.has_toplevel_qname("jax._src.api", "vmap")
+        )
+    }
+
+    /// Handle a `jax.vmap(fun, in_axes, out_axes)` call. The arguments are checked against the
+    /// stub as usual, but the result is the batched signature of `fun` when we can compute it.
+    pub fn call_jax_vmap(
+        &self,
+        vmap_ty: &Type,
+        x: &ExprCall,
+        args: &[CallArg],
+        kws: &[CallKeyword],
+        callee_range: TextRange,
+        arg_range: TextRange,
+        hint: Option<HintRef>,
+        errors: &ErrorCollector,
+    ) -> Type {
+        let arguments = &x.arguments;
+        let has_unpacking = arguments.args.iter().any(|x| matches!(x, Expr::Starred(_)))
+            || arguments.keywords.iter().any(|x| x.arg.is_none());
+        let is_fun = |kw: &CallKeyword| kw.arg.is_some_and(|arg| arg.id.as_str() == "fun");
+        // Infer `fun` once, and check the call against the stub with the inferred type, so that
+        // nested `vmap`s aren't inferred again at every level.
+        let fun = if has_unpacking {
+            None
+        } else {
+            match args.first() {
+                Some(CallArg::Arg(fun)) => Some(*fun),
+                Some(CallArg::Star(..)) => None,
+                None => kws.iter().find(|kw| is_fun(kw)).map(|kw| kw.value),
+            }
+        };
+        let fun_ty = fun.map(|fun| (fun.infer(self, errors), fun.range()));
+        let mut args = args.to_vec();
+        let mut kws = kws.to_vec();
+        if let Some((ty, range)) = &fun_ty {
+            let fun = TypeOrExpr::Type(ty, *range);
+            match args.first_mut() {
+                Some(CallArg::Arg(arg)) => *arg = fun,
+                _ => {
+                    if let Some(kw) = kws.iter_mut().find(|kw| is_fun(kw)) {
+                        kw.value = fun;
+                    }
+                }
+            }
+        }
+        let ret = self.freeform_call_infer(
+            vmap_ty.clone(),
+            &args,
+            &kws,
+            callee_range,
+            arg_range,
+            hint,
+            errors,
+        );
+        let Some((fun_ty, _)) = &fun_ty else {
+            return ret;
+        };
+        let argument = |position: usize, name: &str| {
+            arguments
+                .args
+                .get(position)
+                .or_else(|| arguments.find_keyword(name).map(|x| &x.value))
+        };
+        self.jax_vmap(
+            fun_ty,
+            argument(1, "in_axes"),
+            argument(2, "out_axes"),
+            x.range,
+            errors,
+        )
+        .unwrap_or(ret)
+    }
+
+    /// The signature of `fun` batched by `vmap`: every mapped argument and result gains a batch
+    /// dimension at its axis. The batch size is a fresh `IntVar`, so calls solve it from the
+    /// arguments. Returns `None` if `fun` or the axes are beyond what we model, including when a
+    /// mapped argument or result is not an array.
+    pub fn jax_vmap(
+        &self,
+        fun_ty: &Type,
+        in_axes: Option<&Expr>,
+        out_axes: Option<&Expr>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Option<Type> {
+        let (tparams, callable) = match fun_ty {
+            Type::Function(f) => (None, f.signature.clone()),
+            Type::Callable(c) => (None, (**c).clone()),
+            Type::Forall(forall) => match &forall.body {
+                Forallable::Function(f) => (Some(forall.tparams.dupe()), f.signature.clone()),
+                Forallable::Callable(c) => (Some(forall.tparams.dupe()), c.clone()),
+                Forallable::TypeAlias(_) => return None,
+            },
+            _ => return None,
+        };
+        let Params::List(params) = &callable.params else {
+            return None;
+        };
+        let in_axes_range = in_axes.map_or(range, |x| x.range());
+        let out_axes_range = out_axes.map_or(range, |x| x.range());
+        let in_axes = VmapAxes::from_expr(in_axes)?;
+        let out_axes = VmapAxes::from_expr(out_axes)?;
+        if let VmapAxes::PerPosition(axes) = &in_axes {
+            let positional = params
+                .items()
+                .iter()
+                .filter(|p| matches!(p, Param::PosOnly(..) | Param::Pos(..)))
+                .collect::<Vec<_>>();
+            let required = positional
+                .iter()
+                .filter(|p| {
+                    matches!(
+                        p,
+                        Param::PosOnly(_, _, Required::Required)
+                            | Param::Pos(_, _, Required::Required)
+                    )
+                })
+                .count();
+            let count_error = if axes.len() > positional.len() {
+                Some(format!("takes {} positional parameters", positional.len()))
+            } else if axes.len() < required {
+                Some(format!("requires {required} positional arguments"))
+            } else {
+                None
+            };
+            if let Some(count_error) = count_error {
+                self.error(
+                    errors,
+                    in_axes_range,
+                    ErrorKind::BadArgumentCount,
+                    format!(
+                        "`in_axes` has {} entries, but the mapped function {count_error}",
+                        axes.len()
+                    ),
+                );
+                return None;
+            }
+        }
+        let batch = Quantified::new(
+            QuantifiedIdentity::new(
+                self.module().name(),
+                AnchorIndex::first(range),
+                QuantifiedOrigin::SyntheticCallableResidual,
+            ),
+            Name::new_static("Batch"),
+            QuantifiedKind::IntVar,
+            None,
+            Restriction::Unrestricted,
+            PreInferenceVariance::Invariant,
+        );
+        let batch_ty = self.heap.mk_quantified(batch.clone());
+        let mut position = 0;
+        let mut batched_params = Vec::with_capacity(params.items().len());
+        for param in params.items() {
+            let mut param = param.clone();
+            if let Param::PosOnly(_, ty, _) | Param::Pos(_, ty, _) = &mut param {
+                if let Some(axis) = in_axes.get(position) {
+                    *ty = self.vmap_batched_type(
+                        ty,
+                        axis,
+                        Int::Symbolic(Box::new(batch_ty.clone())),
+                        "in_axes",
+                        in_axes_range,
+                        errors,
+                    )?;
+                }
+                position += 1;
+            }
+            batched_params.push(param);
+        }
+        // Only a batch dimension that a call can solve from its arguments is kept symbolic.
+        let batch_is_solvable = batched_params.iter().any(|p| {
+            p.as_type()
+                .any(|t| matches!(t, Type::Quantified(q) if **q == batch))
+        });
+        let batch_dim = if batch_is_solvable {
+            Int::Symbolic(Box::new(batch_ty))
+        } else {
+            Int::Int
+        };
+        let batch_result = |ty: &Type, axis: Option<i64>| match axis {
+            Some(axis) => self.vmap_batched_type(
+                ty,
+                axis,
+                batch_dim.clone(),
+                "out_axes",
+                out_axes_range,
+                errors,
+            ),
+            None => Some(ty.clone()),
+        };
+        let ret = match (&out_axes, &callable.ret) {
+            (VmapAxes::PerPosition(axes), Type::Tuple(Tuple::Concrete(elts)))
+                if axes.len() == elts.len() =>
+            {
+                self.heap.mk_concrete_tuple(
+                    elts.iter()
+                        .zip(axes)
+                        .map(|(ty, axis)| batch_result(ty, *axis))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            (VmapAxes::PerPosition(_), _) => return None,
+            (VmapAxes::All(axis), Type::Tuple(Tuple::Concrete(elts))) => {
+                self.heap.mk_concrete_tuple(
+                    elts.iter()
+                        .map(|ty| batch_result(ty, *axis))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            (VmapAxes::All(axis), ret) => batch_result(ret, *axis)?,
+        };
+        let mut tparams = tparams.map_or_else(Vec::new, |x| x.as_vec().to_vec());
+        if batch_is_solvable {
+            tparams.push(batch);
+        }
+        Some(
+            Forallable::Callable(Callable::list(ParamList::new(batched_params), ret))
+                .forall(Arc::new(TParams::new(tparams))),
+        )
+    }
+
+    /// `ty` with a batch dimension at `axis`. Returns `None` after reporting an out-of-bounds
+    /// axis, or if `ty` is neither an array nor `Any`: pytrees and scalars are batched too, but
+    /// we don't model their batched types.
+    fn vmap_batched_type(
+        &self,
+        ty: &Type,
+        axis: i64,
+        dim: Int,
+        axes_name: &str,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Option<Type> {
+        let array = match ty {
+            Type::ShapedArray(array) => array,
+            Type::Any(_) => return Some(ty.clone()),
+            _ => return None,
+        };
+        let Some(shape) = insert_axis(&array.shape(), axis, dim) else {
+            self.error(
+                errors,
+                range,
+                ErrorKind::BadArgumentType,
+                format!(
+                    "Axis `{axis}` in `{axes_name}` is out of bounds for `{}`",
+                    self.for_display(ty.clone()),
+                ),
+            );
+            return None;
+        };
+        let mut array = (**array).clone();
+        array.set_shape(shape);
+        Some(self.heap.mk_shaped_array(array))
+    }
+}
@@ -2248,20 +2248,20 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         }
     }
 
-    pub fn check_match_exhaustiveness(
+    /// The type of a `match` subject and the type it has when none of the cases match, or
+    /// `None` if the subject is not of a type we check exhaustiveness for.
+    pub fn match_fall_through_type(
         &self,
         subject_idx: &Idx<Key>,
         narrowing_subject: Option<&NarrowingSubject>,
         narrow_ops_for_fall_through: &(Box<NarrowOp>, TextRange),
         subject_range: &TextRange,
-        show_subject_expr: bool,
-        errors: &ErrorCollector,
-    ) {
+    ) -> Option<(TypeInfo, Type)> {
         let (op, narrow_range) = narrow_ops_for_fall_through;
         let subject_info = self.with_type_for_exhaustiveness_check(self.get_idx(*subject_idx));
         // We only check match exhaustiveness if the subject is an enum or a union of enum literals
         if !self.should_check_exhaustiveness(subject_info.ty()) {
-            return;
+            return None;
         }
         let ignore_errors = self.error_swallower();
         // Get the narrowed type of the match subject when none of the cases match
@@ -2271,9 +2271,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 .ty()
                 .clone(),
             Some(NarrowingSubject::Facets(_, facets)) => {
-                let Some(resolved_chain) = self.resolve_facet_chain(facets.chain.clone()) else {
-                    return;
-                };
+                let resolved_chain = self.resolve_facet_chain(facets.chain.clone())?;
                 // If the narrowing subject is the facet of some variable like `x.foo`,
                 // We need to make a `TypeInfo` rooted at `x` using the type of `x.foo`
                 let type_info = TypeInfo::of_ty(self.heap.mk_any_implicit());
@@ -2289,6 +2287,26 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         };
         self.expand_mut(&mut remaining_ty);
+        Some((subject_info, remaining_ty))
+    }
+
+    pub fn check_match_exhaustiveness(
+        &self,
+        subject_idx: &Idx<Key>,
+        narrowing_subject: Option<&NarrowingSubject>,
+        narrow_ops_for_fall_through: &(Box<NarrowOp>, TextRange),
+        subject_range: &TextRange,
+        show_subject_expr: bool,
+        errors: &ErrorCollector,
+    ) {
+        let Some((subject_info, remaining_ty)) = self.match_fall_through_type(
+            subject_idx,
+            narrowing_subject,
+            narrow_ops_for_fall_through,
+            subject_range,
+        ) else {
+            return;
+        };
         // If the result is `Never` then the cases were exhaustive
         if remaining_ty.is_never() || remaining_ty.is_any() {
             return;
@@ -7,6 +7,7 @@
 
 use std::cmp::max;
 
+use dupe::Dupe;
 use itertools::Either;
 use itertools::Itertools;
 use pyrefly_types::callable::ArgCount;
@@ -25,8 +26,10 @@ use pyrefly_util::gas::Gas;
 use pyrefly_util::owner::Owner;
 use pyrefly_util::prelude::SliceExt;
 use pyrefly_util::prelude::VecExt;
+use ruff_python_ast::name::Name;
 use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
 use starlark_map::small_set::SmallSet;
 use vec1::Vec1;
 
@@ -42,8 +45,10 @@ use crate::alt::callable::ReturnTypeResolutionError;
 use crate::alt::expr::TypeOrExpr;
 use crate::alt::unwrap::HintRef;
 use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorBuilder;
 use crate::error::collector::ErrorCollector;
 use crate::error::context::ErrorContext;
+use crate::error::signature_diff::render_signature_diff;
 use crate::solver::solver::TypeVarSpecializationError;
 use crate::types::callable::Callable;
 use crate::types::callable::Params;
@@ -66,6 +71,23 @@ struct CalledOverload<'f> {
     argmap: ArgMap,
 }
 
+/// Why an overload did not match a call. When no overload matches, we report these for every
+/// candidate so the user can see how close each one came.
+enum OverloadMismatch {
+    /// The call passed a number of arguments the overload can't accept.
+    Arity(String),
+    /// An argument's type is not assignable to the parameter it was matched against.
+    Argument {
+        param: Option<Name>,
+        expected: Type,
+        actual: Type,
+    },
+    /// A type variable was solved to a type that violates its bound or constraints.
+    TypeVar(String),
+    /// Any other call error, such as an unexpected keyword argument.
+    Other(String),
+}
+
 impl CalledOverload<'_> {
     fn num_match_errors(&self) -> usize {
         self.call_errors.len_hard() + self.specialization_errors.len()
@@ -412,17 +434,96 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         };
 
+        // Work out why each overload failed, before the closest overload's errors are consumed.
+        // This explains the failure when nothing matches and the error is reported, and is shown
+        // by IDE services.
+        let tracing = self.current().tracing_enabled();
+        let mismatches = if (!matched && errors.is_active()) || tracing {
+            // Arguments kept as expressions were already inferred (and their errors reported)
+            // while calling the overloads, so don't report them again.
+            let swallower = self.error_swallower();
+            let arg_types = args
+                .iter()
+                .filter_map(|arg| match arg {
+                    CallArg::Arg(value) => Some((arg.range(), value.infer(self, &swallower))),
+                    CallArg::Star(..) => None,
+                })
+                .chain(
+                    keywords
+                        .iter()
+                        .filter(|kw| kw.arg.is_some())
+                        .map(|kw| (kw.range, kw.value.infer(self, &swallower))),
+                )
+                .collect::<Vec<_>>();
+            let placeholder_vars =
+                self.collect_placeholder_vars(self_obj.as_ref(), &args, &keywords);
+            overloads
+                .iter()
+                .map(|overload| {
+                    let is_closest = std::ptr::eq(overload, closest_overload.func);
+                    if matched && is_closest {
+                        return Vec::new();
+                    }
+                    let arg_counts = overload.1.signature.arg_counts();
+                    let arity_compatible =
+                        self.arity_mismatch_size(&arg_counts, self_obj.as_ref(), &args, &keywords)
+                            == 0;
+                    let mismatches = if is_closest {
+                        self.overload_mismatches(&closest_overload, &arg_types)
+                    } else if arity_compatible {
+                        // Only the closest overload's errors are kept while resolving the call,
+                        // so call the others again to find out why they failed.
+                        let snapshot = self.solver().snapshot_vars(&placeholder_vars);
+                        let called = self.call_overload(
+                            overload,
+                            metadata,
+                            shape_transform,
+                            self_obj.as_ref(),
+                            &args,
+                            &keywords,
+                            arguments_range,
+                            None,
+                            &ctor_targs,
+                        );
+                        self.solver().restore_vars(snapshot);
+                        self.overload_mismatches(&called, &arg_types)
+                    } else {
+                        Vec::new()
+                    };
+                    if !mismatches.is_empty() {
+                        return mismatches;
+                    }
+                    // Overloads that were never called were eliminated for their arity, or come
+                    // after the one that matched.
+                    match self.arity_mismatch(
+                        &overload.1.signature,
+                        self_obj.as_ref(),
+                        &args,
+                        &keywords,
+                    ) {
+                        Some(reason) => vec![OverloadMismatch::Arity(reason)],
+                        None if matched => Vec::new(),
+                        None => vec![OverloadMismatch::Arity(
+                            "Incompatible number of arguments".to_owned(),
+                        )],
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
         if matched
             && let Some(targs) = ctor_targs
             && let Some(chosen_targs) = closest_overload.ctor_targs
         {
             *targs = chosen_targs;
         }
-        // Record the closest overload to power IDE services. Guard on the trace
-        // sink before building the (per-signature cloned) traces, which would
-        // otherwise be wasted work in a normal non-tracing check.
-        if self.current().tracing_enabled() {
-            let mut overload_trace = |target: &TargetWithTParams<Function>| {
+        // Record the closest overload, why the others were rejected and how the chosen one's type
+        // parameters were solved, to power IDE services. Guard on the trace sink before building
+        // the (per-signature cloned) traces, which would otherwise be wasted work in a normal
+        // non-tracing check.
+        if tracing {
+            let overload_trace = |target: &TargetWithTParams<Function>| {
                 let tparams = target
                     .0
                     .as_ref()
@@ -430,7 +531,18 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     .cloned();
                 OverloadTrace::new(target.1.signature.clone(), tparams)
             };
-            let all_overload_traces = overloads.iter().map(&mut overload_trace).collect();
+            let all_overload_traces = overloads
+                .iter()
+                .zip(&mismatches)
+                .map(|(target, mismatches)| {
+                    overload_trace(target).with_errors(
+                        mismatches
+                            .iter()
+                            .map(|mismatch| self.format_overload_mismatch(mismatch))
+                            .collect(),
+                    )
+                })
+                .collect();
             let closest_overload_trace = overload_trace(closest_overload.func);
             self.record_overload_trace(
                 arguments_range,
@@ -438,6 +550,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 closest_overload_trace,
                 matched,
             );
+            if matched {
+                self.record_call_solutions(
+                    arguments_range,
+                    &closest_overload.argmap.type_var_solutions,
+                );
+            }
         }
         if matched {
             // If the selected overload is deprecated, we log a deprecation error.
@@ -501,9 +619,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 arguments_range,
                 errors,
                 context,
-                &closest_overload.func.1.signature,
+                closest_overload.func,
                 closest_overload.call_errors,
                 closest_overload.argmap,
+                mismatches,
             );
             (
                 self.heap.mk_any_error(),
@@ -553,6 +672,116 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         pos_mismatch + kw_mismatch + overall_mismatch
     }
 
+    /// Describes why `signature` can't accept the arguments passed, if the number of arguments is
+    /// the problem.
+    fn arity_mismatch(
+        &self,
+        signature: &Callable,
+        self_obj: Option<&Type>,
+        args: &[CallArg],
+        keywords: &[CallKeyword],
+    ) -> Option<String> {
+        let mut arg_counts = signature.arg_counts();
+        let nposargs = args.len();
+        let nkwargs = keywords.len();
+        if self_obj.is_some() && arg_counts.positional.max == Some(0) {
+            return Some("This method has no `self` parameter to receive the implicit instance argument. Add a `self` parameter.".to_owned());
+        }
+        if self_obj.is_some() {
+            arg_counts.positional.min = arg_counts.positional.min.saturating_sub(1);
+            if let Some(max) = arg_counts.positional.max {
+                arg_counts.positional.max = Some(max.saturating_sub(1));
+            }
+            arg_counts.overall.min = arg_counts.overall.min.saturating_sub(1);
+            if let Some(max) = arg_counts.overall.max {
+                arg_counts.overall.max = Some(max.saturating_sub(1));
+            }
+        }
+        let check = |actual, expected: ArgCount, descriptor_prefix| {
+            let descriptor = format!("{descriptor_prefix}argument");
+            if actual < expected.min {
+                Some(format!(
+                    "Expected at least {}, got {actual}",
+                    count(expected.min, &descriptor)
+                ))
+            } else if let Some(max) = expected.max
+                && actual > max
+            {
+                Some(format!(
+                    "Expected at most {}, got {actual}",
+                    count(max, &descriptor)
+                ))
+            } else {
+                None
+            }
+        };
+        check(nposargs + nkwargs, arg_counts.overall, "")
+            .or_else(|| check(nposargs, arg_counts.positional, "positional "))
+            .or_else(|| check(nkwargs, arg_counts.keyword, "keyword "))
+    }
+
+    /// The reasons a called overload failed to match. `arg_types` holds the range and type of
+    /// every argument that isn't unpacked.
+    fn overload_mismatches(
+        &self,
+        overload: &CalledOverload<'_>,
+        arg_types: &[(TextRange, Type)],
+    ) -> Vec<OverloadMismatch> {
+        let mut mismatches = overload.call_errors.hard_errors().into_map(|error| {
+            let matched = if error.error_kind() == ErrorKind::BadArgumentType {
+                arg_types
+                    .iter()
+                    .find(|(range, _)| range.contains_range(error.range()))
+                    .and_then(|(range, actual)| {
+                        Some((overload.argmap.range_to_param.get(range)?, actual))
+                    })
+            } else {
+                None
+            };
+            match matched {
+                Some((param, actual)) => OverloadMismatch::Argument {
+                    param: param.name.clone(),
+                    expected: param.ty.clone(),
+                    actual: actual.clone(),
+                },
+                None => OverloadMismatch::Other(error.msg_header().to_owned()),
+            }
+        });
+        mismatches.extend(
+            overload
+                .specialization_errors
+                .iter()
+                .map(|e| OverloadMismatch::TypeVar(e.clone().to_error_msg(self))),
+        );
+        mismatches
+    }
+
+    fn format_overload_mismatch(&self, mismatch: &OverloadMismatch) -> String {
+        match mismatch {
+            OverloadMismatch::Arity(msg)
+            | OverloadMismatch::TypeVar(msg)
+            | OverloadMismatch::Other(msg) => msg.clone(),
+            OverloadMismatch::Argument {
+                param,
+                expected,
+                actual,
+            } => {
+                let expected = self.solver().for_display(expected.clone());
+                let actual = self.solver().for_display(actual.clone());
+                let ctx = TypeDisplayContext::new(&[&expected, &actual]);
+                let param = match param {
+                    Some(name) => format!("Argument `{name}`"),
+                    None => "Argument".to_owned(),
+                };
+                format!(
+                    "{param}: expected `{}`, got `{}`",
+                    ctx.display(&expected),
+                    ctx.display(&actual)
+                )
+            }
+        }
+    }
+
     fn overload_error(
         &self,
         overloads: &[TargetWithTParams<Function>],
@@ -563,22 +792,26 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         arguments_range: TextRange,
         errors: &ErrorCollector,
         context: Option<&dyn Fn() -> ErrorContext>,
-        closest_overload_signature: &Callable,
+        closest_overload: &TargetWithTParams<Function>,
         closest_overload_call_errors: ErrorCollector,
         mut closest_overload_argmap: ArgMap,
+        mismatches: Vec<Vec<OverloadMismatch>>,
     ) {
-        // Build a string showing the argument types for error messages
+        let closest_overload_signature = &closest_overload.1.signature;
+        // Build a string showing the argument types for error messages. Any errors in the
+        // arguments were already reported while calling the overloads.
+        let swallower = self.error_swallower();
         let mut arg_type_strs = Vec::new();
         for arg in args {
             let (ty, prefix) = match arg {
-                CallArg::Arg(value) => (value.infer(self, errors), ""),
-                CallArg::Star(value, _) => (value.infer(self, errors), "*"),
+                CallArg::Arg(value) => (value.infer(self, &swallower), ""),
+                CallArg::Star(value, _) => (value.infer(self, &swallower), "*"),
             };
             let ty_display = self.for_display(ty);
             arg_type_strs.push(format!("{}{}", prefix, ty_display));
         }
         for kw in keywords {
-            let ty = kw.value.infer(self, errors);
+            let ty = kw.value.infer(self, &swallower);
             let ty_display = self.for_display(ty);
             if let Some(arg_name) = kw.arg {
                 arg_type_strs.push(format!("{}={}", arg_name.as_str(), ty_display));
@@ -696,55 +929,117 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             .with_details(details);
         if closest_overload_call_errors.is_empty() {
             // If there were no call errors, the failure must have been an arity mismatch.
-            let mut arg_counts = closest_overload_signature.arg_counts();
-            let nposargs = args.len();
-            let nkwargs = keywords.len();
-            let missing_self_param = self_obj.is_some() && arg_counts.positional.max == Some(0);
-            if self_obj.is_some() {
-                arg_counts.positional.min = arg_counts.positional.min.saturating_sub(1);
-                if let Some(max) = arg_counts.positional.max {
-                    arg_counts.positional.max = Some(max.saturating_sub(1));
-                }
-                arg_counts.overall.min = arg_counts.overall.min.saturating_sub(1);
-                if let Some(max) = arg_counts.overall.max {
-                    arg_counts.overall.max = Some(max.saturating_sub(1));
-                }
-            }
-            let check = |actual, expected: ArgCount, descriptor_prefix| {
-                let descriptor = format!("{descriptor_prefix}argument");
-                if actual < expected.min {
-                    Some(format!(
-                        "Expected at least {}, got {actual}",
-                        count(expected.min, &descriptor)
-                    ))
-                } else if let Some(max) = expected.max
-                    && actual > max
-                {
-                    Some(format!(
-                        "Expected at most {}, got {actual}",
-                        count(max, &descriptor)
-                    ))
-                } else {
-                    None
-                }
-            };
-            let arity_mismatch = if missing_self_param {
-                "This method has no `self` parameter to receive the implicit instance argument. Add a `self` parameter.".to_owned()
-            } else {
-                check(nposargs + nkwargs, arg_counts.overall, "").unwrap_or_else(|| {
-                    check(nposargs, arg_counts.positional, "positional ").unwrap_or_else(|| {
-                        check(nkwargs, arg_counts.keyword, "keyword ")
-                            .expect("Overload evaluation: expected arity mismatch not found")
-                    })
-                })
-            };
+            let arity_mismatch = self
+                .arity_mismatch(closest_overload_signature, self_obj, args, keywords)
+                .expect("Overload evaluation: expected arity mismatch not found");
             builder = builder.with_detail(arity_mismatch);
         } else {
             builder = builder.with_errors_as_details(closest_overload_call_errors);
         }
+        builder = self.explain_overload_mismatches(
+            builder,
+            overloads,
+            self_obj,
+            closest_overload,
+            &mismatches,
+        );
         builder.emit();
     }
 
+    /// Adds a table of why each overload failed to match, and attaches each reason to the
+    /// overload's definition. If the closest overload failed because of argument types, also
+    /// shows its signature side by side with the types it was called with.
+    fn explain_overload_mismatches<'b>(
+        &self,
+        mut builder: ErrorBuilder<'b>,
+        overloads: &[TargetWithTParams<Function>],
+        self_obj: Option<&Type>,
+        closest_overload: &TargetWithTParams<Function>,
+        mismatches: &[Vec<OverloadMismatch>],
+    ) -> ErrorBuilder<'b> {
+        let is_closest =
+            |overload: &TargetWithTParams<Function>| std::ptr::eq(overload, closest_overload);
+        let labels = overloads
+            .iter()
+            .enumerate()
+            .map(|(i, overload)| {
+                if is_closest(overload) {
+                    format!("{} [closest match]", i + 1)
+                } else {
+                    (i + 1).to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        let width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+        let mut details = vec!["Why each overload failed:".to_owned()];
+        for ((overload, label), mismatches) in overloads.iter().zip(&labels).zip(mismatches) {
+            let Some(first) = mismatches.first() else {
+                continue;
+            };
+            let mut reason = self.format_overload_mismatch(first);
+            if mismatches.len() > 1 {
+                reason.push_str(&format!(" (+{} more)", mismatches.len() - 1));
+            }
+            details.push(format!("  {label:<width$} | {reason}"));
+            if let Some(def) = overload.1.metadata.kind.as_func_def_id() {
+                builder = builder.with_related_annotation(
+                    def.qname.module().dupe(),
+                    def.qname.range(),
+                    format!("Overload {label} does not match: {reason}"),
+                );
+            }
+        }
+        builder = builder.with_details(details);
+
+        let actual_types = overloads
+            .iter()
+            .zip(mismatches)
+            .filter(|(overload, _)| is_closest(overload))
+            .flat_map(|(_, mismatches)| mismatches)
+            .filter_map(|mismatch| match mismatch {
+                OverloadMismatch::Argument {
+                    param: Some(name),
+                    actual,
+                    ..
+                } => Some((name, actual)),
+                _ => None,
+            })
+            .collect::<SmallMap<_, _>>();
+        if actual_types.is_empty() {
+            return builder;
+        }
+        let closest_overload_signature = &closest_overload.1.signature;
+        let expected = match self_obj {
+            Some(_) => closest_overload_signature
+                .strip_first_param()
+                .unwrap_or_else(|| closest_overload_signature.clone()),
+            None => closest_overload_signature.clone(),
+        };
+        let mut found = expected.clone();
+        if let Params::List(params) = &mut found.params {
+            for param in params.items_mut() {
+                if let Some(actual) = param
+                    .name()
+                    .and_then(|name| actual_types.get(name).copied())
+                {
+                    *param.as_type_mut() = actual.clone();
+                }
+            }
+        }
+        let expected = self
+            .solver()
+            .for_display(self.heap.mk_callable_from(expected));
+        let found = self.solver().for_display(self.heap.mk_callable_from(found));
+        let ctx = TypeDisplayContext::new(&[&expected, &found]);
+        if let Some(lines) = render_signature_diff(
+            &ctx.display(&expected).to_string(),
+            &ctx.display(&found).to_string(),
+        ) {
+            builder = builder.with_details(lines);
+        }
+        builder
+    }
+
     /// Returns the overload that matches the given arguments, or the one that produces the fewest
     /// errors if none matches, plus a bool to indicate whether we found a match.
     fn find_closest_overload<'c>(
@@ -611,12 +611,15 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
 
     /// Check that got is assignable to want
     pub fn is_subset_eq(&self, got: &Type, want: &Type) -> bool {
-        self.is_subset_eq_with_reason(got, want).is_ok()
+        self.solver()
+            .is_subset_eq(got, want, self.type_order(), None)
+            .is_ok()
     }
 
+    /// Like `is_subset_eq`, for callers that report the reason the check failed.
     pub fn is_subset_eq_with_reason(&self, got: &Type, want: &Type) -> Result<(), SubsetError> {
         self.solver()
-            .is_subset_eq(got, want, self.type_order(), None)
+            .is_subset_eq_with_reason(got, want, self.type_order(), None)
     }
 
     pub fn is_consistent(&self, got: &Type, want: &Type) -> bool {
@@ -1117,7 +1120,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         range: TextRange,
         allow_none: bool,
         errors: &ErrorCollector,
-    ) {
+    ) -> Type {
         let actual_type = self.expr_infer(x, errors);
         let base_exception_class = self.stdlib.base_exception();
         let base_exception_class_type = self
@@ -1138,11 +1141,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 format!(
                     "Expression `{}` has type `{}`, expected {}",
                     self.module().display(x),
-                    self.for_display(actual_type),
+                    self.for_display(actual_type.clone()),
                     expected,
                 ),
             );
         }
+        actual_type
     }
 
     fn tvars_to_tparams_for_type_alias_type(
@@ -2772,6 +2776,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                     );
                 }
             }
+            BindingExpect::UnclosedResource { name, idx, range } => {
+                self.check_for_unclosed_resource(name, *idx, *range, errors);
+            }
         }
         Arc::new(EmptyAnswer)
     }
@@ -3172,6 +3179,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 if let Some(new_fields) = self.get_factory_boy_synthesized_fields(cls) {
                     fields = fields.combine(new_fields);
                 }
+                if let Some(new_fields) = self.get_msgspec_synthesized_fields(cls) {
+                    fields = fields.combine(new_fields);
+                }
                 fields
             }
         };
@@ -3574,6 +3584,29 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     // Helper functions for binding_to_type - extracted to reduce stack frame size
     // -------------------------------------------------------------------------
 
+    /// The type a narrow entry of a `Binding::Exhaustive` leaves its subject with when none
+    /// of the branches are taken.
+    pub fn exhaustive_fall_through_type(
+        &self,
+        subject_idx: Idx<Key>,
+        op: &NarrowOp,
+        narrow_range: TextRange,
+    ) -> Type {
+        let ignore_errors = self.error_swallower();
+        let subject_info = self.with_type_for_exhaustiveness_check(self.get_idx(subject_idx));
+        let facet_chain = Self::extract_facet_from_op(op)
+            .and_then(|facets| self.resolve_facet_chain(facets.chain.clone()));
+        let narrowed = self.narrow(&subject_info, op, narrow_range, &ignore_errors);
+        let mut remaining_ty = match &facet_chain {
+            Some(resolved_chain) => {
+                self.get_facet_chain_type(&narrowed, resolved_chain, narrow_range)
+            }
+            None => narrowed.ty().clone(),
+        };
+        self.expand_mut(&mut remaining_ty);
+        remaining_ty
+    }
+
     /// Handle `Binding::Exhaustive` - check if a match or if/elif chain is exhaustive.
     ///
     /// Loops over all narrow entries. For each, resolves the subject type, optionally
@@ -3585,20 +3618,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
         &self,
         narrow_entries: &[(Idx<Key>, Box<NarrowOp>, TextRange)],
     ) -> Type {
-        let ignore_errors = self.error_swallower();
         for (subject_idx, op, narrow_range) in narrow_entries {
-            let subject_info = self.with_type_for_exhaustiveness_check(self.get_idx(*subject_idx));
-            let facet_chain = Self::extract_facet_from_op(op)
-                .and_then(|facets| self.resolve_facet_chain(facets.chain.clone()));
-            let narrowed = self.narrow(&subject_info, op.as_ref(), *narrow_range, &ignore_errors);
-            let mut remaining_ty = match &facet_chain {
-                Some(resolved_chain) => {
-                    self.get_facet_chain_type(&narrowed, resolved_chain, *narrow_range)
-                }
-                None => narrowed.ty().clone(),
-            };
-            self.expand_mut(&mut remaining_ty);
-            if remaining_ty.is_never() {
+            if self
+                .exhaustive_fall_through_type(*subject_idx, op, *narrow_range)
+                .is_never()
+            {
                 return self.heap.mk_never();
             }
         }
@@ -4275,6 +4299,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             }
         };
         let exceptions = self.unions(exceptions);
+        self.check_for_unreachable_except(ann, &exceptions, errors);
         if is_star && let Some(t) = self.stdlib.exception_group(exceptions.clone()) {
             self.heap.mk_class_type(t)
         } else {
@@ -4909,6 +4934,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 }
                 error_builder.emit();
             }
+            if let Some(range) = x.check_deprecated
+                && !self.banned_apis().is_empty()
+            {
+                // A banned module is reported by the `Binding::Module` for the statement.
+                self.report_banned_api(&[format!("{m}.{name}")], range, errors);
+            }
             self.get_from_export(m, None, &KeyExport(name.clone()))
                 .arc_clone()
         };
@@ -5801,6 +5832,11 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                 self.binding_to_type_class_body_unknown_name(x.0, &x.1, &x.2, errors)
             }
             Binding::Exhaustive(x) => self.binding_to_type_exhaustive(&x.narrow_entries),
+            Binding::ArgparseNamespace(x) => match &self.get_idx(*x).0 {
+                None => self.heap.mk_any_implicit(),
+                Some(cls) => self.instantiate(cls),
+            },
+            Binding::ArgparseDestination(xs) => self.argparse_destination_type(xs, errors),
             Binding::SuppressedException(x) => {
                 let body_terminates = x.body.is_none_or(|body| self.get_idx(body).ty().is_never());
                 if body_terminates
@@ -6040,6 +6076,7 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
             Binding::Module(x) => {
                 if let Some(error_range) = x.3 {
                     self.report_module_find_error(x.0, error_range, errors);
+                    self.check_for_banned_module(x.0, error_range, errors);
                 }
                 self.binding_to_type_module(x.0, &x.1, x.2)
             }
@@ -6111,11 +6148,25 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     }
 
     pub fn solve_decorator(&self, x: &BindingDecorator, errors: &ErrorCollector) -> Arc<Decorator> {
-        let mut ty = self.expr_infer(&x.expr, errors);
+        let (mut ty, callee) = match &x.expr {
+            Expr::Call(call) => {
+                let prepared = self.prepare_expr_call(call, errors);
+                let callee = prepared.callee().cloned();
+                (
+                    self.finish_prepared_expr_call_with_trace(call, prepared, errors),
+                    callee,
+                )
+            }
+            _ => (self.expr_infer(&x.expr, errors), None),
+        };
         self.pin_all_placeholder_types(&mut ty, true, x.expr.range(), errors);
         self.expand_mut(&mut ty);
         let deprecation = parse_deprecation(&x.expr);
-        Arc::new(Decorator { ty, deprecation })
+        Arc::new(Decorator {
+            ty,
+            deprecation,
+            callee,
+        })
     }
 
     pub fn solve_decorated_function(
@@ -6125,7 +6176,10 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
     ) -> Arc<Type> {
         let b = self.bindings().get(x.undecorated_idx);
         let def = self.get_idx(x.undecorated_idx);
-        self.decorated_function_type(&def, &b.def, errors)
+        let ty = self.decorated_function_type(&def, &b.def, errors);
+        self.check_fastapi_function(&def, &b.def, &b.decorators, errors);
+        self.check_click_command(&def, &b.def, &b.decorators, errors);
+        ty
     }
 
     pub fn solve_undecorated_function(
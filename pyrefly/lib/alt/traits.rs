@@ -25,6 +25,7 @@ use crate::alt::types::class_metadata::ClassSynthesizedFields;
 use crate::alt::types::class_metadata::DjangoReverseRelationIndex;
 use crate::alt::types::decorated_function::Decorator;
 use crate::alt::types::decorated_function::UndecoratedFunction;
+use crate::alt::types::function_raises::FunctionRaises;
 use crate::alt::types::legacy_lookup::LegacyTypeParameterLookup;
 use crate::alt::types::yields::YieldFromResult;
 use crate::alt::types::yields::YieldResult;
@@ -48,6 +49,8 @@ use crate::binding::binding::BindingDecorator;
 use crate::binding::binding::BindingDjangoRelations;
 use crate::binding::binding::BindingExpect;
 use crate::binding::binding::BindingExport;
+use crate::binding::binding::BindingFunctionLocalRaises;
+use crate::binding::binding::BindingFunctionRaises;
 use crate::binding::binding::BindingLegacyTypeParam;
 use crate::binding::binding::BindingTParams;
 use crate::binding::binding::BindingTypeAlias;
@@ -74,6 +77,8 @@ use crate::binding::binding::KeyDecorator;
 use crate::binding::binding::KeyDjangoRelations;
 use crate::binding::binding::KeyExpect;
 use crate::binding::binding::KeyExport;
+use crate::binding::binding::KeyFunctionLocalRaises;
+use crate::binding::binding::KeyFunctionRaises;
 use crate::binding::binding::KeyLegacyTypeParam;
 use crate::binding::binding::KeyTParams;
 use crate::binding::binding::KeyTypeAlias;
@@ -272,6 +277,7 @@ impl<Ans: LookupAnswer> Solve<Ans> for KeyDecorator {
         Decorator {
             ty: heap.mk_any_implicit(),
             deprecation: None,
+            callee: None,
         }
     }
 }
@@ -324,6 +330,38 @@ impl<Ans: LookupAnswer> Solve<Ans> for KeyUndecoratedFunctionRange {
     }
 }
 
+impl<Ans: LookupAnswer> Solve<Ans> for KeyFunctionRaises {
+    fn solve(
+        answers: &AnswersSolver<Ans>,
+        binding: &BindingFunctionRaises,
+        _range: TextRange,
+        errors: &ErrorCollector,
+    ) -> Arc<FunctionRaises> {
+        answers.solve_function_raises(binding, errors)
+    }
+
+    fn promote_recursive(_heap: &TypeHeap, _: Var) -> Self::Answer {
+        // A recursive call adds nothing the function doesn't already raise.
+        FunctionRaises::default()
+    }
+}
+
+impl<Ans: LookupAnswer> Solve<Ans> for KeyFunctionLocalRaises {
+    fn solve(
+        answers: &AnswersSolver<Ans>,
+        binding: &BindingFunctionLocalRaises,
+        _range: TextRange,
+        _errors: &ErrorCollector,
+    ) -> Arc<FunctionRaises> {
+        answers.solve_function_local_raises(binding)
+    }
+
+    fn promote_recursive(_heap: &TypeHeap, _: Var) -> Self::Answer {
+        // A recursive call adds nothing the function doesn't already raise.
+        FunctionRaises::default()
+    }
+}
+
 impl<Ans: LookupAnswer> Solve<Ans> for KeyClass {
     fn solve(
         answers: &AnswersSolver<Ans>,
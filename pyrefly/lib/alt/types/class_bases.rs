@@ -274,6 +274,9 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                         .mk_class_type(self.stdlib.named_tuple_fallback().clone()),
                     x.range(),
                 )),
+                BaseClass::ArgparseNamespace(range, _) => {
+                    self.argparse_namespace_base().map(|ty| (ty, *range))
+                }
                 BaseClass::SynthesizedBase(class_idx, _) => {
                     self.get_idx(*class_idx).as_ref().0.as_ref().map(|cls| {
                         let ct = self.promote_nontypeddict_silently_to_classtype(cls);
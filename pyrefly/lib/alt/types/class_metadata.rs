@@ -113,6 +113,13 @@ pub struct ClassMetadata {
     django_model_metadata: Option<DjangoModelMetadata>,
     is_marshmallow_schema: bool,
     is_factory_boy_factory: bool,
+    /// The encoding options of a class inheriting from `msgspec.Struct`.
+    msgspec_metadata: Option<MsgspecStructMetadata>,
+    sqlmodel_metadata: Option<SqlModelMetadata>,
+    /// Whether this class is a namespace synthesized for an `argparse` parser that knows
+    /// all of its attributes, i.e. one without destinations declared only on subparsers.
+    /// This is not inherited.
+    is_closed_argparse_namespace: bool,
     /// Whether this class is a metaclass (i.e., a subclass of `type`).
     is_metaclass: bool,
     explicit_slots: ExplicitSlots,
@@ -209,6 +216,9 @@ impl ClassMetadata {
         django_model_metadata: Option<DjangoModelMetadata>,
         is_marshmallow_schema: bool,
         is_factory_boy_factory: bool,
+        msgspec_metadata: Option<MsgspecStructMetadata>,
+        sqlmodel_metadata: Option<SqlModelMetadata>,
+        is_closed_argparse_namespace: bool,
         is_metaclass: bool,
         explicit_slots: ExplicitSlots,
         capture_init: Option<Vec<Name>>,
@@ -238,6 +248,9 @@ impl ClassMetadata {
             django_model_metadata,
             is_marshmallow_schema,
             is_factory_boy_factory,
+            msgspec_metadata,
+            sqlmodel_metadata,
+            is_closed_argparse_namespace,
             is_metaclass,
             explicit_slots,
             capture_init,
@@ -270,6 +283,9 @@ impl ClassMetadata {
             django_model_metadata: None,
             is_marshmallow_schema: false,
             is_factory_boy_factory: false,
+            msgspec_metadata: None,
+            sqlmodel_metadata: None,
+            is_closed_argparse_namespace: false,
             is_metaclass: false,
             explicit_slots: ExplicitSlots::Absent,
             capture_init: None,
@@ -314,6 +330,18 @@ impl ClassMetadata {
         self.is_factory_boy_factory
     }
 
+    pub fn msgspec_metadata(&self) -> Option<&MsgspecStructMetadata> {
+        self.msgspec_metadata.as_ref()
+    }
+
+    pub fn sqlmodel_metadata(&self) -> Option<&SqlModelMetadata> {
+        self.sqlmodel_metadata.as_ref()
+    }
+
+    pub fn is_closed_argparse_namespace(&self) -> bool {
+        self.is_closed_argparse_namespace
+    }
+
     /// Whether this class is a metaclass (i.e., a subclass of `type`).
     pub fn is_metaclass(&self) -> bool {
         self.is_metaclass
@@ -703,6 +731,49 @@ pub struct DjangoModelMetadata {
     pub fields_with_choices: Vec<Name>,
 }
 
+/// How a `msgspec.Struct` subclass renames its fields when encoded.
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq, Default)]
+pub enum MsgspecRename {
+    #[default]
+    Unchanged,
+    /// One of the naming conventions accepted as a string, e.g. `"camel"`.
+    Convention(String),
+    /// A mapping or callable we don't follow.
+    Unknown,
+}
+
+/// The tag identifying a `msgspec.Struct` subclass in a tagged union.
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
+pub enum MsgspecTag {
+    /// `tag=True`, or a `tag_field` without a `tag`: the name of each class.
+    ClassName,
+    Str(String),
+    Int(i64),
+    /// A callable computing the tag from the class name.
+    Unknown,
+}
+
+/// The options of a `msgspec.Struct` subclass that change how it is encoded. Each one is
+/// inherited unless the class overrides it with a keyword.
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq, Default)]
+pub struct MsgspecStructMetadata {
+    pub rename: MsgspecRename,
+    /// Set if the struct is tagged.
+    pub tag: Option<MsgspecTag>,
+    /// The field holding the tag, which defaults to `"type"` for a tagged struct.
+    pub tag_field: Option<String>,
+    /// Whether the struct is encoded as an array, in which case the tag is its first element
+    /// and the field names are not encoded.
+    pub array_like: bool,
+}
+
+#[derive(Clone, Debug, TypeEq, PartialEq, Eq)]
+pub struct SqlModelMetadata {
+    /// Whether the class was declared with `table=True`, making it a SQLAlchemy model
+    /// as well as a pydantic one. This is not inherited.
+    pub table: bool,
+}
+
 #[derive(Clone, Debug, TypeEq, PartialEq, Eq, Default)]
 pub struct DjangoReverseRelationIndex(SmallMap<Class, ClassSynthesizedFields>);
 
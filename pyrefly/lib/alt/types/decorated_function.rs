@@ -74,6 +74,8 @@ pub struct DecoratedFunction {
 pub struct Decorator {
     pub ty: Type,
     pub deprecation: Option<Deprecation>,
+    /// For a decorator that is a call, like `@app.get("/")`, the type of the function called.
+    pub callee: Option<Type>,
 }
 
 impl Display for Decorator {
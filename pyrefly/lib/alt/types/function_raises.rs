@@ -0,0 +1,38 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use pyrefly_derive::TypeEq;
+use pyrefly_derive::Visit;
+use pyrefly_derive::VisitMut;
+
+use crate::types::class::Class;
+
+/// The exception classes that may escape a call to a function.
+///
+/// To keep the work bounded, we only follow calls into other modules one level deep: the answer
+/// for `KeyFunctionLocalRaises` follows calls to functions in the same module, and the answer
+/// for `KeyFunctionRaises` additionally includes the local raises of functions called in other
+/// modules.
+#[derive(Clone, Debug, Default, Visit, VisitMut, TypeEq, PartialEq, Eq)]
+pub struct FunctionRaises(pub Box<[Class]>);
+
+impl Display for FunctionRaises {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "FunctionRaises(")?;
+        for (i, cls) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", cls.name())?;
+        }
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Reporting closeable resources that a function may leave open. See `binding::resources`
+//! for how we find the resources that are still open when a function returns.
+
+use pyrefly_graph::index::Idx;
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+
+use crate::alt::answers::LookupAnswer;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::binding::binding::Key;
+use crate::binding::resources::CLOSE_METHODS;
+use crate::config::error_kind::ErrorKind;
+use crate::error::collector::ErrorCollector;
+use crate::types::types::Type;
+
+/// Classes whose instances hold a resource that should be released with `with` or `.close()`,
+/// including through subclasses. Users can add to these with the `extra-closeable-types`
+/// config option.
+const DEFAULT_CLOSEABLE_TYPES: &[&str] = &[
+    "typing.IO",
+    "tempfile._TemporaryFileWrapper",
+    "tempfile.SpooledTemporaryFile",
+    "tempfile.TemporaryDirectory",
+    "sqlite3.Connection",
+    "socket.socket",
+    "subprocess.Popen",
+    "zipfile.ZipFile",
+    "tarfile.TarFile",
+    "_thread.lock",
+    "_thread.LockType",
+    "_thread.RLock",
+    "threading._RLock",
+    "concurrent.futures.Executor",
+    "aiohttp.ClientSession",
+    "requests.Session",
+    "httpx.Client",
+    "httpx.AsyncClient",
+];
+
+/// Closeable classes whose instances only hold memory, so leaving them open is harmless.
+const IN_MEMORY_TYPES: &[&str] = &["io.StringIO", "io.BytesIO"];
+
+impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
+    /// Report the resource created by the call at `range` and held by `name`, if its type
+    /// (the type of the definition `idx`) is closeable.
+    pub fn check_for_unclosed_resource(
+        &self,
+        name: &Name,
+        idx: Idx<Key>,
+        range: TextRange,
+        errors: &ErrorCollector,
+    ) {
+        let Some(extra_closeable_types) = &self.bindings().lint_config().extra_closeable_types else {
+            return;
+        };
+        let resource = self.get_idx(idx);
+        let Type::ClassType(cls) = resource.ty() else {
+            return;
+        };
+        let names = self.api_class_names(cls.class_object());
+        if IN_MEMORY_TYPES
+            .iter()
+            .any(|api| self.refers_to_api(&names, api))
+        {
+            return;
+        }
+        if !DEFAULT_CLOSEABLE_TYPES
+            .iter()
+            .copied()
+            .chain(extra_closeable_types.iter().map(String::as_str))
+            .any(|api| self.refers_to_api(&names, api))
+        {
+            return;
+        }
+        // Locks are released and executors shut down, rather than closed.
+        let detail = match CLOSE_METHODS
+            .iter()
+            .find(|method| self.has_attr(resource.ty(), &Name::new(**method)))
+        {
+            Some(method) => format!(
+                "Use the resource in a `with` statement, or call `.{method}()` on it on every path."
+            ),
+            None => "Use the resource in a `with` statement on every path.".to_owned(),
+        };
+        errors
+            .error_builder(
+                range,
+                ErrorKind::UnclosedResource,
+                format!(
+                    "`{name}` holds a `{}` that may not be closed before the function returns",
+                    cls.name(),
+                ),
+            )
+            .with_detail(detail)
+            .emit();
+    }
+}
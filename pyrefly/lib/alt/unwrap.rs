@@ -538,7 +538,12 @@ impl<'a, Ans: LookupAnswer> AnswersSolver<'a, Ans> {
                                 return Err(SubsetError::Other);
                             };
                             let ty = infer(Some(d), Some(HintRef(hint.types(), None)));
-                            let result = self.is_subset_eq_with_reason(&ty, branch_hint);
+                            let result = self.solver().is_subset_eq(
+                                &ty,
+                                branch_hint,
+                                self.type_order(),
+                                None,
+                            );
                             ret = Some(ty);
                             result
                         });
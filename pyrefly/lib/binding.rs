@@ -5,12 +5,14 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+pub mod argparse;
 pub mod attrs;
 pub mod base_class;
 pub mod binding;
 pub mod bindings;
 pub mod class;
 pub mod django;
+pub mod exception_flow;
 pub mod expr;
 pub mod function;
 pub mod metadata;
@@ -19,6 +21,7 @@ pub mod pattern;
 pub mod polars;
 pub mod pydantic;
 pub mod pytest;
+pub mod resources;
 pub mod scope;
 pub mod stmt;
 pub mod table;
@@ -0,0 +1,541 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Binding-time tracking of local `argparse.ArgumentParser` construction.
+//!
+//! We follow a parser from `parser = argparse.ArgumentParser(...)` through the
+//! `add_argument`, `set_defaults` and `add_subparsers` calls made on it (and on its
+//! argument groups and subparsers). When the parser reaches `parser.parse_args()`, we
+//! synthesize a namespace class whose fields are the destinations declared so far, so
+//! that `args.foo` is checked like any other attribute access.
+//!
+//! Only the arguments of the chosen subcommand are set by `parse_args()`, so destinations
+//! declared only on subparsers are left out of the namespace, and a namespace with such
+//! destinations falls back to `argparse.Namespace.__getattr__` for the names it doesn't know.
+//!
+//! A parser we cannot model precisely, e.g. one that is passed to another function or
+//! given a computed option name, is marked as escaped, and its `parse_args()` keeps
+//! returning a plain `argparse.Namespace`.
+
+use pyrefly_graph::index::Idx;
+use ruff_python_ast::Arguments;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Identifier;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::binding::binding::Binding;
+use crate::binding::binding::Key;
+use crate::binding::bindings::BindingsBuilder;
+use crate::binding::scope::FlowStyle;
+use crate::export::special::SpecialExport;
+
+/// How an argument stores its value on the namespace, following the `action=` of `add_argument`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgparseAction {
+    Store,
+    StoreConst,
+    StoreTrue,
+    StoreFalse,
+    Append,
+    AppendConst,
+    Extend,
+    Count,
+    BooleanOptional,
+    /// The `dest=` of `add_subparsers`, holding the name of the chosen command.
+    Subcommand,
+    /// A value provided by `set_defaults(name=value)`.
+    SetDefault,
+    /// A user-defined `Action` subclass or a registered action we don't know about.
+    Unknown,
+}
+
+/// How many values an argument consumes, following the `nargs=` of `add_argument`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgparseNargs {
+    /// No `nargs`: a single value.
+    Single,
+    /// `nargs="?"`: a single value, or `const`/`default` when the option has no value.
+    Optional,
+    /// `nargs="*"`, `"+"`, an integer or `argparse.REMAINDER`: a list of values.
+    Multiple,
+}
+
+/// One declaration of a namespace destination.
+#[derive(Clone, Debug)]
+pub struct ArgparseArgument {
+    /// The range of the option string or `dest=` that names the destination.
+    pub range: TextRange,
+    pub action: ArgparseAction,
+    pub nargs: ArgparseNargs,
+    /// The `type=` conversion, called with each string value.
+    pub conversion: Option<Expr>,
+    pub default: Option<Expr>,
+    pub constant: Option<Expr>,
+    /// Whether the parser always sets this destination: positional arguments and
+    /// `required=True` options.
+    pub required: bool,
+}
+
+/// Parser methods that return another object whose arguments land in the same namespace.
+const CHILD_METHODS: &[&str] = &[
+    "add_argument_group",
+    "add_mutually_exclusive_group",
+    "add_subparsers",
+    "add_parser",
+];
+
+/// Parser methods that neither add destinations nor let the parser escape.
+const INERT_METHODS: &[&str] = &[
+    "parse_args",
+    "parse_known_args",
+    "parse_intermixed_args",
+    "parse_known_intermixed_args",
+    "print_help",
+    "print_usage",
+    "format_help",
+    "format_usage",
+    "get_default",
+    "error",
+    "exit",
+];
+
+/// Parser methods whose result we replace with a synthesized namespace.
+const PARSE_METHODS: &[&str] = &["parse_args", "parse_intermixed_args"];
+
+/// What a method call on a followed parser does to its namespace.
+enum ArgparseCall {
+    /// The call declares these destinations.
+    Destinations(Vec<(Name, ArgparseArgument)>),
+    /// The call doesn't change the namespace.
+    Inert,
+    /// We can't tell what the call does to the namespace.
+    Opaque,
+}
+
+#[derive(Debug, Default)]
+struct ArgparseRoot {
+    destinations: SmallMap<Name, Vec<ArgparseArgument>>,
+    /// Destinations declared on a subparser, which may be absent after `parse_args()`.
+    subcommand_destinations: SmallMap<Name, Vec<ArgparseArgument>>,
+    escaped: bool,
+}
+
+/// The root parser whose namespace a parser-like object populates.
+#[derive(Clone, Copy, Debug)]
+pub struct ArgparseTarget {
+    root: usize,
+    /// Whether the object is (or belongs to) a subparser created by `add_parser`.
+    in_subcommand: bool,
+}
+
+/// The parsers being followed in the module currently being bound.
+#[derive(Debug, Default)]
+pub struct ArgparseParsers {
+    /// Names bound to a parser-like object, with the binding that defined them and
+    /// the root parser whose namespace they populate.
+    names: SmallMap<Name, (Idx<Key>, ArgparseTarget)>,
+    roots: Vec<ArgparseRoot>,
+    /// Ranges of receiver names in recognized method calls, whose reads are not escapes.
+    recognized_receivers: SmallSet<TextRange>,
+}
+
+fn is_argparse_constant(x: &Expr, constant: &str) -> bool {
+    match x {
+        Expr::Name(x) => x.id.as_str() == constant,
+        Expr::Attribute(x) => x.attr.id.as_str() == constant,
+        _ => false,
+    }
+}
+
+fn string_literal(x: &Expr) -> Option<&str> {
+    match x {
+        Expr::StringLiteral(x) => Some(x.value.to_str()),
+        _ => None,
+    }
+}
+
+fn has_unpacking(arguments: &Arguments) -> bool {
+    arguments.args.iter().any(|x| x.is_starred_expr())
+        || arguments.keywords.iter().any(|x| x.arg.is_none())
+}
+
+fn is_true_literal(x: Option<&Expr>) -> bool {
+    matches!(x, Some(Expr::BooleanLiteral(b)) if b.value)
+}
+
+fn parse_action(x: Option<&Expr>) -> Option<ArgparseAction> {
+    let Some(x) = x else {
+        return Some(ArgparseAction::Store);
+    };
+    if is_argparse_constant(x, "BooleanOptionalAction") {
+        return Some(ArgparseAction::BooleanOptional);
+    }
+    let Some(action) = string_literal(x) else {
+        return Some(ArgparseAction::Unknown);
+    };
+    Some(match action {
+        "store" => ArgparseAction::Store,
+        "store_const" => ArgparseAction::StoreConst,
+        "store_true" => ArgparseAction::StoreTrue,
+        "store_false" => ArgparseAction::StoreFalse,
+        "append" => ArgparseAction::Append,
+        "append_const" => ArgparseAction::AppendConst,
+        "extend" => ArgparseAction::Extend,
+        "count" => ArgparseAction::Count,
+        // These actions exit the program instead of storing anything.
+        "help" | "version" => return None,
+        _ => ArgparseAction::Unknown,
+    })
+}
+
+fn parse_nargs(x: Option<&Expr>) -> Option<ArgparseNargs> {
+    let Some(x) = x else {
+        return Some(ArgparseNargs::Single);
+    };
+    match x {
+        Expr::StringLiteral(s) if s.value.to_str() == "?" => Some(ArgparseNargs::Optional),
+        Expr::StringLiteral(s) if matches!(s.value.to_str(), "*" | "+" | "...") => {
+            Some(ArgparseNargs::Multiple)
+        }
+        Expr::NumberLiteral(_) => Some(ArgparseNargs::Multiple),
+        _ if is_argparse_constant(x, "REMAINDER") => Some(ArgparseNargs::Multiple),
+        _ => None,
+    }
+}
+
+/// The destination declared by `add_argument(*flags, **kwargs)`.
+/// Returns `Err(())` if we can't tell, and `Ok(None)` if the argument stores nothing.
+fn add_argument_destination(arguments: &Arguments) -> Result<Option<(Name, ArgparseArgument)>, ()> {
+    if has_unpacking(arguments) {
+        return Err(());
+    }
+    let keyword = |name: &str| arguments.find_keyword(name).map(|kw| &kw.value);
+    let mut flags = Vec::with_capacity(arguments.args.len());
+    for arg in &arguments.args {
+        flags.push((string_literal(arg).ok_or(())?, arg.range()));
+    }
+    let Some(action) = parse_action(keyword("action")) else {
+        return Ok(None);
+    };
+    // An unknown `nargs` still stores something, we just can't say what.
+    let (action, nargs) = match parse_nargs(keyword("nargs")) {
+        Some(nargs) => (action, nargs),
+        None => (ArgparseAction::Unknown, ArgparseNargs::Single),
+    };
+    let is_positional = matches!(flags.as_slice(), [(flag, _)] if !flag.starts_with('-'));
+    let (dest, range) = if let Some(dest) = keyword("dest") {
+        if is_argparse_constant(dest, "SUPPRESS") {
+            return Ok(None);
+        }
+        (string_literal(dest).ok_or(())?.to_owned(), dest.range())
+    } else if is_positional {
+        let (flag, range) = flags[0];
+        (flag.to_owned(), range)
+    } else {
+        // argparse prefers the first long option, then falls back to the first short one.
+        let (flag, range) = flags
+            .iter()
+            .find(|(flag, _)| flag.starts_with("--"))
+            .or_else(|| flags.first())
+            .ok_or(())?;
+        (flag.trim_start_matches('-').replace('-', "_"), *range)
+    };
+    if dest.is_empty() {
+        return Err(());
+    }
+    let required = if is_positional {
+        nargs != ArgparseNargs::Optional
+    } else {
+        is_true_literal(keyword("required"))
+    };
+    Ok(Some((
+        Name::new(dest),
+        ArgparseArgument {
+            range,
+            action,
+            nargs,
+            conversion: keyword("type").cloned(),
+            default: keyword("default").cloned(),
+            constant: keyword("const").cloned(),
+            required,
+        },
+    )))
+}
+
+fn classify_call(method: &str, arguments: &Arguments) -> ArgparseCall {
+    match method {
+        "add_argument" => match add_argument_destination(arguments) {
+            Ok(destination) => ArgparseCall::Destinations(destination.into_iter().collect()),
+            Err(()) => ArgparseCall::Opaque,
+        },
+        "set_defaults" if !has_unpacking(arguments) && arguments.args.is_empty() => {
+            ArgparseCall::Destinations(
+                arguments
+                    .keywords
+                    .iter()
+                    .filter_map(|kw| {
+                        let name = kw.arg.as_ref()?;
+                        Some((
+                            name.id.clone(),
+                            ArgparseArgument {
+                                range: name.range,
+                                action: ArgparseAction::SetDefault,
+                                nargs: ArgparseNargs::Single,
+                                conversion: None,
+                                default: Some(kw.value.clone()),
+                                constant: None,
+                                required: true,
+                            },
+                        ))
+                    })
+                    .collect(),
+            )
+        }
+        "add_subparsers" if !has_unpacking(arguments) => {
+            match arguments.find_keyword("dest").map(|kw| &kw.value) {
+                None => ArgparseCall::Inert,
+                Some(dest) => match string_literal(dest) {
+                    Some(name) => ArgparseCall::Destinations(vec![(
+                        Name::new(name),
+                        ArgparseArgument {
+                            range: dest.range(),
+                            action: ArgparseAction::Subcommand,
+                            nargs: ArgparseNargs::Single,
+                            conversion: None,
+                            default: None,
+                            constant: None,
+                            required: is_true_literal(
+                                arguments.find_keyword("required").map(|kw| &kw.value),
+                            ),
+                        },
+                    )]),
+                    None => ArgparseCall::Opaque,
+                },
+            }
+        }
+        _ if CHILD_METHODS.contains(&method) || INERT_METHODS.contains(&method) => {
+            ArgparseCall::Inert
+        }
+        _ => ArgparseCall::Opaque,
+    }
+}
+
+impl ArgparseParsers {
+    fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    fn escape(&mut self, root: usize) {
+        self.roots[root].escaped = true;
+    }
+}
+
+impl<'a> BindingsBuilder<'a> {
+    /// The root parser that `receiver` feeds, if it is a followed parser-like object.
+    /// Reaching a followed name through a different binding than the one we recorded
+    /// (e.g. a loop phi) conservatively escapes the parser.
+    fn argparse_root(&mut self, receiver: &Expr) -> Option<ArgparseTarget> {
+        match receiver {
+            Expr::Name(name) => {
+                let (idx, target) = *self.argparse.names.get(&name.id)?;
+                if self.scopes.current_flow_idx(&name.id) == Some(idx) {
+                    Some(target)
+                } else {
+                    self.argparse.escape(target.root);
+                    None
+                }
+            }
+            Expr::Call(call) => match &*call.func {
+                Expr::Attribute(func) if CHILD_METHODS.contains(&func.attr.id.as_str()) => {
+                    self.argparse_child(&func.value, func.attr.id.as_str())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The root parser fed by the object that `receiver.method(...)` returns.
+    fn argparse_child(&mut self, receiver: &Expr, method: &str) -> Option<ArgparseTarget> {
+        let target = self.argparse_root(receiver)?;
+        Some(ArgparseTarget {
+            root: target.root,
+            in_subcommand: target.in_subcommand || method == "add_parser",
+        })
+    }
+
+    /// Whether `name = value` may bind a parser-like object we want to follow. The
+    /// value is only inspected by `argparse_track_assign` once the name is bound.
+    pub fn is_argparse_assign_value(&self, value: &Expr) -> bool {
+        let Expr::Call(call) = value else {
+            return false;
+        };
+        match &*call.func {
+            Expr::Attribute(func)
+                if !self.argparse.is_empty() && CHILD_METHODS.contains(&func.attr.id.as_str()) =>
+            {
+                true
+            }
+            func => {
+                self.may_be_argument_parser(func)
+                    && self.as_special_export(func) == Some(SpecialExport::ArgparseArgumentParser)
+            }
+        }
+    }
+
+    /// Whether `func` may name `argparse.ArgumentParser`, without demanding anything from
+    /// another module for names imported from elsewhere.
+    fn may_be_argument_parser(&self, func: &Expr) -> bool {
+        match func {
+            Expr::Name(name) => match self.scopes.binding_idx_for_name(&name.id) {
+                Some((_, FlowStyle::Import(_, upstream_name))) => upstream_name == "ArgumentParser",
+                Some((_, FlowStyle::MergeableImport(_) | FlowStyle::ImportAs(_))) | None => false,
+                // May be a local alias, e.g. `Parser = argparse.ArgumentParser`.
+                Some(_) => true,
+            },
+            Expr::Attribute(attr) => attr.attr.id == "ArgumentParser",
+            _ => false,
+        }
+    }
+
+    /// Start following the parser created by `name = argparse.ArgumentParser(...)`, or
+    /// the argument group, subparsers action or subparser bound by
+    /// `name = <parser>.add_argument_group(...)` and similar calls.
+    pub fn argparse_track_assign(&mut self, name: &Name, value: &Expr) {
+        let Expr::Call(call) = value else {
+            return;
+        };
+        let Some(idx) = self.scopes.current_flow_idx(name) else {
+            return;
+        };
+        if let Expr::Attribute(func) = &*call.func
+            && CHILD_METHODS.contains(&func.attr.id.as_str())
+        {
+            if let Some(target) = self.argparse_child(&func.value, func.attr.id.as_str()) {
+                self.argparse.names.insert(name.clone(), (idx, target));
+            }
+            return;
+        }
+        // These options make the set of destinations or their spelling depend on
+        // something we don't follow.
+        let escaped = has_unpacking(&call.arguments)
+            || [
+                "parents",
+                "prefix_chars",
+                "argument_default",
+                "fromfile_prefix_chars",
+            ]
+            .iter()
+            .any(|kw| call.arguments.find_keyword(kw).is_some());
+        let target = ArgparseTarget {
+            root: self.argparse.roots.len(),
+            in_subcommand: false,
+        };
+        self.argparse.roots.push(ArgparseRoot {
+            destinations: SmallMap::new(),
+            subcommand_destinations: SmallMap::new(),
+            escaped,
+        });
+        self.argparse.names.insert(name.clone(), (idx, target));
+    }
+
+    /// If `call` is a method call on a followed parser, return the parser's root. This
+    /// must happen before the call's children are bound, so that the receiver isn't seen
+    /// as an escaping read.
+    pub fn argparse_receiver(&mut self, call: &ExprCall) -> Option<ArgparseTarget> {
+        if self.argparse.is_empty() {
+            return None;
+        }
+        let Expr::Attribute(func) = &*call.func else {
+            return None;
+        };
+        let target = self.argparse_root(&func.value)?;
+        if let Expr::Name(receiver) = &*func.value {
+            self.argparse.recognized_receivers.insert(receiver.range);
+        }
+        Some(target)
+    }
+
+    /// Record what a method call on a followed parser does to its namespace. A call
+    /// to `parse_args()` synthesizes the namespace class for the call's result.
+    pub fn argparse_call(&mut self, target: ArgparseTarget, call: &ExprCall) {
+        let Expr::Attribute(func) = &*call.func else {
+            return;
+        };
+        let method = func.attr.id.as_str();
+        let root = target.root;
+        match classify_call(method, &call.arguments) {
+            ArgparseCall::Destinations(destinations) => {
+                let root = &mut self.argparse.roots[root];
+                let destinations_of_target = if target.in_subcommand {
+                    &mut root.subcommand_destinations
+                } else {
+                    &mut root.destinations
+                };
+                for (name, argument) in destinations {
+                    destinations_of_target
+                        .entry(name)
+                        .or_default()
+                        .push(argument);
+                }
+            }
+            ArgparseCall::Inert => {}
+            ArgparseCall::Opaque => self.argparse.escape(root),
+        }
+        if PARSE_METHODS.contains(&method)
+            && !self.argparse.roots[root].escaped
+            && !has_unpacking(&call.arguments)
+            && call.arguments.find_keyword("namespace").is_none()
+            && call.arguments.args.len() < 2
+        {
+            let parsed = &self.argparse.roots[root];
+            // A destination that the root parser always sets may still be overwritten by
+            // the chosen subcommand.
+            let members = parsed
+                .destinations
+                .iter()
+                .map(|(name, arguments)| {
+                    let mut arguments = arguments.clone();
+                    if let Some(overrides) = parsed.subcommand_destinations.get(name) {
+                        arguments.extend(overrides.iter().cloned());
+                    }
+                    (name.clone(), arguments.into_boxed_slice())
+                })
+                .collect();
+            let has_subcommand_destinations = parsed
+                .subcommand_destinations
+                .keys()
+                .any(|name| !parsed.destinations.contains_key(name));
+            let class_name = Identifier::new(Name::new_static("Namespace"), func.attr.range);
+            let parent = self.scopes.nesting_context();
+            let class_idx = self.synthesize_argparse_namespace_def(
+                class_name,
+                &parent,
+                members,
+                has_subcommand_destinations,
+            );
+            self.insert_binding(Key::Anon(call.range), Binding::ArgparseNamespace(class_idx));
+        }
+    }
+
+    /// Note a read of `name`. Reading a followed parser anywhere other than as the
+    /// receiver of a recognized method call lets it escape.
+    pub fn argparse_observe_read(&mut self, name: &Identifier) {
+        if self.argparse.is_empty() || self.argparse.recognized_receivers.contains(&name.range) {
+            return;
+        }
+        if let Some((_, target)) = self.argparse.names.get(&name.id) {
+            let root = target.root;
+            self.argparse.escape(root);
+        }
+    }
+}
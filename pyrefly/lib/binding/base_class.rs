@@ -107,6 +107,10 @@ pub enum BaseClass {
     /// At runtime, evaluates to the metaclass of X (if X is a class)
     /// or the class of X (if X is an instance).
     TypeOf(BaseClassExpr, TextRange),
+    /// `argparse.Namespace`, as the base of a namespace synthesized for a parser.
+    /// The bool indicates whether destinations declared only on subparsers were left out,
+    /// so that the namespace doesn't know all of its attributes.
+    ArgparseNamespace(TextRange, bool),
 }
 
 impl BaseClass {
@@ -135,6 +139,7 @@ impl Ranged for BaseClass {
             BaseClass::NamedTuple(range, _) => *range,
             BaseClass::SynthesizedBase(_, range) => *range,
             BaseClass::TypeOf(_, range) => *range,
+            BaseClass::ArgparseNamespace(range, _) => *range,
         }
     }
 }
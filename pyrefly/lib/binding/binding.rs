@@ -66,9 +66,11 @@ use crate::alt::types::class_metadata::ClassSynthesizedFields;
 use crate::alt::types::class_metadata::DjangoReverseRelationIndex;
 use crate::alt::types::decorated_function::Decorator;
 use crate::alt::types::decorated_function::UndecoratedFunction;
+use crate::alt::types::function_raises::FunctionRaises;
 use crate::alt::types::legacy_lookup::LegacyTypeParameterLookup;
 use crate::alt::types::yields::YieldFromResult;
 use crate::alt::types::yields::YieldResult;
+use crate::binding::argparse::ArgparseArgument;
 use crate::binding::base_class::BaseClass;
 use crate::binding::base_class::BaseClassGeneric;
 use crate::binding::bindings::BindingEntry;
@@ -121,6 +123,8 @@ assert_words!(KeyYieldFrom, 1);
 assert_words!(KeyDecorator, 1);
 assert_words!(KeyDecoratedFunction, 1);
 assert_words!(KeyUndecoratedFunction, 1);
+assert_bytes!(KeyFunctionRaises, 4);
+assert_bytes!(KeyFunctionLocalRaises, 4);
 
 assert_words!(Binding, 6);
 assert_words!(BindingExpect, 14);
@@ -144,6 +148,8 @@ assert_words!(BindingYieldFrom, 4);
 assert_words!(BindingDecorator, 11);
 assert_bytes!(BindingDecoratedFunction, 20);
 assert_words!(BindingUndecoratedFunction, 18);
+assert_words!(BindingFunctionRaises, 5);
+assert_bytes!(BindingFunctionLocalRaises, 4);
 
 #[derive(Clone, Dupe, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AnyIdx {
@@ -162,6 +168,8 @@ pub enum AnyIdx {
     KeyDecoratedFunction(Idx<KeyDecoratedFunction>),
     KeyUndecoratedFunction(Idx<KeyUndecoratedFunction>),
     KeyUndecoratedFunctionRange(Idx<KeyUndecoratedFunctionRange>),
+    KeyFunctionRaises(Idx<KeyFunctionRaises>),
+    KeyFunctionLocalRaises(Idx<KeyFunctionLocalRaises>),
     KeyAnnotation(Idx<KeyAnnotation>),
     KeyClassMetadata(Idx<KeyClassMetadata>),
     KeyDjangoRelations(Idx<KeyDjangoRelations>),
@@ -229,6 +237,12 @@ macro_rules! dispatch_anyidx {
             AnyIdx::KeyUndecoratedFunctionRange(idx) => {
                 $self.$method::<$crate::binding::binding::KeyUndecoratedFunctionRange>(*idx)
             }
+            AnyIdx::KeyFunctionRaises(idx) => {
+                $self.$method::<$crate::binding::binding::KeyFunctionRaises>(*idx)
+            }
+            AnyIdx::KeyFunctionLocalRaises(idx) => {
+                $self.$method::<$crate::binding::binding::KeyFunctionLocalRaises>(*idx)
+            }
             AnyIdx::KeyAnnotation(idx) => {
                 $self.$method::<$crate::binding::binding::KeyAnnotation>(*idx)
             }
@@ -305,6 +319,12 @@ macro_rules! dispatch_anyidx {
             AnyIdx::KeyUndecoratedFunctionRange(idx) => {
                 $self.$method::<$crate::binding::binding::KeyUndecoratedFunctionRange>(*idx, $($args),+)
             }
+            AnyIdx::KeyFunctionRaises(idx) => {
+                $self.$method::<$crate::binding::binding::KeyFunctionRaises>(*idx, $($args),+)
+            }
+            AnyIdx::KeyFunctionLocalRaises(idx) => {
+                $self.$method::<$crate::binding::binding::KeyFunctionLocalRaises>(*idx, $($args),+)
+            }
             AnyIdx::KeyAnnotation(idx) => {
                 $self.$method::<$crate::binding::binding::KeyAnnotation>(*idx, $($args),+)
             }
@@ -360,6 +380,8 @@ impl DisplayWith<Bindings> for AnyIdx {
             Self::KeyDecoratedFunction(idx) => write!(f, "{}", ctx.display(*idx)),
             Self::KeyUndecoratedFunction(idx) => write!(f, "{}", ctx.display(*idx)),
             Self::KeyUndecoratedFunctionRange(idx) => write!(f, "{}", ctx.display(*idx)),
+            Self::KeyFunctionRaises(idx) => write!(f, "{}", ctx.display(*idx)),
+            Self::KeyFunctionLocalRaises(idx) => write!(f, "{}", ctx.display(*idx)),
             Self::KeyAnnotation(idx) => write!(f, "{}", ctx.display(*idx)),
             Self::KeyClassMetadata(idx) => write!(f, "{}", ctx.display(*idx)),
             Self::KeyDjangoRelations(idx) => write!(f, "{}", ctx.display(*idx)),
@@ -391,6 +413,8 @@ pub enum AnyExportedKey {
     KeyAbstractClassCheck(KeyAbstractClassCheck),
     KeyClassSubscriptSymmetry(KeyClassSubscriptSymmetry),
     KeyTypeAlias(KeyTypeAlias),
+    KeyFunctionRaises(KeyFunctionRaises),
+    KeyFunctionLocalRaises(KeyFunctionLocalRaises),
 }
 
 /// Any key that sets `EXPORTED` to `true` should not include positions
@@ -681,6 +705,50 @@ impl Keyed for KeyUndecoratedFunctionRange {
         bindings.get(idx).0.range()
     }
 }
+impl Keyed for KeyFunctionRaises {
+    const EXPORTED: bool = true;
+    type Value = BindingFunctionRaises;
+    type Answer = FunctionRaises;
+    fn to_anyidx(idx: Idx<Self>) -> AnyIdx {
+        AnyIdx::KeyFunctionRaises(idx)
+    }
+    fn range_with(idx: Idx<Self>, bindings: &Bindings) -> TextRange
+    where
+        BindingTable: TableKeyed<Self, Value = BindingEntry<Self>>,
+    {
+        bindings.get(idx).range
+    }
+    fn try_to_anykey(&self) -> Option<AnyExportedKey> {
+        Some(AnyExportedKey::KeyFunctionRaises(self.clone()))
+    }
+}
+impl Exported for KeyFunctionRaises {
+    fn to_anykey(&self) -> AnyExportedKey {
+        AnyExportedKey::KeyFunctionRaises(self.clone())
+    }
+}
+impl Keyed for KeyFunctionLocalRaises {
+    const EXPORTED: bool = true;
+    type Value = BindingFunctionLocalRaises;
+    type Answer = FunctionRaises;
+    fn to_anyidx(idx: Idx<Self>) -> AnyIdx {
+        AnyIdx::KeyFunctionLocalRaises(idx)
+    }
+    fn range_with(idx: Idx<Self>, bindings: &Bindings) -> TextRange
+    where
+        BindingTable: TableKeyed<Self, Value = BindingEntry<Self>>,
+    {
+        bindings.get(bindings.get(idx).0).range
+    }
+    fn try_to_anykey(&self) -> Option<AnyExportedKey> {
+        Some(AnyExportedKey::KeyFunctionLocalRaises(self.clone()))
+    }
+}
+impl Exported for KeyFunctionLocalRaises {
+    fn to_anykey(&self) -> AnyExportedKey {
+        AnyExportedKey::KeyFunctionLocalRaises(self.clone())
+    }
+}
 impl Keyed for KeyAnnotation {
     type Value = BindingAnnotation;
     type Answer = AnnotationWithTarget;
@@ -1109,6 +1177,8 @@ pub enum KeyExpect {
     ImplicitAliasCheck(TextRange),
     /// Validate an implementation's implicit return against its annotation.
     ValidateImplicitReturn(TextRange),
+    /// A resource created by a call that may be left open.
+    UnclosedResource(TextRange),
 }
 
 impl Ranged for KeyExpect {
@@ -1126,7 +1196,8 @@ impl Ranged for KeyExpect {
             | KeyExpect::UninitializedCheck(range)
             | KeyExpect::ForwardRefUnion(range)
             | KeyExpect::ImplicitAliasCheck(range)
-            | KeyExpect::ValidateImplicitReturn(range) => *range,
+            | KeyExpect::ValidateImplicitReturn(range)
+            | KeyExpect::UnclosedResource(range) => *range,
         }
     }
 }
@@ -1147,6 +1218,7 @@ impl DisplayWith<ModuleInfo> for KeyExpect {
             KeyExpect::ForwardRefUnion(r) => ("ForwardRefUnion", r),
             KeyExpect::ImplicitAliasCheck(r) => ("ImplicitAliasCheck", r),
             KeyExpect::ValidateImplicitReturn(r) => ("ValidateImplicitReturn", r),
+            KeyExpect::UnclosedResource(r) => ("UnclosedResource", r),
         };
         write!(f, "KeyExpect::{}({})", name, ctx.display(range))
     }
@@ -1269,6 +1341,16 @@ pub enum BindingExpect {
         expr: Box<Expr>,
         problem: Box<str>,
     },
+    /// A resource created by a call and assigned to a local variable, which the function may
+    /// return or fall off its end without closing. Reported if its type is closeable.
+    UnclosedResource {
+        /// The variable holding the resource.
+        name: Name,
+        /// The definition of the variable.
+        idx: Idx<Key>,
+        /// The range of the call that created the resource.
+        range: TextRange,
+    },
 }
 
 impl DisplayWith<Bindings> for BindingExpect {
@@ -1398,6 +1480,9 @@ impl DisplayWith<Bindings> for BindingExpect {
             Self::ImplicitAliasCheck { name, problem, .. } => {
                 write!(f, "ImplicitAliasCheck({name}, {problem})",)
             }
+            Self::UnclosedResource { name, idx, .. } => {
+                write!(f, "UnclosedResource({name}, {})", ctx.display(*idx))
+            }
         }
     }
 }
@@ -1570,6 +1655,86 @@ impl DisplayWith<Bindings> for BindingUndecoratedFunctionRange {
     }
 }
 
+/// The exceptions that may escape a call to a function, used by the exception flow checks and
+/// shown on hover.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyFunctionRaises(pub FuncDefIndex);
+
+impl DisplayWith<ModuleInfo> for KeyFunctionRaises {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, _: &ModuleInfo) -> fmt::Result {
+        write!(f, "KeyFunctionRaises({})", self.0)
+    }
+}
+
+/// The exceptions that may escape a call to a function, following only calls to functions in
+/// the same module. Calls from other modules ask for this rather than `KeyFunctionRaises`, so
+/// they don't force us to follow calls out of this module as well.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyFunctionLocalRaises(pub FuncDefIndex);
+
+impl DisplayWith<ModuleInfo> for KeyFunctionLocalRaises {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, _: &ModuleInfo) -> fmt::Result {
+        write!(f, "KeyFunctionLocalRaises({})", self.0)
+    }
+}
+
+/// What a function raises directly, or by calling another function.
+#[derive(Clone, Debug)]
+pub enum RaiseSiteKind {
+    /// The expression of a `raise` statement.
+    Raise(Expr),
+    /// The callee of a call, when it is a name or a chain of attributes on one.
+    Call(Expr),
+}
+
+#[derive(Clone, Debug)]
+pub struct RaiseSite {
+    pub kind: RaiseSiteKind,
+    /// The `except` clauses of the `try` statements whose body contains the site.
+    pub handlers: Box<[Idx<Key>]>,
+}
+
+impl Ranged for RaiseSite {
+    fn range(&self) -> TextRange {
+        match &self.kind {
+            RaiseSiteKind::Raise(x) | RaiseSiteKind::Call(x) => x.range(),
+        }
+    }
+}
+
+/// Binding value for `KeyFunctionRaises`: the sites in the function body that may raise an
+/// exception, except those inside a `try` with a bare `except:` or `except*` clause.
+#[derive(Clone, Debug)]
+pub struct BindingFunctionRaises {
+    /// The range of the function name.
+    pub range: TextRange,
+    /// The exception names listed in the `Raises` section of the docstring, if it has one.
+    pub documented: Option<Box<[String]>>,
+    pub sites: Box<[RaiseSite]>,
+}
+
+impl DisplayWith<Bindings> for BindingFunctionRaises {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, ctx: &Bindings) -> fmt::Result {
+        write!(
+            f,
+            "BindingFunctionRaises({}, {} sites)",
+            ctx.module().display(&self.range),
+            self.sites.len()
+        )
+    }
+}
+
+/// Binding value for `KeyFunctionLocalRaises`: the `KeyFunctionRaises` binding of the same
+/// function, which holds the sites.
+#[derive(Clone, Debug)]
+pub struct BindingFunctionLocalRaises(pub Idx<KeyFunctionRaises>);
+
+impl DisplayWith<Bindings> for BindingFunctionLocalRaises {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, ctx: &Bindings) -> fmt::Result {
+        write!(f, "BindingFunctionLocalRaises({})", ctx.display(self.0))
+    }
+}
+
 /// A reference to a class.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct KeyClass(pub ShortIdentifier);
@@ -2538,6 +2703,11 @@ pub enum Binding {
             Box<ExprCall>,
         )>,
     ),
+    /// The result of `parse_args()` on a parser followed at binding time: an instance of
+    /// the synthesized namespace class.
+    ArgparseNamespace(Idx<KeyClass>),
+    /// The value of a synthesized namespace field, from every declaration of its destination.
+    ArgparseDestination(Box<[ArgparseArgument]>),
 }
 
 impl DisplayWith<Bindings> for Binding {
@@ -2869,6 +3039,23 @@ impl DisplayWith<Bindings> for Binding {
                     None => write!(f, "terminated)"),
                 }
             }
+            Self::ArgparseNamespace(class_key) => {
+                write!(
+                    f,
+                    "ArgparseNamespace({})",
+                    m.display(ctx.idx_to_key(*class_key))
+                )
+            }
+            Self::ArgparseDestination(xs) => {
+                write!(f, "ArgparseDestination([")?;
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}", x.action)?;
+                }
+                write!(f, "])")
+            }
         }
     }
 }
@@ -2960,7 +3147,9 @@ impl Binding {
             | Binding::Delete(_)
             | Binding::ClassBodyUnknownName(_)
             | Binding::Exhaustive(_)
-            | Binding::SuppressedException(_) => None,
+            | Binding::SuppressedException(_)
+            | Binding::ArgparseNamespace(_)
+            | Binding::ArgparseDestination(_) => None,
         }
     }
 }
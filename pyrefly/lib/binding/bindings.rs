@@ -55,6 +55,7 @@ use starlark_map::small_set::SmallSet;
 use thin_vec::ThinVec;
 use vec1::Vec1;
 
+use crate::binding::argparse::ArgparseParsers;
 use crate::binding::binding::AnnotationTarget;
 use crate::binding::binding::Binding;
 use crate::binding::binding::BindingAnnotation;
@@ -86,15 +87,19 @@ use crate::binding::binding::LambdaParamId;
 use crate::binding::binding::LastStmt;
 use crate::binding::binding::LegacyTParamBinding;
 use crate::binding::binding::NarrowUseLocation;
+use crate::binding::binding::ReturnTypeKind;
 use crate::binding::binding::TypeAliasParams;
 use crate::binding::binding::TypeAliasRefBinding;
 use crate::binding::binding::TypeParameter;
+use crate::binding::exception_flow::ExceptionFlow;
+use crate::binding::exception_flow::ExceptionFlowBuilder;
 use crate::binding::expr::Usage;
 use crate::binding::metadata::BindingsMetadata;
 use crate::binding::narrow::NarrowOp;
 use crate::binding::narrow::NarrowOps;
 use crate::binding::pytest::PytestBindingInfo;
 use crate::binding::pytest::is_pytest_fixture_function;
+use crate::binding::resources::ResourceTracker;
 use crate::binding::scope::Exportable;
 use crate::binding::scope::FlowStyle;
 use crate::binding::scope::NameReadInfo;
@@ -107,12 +112,14 @@ use crate::binding::scope::fallback_builtin_modules;
 use crate::binding::scope::is_constant_name;
 use crate::binding::table::TableKeyed;
 use crate::config::base::InferReturnTypes;
+use crate::config::config::LintConfig;
 use crate::config::error_kind::ErrorKind;
 use crate::error::collector::ErrorCollector;
 use crate::export::definitions::MutableCaptureKind;
 use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
 use crate::export::special::SpecialExport;
+use crate::module::dependencies::ProjectDependencies;
 use crate::module::module_info::ModuleInfo;
 use crate::solver::solver::Solver;
 use crate::state::errors::ModuleRanges;
@@ -216,6 +223,14 @@ struct BindingsInner {
     unused_variables: Vec<UnusedVariable>,
     pytest_info: Option<PytestBindingInfo>,
     promote_ranges: SmallSet<TextRange>,
+    /// Ranges of the calls made directly in the body of an `async def`, not in a nested
+    /// `def` or `lambda`.
+    async_call_ranges: SmallSet<TextRange>,
+    /// Handler and `raise` information for the exception flow checks, see
+    /// `binding::exception_flow`.
+    exception_flow: ExceptionFlow,
+    /// Settings for the lint checks run on this module.
+    lint_config: LintConfig,
     /// Yield and yield-from indices for each lambda that contains yields,
     /// keyed by the lambda's TextRange. Populated at binding time so the
     /// solver can look up yield info without re-walking the AST.
@@ -317,8 +332,17 @@ pub struct BindingsBuilder<'a> {
     /// Defaults extracted from an adjacent `__new__.__defaults__` assignment,
     /// set by `stmts()` and consumed by namedtuple synthesis in `stmt()`.
     pub adjacent_namedtuple_defaults: Option<Vec<Expr>>,
+    /// `argparse` parsers constructed in this module, see `binding::argparse`.
+    pub argparse: ArgparseParsers,
     pub promote_ranges: SmallSet<TextRange>,
+    pub async_call_ranges: SmallSet<TextRange>,
+    pub exception_flow: ExceptionFlowBuilder,
+    /// Resources created by calls in function bodies, see `binding::resources`.
+    pub resources: ResourceTracker,
     pub type_checking_depth: usize,
+    /// Set when third-party imports should be checked against the project's declared dependencies.
+    project_dependencies: Option<&'a ProjectDependencies>,
+    lint_config: LintConfig,
 }
 
 /// An enum tracking whether we are in a generator expression
@@ -379,6 +403,9 @@ impl Bindings {
             class_scopes: Vec::new(),
             subsequently_initialized: SmallSet::new(),
             promote_ranges: SmallSet::new(),
+            async_call_ranges: SmallSet::new(),
+            exception_flow: ExceptionFlow::default(),
+            lint_config: LintConfig::default(),
         }))
     }
 
@@ -438,6 +465,10 @@ impl Bindings {
     pub(crate) fn pytest_info(&self) -> Option<&PytestBindingInfo> {
         self.0.pytest_info.as_ref()
     }
+
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.0.lint_config
+    }
     /// Returns the yield and yield-from indices for a lambda at the given range,
     /// or empty slices if the lambda has no yields.
     pub fn lambda_yield_keys(&self, range: TextRange) -> (&[Idx<KeyYield>], &[Idx<KeyYieldFrom>]) {
@@ -500,6 +531,16 @@ impl Bindings {
         self.0.promote_ranges.contains(&range)
     }
 
+    /// Is the call at `range` made directly in the body of an `async def`?
+    pub fn is_call_in_async_def(&self, range: TextRange) -> bool {
+        self.0.async_call_ranges.contains(&range)
+    }
+
+    /// The handlers of the `except` clauses before the one whose exception type is at `range`.
+    pub fn earlier_except_handlers(&self, range: TextRange) -> &[Idx<Key>] {
+        self.0.exception_flow.earlier_handlers(range)
+    }
+
     pub fn key_to_idx<K: Keyed>(&self, k: &K) -> Idx<K>
     where
         BindingTable: TableKeyed<K, Value = BindingEntry<K>>,
@@ -588,6 +629,17 @@ impl Bindings {
         }
     }
 
+    /// The explicit return annotation of the function `name`, if it has one.
+    pub fn function_return_annotation(&self, name: &Identifier) -> Option<Idx<KeyAnnotation>> {
+        match self.get(self.key_to_idx(&Key::ReturnType(ShortIdentifier::new(name)))) {
+            Binding::ReturnType(r) => match &r.kind {
+                ReturnTypeKind::ShouldTrustAnnotation { annotation, .. } => Some(*annotation),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     pub fn function_has_return_annotation(&self, name: &Identifier) -> bool {
         let b = self.get(self.key_to_idx(&Key::ReturnType(ShortIdentifier::new(name))));
         if let Binding::ReturnType(r) = b {
@@ -620,6 +672,8 @@ impl Bindings {
         analyze_unannotated_for_ide: bool,
         infer_return_types: InferReturnTypes,
         treat_all_caps_as_final: bool,
+        project_dependencies: Option<&ProjectDependencies>,
+        lint_config: LintConfig,
     ) -> Self {
         let pytest_info = PytestBindingInfo::from_module(&x);
         // Compute module ranges from the AST before consuming it. These are
@@ -656,8 +710,14 @@ impl Bindings {
             class_scopes: Vec::new(),
             subsequently_initialized: SmallSet::new(),
             adjacent_namedtuple_defaults: None,
+            argparse: ArgparseParsers::default(),
             promote_ranges: SmallSet::new(),
+            async_call_ranges: SmallSet::new(),
+            exception_flow: ExceptionFlowBuilder::default(),
+            resources: ResourceTracker::default(),
             type_checking_depth: 0,
+            project_dependencies,
+            lint_config,
         };
         builder.init_static_scope(&x.body, true);
         if module_info.name() != ModuleName::builtins() {
@@ -779,6 +839,9 @@ impl Bindings {
             class_scopes: builder.class_scopes,
             subsequently_initialized: builder.subsequently_initialized,
             promote_ranges: builder.promote_ranges,
+            async_call_ranges: builder.async_call_ranges,
+            exception_flow: builder.exception_flow.finish(),
+            lint_config: builder.lint_config,
         }))
     }
 
@@ -961,6 +1024,21 @@ impl<'a> BindingsBuilder<'a> {
         self.solver.infer_with_first_use
     }
 
+    /// Whether `unclosed-resource` is enabled, so resources need tracking.
+    pub fn track_resources(&self) -> bool {
+        self.lint_config.extra_closeable_types.is_some()
+    }
+
+    /// Whether `blocking-call-in-async` is enabled, so calls in `async def` bodies need tracking.
+    pub fn track_async_calls(&self) -> bool {
+        self.lint_config.extra_blocking_calls.is_some()
+    }
+
+    /// Whether `undocumented-raise` is enabled, so the sites that may raise need tracking.
+    pub fn track_raises(&self) -> bool {
+        self.lint_config.undocumented_raise
+    }
+
     /// Given a `key: K = impl Keyed`, get an `Idx<K>` for it. The intended use case
     /// is when creating a complex binding where the process of creating the binding
     /// requires being able to identify what we are binding.
@@ -1288,6 +1366,40 @@ impl<'a> BindingsBuilder<'a> {
         }
     }
 
+    /// Check that the distribution providing an absolutely imported `module` is declared in the
+    /// project's `pyproject.toml`, and record the import for the unused dependency summary.
+    pub fn check_declared_dependency(&mut self, module: ModuleName, range: TextRange) {
+        let Some(dependencies) = self.project_dependencies else {
+            return;
+        };
+        let Some(path) = self.lookup.module_path(module) else {
+            return;
+        };
+        let type_checking_only = self.type_checking_depth > 0;
+        for distribution in dependencies.providers(module, &path) {
+            self.metadata
+                .record_distribution_import(&distribution.normalized, type_checking_only);
+        }
+        let Some(distributions) = dependencies.undeclared(module, &path) else {
+            return;
+        };
+        let names = distributions
+            .iter()
+            .map(|distribution| format!("`{}`", distribution.name))
+            .join(" or ");
+        let msg = if type_checking_only {
+            format!(
+                "`{module}` is provided by {names}, which is not declared as a dependency in `pyproject.toml`. \
+                It is only imported under `TYPE_CHECKING`, so declaring it in a dependency group is enough"
+            )
+        } else {
+            format!(
+                "`{module}` is provided by {names}, which is not declared as a dependency in `pyproject.toml`"
+            )
+        };
+        self.error(range, ErrorKind::UndeclaredDependency, msg);
+    }
+
     // Only works for things with `Foo`, or `source.Foo`, or `F` where `from module import Foo as F`.
     // Does not work for things with nested modules - but no SpecialExport's have that.
     pub fn as_special_export(&self, e: &Expr) -> Option<SpecialExport> {
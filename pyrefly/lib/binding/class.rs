@@ -33,6 +33,7 @@ use ruff_text_size::Ranged;
 use ruff_text_size::TextRange;
 use starlark_map::small_map::SmallMap;
 
+use crate::binding::argparse::ArgparseArgument;
 use crate::binding::attrs::AttrsDecoratorMethods;
 use crate::binding::attrs::collect_attrs_decorator_methods;
 use crate::binding::base_class::BaseClass;
@@ -101,6 +102,7 @@ enum SynthesizedClassKind {
     TypedDict,
     NamedTuple,
     NewType,
+    ArgparseNamespace,
 }
 
 /// Right-align `default_elts` into `defaults`: a slice of N elements makes the last N fields
@@ -1682,6 +1684,52 @@ impl<'a> BindingsBuilder<'a> {
         );
     }
 
+    /// Synthesize the namespace class returned by `parse_args()` on a parser we followed,
+    /// with one field per destination. The class is anonymous: it is only reachable
+    /// through the type of the `parse_args()` call.
+    pub fn synthesize_argparse_namespace_def(
+        &mut self,
+        class_name: Identifier,
+        parent: &NestingContext,
+        destinations: Vec<(Name, Box<[ArgparseArgument]>)>,
+        has_subcommand_destinations: bool,
+    ) -> Idx<KeyClass> {
+        let (class_object, class_indices) = self.anon_class_object_and_indices(&class_name);
+        let class_idx = class_indices.class_idx;
+        let member_definitions = destinations
+            .into_iter()
+            .map(|(name, arguments)| {
+                let range = arguments.first().map_or(class_name.range, |x| x.range);
+                (
+                    name.to_string(),
+                    range,
+                    None,
+                    Some(ExprOrBinding::Binding(Binding::ArgparseDestination(
+                        arguments,
+                    ))),
+                )
+            })
+            .collect();
+        self.synthesize_class_def(
+            class_name.clone(),
+            class_object,
+            class_indices,
+            parent,
+            None,
+            Box::new([]),
+            member_definitions,
+            IllegalIdentifierHandling::Allow,
+            false,
+            SynthesizedClassKind::ArgparseNamespace,
+            Some(BaseClass::ArgparseNamespace(
+                class_name.range,
+                has_subcommand_destinations,
+            )),
+            false,
+        );
+        class_idx
+    }
+
     // Check that the variable name in a functional class definition matches the first argument string
     pub fn check_functional_definition_name(
         &mut self,
@@ -0,0 +1,223 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Binding-time tracking for the exception flow checks.
+//!
+//! For `unreachable-except`, we remember the handlers that precede each `except` clause of
+//! a `try` statement, so the solver can compare the clause against the ones before it.
+//!
+//! For `undocumented-raise` and hover, we remember the sites in each function body that may
+//! raise an exception: `raise` statements and calls, together with the handlers of the `try`
+//! statements around them. The solver turns these into the exceptions that may escape a call
+//! to the function, under `KeyFunctionRaises` and `KeyFunctionLocalRaises`. The sites are only
+//! recorded when `undocumented-raise` is enabled.
+
+use pyrefly_graph::index::Idx;
+use pyrefly_python::docstring::Docstring;
+use pyrefly_python::docstring::parse_raises_documentation;
+use ruff_python_ast::Expr;
+use ruff_python_ast::Stmt;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::binding::binding::BindingFunctionLocalRaises;
+use crate::binding::binding::BindingFunctionRaises;
+use crate::binding::binding::Key;
+use crate::binding::binding::KeyFunctionLocalRaises;
+use crate::binding::binding::KeyFunctionRaises;
+use crate::binding::binding::RaiseSite;
+use crate::binding::binding::RaiseSiteKind;
+use crate::binding::bindings::BindingsBuilder;
+use crate::types::function::FuncDefIndex;
+
+#[derive(Clone, Debug, Default)]
+pub struct ExceptionFlow {
+    /// The handlers of the earlier `except` clauses of the same `try`, keyed by the range of
+    /// the exception type of a later clause.
+    earlier_handlers: SmallMap<TextRange, Box<[Idx<Key>]>>,
+}
+
+impl ExceptionFlow {
+    pub fn earlier_handlers(&self, range: TextRange) -> &[Idx<Key>] {
+        self.earlier_handlers
+            .get(&range)
+            .map_or(&[], |handlers| &**handlers)
+    }
+}
+
+/// A site in the function body currently being bound, with the handlers around it so far.
+/// `handlers` is `None` once a bare `except:` or `except*` is known to catch everything it raises.
+#[derive(Debug)]
+struct PendingSite {
+    kind: RaiseSiteKind,
+    handlers: Option<Vec<Idx<Key>>>,
+}
+
+/// The function body currently being bound.
+#[derive(Debug)]
+struct RaiseFrame {
+    documented: Option<Box<[String]>>,
+    sites: Vec<PendingSite>,
+    /// For each `try` body we are inside of, the indices of the sites in it so far.
+    open_tries: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Default)]
+pub struct ExceptionFlowBuilder {
+    frames: Vec<RaiseFrame>,
+    flow: ExceptionFlow,
+}
+
+impl ExceptionFlowBuilder {
+    pub fn finish(self) -> ExceptionFlow {
+        self.flow
+    }
+}
+
+/// An `except` clause: the range of its exception type and the key of its handler.
+/// Both are `None` for a bare `except:`.
+pub type ExceptClause = Option<(TextRange, Idx<Key>)>;
+
+impl<'a> BindingsBuilder<'a> {
+    pub fn enter_function_raises(&mut self, body: &[Stmt]) {
+        let documented = if self.track_raises() {
+            Docstring::range_from_stmts(body)
+                .and_then(|range| parse_raises_documentation(self.module_info.code_at(range)))
+                .map(Vec::into_boxed_slice)
+        } else {
+            None
+        };
+        self.exception_flow.frames.push(RaiseFrame {
+            documented,
+            sites: Vec::new(),
+            open_tries: Vec::new(),
+        });
+    }
+
+    /// Leave the body of the function `def_index`, whose name is at `range`, and bind what it
+    /// may raise.
+    pub fn exit_function_raises(&mut self, def_index: FuncDefIndex, range: TextRange) {
+        let frame = self
+            .exception_flow
+            .frames
+            .pop()
+            .expect("exit_function_raises without enter_function_raises");
+        let sites = frame
+            .sites
+            .into_iter()
+            .filter_map(|site| {
+                Some(RaiseSite {
+                    kind: site.kind,
+                    handlers: site.handlers?.into_boxed_slice(),
+                })
+            })
+            .collect();
+        self.insert_function_raises(
+            def_index,
+            BindingFunctionRaises {
+                range,
+                documented: frame.documented,
+                sites,
+            },
+        );
+    }
+
+    /// Bind what the function `def_index` may raise, both in full and following only calls to
+    /// functions in this module.
+    pub fn insert_function_raises(
+        &mut self,
+        def_index: FuncDefIndex,
+        binding: BindingFunctionRaises,
+    ) {
+        let idx = self.insert_binding(KeyFunctionRaises(def_index), binding);
+        self.insert_binding(
+            KeyFunctionLocalRaises(def_index),
+            BindingFunctionLocalRaises(idx),
+        );
+    }
+
+    fn record_raise_site(&mut self, kind: RaiseSiteKind) {
+        let Some(frame) = self.exception_flow.frames.last_mut() else {
+            return;
+        };
+        let index = frame.sites.len();
+        for sites in &mut frame.open_tries {
+            sites.push(index);
+        }
+        frame.sites.push(PendingSite {
+            kind,
+            handlers: Some(Vec::new()),
+        });
+    }
+
+    /// Record a `raise` of `exc`.
+    pub fn record_raise(&mut self, exc: &Expr) {
+        self.record_raise_site(RaiseSiteKind::Raise(exc.clone()));
+    }
+
+    /// Record a call of `callee`. We only follow callees that are a name, or a chain of
+    /// attributes on one, since anything else is expensive to evaluate a second time.
+    pub fn record_call(&mut self, callee: &Expr) {
+        let mut base = callee;
+        while let Expr::Attribute(x) = base {
+            base = &x.value;
+        }
+        if matches!(base, Expr::Name(_)) {
+            self.record_raise_site(RaiseSiteKind::Call(callee.clone()));
+        }
+    }
+
+    pub fn enter_try_body(&mut self) {
+        if let Some(frame) = self.exception_flow.frames.last_mut() {
+            frame.open_tries.push(Vec::new());
+        }
+    }
+
+    /// Leave the body of a `try`, returning the sites in it.
+    pub fn exit_try_body(&mut self) -> Vec<usize> {
+        self.exception_flow
+            .frames
+            .last_mut()
+            .and_then(|frame| frame.open_tries.pop())
+            .unwrap_or_default()
+    }
+
+    /// Record the `except` clauses of a `try`, given the sites in its body.
+    pub fn record_except_clauses(
+        &mut self,
+        sites: Vec<usize>,
+        clauses: &[ExceptClause],
+        is_star: bool,
+    ) {
+        let ExceptionFlowBuilder { frames, flow } = &mut self.exception_flow;
+        let handlers = clauses
+            .iter()
+            .flatten()
+            .map(|(_, idx)| *idx)
+            .collect::<Vec<_>>();
+        if !is_star {
+            for (i, (range, _)) in clauses.iter().flatten().enumerate().skip(1) {
+                flow.earlier_handlers
+                    .insert(*range, handlers[..i].to_vec().into_boxed_slice());
+            }
+        }
+        let Some(frame) = frames.last_mut() else {
+            return;
+        };
+        // We don't look inside the exception groups caught by `except*`, so treat the sites in
+        // the body as caught. A bare `except:` catches everything.
+        let catches_all = is_star || clauses.iter().any(|clause| clause.is_none());
+        for index in sites {
+            let site = &mut frame.sites[index];
+            if catches_all {
+                site.handlers = None;
+            } else if let Some(site_handlers) = &mut site.handlers {
+                site_handlers.extend(handlers.iter().copied());
+            }
+        }
+    }
+}
@@ -424,6 +424,7 @@ impl<'a> BindingsBuilder<'a> {
                 if promote {
                     self.promote_ranges.insert(name.range);
                 }
+                self.argparse_observe_read(name);
                 self.defer_bound_name(key, lookup_result_idx, usage, promote)
             }
             NameLookupResult::NotFound => {
@@ -906,6 +907,13 @@ impl<'a> BindingsBuilder<'a> {
                 }
             }
             Expr::Call(call) => {
+                if self.track_async_calls() && self.scopes.is_in_async_def() {
+                    self.async_call_ranges.insert(call.range);
+                }
+                self.resource_release_on_call(call);
+                if self.track_raises() {
+                    self.record_call(&call.func);
+                }
                 // The `as_special_export` call is load-bearing for
                 // binding-variant choice — it drives a demand edge to
                 // `target::Exports`.
@@ -1093,7 +1101,13 @@ impl<'a> BindingsBuilder<'a> {
                     return;
                 }
                 // Default: recurse into children as for any other expr.
+                let argparse_root = self.argparse_receiver(call);
                 x.recurse_mut(&mut |x| self.ensure_expr(x, usage));
+                if let Some(root) = argparse_root
+                    && let Expr::Call(call) = x
+                {
+                    self.argparse_call(root, call);
+                }
             }
             Expr::Named(x) => {
                 // For scopes defined in terms of Definitions, we should normally already have the name in Static, but
@@ -1156,6 +1170,9 @@ impl<'a> BindingsBuilder<'a> {
                 self.ensure_name(&name, usage, &mut None);
             }
             Expr::Yield(x) => {
+                if let Some(value) = &x.value {
+                    self.resource_release_escaping(value);
+                }
                 self.record_yield(x.clone());
             }
             Expr::YieldFrom(x) => {
@@ -17,6 +17,7 @@ use pyrefly_python::nesting_context::NestingContext;
 use pyrefly_python::short_identifier::ShortIdentifier;
 use pyrefly_python::sys_info::SysInfo;
 use pyrefly_types::function::BodyKind;
+use pyrefly_types::function::FuncDefIndex;
 use pyrefly_types::function::FuncFacts;
 use pyrefly_types::meta_shape_dsl::convert_shape_dsl_function;
 use pyrefly_types::type_level_dsl::ValidatedTypeShapeDslFunction;
@@ -47,6 +48,7 @@ use crate::binding::binding::Binding;
 use crate::binding::binding::BindingAnnotation;
 use crate::binding::binding::BindingDecoratedFunction;
 use crate::binding::binding::BindingExpect;
+use crate::binding::binding::BindingFunctionRaises;
 use crate::binding::binding::BindingUndecoratedFunction;
 use crate::binding::binding::BindingUndecoratedFunctionRange;
 use crate::binding::binding::BindingYield;
@@ -396,6 +398,7 @@ impl<'a> BindingsBuilder<'a> {
         range: TextRange,
         func_name: &Identifier,
         parent: &NestingContext,
+        func_def_index: FuncDefIndex,
         undecorated_idx: Idx<KeyUndecoratedFunction>,
         class_key: Option<Idx<KeyClass>>,
         is_async: bool,
@@ -429,10 +432,14 @@ impl<'a> BindingsBuilder<'a> {
             );
             self.bind_name(&dunder_class_identifier.id, idx, FlowStyle::Other);
         }
+        self.enter_function_raises(&body);
+        self.enter_function_resources();
         self.stmts(
             body,
             &NestingContext::function(ShortIdentifier::new(func_name), parent.dupe()),
         );
+        self.exit_function_resources();
+        self.exit_function_raises(func_def_index, func_name.range);
         let (yields_and_returns, self_assignments, unused_parameters, unused_variables) =
             self.scopes.pop_function_scope();
         (
@@ -700,6 +707,7 @@ impl<'a> BindingsBuilder<'a> {
         return_ann_with_range: Option<(TextRange, Idx<KeyAnnotation>)>,
         func_name: &Identifier,
         parent: &NestingContext,
+        func_def_index: FuncDefIndex,
         undecorated_idx: Idx<KeyUndecoratedFunction>,
         class_key: Option<Idx<KeyClass>>,
     ) -> (BodyKind, bool, Option<SelfAssignments>) {
@@ -781,6 +789,15 @@ impl<'a> BindingsBuilder<'a> {
                 method_self_kind,
                 decorators.has_no_type_check,
             );
+            // We didn't look at the body, so we don't know what it raises.
+            self.insert_function_raises(
+                func_def_index,
+                BindingFunctionRaises {
+                    range: func_name.range,
+                    documented: None,
+                    sites: Box::new([]),
+                },
+            );
             (false, self_assignments)
         } else if is_unannotated {
             let implicit_return = Some(self.implicit_return(&body, func_name));
@@ -790,6 +807,7 @@ impl<'a> BindingsBuilder<'a> {
                 range,
                 func_name,
                 parent,
+                func_def_index,
                 undecorated_idx,
                 class_key,
                 is_async,
@@ -817,6 +835,7 @@ impl<'a> BindingsBuilder<'a> {
                     range,
                     func_name,
                     parent,
+                    func_def_index,
                     undecorated_idx,
                     class_key,
                     is_async,
@@ -1001,6 +1020,7 @@ impl<'a> BindingsBuilder<'a> {
             return_ann_with_range,
             &func_name,
             parent,
+            func_def_index,
             undecorated_idx,
             class_key,
         );
@@ -17,6 +17,7 @@ use pyrefly_types::class::ClassDefIndex;
 use pyrefly_types::class::ClassFields;
 use pyrefly_types::meta_shape_dsl::ShapeDslFunction;
 use ruff_python_ast::name::Name;
+use starlark_map::small_map::SmallMap;
 
 /// Metadata for a single class definition, populated during binding.
 #[derive(Debug, Clone, Default)]
@@ -43,6 +44,10 @@ pub struct ClassMetadata {
 pub struct BindingsMetadata {
     classes: Vec<ClassMetadata>,
     shape_dsl_functions: Vec<(Name, Arc<ShapeDslFunction>)>,
+    /// Normalized names of the third-party distributions imported by the module, each mapped
+    /// to whether every import of it is under `TYPE_CHECKING`. Only populated when imports are
+    /// checked against the project's declared dependencies.
+    imported_distributions: SmallMap<String, bool>,
 }
 
 impl BindingsMetadata {
@@ -50,6 +55,7 @@ impl BindingsMetadata {
         Self {
             classes: Vec::new(),
             shape_dsl_functions: Vec::new(),
+            imported_distributions: SmallMap::new(),
         }
     }
 
@@ -84,4 +90,20 @@ impl BindingsMetadata {
     pub fn shape_dsl_functions(&self) -> &[(Name, Arc<ShapeDslFunction>)] {
         &self.shape_dsl_functions
     }
+
+    /// Record an import of a module provided by the distribution `name`.
+    pub fn record_distribution_import(&mut self, name: &str, type_checking_only: bool) {
+        match self.imported_distributions.get_mut(name) {
+            Some(only) => *only &= type_checking_only,
+            None => {
+                self.imported_distributions
+                    .insert(name.to_owned(), type_checking_only);
+            }
+        }
+    }
+
+    /// The distributions imported by this module, see `record_distribution_import`.
+    pub fn imported_distributions(&self) -> &SmallMap<String, bool> {
+        &self.imported_distributions
+    }
 }
@@ -0,0 +1,256 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Binding-time tracking of resources, such as open files or database connections, that a
+//! function creates with a call and assigns to a local variable.
+//!
+//! Each flow records the resources that are still open in it, and merging flows keeps a
+//! resource open if any of the merged flows left it open. A resource is released when it
+//! is used as the context manager of a `with`, closed with `.close()` or a similar method,
+//! returned or yielded, assigned to an attribute or an item (e.g. `self.conn = conn` or
+//! `cache[key] = conn`), passed to a method of `self`, or handed to an `ExitStack`. Passing it
+//! to any other call, such as `files.append(f)`, keeps it open, since the callee may not keep it.
+//!
+//! Nothing is tracked unless `unclosed-resource` is enabled.
+//!
+//! When a function returns, or falls off its end, with a resource still open, we insert a
+//! `KeyExpect::UnclosedResource`. The solver reports it if the type of the resource is one
+//! of the closeable types; we can't tell that yet while binding.
+
+use pyrefly_graph::index::Idx;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::WithItem;
+use ruff_python_ast::name::Name;
+use ruff_python_ast::visitor::Visitor;
+use ruff_python_ast::visitor::walk_expr;
+use ruff_python_ast::visitor::walk_stmt;
+use ruff_text_size::TextRange;
+use starlark_map::small_set::SmallSet;
+
+use crate::binding::binding::BindingExpect;
+use crate::binding::binding::Key;
+use crate::binding::binding::KeyExpect;
+use crate::binding::bindings::BindingsBuilder;
+
+/// Methods that release a resource.
+pub const CLOSE_METHODS: &[&str] = &["close", "aclose", "release", "cleanup", "shutdown"];
+
+/// Methods that hand a resource to something that closes it later, like an `ExitStack`.
+const HAND_OFF_METHODS: &[&str] = &["enter_context", "enter_async_context"];
+
+/// A resource created by a call and assigned to a local variable.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OpenResource {
+    pub name: Name,
+    /// The range of the call that created the resource.
+    pub range: TextRange,
+    /// The definition of `name` holding the resource.
+    pub idx: Idx<Key>,
+}
+
+#[derive(Debug, Default)]
+pub struct ResourceTracker {
+    /// For each function body being bound, and each `try` with a `finally` we are inside of,
+    /// the names that the `finally` closes.
+    finally_closes: Vec<Vec<SmallSet<Name>>>,
+    /// The resources we already inserted a `KeyExpect::UnclosedResource` for.
+    reported: SmallSet<TextRange>,
+}
+
+/// The name closed by a call to one of the `CLOSE_METHODS`, e.g. `f.close()`.
+fn closed_name(call: &ExprCall) -> Option<&Name> {
+    if let Expr::Attribute(attr) = &*call.func
+        && CLOSE_METHODS.contains(&attr.attr.id.as_str())
+        && let Expr::Name(name) = &*attr.value
+    {
+        Some(&name.id)
+    } else {
+        None
+    }
+}
+
+/// Collects the names closed anywhere in a `finally` body.
+#[derive(Default)]
+struct ClosedNames(SmallSet<Name>);
+
+impl<'a> Visitor<'a> for ClosedNames {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if !matches!(stmt, Stmt::FunctionDef(_) | Stmt::ClassDef(_)) {
+            walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::Call(call) = expr
+            && let Some(name) = closed_name(call)
+        {
+            self.0.insert(name.clone());
+        }
+        if !matches!(expr, Expr::Lambda(_)) {
+            walk_expr(self, expr);
+        }
+    }
+}
+
+/// Collects the names whose values an expression may pass on, e.g. `f` in `f`, `(f, g)` or
+/// `Reader(f)`, but not in `f.read()` or `f[0]`.
+#[derive(Default)]
+struct NamesIn(Vec<Name>);
+
+impl<'a> Visitor<'a> for NamesIn {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Name(name) => self.0.push(name.id.clone()),
+            Expr::Attribute(x) => {
+                if !matches!(&*x.value, Expr::Name(_)) {
+                    self.visit_expr(&x.value);
+                }
+            }
+            Expr::Subscript(x) => {
+                if !matches!(&*x.value, Expr::Name(_)) {
+                    self.visit_expr(&x.value);
+                }
+                self.visit_expr(&x.slice);
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+impl<'a> BindingsBuilder<'a> {
+    pub fn enter_function_resources(&mut self) {
+        self.resources.finally_closes.push(Vec::new());
+    }
+
+    /// Leave a function body, reporting the resources it leaves open when falling off its end.
+    pub fn exit_function_resources(&mut self) {
+        if !self.scopes.has_terminated() {
+            self.report_open_resources();
+        }
+        self.resources.finally_closes.pop();
+    }
+
+    /// Track the resource created by the call at `range`, after it is assigned to `name`.
+    /// A resource that `name` held before is lost, so we report it if it is still open.
+    pub fn resource_track_assign(&mut self, name: &Name, range: TextRange) {
+        if !self.track_resources() || !self.scopes.in_function_body() {
+            return;
+        }
+        let Some(idx) = self.scopes.current_flow_idx(name) else {
+            return;
+        };
+        for resource in self.scopes.open_resources() {
+            if &resource.name == name {
+                self.report_resource(resource);
+            }
+        }
+        self.scopes.release_resource(name);
+        self.scopes.track_open_resource(OpenResource {
+            name: name.clone(),
+            range,
+            idx,
+        });
+    }
+
+    /// Release every resource named in `x`, which is returned, yielded, or stored somewhere
+    /// that outlives the function.
+    pub fn resource_release_escaping(&mut self, x: &Expr) {
+        let mut names = NamesIn::default();
+        names.visit_expr(x);
+        for name in names.0 {
+            self.scopes.release_resource(&name);
+        }
+    }
+
+    /// Release the resources closed or handed off by `call`.
+    pub fn resource_release_on_call(&mut self, call: &ExprCall) {
+        if let Some(name) = closed_name(call) {
+            self.scopes.release_resource(name);
+        } else if let Expr::Attribute(attr) = &*call.func
+            && (HAND_OFF_METHODS.contains(&attr.attr.id.as_str())
+                || self.scopes.is_self_attribute(&attr.value))
+        {
+            for arg in call.arguments.args.iter() {
+                self.resource_release_escaping(arg);
+            }
+        }
+    }
+
+    /// Release the resources used as context managers by a `with` statement, either directly
+    /// (`with f:`) or through a wrapper such as `with closing(f):`.
+    pub fn resource_release_in_with(&mut self, items: &[WithItem]) {
+        for item in items {
+            match &item.context_expr {
+                Expr::Name(name) => self.scopes.release_resource(&name.id),
+                Expr::Call(call) => {
+                    for arg in call.arguments.args.iter() {
+                        if let Expr::Name(name) = arg {
+                            self.scopes.release_resource(&name.id);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Enter the body of a `try` statement with the given `finally` body.
+    pub fn resource_enter_try(&mut self, finalbody: &[Stmt]) {
+        let mut closed = ClosedNames::default();
+        closed.visit_body(finalbody);
+        if let Some(frame) = self.resources.finally_closes.last_mut() {
+            frame.push(closed.0);
+        }
+    }
+
+    /// Leave a `try` statement before binding its `finally` body.
+    pub fn resource_exit_try(&mut self) {
+        if let Some(frame) = self.resources.finally_closes.last_mut() {
+            frame.pop();
+        }
+    }
+
+    /// Report the resources left open when returning `value`.
+    pub fn resource_check_return(&mut self, value: Option<&Expr>) {
+        if !self.scopes.in_function_body() {
+            return;
+        }
+        if let Some(value) = value {
+            self.resource_release_escaping(value);
+        }
+        self.report_open_resources();
+    }
+
+    fn report_open_resources(&mut self) {
+        for resource in self.scopes.open_resources() {
+            let closed_in_finally = self
+                .resources
+                .finally_closes
+                .last()
+                .is_some_and(|frame| frame.iter().any(|names| names.contains(&resource.name)));
+            if !closed_in_finally {
+                self.report_resource(resource);
+            }
+        }
+    }
+
+    fn report_resource(&mut self, resource: OpenResource) {
+        if !self.resources.reported.insert(resource.range) {
+            return;
+        }
+        self.insert_binding(
+            KeyExpect::UnclosedResource(resource.range),
+            BindingExpect::UnclosedResource {
+                name: resource.name,
+                idx: resource.idx,
+                range: resource.range,
+            },
+        );
+    }
+}
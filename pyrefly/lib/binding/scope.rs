@@ -69,6 +69,7 @@ use crate::binding::bindings::InitializedInFlow;
 use crate::binding::expr::Usage;
 use crate::binding::function::SelfAssignments;
 use crate::binding::narrow::NarrowOps;
+use crate::binding::resources::OpenResource;
 use crate::export::definitions::Definition;
 use crate::export::definitions::DefinitionStyle;
 use crate::export::definitions::Definitions;
@@ -607,6 +608,9 @@ pub struct Flow {
     /// The key for the last `Binding::StmtExpr` in this flow, if any.
     /// Used to check for type-based termination (NoReturn/Never) at solve time.
     last_stmt_expr: Option<Idx<Key>>,
+    /// Resources created in this flow that have not been released yet, see
+    /// `binding::resources`.
+    open_resources: SmallSet<OpenResource>,
 }
 
 impl Flow {
@@ -2750,6 +2754,46 @@ impl Scopes {
         self.current().flow.has_terminated
     }
 
+    /// Is the current scope the body of a function, as opposed to a class body, a
+    /// comprehension or the module?
+    pub fn in_function_body(&self) -> bool {
+        matches!(
+            self.current().kind,
+            ScopeKind::Function(_) | ScopeKind::Method(_)
+        )
+    }
+
+    pub fn track_open_resource(&mut self, resource: OpenResource) {
+        self.current_mut().flow.open_resources.insert(resource);
+    }
+
+    pub fn release_resource(&mut self, name: &Name) {
+        self.current_mut()
+            .flow
+            .open_resources
+            .retain(|resource| &resource.name != name);
+    }
+
+    pub fn open_resources(&self) -> Vec<OpenResource> {
+        self.current().flow.open_resources.iter().cloned().collect()
+    }
+
+    /// Is `x` the `self` of the current method, or an attribute reached from it, like
+    /// `self.conn` or `self.pool.conns`?
+    pub fn is_self_attribute(&self, mut x: &Expr) -> bool {
+        while let Expr::Attribute(attr) = x {
+            x = &attr.value;
+        }
+        if let ScopeKind::Method(method_scope) = &self.current().kind
+            && let Some(self_name) = &method_scope.self_name
+            && let Expr::Name(name) = x
+        {
+            name.id == self_name.id
+        } else {
+            false
+        }
+    }
+
     /// Whether the current flow terminated by raising, as opposed to `return`/`break`/
     /// `continue`. Only a raise can be swallowed by an enclosing `with`.
     pub fn terminated_by_raise(&self) -> bool {
@@ -3906,6 +3950,15 @@ impl<'a> BindingsBuilder<'a> {
                 0
             };
 
+        // A resource is still open after the merge if any merged flow left it open.
+        let mut open_resources = SmallSet::new();
+        if matches!(merge_style, MergeStyle::Loop) {
+            open_resources.extend(base.open_resources.iter().cloned());
+        }
+        for flow in &flows {
+            open_resources.extend(flow.open_resources.iter().cloned());
+        }
+
         // Count how many branches have a last_stmt_expr (potential type-based termination)
         let n_branches_with_termination_key =
             flows.iter().filter(|f| f.last_stmt_expr.is_some()).count();
@@ -3969,6 +4022,7 @@ impl<'a> BindingsBuilder<'a> {
             terminated_by_raise: has_terminated && any_terminated_by_raise,
             is_definitely_unreachable: all_are_unreachable,
             last_stmt_expr: None,
+            open_resources,
         };
         self.scopes.current_mut().flow = flow
     }
@@ -708,6 +708,7 @@ impl<'a> BindingsBuilder<'a> {
             }
             Stmt::ClassDef(x) => self.class_def(x, parent),
             Stmt::Return(x) => {
+                self.resource_check_return(x.value.as_deref());
                 self.record_return(x);
             }
             Stmt::Delete(mut x) => {
@@ -861,12 +862,30 @@ impl<'a> BindingsBuilder<'a> {
                             _ => {}
                         }
                     }
+                    let argparse_value = self
+                        .is_argparse_assign_value(&x.value)
+                        .then(|| (*x.value).clone());
+                    let resource_range = match &*x.value {
+                        Expr::Call(call) => Some(call.range),
+                        // Aliasing a resource hands it to the new name, which we don't track.
+                        value @ Expr::Name(_) => {
+                            self.resource_release_escaping(value);
+                            None
+                        }
+                        _ => None,
+                    };
                     self.bind_single_name_assign(
                         &Ast::expr_name_identifier(name.clone()),
                         x.value,
                         None,
                         true,
                     );
+                    if let Some(value) = argparse_value {
+                        self.argparse_track_assign(&name.id, &value);
+                    }
+                    if let Some(range) = resource_range {
+                        self.resource_track_assign(&name.id, range);
+                    }
                 } else if let Expr::Call(call) = &mut *x.value
                     && matches!(call.arguments.args.first(), Some(Expr::StringLiteral(_)))
                     && let Some(
@@ -876,6 +895,13 @@ impl<'a> BindingsBuilder<'a> {
                 {
                     self.bind_multi_target_named_tuple(&mut x.targets, call, special);
                 } else {
+                    if x.targets
+                        .iter()
+                        .any(|target| !matches!(target, Expr::Name(_)))
+                    {
+                        // The value is stored somewhere that may outlive the function.
+                        self.resource_release_escaping(&x.value);
+                    }
                     self.bind_targets_with_value(&mut x.targets, &mut x.value);
                 }
             }
@@ -952,6 +978,10 @@ impl<'a> BindingsBuilder<'a> {
                             _ => AnnAssignHasValue::Yes,
                         },
                     );
+                    let resource_range = match value.as_deref() {
+                        Some(Expr::Call(call)) => Some(call.range),
+                        _ => None,
+                    };
                     let canonical_ann_idx = match value {
                         Some(value) => self.bind_single_name_assign(
                             &name,
@@ -991,6 +1021,9 @@ impl<'a> BindingsBuilder<'a> {
                             },
                         );
                     }
+                    if let Some(range) = resource_range {
+                        self.resource_track_assign(&name.id, range);
+                    }
                 }
                 Expr::Attribute(attr) => {
                     let mut attr = attr;
@@ -1006,6 +1039,7 @@ impl<'a> BindingsBuilder<'a> {
                     );
                     let value = match x.value {
                         Some(mut assigned) => {
+                            self.resource_release_escaping(&assigned);
                             self.bind_attr_assign(attr.clone(), &mut assigned, |v, _| {
                                 ExprOrBinding::Expr(v.clone())
                             })
@@ -1379,6 +1413,7 @@ impl<'a> BindingsBuilder<'a> {
                 // visiting the body: a terminator in the body marks the flow dead, and
                 // we must not resurrect a flow that was already dead beforehand.
                 let reachable = !self.scopes.is_definitely_unreachable();
+                self.resource_release_in_with(&x.items);
                 let mut contexts = Vec::with_capacity(x.items.len());
                 for mut item in x.items {
                     let item_range = item.range();
@@ -1441,6 +1476,9 @@ impl<'a> BindingsBuilder<'a> {
                 if let Some(mut exc) = x.exc {
                     let mut current = self.declare_current_idx(Key::UsageLink(x.range));
                     self.ensure_expr(&mut exc, current.usage());
+                    if self.track_raises() {
+                        self.record_raise(&exc);
+                    }
                     let raised = if let Some(mut cause) = x.cause {
                         self.ensure_expr(&mut cause, current.usage());
                         RaisedException::WithCause(Box::new((*exc, *cause)))
@@ -1468,10 +1506,14 @@ impl<'a> BindingsBuilder<'a> {
                 //   |                     ^
                 //   ----> handler --------|
 
+                self.resource_enter_try(&x.finalbody);
+                self.enter_try_body();
                 self.stmts(x.body, parent);
+                let raises = self.exit_try_body();
                 self.stmts(x.orelse, parent);
                 self.finish_branch();
 
+                let mut clauses = Vec::with_capacity(x.handlers.len());
                 for h in x.handlers {
                     self.start_branch();
                     let range = h.range();
@@ -1481,6 +1523,7 @@ impl<'a> BindingsBuilder<'a> {
                             let mut handler = self
                                 .declare_current_idx(Key::Definition(ShortIdentifier::new(name)));
                             self.ensure_expr(&mut type_, handler.usage());
+                            clauses.push(Some((type_.range(), handler.idx())));
                             self.bind_current_as(
                                 name,
                                 handler,
@@ -1491,6 +1534,7 @@ impl<'a> BindingsBuilder<'a> {
                         (None, Some(mut type_)) => {
                             let mut handler = self.declare_current_idx(Key::Anon(range));
                             self.ensure_expr(&mut type_, handler.usage());
+                            clauses.push(Some((type_.range(), handler.idx())));
                             self.insert_binding_current(
                                 handler,
                                 Binding::ExceptionHandler(type_, x.is_star),
@@ -1507,7 +1551,7 @@ impl<'a> BindingsBuilder<'a> {
                                 FlowStyle::Other,
                             );
                         }
-                        (None, None) => {}
+                        (None, None) => clauses.push(None),
                     }
 
                     self.stmts(h.body, parent);
@@ -1524,8 +1568,10 @@ impl<'a> BindingsBuilder<'a> {
 
                     self.finish_branch();
                 }
+                self.record_except_clauses(raises, &clauses, x.is_star);
 
                 self.finish_exhaustive_fork();
+                self.resource_exit_try();
                 self.scopes.enter_finally();
                 self.stmts(x.finalbody, parent);
                 self.scopes.exit_finally();
@@ -1556,6 +1602,7 @@ impl<'a> BindingsBuilder<'a> {
                         }
                         continue;
                     }
+                    self.check_declared_dependency(m, x.range);
 
                     match x.asname {
                         Some(asname) => {
@@ -1607,6 +1654,9 @@ impl<'a> BindingsBuilder<'a> {
                     x.level,
                     x.module.as_ref().map(|x| &x.id),
                 ) {
+                    if x.level == 0 {
+                        self.check_declared_dependency(m, x.range);
+                    }
                     self.bind_module_exports(x, m);
                 } else {
                     self.error(
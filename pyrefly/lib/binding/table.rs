@@ -32,6 +32,8 @@ macro_rules! table {
             $($vis)* decorated_functions: $t<$crate::binding::binding::KeyDecoratedFunction>,
             $($vis)* undecorated_functions: $t<$crate::binding::binding::KeyUndecoratedFunction>,
             $($vis)* func_defs: $t<$crate::binding::binding::KeyUndecoratedFunctionRange>,
+            $($vis)* function_raises: $t<$crate::binding::binding::KeyFunctionRaises>,
+            $($vis)* function_local_raises: $t<$crate::binding::binding::KeyFunctionLocalRaises>,
             $($vis)* classes: $t<$crate::binding::binding::KeyClass>,
             $($vis)* tparams: $t<$crate::binding::binding::KeyTParams>,
             $($vis)* class_base_types: $t<$crate::binding::binding::KeyClassBaseType>,
@@ -105,6 +107,18 @@ macro_rules! table {
             fn get_mut(&mut self) -> &mut Self::Value { &mut self.func_defs }
         }
 
+        impl $crate::binding::table::TableKeyed<$crate::binding::binding::KeyFunctionRaises> for $name {
+            type Value = $t<$crate::binding::binding::KeyFunctionRaises>;
+            fn get(&self) -> &Self::Value { &self.function_raises }
+            fn get_mut(&mut self) -> &mut Self::Value { &mut self.function_raises }
+        }
+
+        impl $crate::binding::table::TableKeyed<$crate::binding::binding::KeyFunctionLocalRaises> for $name {
+            type Value = $t<$crate::binding::binding::KeyFunctionLocalRaises>;
+            fn get(&self) -> &Self::Value { &self.function_local_raises }
+            fn get_mut(&mut self) -> &mut Self::Value { &mut self.function_local_raises }
+        }
+
         impl $crate::binding::table::TableKeyed<$crate::binding::binding::KeyClass> for $name {
             type Value = $t<$crate::binding::binding::KeyClass>;
             fn get(&self) -> &Self::Value { &self.classes }
@@ -232,6 +246,8 @@ macro_rules! table_for_each(
         $f(&($e).decorated_functions);
         $f(&($e).undecorated_functions);
         $f(&($e).func_defs);
+        $f(&($e).function_raises);
+        $f(&($e).function_local_raises);
         $f(&($e).classes);
         $f(&($e).tparams);
         $f(&($e).class_base_types);
@@ -263,6 +279,8 @@ macro_rules! table_mut_for_each(
         $f(&mut ($e).decorated_functions);
         $f(&mut ($e).undecorated_functions);
         $f(&mut ($e).func_defs);
+        $f(&mut ($e).function_raises);
+        $f(&mut ($e).function_local_raises);
         $f(&mut ($e).classes);
         $f(&mut ($e).tparams);
         $f(&mut ($e).class_base_types);
@@ -294,6 +312,8 @@ macro_rules! table_try_for_each(
         $f(&($e).decorated_functions)?;
         $f(&($e).undecorated_functions)?;
         $f(&($e).func_defs)?;
+        $f(&($e).function_raises)?;
+        $f(&($e).function_local_raises)?;
         $f(&($e).classes)?;
         $f(&($e).tparams)?;
         $f(&($e).class_base_types)?;
@@ -13,6 +13,9 @@ pub mod config_finder;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod coverage;
 pub mod dump_config;
+pub mod edits;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod explain;
 pub mod files;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod infer;
@@ -27,4 +30,8 @@ pub mod suppress;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod tsp;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod upgrade;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod util;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod why;
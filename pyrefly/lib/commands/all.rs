@@ -20,13 +20,16 @@ use crate::commands::config_finder::ConfigConfigurerWrapper;
 use crate::commands::coverage::CoverageCommand;
 use crate::commands::coverage::report::ReportArgs;
 use crate::commands::dump_config::DumpConfigArgs;
+use crate::commands::explain::ExplainArgs;
 use crate::commands::infer::InferArgs;
 use crate::commands::init::InitArgs;
 use crate::commands::lsp::LspArgs;
 use crate::commands::stubgen::StubgenArgs;
 use crate::commands::suppress::SuppressArgs;
 use crate::commands::tsp::TspArgs;
+use crate::commands::upgrade::UpgradeArgs;
 use crate::commands::util::CommandExitStatus;
+use crate::commands::why::WhyArgs;
 use crate::lsp::non_wasm::external_provider::NoExternalProvider;
 
 /// Subcommands to run Pyrefly with.
@@ -59,6 +62,10 @@ pub enum Command {
     Tsp(TspArgs),
     /// Automatically add type annotations to a file or directory.
     Infer(InferArgs),
+    /// Rewrite legacy typing syntax into the modern forms supported by the target Python version.
+    Upgrade(UpgradeArgs),
+    /// Explain how the type of the expression at a position was derived.
+    Why(WhyArgs),
     /// Type coverage commands.
     Coverage {
         /// Coverage subcommand to run.
@@ -70,6 +77,8 @@ pub enum Command {
     Report(ReportArgs),
     /// Suppress type errors by adding ignore comments, or remove unused ignores.
     Suppress(SuppressArgs),
+    /// Print the bundled documentation for an error kind, or list all error kinds.
+    Explain(ExplainArgs),
     /// Generate .pyi stub files from Python source files.
     Stubgen(StubgenArgs),
 }
@@ -111,6 +120,10 @@ impl Command {
                 None,
             )),
             Command::Infer(args) => Ok((args.run(config_configurer_wrapper, thread_count)?, None)),
+            Command::Upgrade(args) => {
+                Ok((args.run(config_configurer_wrapper, thread_count)?, None))
+            }
+            Command::Why(args) => Ok((args.run(config_configurer_wrapper, thread_count)?, None)),
             Command::DumpConfig(args) => Ok((args.run(config_configurer_wrapper)?, None)),
             Command::Coverage { command } => Ok((
                 command.run(version, config_configurer_wrapper, thread_count)?,
@@ -126,6 +139,7 @@ impl Command {
                 args.run(version, config_configurer_wrapper, thread_count)?,
                 None,
             )),
+            Command::Explain(args) => Ok((args.run()?, None)),
             Command::Stubgen(args) => {
                 Ok((args.run(config_configurer_wrapper, thread_count)?, None))
             }
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+mod fix;
 mod sarif;
 
 use std::collections::HashSet;
@@ -30,6 +31,7 @@ use anyhow::ensure;
 use clap::Parser;
 use clap::ValueEnum;
 use dupe::Dupe as _;
+use itertools::Itertools as _;
 use percent_encoding::AsciiSet;
 use percent_encoding::CONTROLS;
 use percent_encoding::utf8_percent_encode;
@@ -67,6 +69,7 @@ use starlark_map::small_set::SmallSet;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
+use tracing::warn;
 
 use self::sarif::write_error_sarif_to_console;
 use self::sarif::write_error_sarif_to_file;
@@ -90,8 +93,10 @@ use crate::error::suppress;
 use crate::error::suppress::CommentLocation;
 use crate::error::suppress::SerializedError;
 use crate::error::suppress::UnusedIgnoreKind;
+use crate::module::dependencies::DependencyUsage;
 use crate::report;
 use crate::state::load::FileContents;
+use crate::state::lsp::ImportFormat;
 use crate::state::require::Require;
 use crate::state::require::RequireLevels;
 use crate::state::state::State;
@@ -151,6 +156,7 @@ impl FullCheckArgs {
         thread_count: ThreadCount,
     ) -> anyhow::Result<(CommandExitStatus, Option<CheckResult>)> {
         self.config_override.validate()?;
+        let project_mode = self.files.is_project_mode();
         let (files_to_check, config_finder, upsell) =
             self.files.resolve(self.config_override, wrapper)?;
         run_check(
@@ -158,6 +164,7 @@ impl FullCheckArgs {
             version,
             self.watch,
             files_to_check,
+            project_mode,
             config_finder,
             upsell,
             thread_count,
@@ -179,6 +186,7 @@ async fn run_check(
     version: &str,
     watch: bool,
     files_to_check: Box<dyn Includes>,
+    project_mode: bool,
     config_finder: ConfigFinder,
     upsell: UpsellDecision,
     thread_count: ThreadCount,
@@ -194,6 +202,7 @@ async fn run_check(
             watcher,
             version,
             files_to_check,
+            project_mode,
             config_finder,
             upsell,
             thread_count,
@@ -201,8 +210,14 @@ async fn run_check(
         .await?;
         Ok((CommandExitStatus::Success, None))
     } else {
-        let (status, _, check_result) =
-            args.run_once(version, files_to_check, config_finder, upsell, thread_count)?;
+        let (status, _, check_result) = args.run_once(
+            version,
+            files_to_check,
+            project_mode,
+            config_finder,
+            upsell,
+            thread_count,
+        )?;
         Ok((status, Some(check_result)))
     }
 }
@@ -256,6 +271,9 @@ impl SnippetCheckArgs {
                 suppress_errors: false,
                 expectations: false,
                 remove_unused_ignores: None,
+                fix: false,
+                fix_only: None,
+                diff: false,
             },
         };
         let (status, check_result) =
@@ -347,6 +365,11 @@ struct OutputArgs {
     )]
     only: Option<Vec<ErrorKind>>,
 
+    /// After the first error of each kind, print the bundled documentation for that kind.
+    /// Only applies to text output written to stdout.
+    #[arg(long)]
+    explain: bool,
+
     /// By default show a progress bar and the number of errors.
     /// Pass `--summary` to additionally show information about lines checked and time/memory,
     /// or `--summary=none` to hide the progress bar and summary line entirely.
@@ -533,6 +556,30 @@ struct BehaviorArgs {
         default_missing_value = "pyrefly"
     )]
     remove_unused_ignores: Option<UnusedIgnoreKind>,
+    /// Apply fixes that need no review (e.g. adding a missing `@override` or removing a
+    /// redundant cast) to the input files, then re-check them.
+    #[arg(long)]
+    fix: bool,
+    /// Like `--fix`, but only fix errors of the given kinds.
+    /// Can be passed multiple times or as a comma-separated list.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ERROR_KIND",
+        hide_possible_values = true,
+        value_delimiter = ','
+    )]
+    fix_only: Option<Vec<ErrorKind>>,
+    /// Print the fixes that `--fix` would apply as a unified diff, without changing any files.
+    #[arg(long)]
+    diff: bool,
+}
+
+impl BehaviorArgs {
+    /// Whether fixes should be computed at all, either to apply or to preview.
+    fn fixing(&self) -> bool {
+        self.fix || self.fix_only.is_some() || self.diff
+    }
 }
 
 fn write_errors_to_file(
@@ -562,10 +609,11 @@ pub(crate) fn write_errors_to_console(
     version: &str,
     relative_to: &Path,
     errors: &[Error],
+    explain: bool,
 ) -> anyhow::Result<()> {
     match format {
-        OutputFormat::MinText => write_error_text_to_console(relative_to, errors, false),
-        OutputFormat::FullText => write_error_text_to_console(relative_to, errors, true),
+        OutputFormat::MinText => write_error_text_to_console(relative_to, errors, false, explain),
+        OutputFormat::FullText => write_error_text_to_console(relative_to, errors, true, explain),
         OutputFormat::FullTextWithGithub => {
             write_error_full_text_with_github_to_console(relative_to, errors)
         }
@@ -596,12 +644,17 @@ fn write_error_text_to_console(
     relative_to: &Path,
     errors: &[Error],
     verbose: bool,
+    explain: bool,
 ) -> anyhow::Result<()> {
     let stdout = stdout();
     let color_choice = stdout.current_choice();
     let mut renderer = ErrorRenderer::new(BufWriter::new(stdout.lock()), color_choice);
+    let mut explained = SmallSet::new();
     for error in errors {
         renderer.write(error, relative_to, verbose)?;
+        if explain && error.severity().is_enabled() && explained.insert(error.error_kind()) {
+            renderer.write_explanation(error.error_kind())?;
+        }
         renderer.flush()?;
     }
     renderer.flush()?;
@@ -1113,6 +1166,36 @@ fn decide_upsell(
     }
 }
 
+/// Write the declared dependencies that no checked module imports at runtime, which are
+/// reported alongside `undeclared-dependency` errors.
+fn write_dependency_usage<W: Write>(
+    usage: &DependencyUsage,
+    relative_to: &Path,
+    out: &mut W,
+) -> std::io::Result<()> {
+    let pyproject = usage
+        .pyproject
+        .strip_prefix(relative_to)
+        .unwrap_or(&usage.pyproject)
+        .display();
+    let names = |names: &[String]| names.iter().map(|name| format!("`{name}`")).join(", ");
+    if !usage.unused.is_empty() {
+        writeln!(
+            out,
+            "Dependencies declared in `{pyproject}` but never imported: {}",
+            names(&usage.unused)
+        )?;
+    }
+    if !usage.type_checking_only.is_empty() {
+        writeln!(
+            out,
+            "Dependencies declared in `{pyproject}` but only imported under `TYPE_CHECKING`: {}",
+            names(&usage.type_checking_only)
+        )?;
+    }
+    Ok(())
+}
+
 /// Write the "no pyrefly.toml found" upsell for a single
 /// `SynthesizedPresetReason`. Pure function of the reason — trivial to
 /// unit-test against a `Vec<u8>` without spinning up a real check run.
@@ -1159,11 +1242,13 @@ fn write_unconfigured_upsell<W: Write>(
 
 impl CheckArgs {
     /// Run a one-shot type check. Returns the exit status, the CLI-visible errors,
-    /// and a `CheckResult` suitable for telemetry logging.
+    /// and a `CheckResult` suitable for telemetry logging. `project_mode` is set when
+    /// `files_to_check` are the project's files rather than files given on the command line.
     pub fn run_once(
         mut self,
         version: &str,
         files_to_check: Box<dyn Includes>,
+        project_mode: bool,
         config_finder: ConfigFinder,
         upsell: UpsellDecision,
         thread_count: ThreadCount,
@@ -1220,6 +1305,7 @@ impl CheckArgs {
             &loaded_handles,
             sourcedb_errors,
             require_levels.specified,
+            project_mode,
             upsell,
         )?;
         let check_result = CheckResult::from_errors(&errors, &relative_to, checked_file_count);
@@ -1279,6 +1365,7 @@ impl CheckArgs {
             &[handle],
             vec![],
             require_levels.specified,
+            false,
             // Snippet checks are interactive ad-hoc inputs — never upsell.
             UpsellDecision::Skip,
         )?;
@@ -1290,11 +1377,16 @@ impl CheckArgs {
         mut watcher: Watcher,
         version: &str,
         files_to_check: Box<dyn Includes>,
+        project_mode: bool,
         config_finder: ConfigFinder,
         mut upsell: UpsellDecision,
         thread_count: ThreadCount,
     ) -> anyhow::Result<()> {
         self.output.validate_outputs()?;
+        ensure!(
+            !self.behavior.fixing(),
+            "`--fix`, `--fix-only` and `--diff` can't be used with `--watch`"
+        );
         // TODO: We currently make 1 unrealistic assumptions, which should be fixed in the future:
         // - Config search is stable across incremental runs.
         let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
@@ -1343,6 +1435,7 @@ impl CheckArgs {
                 &loaded_handles,
                 sourcedb_errors,
                 require_levels.specified,
+                project_mode,
                 upsell,
             );
             // The upsell is a one-time CTA. Re-nagging on every file
@@ -1393,6 +1486,86 @@ impl CheckArgs {
         }
     }
 
+    /// The machine-applicable fixes for `handles`, merged so they can be applied together.
+    fn fixes(&self, transaction: &Transaction, handles: &[Handle]) -> Vec<fix::FileFixes> {
+        fix::merge_fixes(
+            handles.iter().flat_map(|handle| {
+                transaction.machine_applicable_fixes(handle, ImportFormat::Absolute)
+            }),
+            self.behavior.fix_only.as_deref(),
+        )
+    }
+
+    /// Applies fixes and re-checks until no more fixes apply. Fixes that conflicted with
+    /// another fix get recomputed against the updated files on the next pass. Stops early if a
+    /// fix didn't remove its error, since it would otherwise be reapplied on every pass.
+    fn apply_fixes(
+        &self,
+        transaction: &mut Transaction,
+        handles: &[Handle],
+        require: Require,
+    ) -> anyhow::Result<()> {
+        /// Bounds the passes in case fixes keep producing new fixable errors.
+        const MAX_FIX_PASSES: usize = 10;
+        let mut fixed_count = 0;
+        let mut fixed_files = SmallSet::new();
+        let mut files = self.fixes(transaction, handles);
+        for _ in 0..MAX_FIX_PASSES {
+            if files.is_empty() {
+                break;
+            }
+            let written = fix::write_fixes(&files)?;
+            fixed_files.extend(written.iter().cloned());
+            transaction.invalidate_disk(&written);
+            transaction.run(handles, require, None);
+
+            let errors = transaction.get_errors(handles).collect_errors().ordinary;
+            let mut unresolved = Vec::new();
+            for file in &files {
+                for (kind, range) in file.fixed_errors() {
+                    match errors.iter().find(|error| {
+                        error.path() == file.module.path()
+                            && error.error_kind() == kind
+                            && error.range() == range
+                    }) {
+                        Some(error) => unresolved.push(error),
+                        None => fixed_count += 1,
+                    }
+                }
+            }
+            if !unresolved.is_empty() {
+                let relative_to = resolve_relative_to(self.output.relative_to.as_ref());
+                for error in &unresolved {
+                    warn!(
+                        "Fix for `{}` did not remove the error at {}:{}",
+                        error.error_kind().to_name(),
+                        error.path_string_with_fragment(&relative_to),
+                        error.display_range()
+                    );
+                }
+                warn!(
+                    "Stopped fixing: {} still reported after being fixed",
+                    count(unresolved.len(), "error")
+                );
+                files = Vec::new();
+                break;
+            }
+            files = self.fixes(transaction, handles);
+        }
+        info!(
+            "Fixed {} in {}",
+            count(fixed_count, "error"),
+            count(fixed_files.len(), "file")
+        );
+        if !files.is_empty() {
+            warn!(
+                "{} still fixable after {MAX_FIX_PASSES} passes",
+                count(files.iter().map(|file| file.errors.len()).sum(), "error")
+            );
+        }
+        Ok(())
+    }
+
     fn run_inner(
         &self,
         mut timings: Timings,
@@ -1401,6 +1574,7 @@ impl CheckArgs {
         handles: &[Handle],
         mut sourcedb_errors: Vec<ConfigError>,
         require: Require,
+        project_mode: bool,
         upsell: UpsellDecision,
     ) -> anyhow::Result<(CommandExitStatus, Vec<Error>)> {
         // Baseline maintenance actions are mutually exclusive.
@@ -1473,6 +1647,25 @@ impl CheckArgs {
         transaction.run(handles, require, None);
         transaction.set_subscriber(None);
 
+        if self.behavior.diff {
+            let files = self.fixes(transaction, handles);
+            let relative_to = resolve_relative_to(self.output.relative_to.as_ref());
+            let mut stdout = stdout();
+            for file in &files {
+                write!(stdout, "{}", file.diff(&relative_to))?;
+            }
+            stdout.flush()?;
+            let status = if files.is_empty() {
+                CommandExitStatus::Success
+            } else {
+                CommandExitStatus::UserError
+            };
+            return Ok((status, Vec::new()));
+        }
+        if self.behavior.fixing() {
+            self.apply_fixes(transaction, handles, require)?;
+        }
+
         let loads = if self.behavior.check_all {
             transaction.get_all_errors()
         } else {
@@ -1654,6 +1847,7 @@ impl CheckArgs {
                 version,
                 relative_to.as_path(),
                 &output_errors,
+                self.output.explain,
             )?;
         } else {
             for output in &self.output.output {
@@ -1664,6 +1858,7 @@ impl CheckArgs {
                         version,
                         relative_to.as_path(),
                         &output_errors,
+                        self.output.explain,
                     )?,
                     ErrorOutputDestination::File(path) => write_errors_to_file(
                         format,
@@ -1682,6 +1877,12 @@ impl CheckArgs {
         if self.output.summarize_errors.is_some() {
             print_error_summary(&output_errors);
         }
+        // Whether a dependency is never imported is only known once every file was checked.
+        if self.output.summary != Summary::None && project_mode {
+            for usage in transaction.dependency_usage(handles) {
+                write_dependency_usage(&usage, &relative_to, &mut std::io::stderr())?;
+            }
+        }
         timings.report_errors = report_errors_start.elapsed();
 
         if self.output.summary != Summary::None {
@@ -2197,6 +2398,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fix_cli_values() {
+        let args = CheckArgs::parse_from(["check"]);
+        assert!(!args.behavior.fixing());
+        let args = CheckArgs::parse_from(["check", "--diff"]);
+        assert!(args.behavior.fixing());
+        let args = CheckArgs::parse_from([
+            "check",
+            "--fix-only=redundant-cast,missing-override-decorator",
+        ]);
+        assert!(args.behavior.fixing());
+        assert_eq!(
+            args.behavior.fix_only,
+            Some(vec![
+                ErrorKind::RedundantCast,
+                ErrorKind::MissingOverrideDecorator
+            ])
+        );
+    }
+
     fn upsell_string(reason: SynthesizedPresetReason) -> String {
         let mut buf = Vec::new();
         write_unconfigured_upsell(reason, &mut buf).unwrap();
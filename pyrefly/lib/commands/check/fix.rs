@@ -0,0 +1,487 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Applying machine-applicable fixes for `pyrefly check --fix`.
+//!
+//! Fixes are merged per file before anything is written. A fix whose edits overlap an edit
+//! that was already accepted is dropped for this pass; the caller re-checks and tries again,
+//! by which point the conflicting fix has usually either disappeared or been recomputed
+//! against the new contents.
+
+use std::cmp;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use dupe::Dupe;
+use pyrefly_config::error_kind::ErrorKind;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_util::fs_anyhow;
+use pyrefly_util::unix_path::path_to_unix_string;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::small_map::SmallMap;
+use vec1::Vec1;
+use vec1::vec1;
+
+use crate::commands::edits::apply_edits;
+use crate::state::lsp::MachineApplicableFix;
+
+/// Lines of unchanged context shown around each change in a diff.
+const CONTEXT_LINES: usize = 3;
+
+/// The accepted edits for one file, sorted by range and free of overlaps.
+pub struct FileFixes {
+    pub module: Module,
+    pub edits: Vec<(TextRange, String)>,
+    /// The errors in this file that the accepted fixes resolve, at their ranges before the
+    /// edits are applied.
+    pub errors: Vec<(ErrorKind, TextRange)>,
+}
+
+impl FileFixes {
+    /// The file contents with every edit applied.
+    pub fn apply(&self) -> anyhow::Result<String> {
+        // `apply_edits` puts insertions at the same position in reverse order, so pass the edits
+        // back to front to keep the order that `diff` shows.
+        apply_edits(
+            self.module.contents(),
+            self.edits.iter().rev().cloned().collect(),
+        )
+    }
+
+    /// The errors the fixes resolve, at their ranges once the edits are applied. If the fixes
+    /// worked, re-checking the file no longer reports any of them.
+    pub fn fixed_errors(&self) -> impl Iterator<Item = (ErrorKind, TextRange)> + '_ {
+        self.errors.iter().map(|(kind, range)| {
+            let start = map_offset(range.start(), &self.edits);
+            let end = map_offset(range.end(), &self.edits);
+            (*kind, TextRange::new(start, end))
+        })
+    }
+
+    /// A unified diff of the edits, with paths shown relative to `relative_to`.
+    pub fn diff(&self, relative_to: &Path) -> String {
+        let path = self.module.path().as_path();
+        let path = path_to_unix_string(path.strip_prefix(relative_to).unwrap_or(path));
+        unified_diff(&path, self.module.contents(), &self.edits)
+    }
+}
+
+/// Merges fixes into per-file edit lists, keeping only fixes that can be applied on disk.
+/// Identical edits from different fixes (e.g. two fixes that both need the same import) are
+/// applied once. `only` restricts the fixes to the given error kinds.
+pub fn merge_fixes(
+    fixes: impl IntoIterator<Item = MachineApplicableFix>,
+    only: Option<&[ErrorKind]>,
+) -> Vec<FileFixes> {
+    let mut files: SmallMap<Module, FileFixes> = SmallMap::new();
+    let mut writable: SmallMap<Module, bool> = SmallMap::new();
+    'fixes: for fix in fixes {
+        if only.is_some_and(|only| !only.contains(&fix.error_kind)) {
+            continue;
+        }
+        let mut new_edits: Vec<(Module, TextRange, String)> = Vec::new();
+        for (module, range, text) in fix.edits {
+            if !*writable
+                .entry(module.dupe())
+                .or_insert_with(|| is_writable(&module))
+            {
+                continue 'fixes;
+            }
+            if let Some(file) = files.get(&module) {
+                if file.edits.iter().any(|(r, t)| *r == range && *t == text) {
+                    continue;
+                }
+                if file.edits.iter().any(|(r, _)| overlapping(*r, range)) {
+                    continue 'fixes;
+                }
+            }
+            if new_edits
+                .iter()
+                .any(|(m, r, _)| *m == module && (*r == range || overlapping(*r, range)))
+            {
+                continue 'fixes;
+            }
+            new_edits.push((module, range, text));
+        }
+        for (module, range, text) in new_edits {
+            files
+                .entry(module.dupe())
+                .or_insert_with(|| FileFixes {
+                    module,
+                    edits: Vec::new(),
+                    errors: Vec::new(),
+                })
+                .edits
+                .push((range, text));
+        }
+        if let Some(file) = files.get_mut(&fix.error_module) {
+            file.errors.push((fix.error_kind, fix.error_range));
+        }
+    }
+    let mut files: Vec<_> = files.into_values().collect();
+    for file in &mut files {
+        file.edits
+            .sort_by_key(|(range, _)| (range.start(), range.end()));
+    }
+    files.sort_by(|a, b| a.module.path().as_path().cmp(b.module.path().as_path()));
+    files
+}
+
+fn overlapping(a: TextRange, b: TextRange) -> bool {
+    a.start() < b.end() && b.start() < a.end()
+}
+
+/// Where `offset` ends up once the sorted, non-overlapping `edits` are applied. An offset inside
+/// a replaced range moves to the end of its replacement.
+fn map_offset(offset: TextSize, edits: &[(TextRange, String)]) -> TextSize {
+    let mut mapped = offset.to_usize() as isize;
+    for (range, text) in edits {
+        if range.end() <= offset {
+            mapped += text.len() as isize - range.len().to_usize() as isize;
+        } else {
+            if range.start() < offset {
+                mapped +=
+                    (range.start().to_usize() + text.len()) as isize - offset.to_usize() as isize;
+            }
+            break;
+        }
+    }
+    TextSize::new(mapped as u32)
+}
+
+/// Only plain files whose contents on disk still match what was checked are safe to rewrite.
+fn is_writable(module: &Module) -> bool {
+    match module.path().details() {
+        ModulePathDetails::FileSystem(path) => {
+            !module.is_notebook()
+                && fs_anyhow::read_to_string(path)
+                    .is_ok_and(|contents| contents == **module.contents())
+        }
+        _ => false,
+    }
+}
+
+/// Writes the fixed contents of each file, returning the paths that were written.
+pub fn write_fixes(files: &[FileFixes]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut written = Vec::with_capacity(files.len());
+    for file in files {
+        let path = file.module.path().as_path();
+        let contents = file
+            .apply()
+            .with_context(|| format!("Failed to rewrite `{}`", path.display()))?;
+        fs_anyhow::write(path, contents)?;
+        written.push(path.to_path_buf());
+    }
+    Ok(written)
+}
+
+/// A contiguous block of changed lines: `old` lines starting at line `start` are replaced by
+/// `new`.
+struct Change<'a> {
+    start: usize,
+    old: Vec<&'a str>,
+    new: Vec<String>,
+}
+
+/// Splits the lines touched by `edits` into changes. Edits that touch the same line end up in
+/// the same change, and lines that are identical before and after are trimmed from the ends.
+fn line_changes<'a>(contents: &'a str, edits: &[(TextRange, String)]) -> Vec<Change<'a>> {
+    let mut line_starts = vec![0];
+    line_starts.extend(contents.match_indices('\n').map(|(i, _)| i + 1));
+    if line_starts.last() == Some(&contents.len()) && !contents.is_empty() {
+        line_starts.pop();
+    }
+    let line_of = |offset: usize| line_starts.partition_point(|start| *start <= offset) - 1;
+    let line_end = |line: usize| line_starts.get(line + 1).copied().unwrap_or(contents.len());
+
+    let mut groups: Vec<(usize, usize, Vec<&(TextRange, String)>)> = Vec::new();
+    for edit in edits {
+        let (start, end) = (edit.0.start().to_usize(), edit.0.end().to_usize());
+        let first = line_of(start);
+        let last = if end > start { line_of(end - 1) } else { first };
+        match groups.last_mut() {
+            Some((_, group_last, group)) if first <= *group_last => {
+                *group_last = cmp::max(*group_last, last);
+                group.push(edit);
+            }
+            _ => groups.push((first, last, vec![edit])),
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (first, last, group) in groups {
+        let region_start = line_starts[first];
+        let region_end = line_end(last);
+        let mut new_text = String::new();
+        let mut cursor = region_start;
+        for (range, text) in group {
+            new_text.push_str(&contents[cursor..range.start().to_usize()]);
+            new_text.push_str(text);
+            cursor = range.end().to_usize();
+        }
+        new_text.push_str(&contents[cursor..region_end]);
+
+        let mut old: Vec<&str> = contents[region_start..region_end]
+            .split_inclusive('\n')
+            .collect();
+        let mut new: Vec<String> = new_text.split_inclusive('\n').map(str::to_owned).collect();
+        let common_suffix = old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .take_while(|(a, b)| **a == b.as_str())
+            .count();
+        old.truncate(old.len() - common_suffix);
+        new.truncate(new.len() - common_suffix);
+        let common_prefix = old
+            .iter()
+            .zip(new.iter())
+            .take_while(|(a, b)| **a == b.as_str())
+            .count();
+        old.drain(..common_prefix);
+        new.drain(..common_prefix);
+        if !old.is_empty() || !new.is_empty() {
+            changes.push(Change {
+                start: first + common_prefix,
+                old,
+                new,
+            });
+        }
+    }
+    changes
+}
+
+/// The line after the last changed line of a hunk.
+fn hunk_end(hunk: &Vec1<&Change>) -> usize {
+    let last = hunk.last();
+    last.start + last.old.len()
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// The `start,count` half of a hunk header, where `start` is a zero-based line index.
+fn hunk_range(start: usize, count: usize) -> String {
+    // An empty range names the line before it, as `diff -u` does.
+    let start = if count == 0 { start } else { start + 1 };
+    if count == 1 {
+        format!("{start}")
+    } else {
+        format!("{start},{count}")
+    }
+}
+
+/// Renders sorted, non-overlapping edits to `contents` as a unified diff of `path`.
+fn unified_diff(path: &str, contents: &str, edits: &[(TextRange, String)]) -> String {
+    let changes = line_changes(contents, edits);
+    if changes.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+
+    // Changes whose context would touch or overlap share a hunk.
+    let mut hunks: Vec<Vec1<&Change>> = Vec::new();
+    for change in &changes {
+        match hunks.last_mut() {
+            Some(hunk) if hunk_end(hunk) + 2 * CONTEXT_LINES >= change.start => hunk.push(change),
+            _ => hunks.push(vec1![change]),
+        }
+    }
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let mut delta: isize = 0;
+    for hunk in hunks {
+        let first = hunk.first();
+        let context_start = first.start.saturating_sub(CONTEXT_LINES);
+        let context_end = cmp::min(lines.len(), hunk_end(&hunk) + CONTEXT_LINES);
+        let old_count = context_end - context_start;
+        let hunk_delta: isize = hunk
+            .iter()
+            .map(|change| change.new.len() as isize - change.old.len() as isize)
+            .sum();
+        let new_start = (context_start as isize + delta) as usize;
+        let new_count = (old_count as isize + hunk_delta) as usize;
+        writeln!(
+            out,
+            "@@ -{} +{} @@",
+            hunk_range(context_start, old_count),
+            hunk_range(new_start, new_count)
+        )
+        .unwrap();
+        let mut cursor = context_start;
+        for change in hunk {
+            for line in &lines[cursor..change.start] {
+                push_line(&mut out, ' ', line);
+            }
+            for line in &change.old {
+                push_line(&mut out, '-', line);
+            }
+            for line in &change.new {
+                push_line(&mut out, '+', line);
+            }
+            cursor = change.start + change.old.len();
+        }
+        for line in &lines[cursor..context_end] {
+            push_line(&mut out, ' ', line);
+        }
+        delta += hunk_delta;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pyrefly_python::module_name::ModuleName;
+    use pyrefly_python::module_path::ModulePath;
+    use ruff_text_size::TextSize;
+
+    use super::*;
+
+    fn edit(start: u32, end: u32, text: &str) -> (TextRange, String) {
+        (
+            TextRange::new(TextSize::new(start), TextSize::new(end)),
+            text.to_owned(),
+        )
+    }
+
+    #[test]
+    fn test_diff_insertion() {
+        let contents = "class B(A):\n    def f(self) -> None: ...\n";
+        let diff = unified_diff("a.py", contents, &[edit(12, 12, "    @override\n")]);
+        assert_eq!(
+            diff,
+            r#"--- a/a.py
++++ b/a.py
+@@ -1,2 +1,3 @@
+ class B(A):
++    @override
+     def f(self) -> None: ...
+"#
+        );
+    }
+
+    #[test]
+    fn test_diff_separate_hunks() {
+        let contents = (1..=20).map(|i| format!("x{i}\n")).collect::<String>();
+        let first = contents.find("x2\n").unwrap() as u32;
+        let last = contents.find("x19\n").unwrap() as u32;
+        let diff = unified_diff(
+            "a.py",
+            &contents,
+            &[edit(first, first + 2, "y2"), edit(last, last + 3, "y19")],
+        );
+        assert_eq!(
+            diff,
+            r#"--- a/a.py
++++ b/a.py
+@@ -1,5 +1,5 @@
+ x1
+-x2
++y2
+ x3
+ x4
+ x5
+@@ -16,5 +16,5 @@
+ x16
+ x17
+ x18
+-x19
++y19
+ x20
+"#
+        );
+    }
+
+    #[test]
+    fn test_diff_no_newline_at_end() {
+        let diff = unified_diff("a.py", "x = int(1)", &[edit(4, 10, "1")]);
+        assert_eq!(
+            diff,
+            r#"--- a/a.py
++++ b/a.py
+@@ -1 +1 @@
+-x = int(1)
+\ No newline at end of file
++x = 1
+\ No newline at end of file
+"#
+        );
+    }
+
+    #[test]
+    fn test_merge_fixes_drops_conflicts() {
+        let tdir = tempfile::tempdir().unwrap();
+        let path = tdir.path().join("a.py");
+        let contents = "x = str(str(y))\n";
+        fs_anyhow::write(&path, contents).unwrap();
+        let module = Module::new(
+            ModuleName::from_str("a"),
+            ModulePath::filesystem(path),
+            Arc::new(contents.to_owned()),
+        );
+        let fix = |kind, edits: Vec<(TextRange, String)>| MachineApplicableFix {
+            error_kind: kind,
+            error_module: module.dupe(),
+            error_range: edits[0].0,
+            edits: edits
+                .into_iter()
+                .map(|(range, text)| (module.dupe(), range, text))
+                .collect(),
+        };
+        let import = edit(0, 0, "from typing import override\n");
+        let files = merge_fixes(
+            [
+                fix(
+                    ErrorKind::UnnecessaryTypeConversion,
+                    vec![edit(4, 15, "str(y)"), import.clone()],
+                ),
+                // Overlaps the first fix, so it waits for the next pass.
+                fix(ErrorKind::UnnecessaryTypeConversion, vec![edit(8, 14, "y")]),
+                // Shares the import with the first fix.
+                fix(ErrorKind::MissingOverrideDecorator, vec![import.clone()]),
+            ],
+            None,
+        );
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].errors.len(), 2);
+        assert_eq!(
+            files[0].apply().unwrap(),
+            "from typing import override\nx = str(y)\n"
+        );
+        // The import moves both errors down a line.
+        assert_eq!(
+            files[0].fixed_errors().collect::<Vec<_>>(),
+            vec![
+                (
+                    ErrorKind::UnnecessaryTypeConversion,
+                    TextRange::new(TextSize::new(32), TextSize::new(38))
+                ),
+                (
+                    ErrorKind::MissingOverrideDecorator,
+                    TextRange::new(TextSize::new(28), TextSize::new(28))
+                ),
+            ]
+        );
+
+        let files = merge_fixes(
+            [fix(ErrorKind::RedundantCast, vec![edit(4, 15, "y")])],
+            Some(&[ErrorKind::UnnecessaryTypeConversion]),
+        );
+        assert!(files.is_empty());
+    }
+}
@@ -17,6 +17,7 @@ use std::collections::hash_map::Entry;
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
+use std::iter;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -25,6 +26,7 @@ use anyhow::Context as _;
 use itertools::Itertools as _;
 use lsp_types::Url;
 use pyrefly_config::error_kind::Severity;
+use pyrefly_python::module::Module;
 use pyrefly_util::absolutize::Absolutize;
 use pyrefly_util::lined_buffer::DisplayRange;
 use serde::Serialize;
@@ -254,9 +256,17 @@ fn errors_to_sarif(version: &str, relative_to: &Path, errors: &[Error]) -> anyho
 
     let mut artifact_uris = HashMap::new();
     for error in &errors {
-        let path = error.path().as_path();
-        if let Entry::Vacant(entry) = artifact_uris.entry(path.to_path_buf()) {
-            entry.insert(artifact_uri(path, relative_to)?);
+        let paths = iter::once(error.path()).chain(
+            error
+                .secondary_annotations()
+                .iter()
+                .map(|annotation| error.annotation_module(annotation).path()),
+        );
+        for path in paths {
+            let path = path.as_path();
+            if let Entry::Vacant(entry) = artifact_uris.entry(path.to_path_buf()) {
+                entry.insert(artifact_uri(path, relative_to)?);
+            }
         }
     }
 
@@ -276,22 +286,24 @@ fn errors_to_sarif(version: &str, relative_to: &Path, errors: &[Error]) -> anyho
         .iter()
         .map(|error| {
             let kind = error.error_kind();
-            let base_uri = artifact_uris
-                .get(error.path().as_path())
-                .expect("error path was collected into the SARIF artifact URI table");
             // A notebook line number is relative to its cell, so name the cell in the URI
             // fragment to keep the position unambiguous.
-            let location = |range: &DisplayRange, message| Location {
-                physical_location: PhysicalLocation {
-                    artifact_location: ArtifactLocation {
-                        uri: match range.start.cell() {
-                            Some(cell) => format!("{base_uri}#{cell}"),
-                            None => base_uri.clone(),
+            let location = |module: &Module, range: &DisplayRange, message| {
+                let base_uri = artifact_uris
+                    .get(module.path().as_path())
+                    .expect("error path was collected into the SARIF artifact URI table");
+                Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: ArtifactLocation {
+                            uri: match range.start.cell() {
+                                Some(cell) => format!("{base_uri}#{cell}"),
+                                None => base_uri.clone(),
+                            },
                         },
+                        region: region(range),
                     },
-                    region: region(range),
-                },
-                message,
+                    message,
+                }
             };
             SarifResult {
                 rule_id: kind.to_name(),
@@ -302,13 +314,15 @@ fn errors_to_sarif(version: &str, relative_to: &Path, errors: &[Error]) -> anyho
                 message: Message {
                     text: message_text(error),
                 },
-                locations: vec![location(error.display_range(), None)],
+                locations: vec![location(error.module(), error.display_range(), None)],
                 related_locations: error
                     .secondary_annotations()
                     .iter()
                     .map(|annotation| {
+                        let module = error.annotation_module(annotation);
                         location(
-                            &error.module().display_range(annotation.range),
+                            module,
+                            &module.display_range(annotation.range),
                             Some(Message {
                                 text: annotation.label.to_string(),
                             }),
@@ -737,6 +751,43 @@ mod tests {
         assert_eq!((region.start_column, region.end_column), (1, 4));
     }
 
+    #[test]
+    fn related_annotations_point_into_their_own_file() {
+        let base = Module::new(
+            ModuleName::from_str("base"),
+            ModulePath::filesystem(PathBuf::from("/repo/base.py")),
+            Arc::new("class A:\n    def f(self) -> int: ...".to_owned()),
+        );
+        let main = Module::new(
+            ModuleName::from_str("main"),
+            ModulePath::filesystem(PathBuf::from("/repo/main.py")),
+            Arc::new("def f(self) -> str: ...".to_owned()),
+        );
+        let error = Error::new(
+            main,
+            TextRange::new(TextSize::from(4), TextSize::from(5)),
+            "bad override".to_owned(),
+            Vec::new(),
+            ErrorKind::BadOverride,
+        )
+        .with_related_annotation(
+            base,
+            TextRange::new(TextSize::from(17), TextSize::from(18)),
+            "`A.f` is declared here".to_owned(),
+        );
+
+        let sarif = errors_to_sarif(VERSION, Path::new("/repo"), &[error]).unwrap();
+        let result = &sarif.runs[0].results[0];
+        assert_eq!(
+            result.locations[0].physical_location.artifact_location.uri,
+            "main.py"
+        );
+        let related = &result.related_locations[0];
+        assert_eq!(related.physical_location.artifact_location.uri, "base.py");
+        let region = &related.physical_location.region;
+        assert_eq!((region.start_line, region.start_column), (2, 9));
+    }
+
     #[test]
     fn results_omit_related_locations_when_there_are_none() {
         let errors = vec![sample_error(
@@ -689,6 +689,61 @@ mod tests {
         assert_eq!(config.source, ConfigSource::File(root.join("pyrefly.toml")));
     }
 
+    /// The root of a uv workspace is in Group 1 even without `[tool.pyrefly]`, so it
+    /// takes priority over a Python tool marker in a member. Otherwise the member's
+    /// `pyproject.toml` would be used on its own, and the member couldn't import the
+    /// members it depends on.
+    #[test]
+    fn test_workspace_root_beats_member_tool_marker() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+
+        TestPath::setup_test_directory(
+            root,
+            vec![
+                TestPath::file_with_contents(
+                    "pyproject.toml",
+                    "[tool.uv.workspace]\nmembers = [\"packages/*\"]\n",
+                ),
+                TestPath::dir(
+                    "packages",
+                    vec![
+                        TestPath::dir(
+                            "a",
+                            vec![
+                                TestPath::file_with_contents(
+                                    "pyproject.toml",
+                                    "[project]\nname = \"a\"\ndependencies = [\"b\"]\n\n[tool.ruff]\nline-length = 88\n",
+                                ),
+                                TestPath::dir("a", vec![TestPath::file("core.py")]),
+                            ],
+                        ),
+                        TestPath::dir(
+                            "b",
+                            vec![TestPath::file_with_contents(
+                                "pyproject.toml",
+                                "[project]\nname = \"b\"\n",
+                            )],
+                        ),
+                    ],
+                ),
+            ],
+        );
+
+        let finder = TestConfigurer::new_standard(|_, x, _| {
+            ConfigOverrideArgs::default().override_config(x)
+        });
+        let config = finder.python_file(
+            ModuleNameWithKind::guaranteed(ModuleName::from_str("a.core")),
+            &ModulePath::filesystem(root.join("packages/a/a/core.py")),
+        );
+
+        assert_eq!(config.source.root(), Some(root));
+        let member = config.workspace_member.as_ref().unwrap();
+        assert_eq!(member.name, "a");
+        assert_eq!(member.workspace_dependencies, vec!["b".to_owned()]);
+    }
+
     /// Going through `default_config_finder` (which uses
     /// [`DefaultConfigConfigurer`]) on an unconfigured project with no
     /// nearby mypy/pyright config produces a config with the basic preset
@@ -104,7 +104,7 @@ impl CheckArgs {
         );
 
         let root = std::env::current_dir().unwrap_or_default();
-        write_errors_to_console(self.output_format, version, &root, &errors)?;
+        write_errors_to_console(self.output_format, version, &root, &errors, false)?;
 
         if coverage + 1e-9 >= self.fail_under {
             eprintln!("{} {summary}", Severity::Info.painted());
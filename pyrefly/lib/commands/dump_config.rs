@@ -146,6 +146,27 @@ fn dump_config(
                 println!("Configuration at `{}`{}", path.display(), config_from);
             }
         }
+        if let Some(workspace) = &config.workspace {
+            println!(
+                "  Root of a {} workspace with {} members",
+                workspace.kind,
+                workspace.members.len()
+            );
+        }
+        if let Some(member) = &config.workspace_member {
+            println!(
+                "  Workspace member `{}` at `{}`, using Python {}",
+                member.name,
+                member.root.display(),
+                config.python_version()
+            );
+            if !member.workspace_dependencies.is_empty() {
+                println!(
+                    "  Depends on workspace members: {}",
+                    member.workspace_dependencies.join(", ")
+                );
+            }
+        }
         println!("  Using interpreter: {}", config.interpreters);
         println!("  Covered files:");
         for (i, fi) in files.iter().enumerate() {
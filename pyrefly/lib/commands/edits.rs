@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Applying text edits to files, shared by the commands that rewrite source code
+//! (`check --fix`, `infer` and `upgrade`).
+
+use std::cmp::Reverse;
+use std::path::Path;
+
+use anyhow::Context as _;
+use pyrefly_util::fs_anyhow;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+
+/// Apply `(range, replacement)` edits to `source`. Edits are applied from the end of
+/// the text so earlier offsets stay valid; edits that fall outside the text are dropped.
+/// Insertions at the same position end up in reverse order of `edits`. Fails if two edits
+/// overlap, since applying either one would invalidate the other.
+pub fn apply_edits(source: &str, mut edits: Vec<(TextRange, String)>) -> anyhow::Result<String> {
+    edits.sort_by_key(|(range, _)| Reverse((range.start(), range.end())));
+    let mut result = source.to_owned();
+    let mut applied_start = TextSize::of(source);
+    for (range, replacement) in edits {
+        if range.end() > TextSize::of(source) {
+            continue;
+        }
+        if range.end() > applied_start {
+            return Err(anyhow::anyhow!(
+                "Overlapping edits: {range:?} overlaps an edit starting at {applied_start:?}"
+            ));
+        }
+        result.replace_range(
+            range.start().to_usize()..range.end().to_usize(),
+            &replacement,
+        );
+        applied_start = range.start();
+    }
+    Ok(result)
+}
+
+/// Rewrite `file_path` in place with `edits`, as described by [`apply_edits`]. The file is
+/// left untouched if the edits overlap.
+pub fn apply_edits_to_file(
+    file_path: &Path,
+    edits: Vec<(TextRange, String)>,
+) -> anyhow::Result<()> {
+    let file_content = fs_anyhow::read_to_string(file_path)?;
+    let new_content = apply_edits(&file_content, edits)
+        .with_context(|| format!("Failed to rewrite `{}`", file_path.display()))?;
+    fs_anyhow::write(file_path, new_content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(start: u32, end: u32, text: &str) -> (TextRange, String) {
+        (
+            TextRange::new(TextSize::new(start), TextSize::new(end)),
+            text.to_owned(),
+        )
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let contents = "x = cast(int, 1)\n";
+        let edits = vec![edit(15, 16, ""), edit(4, 14, "")];
+        assert_eq!(apply_edits(contents, edits).unwrap(), "x = 1\n");
+    }
+
+    #[test]
+    fn test_apply_overlapping_edits() {
+        let edits = vec![edit(0, 3, "a"), edit(2, 5, "b")];
+        assert!(apply_edits("x = 1", edits).is_err());
+    }
+}
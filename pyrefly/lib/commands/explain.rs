@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use clap::Parser;
+use clap::ValueEnum as _;
+
+use crate::commands::util::CommandExitStatus;
+use crate::config::error_kind::ErrorKind;
+use crate::error::error::explain_error_kind;
+
+/// Print the bundled documentation for an error kind.
+#[derive(Debug, Clone, Parser)]
+pub struct ExplainArgs {
+    /// The error kind to explain, by name or as any output format identifies it
+    /// (e.g. `bad-argument-type`, `[bad-argument-type]` or `pyrefly/bad-argument-type`).
+    /// If omitted, list every error kind.
+    #[arg(value_name = "ERROR_KIND|ERROR_ID")]
+    kind: Option<String>,
+}
+
+impl ExplainArgs {
+    pub fn run(self) -> anyhow::Result<CommandExitStatus> {
+        match self.kind {
+            None => {
+                for kind in ErrorKind::value_variants() {
+                    if !kind.is_coverage() {
+                        println!("{}", kind.to_name());
+                    }
+                }
+            }
+            Some(kind) => match ErrorKind::from_error_id(&kind) {
+                Some(kind) => print!("{}", explain_error_kind(kind)?),
+                None => {
+                    eprintln!(
+                        "Unknown error kind `{kind}`. Run `pyrefly explain` to list all error kinds."
+                    );
+                    return Ok(CommandExitStatus::UserError);
+                }
+            },
+        }
+        Ok(CommandExitStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_error_kind() {
+        let explanation = explain_error_kind(ErrorKind::AbstractMethodCall).unwrap();
+        assert!(explanation.starts_with("[abstract-method-call] (default severity: error)\n\n"));
+        assert!(explanation.contains("class Base(ABC):"));
+        assert!(explanation.contains("# pyrefly: ignore[abstract-method-call]"));
+        assert!(explanation.contains(&ErrorKind::AbstractMethodCall.docs_url()));
+    }
+}
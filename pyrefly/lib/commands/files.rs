@@ -330,6 +330,11 @@ fn get_globs_and_config_for_files(
 }
 
 impl FilesArgs {
+    /// Whether no files were given, so the project's files are checked.
+    pub fn is_project_mode(&self) -> bool {
+        self.files.is_empty()
+    }
+
     pub fn resolve(
         self,
         config_override: ConfigOverrideArgs,
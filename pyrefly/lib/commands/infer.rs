@@ -21,10 +21,12 @@ use pyrefly_util::thread_pool::ThreadCount;
 use ruff_python_ast::Stmt;
 use ruff_python_ast::helpers::is_docstring_stmt;
 use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
 use crate::commands::check::Handles;
 use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::edits::apply_edits_to_file;
 use crate::commands::files::FilesArgs;
 use crate::commands::util::CommandExitStatus;
 use crate::lsp::wasm::inlay_hints::ParameterAnnotation;
@@ -426,17 +428,13 @@ impl InferArgs {
         file_path: &Path,
         sorted: Vec<(TextSize, String)>,
     ) -> anyhow::Result<()> {
-        let file_content = fs_anyhow::read_to_string(file_path)?;
-        let mut result = file_content;
-        for inlay_hint in sorted {
-            let (position, hint) = inlay_hint;
-            // Convert the TextSize to a byte offset
-            let offset = (position).into();
-            if offset <= result.len() {
-                result.insert_str(offset, &hint);
-            }
-        }
-        fs_anyhow::write(file_path, result)
+        apply_edits_to_file(
+            file_path,
+            sorted
+                .into_iter()
+                .map(|(position, hint)| (TextRange::empty(position), hint))
+                .collect(),
+        )
     }
 
     fn add_imports_to_file(
@@ -175,7 +175,14 @@ impl InitArgs {
         )?;
 
         // Run the check directly
-        let res = check_args.run_once(version, filtered_globs, config_finder, upsell, thread_count);
+        let res = check_args.run_once(
+            version,
+            filtered_globs,
+            true,
+            config_finder,
+            upsell,
+            thread_count,
+        );
         if let Err(e) = &res {
             error!("Failed to run pyrefly check: {}", e);
         }
@@ -218,6 +225,7 @@ impl InitArgs {
             match suppress_args.run_once(
                 version,
                 suppress_globs,
+                true,
                 suppress_config_finder,
                 suppress_upsell,
                 thread_count,
@@ -98,6 +98,7 @@ impl SuppressArgs {
                 check_args.run_once(
                     version,
                     files_to_check,
+                    self.files.is_project_mode(),
                     config_finder,
                     upsell,
                     thread_count,
@@ -129,6 +130,7 @@ impl SuppressArgs {
                 let (_, errors, _check_result) = check_args.run_once(
                     version,
                     files_to_check,
+                    self.files.is_project_mode(),
                     config_finder,
                     upsell,
                     thread_count,
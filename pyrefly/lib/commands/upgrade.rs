@@ -0,0 +1,1301 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Rewrite legacy typing syntax into the forms available in the configured Python version.
+//!
+//! Every rewrite is driven by what pyrefly resolved the original syntax to, so only
+//! constructs that mean the same thing before and after the rewrite are touched:
+//!
+//! * `Optional[X]` and `Union[X, Y]` become `X | None` and `X | Y` (3.10+).
+//! * `List`, `Dict`, `Set`, `FrozenSet`, `Tuple` and `Type` become their builtins (3.9+).
+//! * Classes and functions generic over legacy `TypeVar`s and `ParamSpec`s use PEP 695
+//!   type parameter lists (3.12+). A class is only rewritten when the variance its type
+//!   variables declare is the variance PEP 695 would infer for them.
+//! * `_X: TypeAlias = ...` becomes `type _X = ...` (3.12+) when the private alias `_X` is only
+//!   used in annotations.
+//! * Names imported from `typing_extensions` are imported from `typing` once `typing`'s
+//!   version behaves the same.
+
+use std::collections::HashSet;
+use std::slice;
+
+use clap::Parser;
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::finder::ConfigFinder;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::short_identifier::ShortIdentifier;
+use pyrefly_python::sys_info::PythonVersion;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::includes::Includes;
+use pyrefly_util::thread_pool::ThreadCount;
+use pyrefly_util::visit::Visit;
+use ruff_python_ast::ArgOrKeyword;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ExprContext;
+use ruff_python_ast::ExprSubscript;
+use ruff_python_ast::Identifier;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Operator;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtAnnAssign;
+use ruff_python_ast::StmtClassDef;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::StmtImportFrom;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::Hashed;
+use starlark_map::small_map::SmallMap;
+
+use crate::binding::binding::KeyClass;
+use crate::binding::binding::KeyDecoratedFunction;
+use crate::commands::check::Handles;
+use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::edits::apply_edits;
+use crate::commands::edits::apply_edits_to_file;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::state::require::Require;
+use crate::state::state::State;
+use crate::state::state::Transaction;
+use crate::types::literal::Lit;
+use crate::types::quantified::QuantifiedKind;
+use crate::types::special_form::SpecialForm;
+use crate::types::type_var::PreInferenceVariance;
+use crate::types::type_var::Variance;
+use crate::types::types::Type;
+
+/// Names whose `typing_extensions` version keeps behaving differently from the `typing`
+/// one until the given minor version of Python 3, even where `typing` already exports them.
+const TYPING_EXTENSIONS_PARITY: &[(&str, u32)] = &[
+    ("Any", 11),
+    ("Concatenate", 11),
+    ("Literal", 11),
+    ("NamedTuple", 13),
+    ("NewType", 10),
+    ("ParamSpec", 13),
+    ("Protocol", 12),
+    ("TypeAliasType", 14),
+    ("TypeVar", 13),
+    ("TypeVarTuple", 13),
+    ("TypedDict", 13),
+    ("Unpack", 12),
+    ("dataclass_transform", 11),
+    ("get_args", 10),
+    ("get_origin", 10),
+    ("get_overloads", 11),
+    ("get_type_hints", 11),
+    ("overload", 11),
+    ("runtime_checkable", 12),
+];
+
+/// Arguments for the upgrade command, which rewrites legacy typing syntax into the
+/// modern forms supported by the configured Python version
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct UpgradeArgs {
+    /// Which files to upgrade.
+    #[command(flatten)]
+    files: FilesArgs,
+
+    /// Type checking arguments and configuration
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+
+    /// Print what would change and exit (1 if any changes, else 0), without writing any files.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl UpgradeArgs {
+    pub fn run(
+        self,
+        wrapper: Option<ConfigConfigurerWrapper>,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let (files_to_check, config_finder, _) =
+            self.files.resolve(self.config_override, wrapper)?;
+        Self::run_inner(files_to_check, config_finder, self.dry_run, thread_count)
+    }
+
+    pub fn run_inner(
+        files_to_check: Box<dyn Includes>,
+        config_finder: ConfigFinder,
+        dry_run: bool,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
+        let state = State::new(config_finder, thread_count);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Exports, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        transaction.run(&handles, Require::Everything, None);
+        let mut any_changes = false;
+        for handle in handles {
+            let edits = upgrade_edits(transaction, &handle);
+            if edits.is_empty() {
+                continue;
+            }
+            let file_path = handle.path().as_path();
+            if dry_run {
+                any_changes = true;
+                println!(
+                    "{}: would apply {} rewrite(s)",
+                    file_path.display(),
+                    edits.len()
+                );
+            } else {
+                apply_edits_to_file(file_path, edits)?;
+            }
+        }
+        Ok(if dry_run && any_changes {
+            CommandExitStatus::UserError
+        } else {
+            CommandExitStatus::Success
+        })
+    }
+}
+
+/// The edits that upgrade the typing syntax of `handle`, which must already be solved.
+pub(crate) fn upgrade_edits(
+    transaction: &Transaction,
+    handle: &Handle,
+) -> Vec<(TextRange, String)> {
+    let (Some(module), Some(ast)) = (
+        transaction.get_module_info(handle),
+        transaction.get_ast(handle),
+    ) else {
+        return Vec::new();
+    };
+    let mut upgrader = Upgrader::new(transaction, handle, &module, &ast);
+    upgrader.upgrade();
+    upgrader.edits
+}
+
+/// A module-level `T = TypeVar("T", ...)` or `P = ParamSpec("P", ...)` declaration.
+struct LegacyTypeParam<'a> {
+    kind: QuantifiedKind,
+    stmt_range: TextRange,
+    /// The `TypeVar` or `ParamSpec` callee.
+    func: &'a Expr,
+    bound: Option<&'a Expr>,
+    constraints: &'a [Expr],
+    default: Option<&'a Expr>,
+}
+
+/// What happens to a name imported from `typing` or `typing_extensions`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ImportFate {
+    Keep,
+    /// No longer referenced once the rewrites are applied.
+    Drop,
+    /// Imported from `typing` rather than `typing_extensions`.
+    Move,
+}
+
+/// Collects the edits that upgrade a single module.
+struct Upgrader<'a> {
+    transaction: &'a Transaction<'a>,
+    handle: &'a Handle,
+    module: &'a Module,
+    ast: &'a ModModule,
+    version: PythonVersion,
+    /// Local names bound by `from typing import ...` or `from typing_extensions import ...`,
+    /// mapped to the name they import.
+    typing_names: SmallMap<Name, Name>,
+    /// Local names bound to the `typing` or `typing_extensions` modules.
+    typing_modules: HashSet<Name>,
+    type_params: SmallMap<Name, LegacyTypeParam<'a>>,
+    /// Every load of each name in the module.
+    references: SmallMap<Name, Vec<TextRange>>,
+    /// Every name bound anywhere in the module, which a builtin would be shadowed by.
+    bound_names: HashSet<Name>,
+    /// Annotations, and the values of type aliases.
+    type_contexts: Vec<TextRange>,
+    /// For each legacy type parameter, the scopes that now declare it themselves.
+    rescoped: SmallMap<Name, Vec<TextRange>>,
+    /// References that the rewrites remove from the module.
+    consumed: HashSet<TextRange>,
+    /// Ranges already rewritten as a whole, which expression rewrites must leave alone.
+    replaced: Vec<TextRange>,
+    /// Whole lines of removed statements, deleted together with the blank lines around them
+    /// once every rewrite is known.
+    deleted_lines: Vec<TextRange>,
+    edits: Vec<(TextRange, String)>,
+}
+
+fn each_stmt<'a>(stmt: &'a Stmt, f: &mut dyn FnMut(&'a Stmt)) {
+    f(stmt);
+    stmt.recurse(&mut |x| each_stmt(x, f));
+}
+
+fn each_expr<'a>(expr: &'a Expr, f: &mut dyn FnMut(&'a Expr)) {
+    f(expr);
+    expr.recurse(&mut |x| each_expr(x, f));
+}
+
+/// Whether a union spliced in as the `child` of `parent` must be parenthesized to keep
+/// binding as tightly as the subscript it replaces.
+fn union_needs_parens(parent: &Expr, child: &Expr) -> bool {
+    match parent {
+        Expr::Attribute(_) | Expr::UnaryOp(_) | Expr::Await(_) => true,
+        Expr::BinOp(x) => x.op != Operator::BitOr,
+        Expr::Subscript(x) => x.value.range() == child.range(),
+        Expr::Call(x) => x.func.range() == child.range(),
+        _ => false,
+    }
+}
+
+/// Whether a class type parameter declared with `declared` variance keeps its variance
+/// once PEP 695 infers it as `inferred`.
+fn variance_preserved(declared: PreInferenceVariance, inferred: Variance) -> bool {
+    // An unused type parameter is treated as covariant.
+    let inferred = if inferred == Variance::Bivariant {
+        Variance::Covariant
+    } else {
+        inferred
+    };
+    match declared {
+        PreInferenceVariance::Undefined => true,
+        PreInferenceVariance::Invariant => inferred == Variance::Invariant,
+        PreInferenceVariance::Covariant => inferred == Variance::Covariant,
+        PreInferenceVariance::Contravariant => inferred == Variance::Contravariant,
+    }
+}
+
+/// The ranges to delete from a comma-separated list of `items` so that only those
+/// marked in `keep` are left. At least one item must be kept.
+fn removal_ranges(items: &[TextRange], keep: &[bool]) -> Vec<TextRange> {
+    let last_kept = keep
+        .iter()
+        .rposition(|x| *x)
+        .expect("at least one item is kept");
+    let mut ranges = Vec::new();
+    for (i, kept) in keep[..last_kept].iter().enumerate() {
+        if !kept {
+            ranges.push(TextRange::new(items[i].start(), items[i + 1].start()));
+        }
+    }
+    if last_kept + 1 < items.len() {
+        ranges.push(TextRange::new(
+            items[last_kept].end(),
+            items[items.len() - 1].end(),
+        ));
+    }
+    ranges
+}
+
+impl<'a> Upgrader<'a> {
+    fn new(
+        transaction: &'a Transaction<'a>,
+        handle: &'a Handle,
+        module: &'a Module,
+        ast: &'a ModModule,
+    ) -> Self {
+        let mut upgrader = Self {
+            transaction,
+            handle,
+            module,
+            ast,
+            version: handle.sys_info().version(),
+            typing_names: SmallMap::new(),
+            typing_modules: HashSet::new(),
+            type_params: SmallMap::new(),
+            references: SmallMap::new(),
+            bound_names: HashSet::new(),
+            type_contexts: Vec::new(),
+            rescoped: SmallMap::new(),
+            consumed: HashSet::new(),
+            replaced: Vec::new(),
+            deleted_lines: Vec::new(),
+            edits: Vec::new(),
+        };
+        upgrader.collect_typing_imports();
+        upgrader.collect_type_params();
+        upgrader.collect_names();
+        upgrader
+    }
+
+    fn is_typing_module(module: Option<&Identifier>) -> bool {
+        module.is_some_and(|m| m.id == "typing" || m.id == "typing_extensions")
+    }
+
+    fn collect_typing_imports(&mut self) {
+        let ast = self.ast;
+        for stmt in &ast.body {
+            match stmt {
+                Stmt::ImportFrom(x)
+                    if x.level == 0 && Self::is_typing_module(x.module.as_ref()) =>
+                {
+                    for alias in &x.names {
+                        let local = alias.asname.as_ref().unwrap_or(&alias.name);
+                        self.typing_names
+                            .insert(local.id.clone(), alias.name.id.clone());
+                    }
+                }
+                Stmt::Import(x) => {
+                    for alias in &x.names {
+                        if alias.name.id == "typing" || alias.name.id == "typing_extensions" {
+                            let local = alias.asname.as_ref().unwrap_or(&alias.name);
+                            self.typing_modules.insert(local.id.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The name `x` refers to in `typing` or `typing_extensions`, if it is one of theirs.
+    fn typing_name<'b>(&'b self, x: &'b Expr) -> Option<&'b str> {
+        match x {
+            Expr::Name(x) => self.typing_names.get(&x.id).map(|x| x.as_str()),
+            Expr::Attribute(x)
+                if let Expr::Name(base) = &*x.value
+                    && self.typing_modules.contains(&base.id) =>
+            {
+                Some(x.attr.id.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    fn collect_type_params(&mut self) {
+        let ast = self.ast;
+        for stmt in &ast.body {
+            let Stmt::Assign(assign) = stmt else {
+                continue;
+            };
+            let ([Expr::Name(target)], Expr::Call(call)) =
+                (assign.targets.as_slice(), &*assign.value)
+            else {
+                continue;
+            };
+            if let Some(param) = self.legacy_type_param(&target.id, stmt.range(), call) {
+                self.type_params.insert(target.id.clone(), param);
+            }
+        }
+    }
+
+    fn legacy_type_param(
+        &self,
+        name: &Name,
+        stmt_range: TextRange,
+        call: &'a ExprCall,
+    ) -> Option<LegacyTypeParam<'a>> {
+        let kind = match self.typing_name(&call.func)? {
+            "TypeVar" => QuantifiedKind::TypeVar,
+            "ParamSpec" => QuantifiedKind::ParamSpec,
+            _ => return None,
+        };
+        let (Expr::StringLiteral(declared_name), constraints) =
+            call.arguments.args.split_first()?
+        else {
+            return None;
+        };
+        if declared_name.value.to_str() != name.as_str()
+            || constraints.len() == 1
+            || (kind == QuantifiedKind::ParamSpec && !constraints.is_empty())
+        {
+            return None;
+        }
+        let mut param = LegacyTypeParam {
+            kind,
+            stmt_range,
+            func: &call.func,
+            bound: None,
+            constraints,
+            default: None,
+        };
+        for keyword in &call.arguments.keywords {
+            match keyword.arg.as_ref()?.as_str() {
+                "bound" if kind == QuantifiedKind::TypeVar && constraints.is_empty() => {
+                    param.bound = Some(&keyword.value)
+                }
+                "default" => param.default = Some(&keyword.value),
+                "covariant" | "contravariant" | "infer_variance"
+                    if kind == QuantifiedKind::TypeVar
+                        && matches!(keyword.value, Expr::BooleanLiteral(_)) => {}
+                _ => return None,
+            }
+        }
+        Some(param)
+    }
+
+    fn collect_names(&mut self) {
+        let mut references: SmallMap<Name, Vec<TextRange>> = SmallMap::new();
+        let mut bound_names = HashSet::new();
+        let mut type_contexts = Vec::new();
+        for stmt in &self.ast.body {
+            each_stmt(stmt, &mut |stmt| match stmt {
+                Stmt::FunctionDef(x) => {
+                    bound_names.insert(x.name.id.clone());
+                    for param in x.parameters.iter() {
+                        bound_names.insert(param.name().id.clone());
+                        if let Some(annotation) = param.annotation() {
+                            type_contexts.push(annotation.range());
+                        }
+                    }
+                    if let Some(returns) = &x.returns {
+                        type_contexts.push(returns.range());
+                    }
+                }
+                Stmt::ClassDef(x) => {
+                    bound_names.insert(x.name.id.clone());
+                }
+                Stmt::AnnAssign(x) => {
+                    type_contexts.push(x.annotation.range());
+                    if let Some(value) = &x.value
+                        && self.typing_name(&x.annotation) == Some("TypeAlias")
+                    {
+                        type_contexts.push(value.range());
+                    }
+                }
+                Stmt::TypeAlias(x) => type_contexts.push(x.value.range()),
+                Stmt::Import(x) => {
+                    for alias in &x.names {
+                        let local = alias.asname.as_ref().unwrap_or(&alias.name);
+                        bound_names.insert(Name::new(local.id.split('.').next().unwrap()));
+                    }
+                }
+                Stmt::ImportFrom(x) => {
+                    for alias in &x.names {
+                        let local = alias.asname.as_ref().unwrap_or(&alias.name);
+                        bound_names.insert(local.id.clone());
+                    }
+                }
+                _ => {}
+            });
+        }
+        self.ast.visit(&mut |x: &Expr| {
+            each_expr(x, &mut |x| {
+                if let Expr::Name(x) = x {
+                    if x.ctx == ExprContext::Load {
+                        references.entry(x.id.clone()).or_default().push(x.range);
+                    } else {
+                        bound_names.insert(x.id.clone());
+                    }
+                }
+            })
+        });
+        self.references = references;
+        self.bound_names = bound_names;
+        self.type_contexts = type_contexts;
+    }
+
+    fn upgrade(&mut self) {
+        if self.version.at_least(3, 12) {
+            let ast = self.ast;
+            for stmt in &ast.body {
+                each_stmt(stmt, &mut |stmt| match stmt {
+                    Stmt::ClassDef(x) => self.upgrade_class(x),
+                    Stmt::FunctionDef(x) => self.upgrade_function(x),
+                    _ => {}
+                });
+            }
+            self.remove_type_param_declarations();
+            for stmt in &ast.body {
+                if let Stmt::AnnAssign(x) = stmt {
+                    self.upgrade_type_alias(stmt.range(), x);
+                }
+            }
+        }
+        let ast = self.ast;
+        ast.visit(&mut |x: &Expr| self.upgrade_expr(x, false));
+        for stmt in &ast.body {
+            if let Stmt::ImportFrom(x) = stmt {
+                self.upgrade_import(x);
+            }
+        }
+        self.delete_lines();
+    }
+
+    fn consume(&mut self, x: &Expr) {
+        if let Expr::Name(x) = x {
+            self.consumed.insert(x.range);
+        }
+    }
+
+    fn special_form(&self, x: &Expr) -> Option<SpecialForm> {
+        match self.transaction.get_type_trace(self.handle, x.range())? {
+            Type::Type(inner) => match *inner {
+                Type::SpecialForm(form) => Some(form),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `x` with every rewrite inside it applied, or `None` if those rewrites overlap.
+    fn render(&mut self, x: &Expr) -> Option<String> {
+        let outer = std::mem::take(&mut self.edits);
+        self.upgrade_expr(x, false);
+        let inner = std::mem::replace(&mut self.edits, outer);
+        let start = x.range().start();
+        apply_edits(
+            self.module.code_at(x.range()),
+            inner
+                .into_iter()
+                .map(|(range, text)| (range - start, text))
+                .collect(),
+        )
+        .ok()
+    }
+
+    fn upgrade_expr(&mut self, x: &Expr, parenthesize: bool) {
+        if self.replaced.iter().any(|r| r.contains_range(x.range())) {
+            return;
+        }
+        let replacement = match x {
+            Expr::Subscript(subscript) => self.upgrade_union(subscript, parenthesize),
+            Expr::Name(_) | Expr::Attribute(_) => self.upgrade_builtin_alias(x),
+            _ => None,
+        };
+        if let Some(replacement) = replacement {
+            self.edits.push((x.range(), replacement));
+        } else {
+            x.recurse(&mut |child| self.upgrade_expr(child, union_needs_parens(x, child)));
+        }
+    }
+
+    /// Whether `x` can be written as a member of an `|` union without changing its meaning
+    /// or failing at runtime.
+    fn is_union_member(&self, x: &Expr) -> bool {
+        let is_expression = match x {
+            Expr::Name(_) | Expr::Attribute(_) | Expr::Subscript(_) | Expr::NoneLiteral(_) => true,
+            Expr::BinOp(x) => x.op == Operator::BitOr,
+            _ => false,
+        };
+        let is_string = || match self.transaction.get_type_trace(self.handle, x.range()) {
+            Some(Type::Literal(lit)) => matches!(lit.value, Lit::Str(_)),
+            Some(Type::ClassType(cls)) => cls.is_builtin("str"),
+            Some(Type::LiteralString(_)) => true,
+            _ => false,
+        };
+        is_expression && !is_string()
+    }
+
+    fn upgrade_union(&mut self, x: &ExprSubscript, parenthesize: bool) -> Option<String> {
+        if !self.version.at_least(3, 10) {
+            return None;
+        }
+        let form = self.special_form(&x.value)?;
+        let members = match form {
+            SpecialForm::Optional if !matches!(&*x.slice, Expr::Tuple(_)) => {
+                slice::from_ref(&*x.slice)
+            }
+            SpecialForm::Union => Ast::unpack_slice(&x.slice),
+            _ => return None,
+        };
+        if members.is_empty()
+            || !members.iter().all(|m| self.is_union_member(m))
+            // `None | None` is a runtime error.
+            || (form == SpecialForm::Optional || members.len() == 1)
+                && members.iter().all(|m| matches!(m, Expr::NoneLiteral(_)))
+        {
+            return None;
+        }
+        let mut parts = members
+            .iter()
+            .map(|m| self.render(m))
+            .collect::<Option<Vec<_>>>()?;
+        if form == SpecialForm::Optional {
+            parts.push("None".to_owned());
+        }
+        self.consume(&x.value);
+        let union = parts.join(" | ");
+        Some(if parenthesize && parts.len() > 1 {
+            format!("({union})")
+        } else {
+            union
+        })
+    }
+
+    fn upgrade_builtin_alias(&mut self, x: &Expr) -> Option<String> {
+        if !self.version.at_least(3, 9) {
+            return None;
+        }
+        let builtin = match self.transaction.get_type_trace(self.handle, x.range())? {
+            Type::ClassDef(cls) => ["list", "dict", "set", "frozenset"]
+                .into_iter()
+                .find(|name| cls.is_builtin(name))?,
+            Type::Type(inner) => match *inner {
+                Type::SpecialForm(SpecialForm::Tuple) => "tuple",
+                Type::SpecialForm(SpecialForm::Type) => "type",
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if self.module.code_at(x.range()) == builtin
+            || self.bound_names.contains(&Name::new_static(builtin))
+        {
+            return None;
+        }
+        self.consume(x);
+        Some(builtin.to_owned())
+    }
+
+    /// The PEP 695 type parameter list for `tparams`, if all of them are legacy type
+    /// parameters declared in this module. Defaults need PEP 696 syntax, which only
+    /// parses on Python 3.13+.
+    fn type_param_list<'b>(
+        &mut self,
+        tparams: impl Iterator<Item = (&'b Name, QuantifiedKind)>,
+    ) -> Option<(Vec<Name>, String)> {
+        let mut params = Vec::new();
+        for (name, kind) in tparams {
+            let param = self.type_params.get(name)?;
+            if param.kind != kind {
+                return None;
+            }
+            params.push((
+                name.clone(),
+                kind,
+                param.bound,
+                param.constraints,
+                param.default,
+            ));
+        }
+        if params.is_empty()
+            || (!self.version.at_least(3, 13) && params.iter().any(|p| p.4.is_some()))
+        {
+            return None;
+        }
+        let mut names = Vec::new();
+        let mut rendered = Vec::new();
+        for (name, kind, bound, constraints, default) in params {
+            let mut text = match kind {
+                QuantifiedKind::ParamSpec => format!("**{name}"),
+                _ => name.to_string(),
+            };
+            if let Some(bound) = bound {
+                text.push_str(&format!(": {}", self.render(bound)?));
+            } else if !constraints.is_empty() {
+                let constraints = constraints
+                    .iter()
+                    .map(|c| self.render(c))
+                    .collect::<Option<Vec<_>>>()?;
+                text.push_str(&format!(": ({})", constraints.join(", ")));
+            }
+            if let Some(default) = default {
+                text.push_str(&format!(" = {}", self.render(default)?));
+            }
+            names.push(name);
+            rendered.push(text);
+        }
+        Some((names, format!("[{}]", rendered.join(", "))))
+    }
+
+    fn rescope(&mut self, names: Vec<Name>, scope: TextRange) {
+        for name in names {
+            self.rescoped.entry(name).or_default().push(scope);
+        }
+    }
+
+    fn upgrade_class(&mut self, x: &StmtClassDef) {
+        if x.type_params.is_some() {
+            return;
+        }
+        let Some(arguments) = &x.arguments else {
+            return;
+        };
+        let (Some(bindings), Some(answers)) = (
+            self.transaction.get_bindings(self.handle),
+            self.transaction.get_answers(self.handle),
+        ) else {
+            return;
+        };
+        let Some(class) = bindings
+            .key_to_idx_hashed_opt(Hashed::new(&KeyClass(ShortIdentifier::new(&x.name))))
+            .and_then(|idx| answers.get_idx(idx))
+            .and_then(|answer| answer.0.dupe())
+        else {
+            return;
+        };
+        let Some((tparams, inferred)) =
+            self.transaction
+                .ad_hoc_solve(self.handle, "upgrade_class_tparams", |solver| {
+                    (
+                        solver.get_class_tparams(&class),
+                        solver.infer_variance_ignoring_declared(&class),
+                    )
+                })
+        else {
+            return;
+        };
+        if tparams.iter().any(|q| {
+            q.kind() == QuantifiedKind::TypeVar
+                && !variance_preserved(q.variance(), inferred.get(q.name()))
+        }) {
+            return;
+        }
+        // Work out what happens to the bases before rendering, which records the
+        // references the rewrite consumes.
+        let mut items = Vec::new();
+        let mut keep = Vec::new();
+        let mut generic = None;
+        let mut protocol = None;
+        for arg in arguments.iter_source_order() {
+            let (range, base) = match arg {
+                ArgOrKeyword::Arg(arg) => (arg.range(), Some(arg)),
+                ArgOrKeyword::Keyword(keyword) => (keyword.range(), None),
+            };
+            items.push(range);
+            match base {
+                Some(Expr::Subscript(base)) => match self.special_form(&base.value) {
+                    Some(SpecialForm::Generic) if generic.is_none() => {
+                        generic = Some(base);
+                        keep.push(false);
+                        continue;
+                    }
+                    Some(SpecialForm::Protocol) => protocol = Some(base),
+                    _ => {}
+                },
+                _ => {}
+            }
+            keep.push(true);
+        }
+        let Some((names, params)) =
+            self.type_param_list(tparams.iter().map(|q| (q.name(), q.kind())))
+        else {
+            return;
+        };
+        self.edits.push((TextRange::empty(x.name.end()), params));
+        if let Some(generic) = generic {
+            self.consume(&generic.value);
+            self.replaced.push(generic.range());
+            if keep.contains(&true) {
+                for range in removal_ranges(&items, &keep) {
+                    self.edits.push((range, String::new()));
+                }
+            } else {
+                self.edits.push((arguments.range(), String::new()));
+            }
+        }
+        if let Some(protocol) = protocol {
+            self.replaced.push(protocol.range());
+            self.edits.push((
+                protocol.range(),
+                self.module.code_at(protocol.value.range()).to_owned(),
+            ));
+        }
+        self.rescope(names, TextRange::new(x.name.end(), x.range().end()));
+    }
+
+    fn upgrade_function(&mut self, x: &StmtFunctionDef) {
+        if x.type_params.is_some() {
+            return;
+        }
+        let (Some(bindings), Some(answers)) = (
+            self.transaction.get_bindings(self.handle),
+            self.transaction.get_answers(self.handle),
+        ) else {
+            return;
+        };
+        let Some(function) = bindings
+            .key_to_idx_hashed_opt(Hashed::new(&KeyDecoratedFunction(ShortIdentifier::new(
+                &x.name,
+            ))))
+            .and_then(|idx| answers.get_idx(bindings.get(idx).undecorated_idx))
+        else {
+            return;
+        };
+        let Some((names, params)) =
+            self.type_param_list(function.tparams.iter().map(|q| (q.name(), q.kind())))
+        else {
+            return;
+        };
+        self.edits.push((TextRange::empty(x.name.end()), params));
+        self.rescope(names, TextRange::new(x.name.end(), x.range().end()));
+    }
+
+    /// Delete private type variable declarations whose every use now refers to a PEP 695
+    /// type parameter instead. Public ones may be imported elsewhere, so they stay.
+    fn remove_type_param_declarations(&mut self) {
+        let mut removed = Vec::new();
+        for (name, param) in &self.type_params {
+            let Some(scopes) = self.rescoped.get(name) else {
+                continue;
+            };
+            if name.starts_with('_')
+                && self.references.get(name).is_some_and(|refs| {
+                    refs.iter()
+                        .all(|r| scopes.iter().any(|scope| scope.contains_range(*r)))
+                })
+            {
+                removed.push((param.stmt_range, param.func));
+            }
+        }
+        for (range, func) in removed {
+            self.consume(func);
+            self.replaced.push(range);
+            self.delete_statement(range);
+        }
+    }
+
+    /// Delete the statement at `range`, along with its whole line if nothing else shares it.
+    fn delete_statement(&mut self, range: TextRange) {
+        let contents = self.module.contents();
+        let start = range.start().to_usize();
+        let end = range.end().to_usize();
+        let line_start = contents[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[end..]
+            .find('\n')
+            .map_or(contents.len(), |i| end + i + 1);
+        if contents[line_start..start].trim().is_empty()
+            && contents[end..line_end].trim().is_empty()
+        {
+            self.deleted_lines.push(TextRange::new(
+                TextSize::try_from(line_start).unwrap(),
+                TextSize::try_from(line_end).unwrap(),
+            ));
+        } else {
+            self.edits.push((range, String::new()));
+        }
+    }
+
+    /// Delete the lines queued by `delete_statement`. Deleted lines separated only by blank
+    /// lines are deleted as one block, and a block that starts the module or follows a blank
+    /// line takes the blank lines after it too, so no gap is left where it was.
+    fn delete_lines(&mut self) {
+        let contents = self.module.contents().as_str();
+        let is_blank = |start: usize, end: usize| contents[start..end].trim().is_empty();
+        let mut lines = std::mem::take(&mut self.deleted_lines);
+        lines.sort_by_key(|range| range.start());
+        let mut blocks: Vec<TextRange> = Vec::new();
+        for range in lines {
+            match blocks.last_mut() {
+                Some(last) if is_blank(last.end().to_usize(), range.start().to_usize()) => {
+                    *last = last.cover(range);
+                }
+                _ => blocks.push(range),
+            }
+        }
+        for block in blocks {
+            let start = block.start().to_usize();
+            let follows_blank = start == 0 || {
+                let previous_line_start = contents[..start - 1].rfind('\n').map_or(0, |i| i + 1);
+                is_blank(previous_line_start, start)
+            };
+            let mut end = block.end().to_usize();
+            if follows_blank {
+                while let Some(line_end) = contents[end..].find('\n').map(|i| end + i + 1)
+                    && is_blank(end, line_end)
+                {
+                    end = line_end;
+                }
+            }
+            self.edits.push((
+                TextRange::new(block.start(), TextSize::try_from(end).unwrap()),
+                String::new(),
+            ));
+        }
+    }
+
+    fn upgrade_type_alias(&mut self, stmt_range: TextRange, x: &StmtAnnAssign) {
+        let (Expr::Name(target), Some(value)) = (&*x.target, &x.value) else {
+            return;
+        };
+        // A `type` alias is only usable in annotations, and can't be generic over
+        // legacy type variables. Public aliases may be used at runtime by other modules,
+        // so only private ones are rewritten.
+        if self.typing_name(&x.annotation) != Some("TypeAlias") || !target.id.starts_with('_') {
+            return;
+        }
+        let only_annotations = self.references.get(&target.id).is_none_or(|refs| {
+            refs.iter()
+                .all(|r| self.type_contexts.iter().any(|c| c.contains_range(*r)))
+        });
+        let mut generic = false;
+        each_expr(value, &mut |x| {
+            if let Expr::Name(name) = x
+                && (self.type_params.contains_key(&name.id)
+                    || matches!(
+                        self.transaction.get_type_trace(self.handle, name.range),
+                        Some(Type::TypeVar(_) | Type::ParamSpec(_) | Type::TypeVarTuple(_))
+                    ))
+            {
+                generic = true;
+            }
+        });
+        if !only_annotations || generic {
+            return;
+        }
+        if let Expr::StringLiteral(s) = &**value {
+            let code = self.module.code_at(s.range());
+            let quoted = code.starts_with(['"', '\'']);
+            let text = s.value.to_str();
+            if !quoted || s.value.is_implicit_concatenated() || text.contains('\n') {
+                return;
+            }
+            self.replaced.push(s.range());
+            self.edits.push((s.range(), text.to_owned()));
+        }
+        self.consume(&x.annotation);
+        self.edits.push((
+            TextRange::new(stmt_range.start(), value.range().start()),
+            format!("type {} = ", target.id),
+        ));
+    }
+
+    /// Whether `typing` provides `name`, behaving like the `typing_extensions` version.
+    fn typing_provides(&self, name: &Name) -> bool {
+        let parity = TYPING_EXTENSIONS_PARITY
+            .iter()
+            .find(|(x, _)| *x == name.as_str())
+            .map_or(0, |(_, minor)| *minor);
+        self.version.at_least(3, parity)
+            && self
+                .transaction
+                .import_handle(self.handle, ModuleName::typing(), None)
+                .finding()
+                .is_some_and(|typing| self.transaction.get_exports(&typing).contains_key(name))
+    }
+
+    /// Drop imported typing names that the rewrites left unused, and import names from
+    /// `typing` rather than `typing_extensions` where possible.
+    fn upgrade_import(&mut self, x: &StmtImportFrom) {
+        if x.level != 0 || !Self::is_typing_module(x.module.as_ref()) {
+            return;
+        }
+        let module = x.module.as_ref().unwrap();
+        let from_extensions = module.id == "typing_extensions";
+        // `__init__.py` files import names to re-export them.
+        let is_init = self.module.path().is_init();
+        let mut items = Vec::new();
+        let mut fates = Vec::new();
+        for alias in &x.names {
+            if alias.name.id == "*" {
+                return;
+            }
+            let local = alias.asname.as_ref().unwrap_or(&alias.name);
+            let unused = !is_init
+                && self
+                    .references
+                    .get(&local.id)
+                    .is_some_and(|refs| refs.iter().all(|r| self.consumed.contains(r)));
+            items.push(alias.range());
+            fates.push(if unused {
+                ImportFate::Drop
+            } else if from_extensions && self.typing_provides(&alias.name.id) {
+                ImportFate::Move
+            } else {
+                ImportFate::Keep
+            });
+        }
+        let keep: Vec<bool> = fates.iter().map(|x| *x == ImportFate::Keep).collect();
+        let moved: Vec<&str> = x
+            .names
+            .iter()
+            .zip(&fates)
+            .filter(|(_, fate)| **fate == ImportFate::Move)
+            .map(|(alias, _)| self.module.code_at(alias.range()))
+            .collect();
+        if keep.contains(&true) {
+            if keep.contains(&false) {
+                for range in removal_ranges(&items, &keep) {
+                    self.edits.push((range, String::new()));
+                }
+            }
+            if !moved.is_empty() {
+                self.edits.push((
+                    TextRange::empty(x.range().start()),
+                    format!("from typing import {}\n", moved.join(", ")),
+                ));
+            }
+        } else if !moved.is_empty() {
+            // Everything left comes from `typing`.
+            let keep: Vec<bool> = fates.iter().map(|x| *x == ImportFate::Move).collect();
+            if keep.contains(&false) {
+                for range in removal_ranges(&items, &keep) {
+                    self.edits.push((range, String::new()));
+                }
+            }
+            self.edits.push((module.range(), "typing".to_owned()));
+        } else {
+            self.delete_statement(x.range());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_str_eq;
+    use pyrefly_util::fs_anyhow;
+    use pyrefly_util::globs::FilteredGlobs;
+    use pyrefly_util::globs::Globs;
+    use pyrefly_util::globs::HiddenDirFilter;
+    use pyrefly_util::thread_pool::TEST_THREAD_COUNT;
+
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    fn assert_upgrade_with_env(mut env: TestEnv, input: &str, output: &str) {
+        let tdir = tempfile::tempdir().unwrap();
+        let path = tdir.path().join("test.py");
+        fs_anyhow::write(&path, input).unwrap();
+        env.add(&path.display().to_string(), input);
+        let includes = Globs::new(vec![format!("{}/**/*", tdir.path().display())]).unwrap();
+        let f_globs = Box::new(FilteredGlobs::new(
+            includes,
+            Globs::empty(),
+            None,
+            HiddenDirFilter::Disabled,
+        ));
+        let result = UpgradeArgs::run_inner(f_globs, env.config_finder(), false, TEST_THREAD_COUNT);
+        assert!(result.is_ok(), "upgrade command failed: {:?}", result.err());
+        let got_file = fs_anyhow::read_to_string(&path).unwrap();
+        assert_str_eq!(output, got_file);
+    }
+
+    fn assert_upgrade(input: &str, output: &str) {
+        assert_upgrade_with_env(TestEnv::new(), input, output)
+    }
+
+    #[test]
+    fn test_union_and_builtin_generics() {
+        assert_upgrade(
+            r#"from typing import Dict, List, Optional, Union
+
+def f(x: Optional[List[int]], y: Union[int, str, None]) -> Dict[str, Optional[int]]:
+    return {}
+"#,
+            r#"def f(x: list[int] | None, y: int | str | None) -> dict[str, int | None]:
+    return {}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_union_keeps_imports_still_in_use() {
+        assert_upgrade(
+            r#"from typing import Optional, cast
+
+x = cast(Optional[int], None)
+y = Optional
+"#,
+            r#"from typing import Optional, cast
+
+x = cast(int | None, None)
+y = Optional
+"#,
+        );
+    }
+
+    #[test]
+    fn test_string_members_left_alone() {
+        assert_upgrade(
+            r#"from typing import Optional
+
+def f(x: Optional["C"]) -> None: ...
+
+class C: ...
+"#,
+            r#"from typing import Optional
+
+def f(x: Optional["C"]) -> None: ...
+
+class C: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_respects_python_version() {
+        assert_upgrade_with_env(
+            TestEnv::new_with_version(PythonVersion::new(3, 9, 0)),
+            r#"from typing import List, Optional
+
+def f(x: Optional[List[int]]) -> None: ...
+"#,
+            r#"from typing import Optional
+
+def f(x: Optional[list[int]]) -> None: ...
+"#,
+        );
+    }
+
+    #[test]
+    fn test_generic_class_and_function() {
+        assert_upgrade(
+            r#"from typing import Generic, TypeVar
+
+_T = TypeVar("_T", bound=int)
+_K = TypeVar("_K")
+
+class Box(Generic[_T]):
+    def __init__(self, x: _T) -> None:
+        self.x = x
+
+def first(xs: list[_K]) -> _K:
+    return xs[0]
+"#,
+            r#"class Box[_T: int]:
+    def __init__(self, x: _T) -> None:
+        self.x = x
+
+def first[_K](xs: list[_K]) -> _K:
+    return xs[0]
+"#,
+        );
+    }
+
+    #[test]
+    fn test_type_var_default_needs_3_13() {
+        assert_upgrade_with_env(
+            TestEnv::new_with_version(PythonVersion::new(3, 12, 0)),
+            r#"from typing import Generic
+from typing_extensions import TypeVar
+
+_T = TypeVar("_T", default=int)
+_K = TypeVar("_K")
+
+class Box(Generic[_T]):
+    x: _T
+
+def first(xs: list[_K]) -> _K:
+    return xs[0]
+"#,
+            r#"from typing import Generic
+from typing_extensions import TypeVar
+
+_T = TypeVar("_T", default=int)
+
+class Box(Generic[_T]):
+    x: _T
+
+def first[_K](xs: list[_K]) -> _K:
+    return xs[0]
+"#,
+        );
+    }
+
+    #[test]
+    fn test_variance_mismatch_keeps_legacy_class() {
+        // `T_co` is declared covariant, but appears in an invariant position, so PEP 695
+        // would infer a different variance.
+        assert_upgrade(
+            r#"from typing import Generic, TypeVar
+
+T_co = TypeVar("T_co", covariant=True)
+
+class Box(Generic[T_co]):
+    x: list[T_co]
+"#,
+            r#"from typing import Generic, TypeVar
+
+T_co = TypeVar("T_co", covariant=True)
+
+class Box(Generic[T_co]):
+    x: list[T_co]
+"#,
+        );
+    }
+
+    #[test]
+    fn test_type_alias() {
+        assert_upgrade(
+            r#"from typing import TypeAlias, Union
+
+_IntOrStr: TypeAlias = Union[int, str]
+_Forward: TypeAlias = "C"
+_Runtime: TypeAlias = int
+Public: TypeAlias = int
+
+def f(x: _IntOrStr, y: _Forward, z: Public) -> None: ...
+
+class C: ...
+
+isinstance(1, _Runtime)
+"#,
+            r#"from typing import TypeAlias
+
+type _IntOrStr = int | str
+type _Forward = C
+_Runtime: TypeAlias = int
+Public: TypeAlias = int
+
+def f(x: _IntOrStr, y: _Forward, z: Public) -> None: ...
+
+class C: ...
+
+isinstance(1, _Runtime)
+"#,
+        );
+    }
+
+    #[test]
+    fn test_removed_lines_leave_no_gap() {
+        assert_upgrade(
+            r#"import os
+from typing import TypeVar
+
+_T = TypeVar("_T")
+
+def first(xs: list[_T]) -> _T:
+    return xs[0]
+"#,
+            r#"import os
+
+def first[_T](xs: list[_T]) -> _T:
+    return xs[0]
+"#,
+        );
+    }
+
+    #[test]
+    fn test_typing_extensions_imports() {
+        assert_upgrade(
+            r#"from typing_extensions import Self, deprecated, override
+
+class A:
+    @override
+    def __eq__(self, other: object) -> bool:
+        return True
+
+    def copy(self) -> Self:
+        return self
+
+@deprecated("use A")
+def old() -> None: ...
+"#,
+            r#"from typing import Self, override
+from typing_extensions import deprecated
+
+class A:
+    @override
+    def __eq__(self, other: object) -> bool:
+        return True
+
+    def copy(self) -> Self:
+        return self
+
+@deprecated("use A")
+def old() -> None: ...
+"#,
+        );
+    }
+}
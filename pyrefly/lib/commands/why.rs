@@ -0,0 +1,280 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use pyrefly_config::args::ConfigOverrideArgs;
+use pyrefly_config::finder::ConfigFinder;
+use pyrefly_util::absolutize::Absolutize as _;
+use pyrefly_util::args::clap_env;
+use pyrefly_util::forgetter::Forgetter;
+use pyrefly_util::includes::Includes;
+use pyrefly_util::lined_buffer::DisplayPos;
+use pyrefly_util::lined_buffer::LineNumber;
+use pyrefly_util::thread_pool::ThreadCount;
+
+use crate::commands::check::Handles;
+use crate::commands::config_finder::ConfigConfigurerWrapper;
+use crate::commands::files::FilesArgs;
+use crate::commands::util::CommandExitStatus;
+use crate::lsp::wasm::type_derivation::type_derivation;
+use crate::state::require::Require;
+use crate::state::state::State;
+
+/// Arguments for the why command, which explains how the type of an expression was derived.
+#[deny(clippy::missing_docs_in_private_items)]
+#[derive(Debug, Parser, Clone)]
+pub struct WhyArgs {
+    /// The position of the expression to explain, as `FILE:LINE:COLUMN` (1-based, as in error messages).
+    #[arg(value_name = "FILE:LINE:COLUMN")]
+    location: String,
+
+    /// Explicitly set the Pyrefly configuration to use.
+    #[arg(long, short, value_name = "FILE", env = clap_env("CONFIG"))]
+    config: Option<PathBuf>,
+
+    /// Type checking arguments and configuration
+    #[command(flatten)]
+    config_override: ConfigOverrideArgs,
+}
+
+/// A parsed `FILE:LINE:COLUMN` location.
+#[derive(Debug, PartialEq, Eq)]
+struct Location {
+    path: String,
+    line: LineNumber,
+    column: NonZeroU32,
+}
+
+fn parse_location(location: &str) -> anyhow::Result<Location> {
+    let mut parts = location.rsplitn(3, ':');
+    let (Some(column), Some(line), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("Expected a location of the form `FILE:LINE:COLUMN`, got `{location}`");
+    };
+    let line = line
+        .parse()
+        .ok()
+        .and_then(LineNumber::new)
+        .with_context(|| format!("Invalid line number `{line}` in `{location}`"))?;
+    let column = column
+        .parse()
+        .ok()
+        .and_then(NonZeroU32::new)
+        .with_context(|| format!("Invalid column number `{column}` in `{location}`"))?;
+    Ok(Location {
+        path: path.to_owned(),
+        line,
+        column,
+    })
+}
+
+impl WhyArgs {
+    pub fn run(
+        self,
+        wrapper: Option<ConfigConfigurerWrapper>,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<CommandExitStatus> {
+        self.config_override.validate()?;
+        let location = parse_location(&self.location)?;
+        let (files_to_check, config_finder, _) = FilesArgs::get(
+            vec![location.path.clone()],
+            self.config,
+            self.config_override,
+            wrapper,
+        )?;
+        let derivation = Self::run_inner(files_to_check, config_finder, &location, thread_count)?;
+        print!("{derivation}");
+        Ok(CommandExitStatus::Success)
+    }
+
+    /// Check the file containing `location` and render the derivation of the type there.
+    fn run_inner(
+        files_to_check: Box<dyn Includes>,
+        config_finder: ConfigFinder,
+        location: &Location,
+        thread_count: ThreadCount,
+    ) -> anyhow::Result<String> {
+        let expanded_file_list = config_finder.checkpoint(files_to_check.files_iter())?;
+        let state = State::new(config_finder, thread_count);
+        let holder = Forgetter::new(state, false);
+        let handles = Handles::new(expanded_file_list);
+        let mut forgetter = Forgetter::new(
+            holder.as_ref().new_transaction(Require::Exports, None),
+            true,
+        );
+        let transaction = forgetter.as_mut();
+
+        let (handles, _, sourcedb_errors) = handles.all(holder.as_ref().config_finder());
+        if !sourcedb_errors.is_empty() {
+            for error in sourcedb_errors {
+                error.print();
+            }
+            return Err(anyhow::anyhow!("Failed to query sourcedb."));
+        }
+        let path = PathBuf::from(&location.path).absolutize();
+        let handle = handles
+            .into_iter()
+            .find(|handle| handle.path().as_path() == path)
+            .with_context(|| format!("`{}` is not a Python file", location.path))?;
+        transaction.run(std::slice::from_ref(&handle), Require::Everything, None);
+        let module = transaction
+            .get_module_info(&handle)
+            .with_context(|| format!("Failed to load `{}`", location.path))?;
+        let position = module.lined_buffer().from_display_pos(DisplayPos::Source {
+            line: location.line,
+            column: location.column,
+        });
+        let derivation = type_derivation(transaction, &handle, position).with_context(|| {
+            format!(
+                "No expression at {}:{}:{}",
+                location.path, location.line, location.column
+            )
+        })?;
+        Ok(derivation.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pyrefly_util::fs_anyhow;
+    use pyrefly_util::globs::FilteredGlobs;
+    use pyrefly_util::globs::Globs;
+    use pyrefly_util::globs::HiddenDirFilter;
+    use pyrefly_util::thread_pool::TEST_THREAD_COUNT;
+
+    use super::*;
+    use crate::test::util::TestEnv;
+
+    fn why(code: &str, line: u32, column: u32) -> String {
+        let tdir = tempfile::tempdir().unwrap();
+        let path = tdir.path().join("test.py");
+        fs_anyhow::write(&path, code).unwrap();
+        let mut env = TestEnv::new();
+        env.add(&path.display().to_string(), code);
+        let includes = Globs::new(vec![format!("{}/**/*", tdir.path().display())]).unwrap();
+        let f_globs = Box::new(FilteredGlobs::new(
+            includes,
+            Globs::empty(),
+            None,
+            HiddenDirFilter::Disabled,
+        ));
+        let location = Location {
+            path: path.display().to_string(),
+            line: LineNumber::new(line).unwrap(),
+            column: NonZeroU32::new(column).unwrap(),
+        };
+        WhyArgs::run_inner(f_globs, env.config_finder(), &location, TEST_THREAD_COUNT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(
+            parse_location("src/a:b.py:3:7").unwrap(),
+            Location {
+                path: "src/a:b.py".to_owned(),
+                line: LineNumber::new(3).unwrap(),
+                column: NonZeroU32::new(7).unwrap(),
+            }
+        );
+        assert!(parse_location("a.py:3").is_err());
+        assert!(parse_location("a.py:0:1").is_err());
+        assert!(parse_location("a.py:x:1").is_err());
+    }
+
+    #[test]
+    fn test_why_narrowing() {
+        let code = r#"
+def f(x: int | None) -> None:
+    if x is not None:
+        x
+"#;
+        let output = why(code, 4, 9);
+        assert!(output.starts_with("`x` has type `int`\n"), "{output}");
+        assert!(
+            output.contains("narrowed to `int` by `x is not None` at 3:8"),
+            "{output}"
+        );
+        assert!(
+            output.contains("parameter declared as `int | None`"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_why_join() {
+        let code = r#"
+def f(b: bool) -> None:
+    if b:
+        y = 1
+    else:
+        y = "a"
+    y
+"#;
+        let output = why(code, 7, 5);
+        assert!(output.contains("from 2 branches"), "{output}");
+        assert!(
+            output.contains("from the assigned value `1` at 4:9"),
+            "{output}"
+        );
+        assert!(
+            output.contains("from the assigned value `\"a\"` at 6:9"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_why_type_var_solution() {
+        let code = r#"
+def first[T](xs: list[T]) -> T: ...
+def g(xs: list[str]) -> None:
+    first(xs)
+"#;
+        let output = why(code, 4, 5);
+        assert!(
+            output.starts_with("`first(xs)` has type `str`\n"),
+            "{output}"
+        );
+        assert!(output.contains("`T` = `str`"), "{output}");
+    }
+
+    #[test]
+    fn test_why_type_var_joined_solution() {
+        let code = r#"
+def pick[T](a: T, b: T) -> T: ...
+pick(1, "a")
+"#;
+        let output = why(code, 3, 1);
+        assert!(output.contains("`T` = `int | str`"), "{output}");
+    }
+
+    #[test]
+    fn test_why_rejected_overload() {
+        let code = r#"
+from typing import overload
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: str) -> str: ...
+def f(x: int | str) -> int | str: ...
+f("a")
+"#;
+        let output = why(code, 8, 1);
+        assert!(output.contains("resolved to overload 2 of 2"), "{output}");
+        assert!(
+            output.contains("overload 1 of 2: `(x: int) -> int`, rejected"),
+            "{output}"
+        );
+        assert!(output.contains("expected `int`"), "{output}");
+        assert!(
+            output.contains("overload 2 of 2: `(x: str) -> str`, selected"),
+            "{output}"
+        );
+    }
+}
@@ -165,6 +165,7 @@ impl ErrorCollector {
             details: Vec::new(),
             context: None,
             annotations: Vec::new(),
+            related_annotations: Vec::new(),
             quick_fixes: Vec::new(),
         }
     }
@@ -214,6 +215,16 @@ impl ErrorCollector {
         self.errors.lock().has_hard()
     }
 
+    /// The hard (non-soft) errors, in source order.
+    pub fn hard_errors(&self) -> Vec<Error> {
+        self.errors
+            .lock()
+            .iter()
+            .filter(|err| !err.error_kind().is_soft())
+            .cloned()
+            .collect()
+    }
+
     /// Checks whether an error is suppressed, considering ignore-all directives,
     /// per-line suppressions, and (for errors inside multi-line f/t-strings)
     /// suppressions on the f-string's start or end lines.
@@ -372,6 +383,7 @@ pub struct ErrorBuilder<'a> {
     details: Vec<String>,
     context: Option<ErrorContext>,
     annotations: Vec<(TextRange, String)>,
+    related_annotations: Vec<(ModuleInfo, TextRange, String)>,
     quick_fixes: Vec<ErrorQuickFix>,
 }
 
@@ -408,6 +420,20 @@ impl ErrorBuilder<'_> {
         self
     }
 
+    /// Add a secondary labeled span in `module`, which may differ from the module the
+    /// error is reported in.
+    pub fn with_related_annotation(
+        mut self,
+        module: ModuleInfo,
+        range: TextRange,
+        label: String,
+    ) -> Self {
+        if self.active {
+            self.related_annotations.push((module, range, label));
+        }
+        self
+    }
+
     /// Add a structured quick fix.
     pub fn with_quick_fix(mut self, fix: ErrorQuickFix) -> Self {
         if self.active {
@@ -452,6 +478,9 @@ impl ErrorBuilder<'_> {
         for (range, label) in annotations {
             err = err.with_annotation(range, label);
         }
+        for (module, range, label) in self.related_annotations {
+            err = err.with_related_annotation(module, range, label);
+        }
         for fix in self.quick_fixes {
             err = err.with_quick_fix(fix);
         }
@@ -13,6 +13,7 @@ use ruff_text_size::TextRange;
 
 use crate::binding::binding::AnnotationTarget;
 use crate::config::error_kind::ErrorKind;
+use crate::module::module_info::ModuleInfo;
 use crate::types::display::TypeDisplayContext;
 use crate::types::types::Type;
 
@@ -115,6 +116,8 @@ pub struct TypeCheckContext {
     pub context: Option<ErrorContext>,
     /// Optional secondary annotations to attach to the error.
     pub annotations: Vec<(TextRange, String)>,
+    /// Optional secondary annotations in other modules, e.g. the declaration being assigned to.
+    pub related_annotations: Vec<(ModuleInfo, TextRange, String)>,
 }
 
 impl TypeCheckContext {
@@ -123,6 +126,7 @@ impl TypeCheckContext {
             kind,
             context: None,
             annotations: Vec::new(),
+            related_annotations: Vec::new(),
         }
     }
 
@@ -143,6 +147,17 @@ impl TypeCheckContext {
             None => self,
         }
     }
+
+    /// Add an annotation at a range of `module`, which may differ from the module being checked.
+    pub fn with_related_annotation(
+        mut self,
+        module: ModuleInfo,
+        range: TextRange,
+        label: String,
+    ) -> Self {
+        self.related_annotations.push((module, range, label));
+        self
+    }
 }
 
 #[derive(Debug)]
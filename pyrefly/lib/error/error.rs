@@ -11,10 +11,14 @@ use std::io;
 use std::io::Write;
 use std::path::Path;
 
+use anyhow::Context as _;
+use clap::ValueEnum as _;
 use itertools::Itertools;
 use lsp_types::CodeDescription;
 use lsp_types::Diagnostic;
+use lsp_types::DiagnosticRelatedInformation;
 use lsp_types::DiagnosticTag;
+use lsp_types::Location;
 use lsp_types::Url;
 use pyrefly_python::ignore::Tool;
 use pyrefly_python::module::Module;
@@ -35,18 +39,34 @@ use yansi::Paint;
 
 use crate::config::error_kind::ErrorKind;
 use crate::config::error_kind::Severity;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
 
-/// A secondary annotation that labels a span in the same file as the primary error.
-/// Used to show additional context, e.g. the types of both operands in a binary operation.
+/// A secondary annotation that labels a span related to the primary error.
+/// Used to show additional context, e.g. the types of both operands in a binary operation,
+/// or the parent method that a bad override is checked against.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SecondaryAnnotation {
     pub range: TextRange,
     pub label: Box<str>,
+    /// The module `range` points into, or `None` if it is the module of the primary error.
+    pub module: Option<Module>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ErrorQuickFix {
-    ReplaceWithEnumMember { replacement: String },
+    ReplaceWithEnumMember {
+        replacement: String,
+    },
+    /// `class` can't be instantiated because it leaves abstract members unimplemented.
+    ImplementAbstractMembers {
+        class: Class,
+    },
+    /// `class` is used as `protocol` but doesn't define some of the protocol's members.
+    ImplementProtocolMembers {
+        class: Class,
+        protocol: ClassType,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -61,7 +81,7 @@ pub struct Error {
     /// The rest of the error message after the first line.
     /// Note that this is formatted for pretty-printing, with two spaces at the beginning and after every newline.
     msg_details: Option<Box<str>>,
-    /// Additional labeled spans in the same file for richer diagnostics.
+    /// Additional labeled spans, possibly in other files, for richer diagnostics.
     secondary_annotations: Vec<SecondaryAnnotation>,
     /// Structured fixes that can be exposed by editor integrations.
     quick_fixes: Vec<ErrorQuickFix>,
@@ -119,7 +139,8 @@ impl<W: Write> ErrorRenderer<W> {
         let origin = error.path_string_with_fragment(project_root);
         if verbose {
             self.write_header(error)?;
-            let snippet = error.get_source_snippet(&origin);
+            let related_origins = error.related_origins(project_root);
+            let snippet = error.get_source_snippet(&origin, &related_origins);
             self.write_snippet(snippet)?;
             if let Some(details) = &error.msg_details {
                 writeln!(self.writer, "{details}")?;
@@ -180,6 +201,80 @@ impl<W: Write> ErrorRenderer<W> {
     fn write_snippet<'a>(&mut self, snippet: Message<'a>) -> io::Result<()> {
         writeln!(self.writer, "{}", self.snippets.render(snippet))
     }
+
+    /// Write the long-form explanation of an error kind, as printed by `pyrefly explain`.
+    pub fn write_explanation(&mut self, kind: ErrorKind) -> anyhow::Result<()> {
+        let explanation = explain_error_kind(kind)?;
+        match self.mode {
+            ErrorRenderMode::Plain => writeln!(self.writer, "{explanation}")?,
+            ErrorRenderMode::Color => {
+                writeln!(self.writer, "{}", Paint::dim(explanation.as_str()))?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The bundled documentation for an error kind, followed by how to configure or suppress it.
+/// This is the same text as the error kinds page of the website, so it is available offline.
+pub fn explain_error_kind(kind: ErrorKind) -> anyhow::Result<String> {
+    let name = kind.to_name();
+    let severity = kind
+        .default_severity()
+        .to_possible_value()
+        .with_context(|| format!("Severity of `{name}` has no name"))?;
+    let severity = severity.get_name();
+    let docs = kind.docs().map_or_else(
+        || "No documentation is available for this error kind.".to_owned(),
+        strip_mdx,
+    );
+    Ok(format!(
+        "[{name}] (default severity: {severity})\n\n{docs}\n\n\
+         To change the severity, set `{name} = \"ignore\" | \"info\" | \"warn\" | \"error\"` \
+         in the `[errors]` table of your Pyrefly configuration, \
+         or pass `--error`, `--warn`, `--info` or `--ignore` with `{name}` on the command line.\n\
+         To suppress a single error, add `# pyrefly: ignore[{name}]` to the line it is reported on.\n\
+         Online documentation: {}\n",
+        kind.docs_url()
+    ))
+}
+
+/// Remove the MDX-only markup of the website docs, i.e. `{/* ... */}` comments, JSX elements
+/// and `:::` admonition fences, so they read as plain Markdown in a terminal. Code blocks are
+/// left untouched.
+fn strip_mdx(docs: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    let mut in_comment = false;
+    for line in docs.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if in_comment || trimmed.starts_with("{/*") {
+                in_comment = !trimmed.ends_with("*/}");
+                continue;
+            }
+            let is_jsx = trimmed
+                .strip_prefix('<')
+                .map(|tag| tag.strip_prefix('/').unwrap_or(tag))
+                .is_some_and(|tag| tag.starts_with(|c: char| c.is_ascii_uppercase()));
+            if is_jsx || trimmed.starts_with(":::") {
+                continue;
+            }
+        }
+        // Don't leave a run of blank lines where markup was removed.
+        if !in_code_block
+            && trimmed.is_empty()
+            && lines
+                .last()
+                .is_none_or(|last: &&str| last.trim().is_empty())
+        {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_owned()
 }
 
 impl Error {
@@ -194,7 +289,29 @@ impl Error {
         }
     }
 
-    fn get_source_snippet<'a>(&'a self, origin: &'a str) -> Message<'a> {
+    /// The display path of every secondary annotation that points into another module,
+    /// indexed like `secondary_annotations`. Same-file annotations get an empty string.
+    fn related_origins(&self, project_root: &Path) -> Vec<String> {
+        self.secondary_annotations
+            .iter()
+            .map(|ann| match &ann.module {
+                Some(module) => {
+                    let path = module.path().as_path();
+                    path.strip_prefix(project_root)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .into_owned()
+                }
+                None => String::new(),
+            })
+            .collect()
+    }
+
+    fn get_source_snippet<'a>(
+        &'a self,
+        origin: &'a str,
+        related_origins: &'a [String],
+    ) -> Message<'a> {
         // Maximum number of lines to show in a single snippet. Annotations further apart
         // than this are shown as separate snippets rather than dumping all lines in between.
         // The primary span is also capped to this many lines for very large multi-line spans.
@@ -212,7 +329,13 @@ impl Error {
         );
         let mut nearby_annotations = Vec::new();
         let mut distant_annotations = Vec::new();
-        for ann in &self.secondary_annotations {
+        let mut related_annotations = Vec::new();
+        for (ann, related_origin) in self.secondary_annotations.iter().zip(related_origins) {
+            if let Some(module) = &ann.module {
+                // Annotations in other files always get their own snippet.
+                related_annotations.push((ann, module, related_origin.as_str()));
+                continue;
+            }
             let ann_display = self.module.display_range(ann.range);
             let ann_start = ann_display.start.line_within_file();
             let ann_end = ann_display.end.line_within_file();
@@ -241,7 +364,8 @@ impl Error {
         };
 
         // Primary snippet with nearby annotations inline.
-        let primary_snippet = self.make_snippet(
+        let primary_snippet = Self::make_snippet(
+            &self.module,
             origin,
             start_line,
             end_line,
@@ -254,7 +378,8 @@ impl Error {
         for (ann, ann_display) in &distant_annotations {
             let ann_start_line = ann_display.start.line_within_file();
             let ann_end_line = ann_display.end.line_within_file();
-            message = message.snippet(self.make_snippet(
+            message = message.snippet(Self::make_snippet(
+                &self.module,
                 origin,
                 ann_start_line,
                 ann_end_line,
@@ -262,13 +387,25 @@ impl Error {
                 &[ann],
             ));
         }
+        for (ann, module, related_origin) in related_annotations {
+            let ann_display = module.display_range(ann.range);
+            message = message.snippet(Self::make_snippet(
+                module,
+                related_origin,
+                ann_display.start.line_within_file(),
+                ann_display.end.line_within_file(),
+                None,
+                &[ann],
+            ));
+        }
         message
     }
 
-    /// Build a source snippet for a line range with an optional primary annotation and
-    /// secondary annotations. Used for both the main error snippet and distant annotation snippets.
+    /// Build a source snippet for a line range of `module` with an optional primary annotation
+    /// and secondary annotations. Used for the main error snippet, distant annotation snippets
+    /// and annotations in other files.
     fn make_snippet<'a>(
-        &'a self,
+        module: &'a Module,
         origin: &'a str,
         from_line: LineNumber,
         to_line: LineNumber,
@@ -276,13 +413,11 @@ impl Error {
         annotations: &[&'a SecondaryAnnotation],
     ) -> Snippet<'a> {
         // Warning: The SourceRange is char indexed, while the snippet is byte indexed.
-        let source = self
-            .module
+        let source = module
             .lined_buffer()
             .content_in_line_range(from_line, to_line);
-        let line_start = self.module.lined_buffer().line_start(from_line);
-        let cell_line = self
-            .module
+        let line_start = module.lined_buffer().line_start(from_line);
+        let cell_line = module
             .display_range(TextRange::new(line_start, line_start))
             .start
             .line_within_cell()
@@ -317,14 +452,27 @@ impl Error {
         self.severity
     }
 
-    /// Create a diagnostic suitable for use in LSP.
-    pub fn to_diagnostic(&self) -> Diagnostic {
+    /// Create a diagnostic suitable for use in LSP. Secondary annotations become related
+    /// information, using `to_location` to turn a range of a module into an LSP location;
+    /// annotations it returns `None` for are dropped.
+    pub fn to_diagnostic(
+        &self,
+        to_location: impl Fn(&Module, TextRange) -> Option<Location>,
+    ) -> Diagnostic {
         let code = self.error_kind().to_name().to_owned();
         let code_description = Url::parse(&self.error_kind().docs_url())
             .ok()
             .map(|href| CodeDescription { href });
-        // TODO: Map secondary_annotations to DiagnosticRelatedInformation for LSP clients.
-        // This requires constructing a Url from the module path, which may not always succeed.
+        let related_information = self
+            .secondary_annotations
+            .iter()
+            .filter_map(|ann| {
+                Some(DiagnosticRelatedInformation {
+                    location: to_location(self.annotation_module(ann), ann.range)?,
+                    message: ann.label.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
         Diagnostic {
             range: self.module.to_lsp_range(self.range()),
             severity: Some(match self.severity() {
@@ -343,6 +491,11 @@ impl Error {
             } else {
                 None
             },
+            related_information: if related_information.is_empty() {
+                None
+            } else {
+                Some(related_information)
+            },
             ..Default::default()
         }
     }
@@ -437,6 +590,29 @@ impl Error {
         self.secondary_annotations.push(SecondaryAnnotation {
             range,
             label: label.into_boxed_str(),
+            module: None,
+        });
+        self
+    }
+
+    /// Add a secondary labeled annotation pointing at a range of `module`, which may be
+    /// a different module than the one the error is reported in, e.g. the definition of
+    /// an overridden method in a base class.
+    pub fn with_related_annotation(
+        mut self,
+        module: Module,
+        range: TextRange,
+        label: String,
+    ) -> Self {
+        let module = if module == self.module {
+            None
+        } else {
+            Some(module)
+        };
+        self.secondary_annotations.push(SecondaryAnnotation {
+            range,
+            label: label.into_boxed_str(),
+            module,
         });
         self
     }
@@ -497,6 +673,11 @@ impl Error {
         &self.secondary_annotations
     }
 
+    /// Return the module a secondary annotation of this error points into.
+    pub fn annotation_module<'a>(&'a self, annotation: &'a SecondaryAnnotation) -> &'a Module {
+        annotation.module.as_ref().unwrap_or(&self.module)
+    }
+
     pub fn quick_fixes(&self) -> &[ErrorQuickFix] {
         &self.quick_fixes
     }
@@ -508,6 +689,7 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::Arc;
 
+    use dupe::Dupe;
     use pyrefly_python::module_name::ModuleName;
     use ruff_text_size::TextSize;
 
@@ -523,6 +705,42 @@ mod tests {
         str::from_utf8(&output).unwrap().to_owned()
     }
 
+    #[test]
+    fn test_strip_mdx() {
+        let docs = r#"Some text.
+
+{/* A comment
+spanning lines */}
+
+:::note
+A note with `code`.
+:::
+
+<Tabs>
+<TabItem value="a">
+
+```python
+x: list[int] = []  # <Kept> inside code
+```
+
+</TabItem>
+</Tabs>
+
+More text with a <b>tag</b>."#;
+        assert_eq!(
+            strip_mdx(docs),
+            r#"Some text.
+
+A note with `code`.
+
+```python
+x: list[int] = []  # <Kept> inside code
+```
+
+More text with a <b>tag</b>."#
+        );
+    }
+
     #[test]
     fn test_multiline_header_is_flattened_only_in_concise_output() {
         let module_info = Module::new(
@@ -660,6 +878,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_with_related_annotation_in_other_file() {
+        let base = Module::new(
+            ModuleName::from_str("base"),
+            ModulePath::filesystem(PathBuf::from("base.py")),
+            Arc::new("class A:\n    def f(self) -> int: ...".to_owned()),
+        );
+        let main = Module::new(
+            ModuleName::from_str("main"),
+            ModulePath::filesystem(PathBuf::from("main.py")),
+            Arc::new("def f(self) -> str: ...".to_owned()),
+        );
+        let error = Error::new(
+            main.dupe(),
+            TextRange::new(TextSize::new(4), TextSize::new(5)),
+            "bad override".to_owned(),
+            Vec::new(),
+            ErrorKind::BadOverride,
+        )
+        .with_related_annotation(
+            base.dupe(),
+            TextRange::new(TextSize::new(17), TextSize::new(18)),
+            "`A.f` is declared here".to_owned(),
+        )
+        .with_related_annotation(
+            main,
+            TextRange::new(TextSize::new(15), TextSize::new(18)),
+            "same file".to_owned(),
+        );
+        let annotations = error.secondary_annotations();
+        assert_eq!(annotations[0].module.as_ref(), Some(&base));
+        // An annotation in the error's own module is stored as a same-file annotation.
+        assert_eq!(annotations[1].module, None);
+
+        let output = render_error(&error, Path::new(""), true);
+        // The annotation in the other file gets its own snippet, under that file's path.
+        assert!(output.contains("main.py:1:5"), "{output}");
+        assert!(output.contains("base.py:2:9"), "{output}");
+        assert!(
+            output.contains("2 |     def f(self) -> int: ..."),
+            "{output}"
+        );
+        assert!(output.contains("`A.f` is declared here"), "{output}");
+
+        let diagnostic = error.to_diagnostic(|module, range| {
+            Some(Location {
+                uri: Url::parse(&format!("file:///{}", module.path())).unwrap(),
+                range: module.to_lsp_range(range),
+            })
+        });
+        let related = diagnostic.related_information.unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].location.uri.as_str(), "file:///base.py");
+        assert_eq!(related[0].location.range.start.line, 1);
+        assert_eq!(related[0].message, "`A.f` is declared here");
+        assert_eq!(related[1].location.uri.as_str(), "file:///main.py");
+    }
+
+    /// Integration test: verify that an override error points at the parent method, even when
+    /// the parent class is defined in another module.
+    #[test]
+    fn test_bad_override_points_at_parent_in_other_module() {
+        let mut env = TestEnv::new();
+        env.add("base", "class A:\n    def f(self) -> int: ...\n");
+        env.add(
+            "main",
+            "from base import A\nclass B(A):\n    def f(self) -> str: ...\n",
+        );
+        let (state, handle) = env.to_state();
+        let errors = state
+            .transaction()
+            .get_errors(&[handle("main")])
+            .collect_errors()
+            .ordinary;
+        let err = errors
+            .iter()
+            .find(|e| e.error_kind() == ErrorKind::BadOverride)
+            .unwrap();
+        let annotations = err.secondary_annotations();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(&*annotations[0].label, "`A.f` is declared here");
+        let module = err.annotation_module(&annotations[0]);
+        assert_eq!(module.name(), ModuleName::from_str("base"));
+        assert_eq!(module.code_at(annotations[0].range), "f");
+    }
+
+    /// Integration test: verify that a call matching no overload points at every overload with
+    /// the reason it was rejected.
+    #[test]
+    fn test_no_matching_overload_points_at_each_overload() {
+        let code = r#"
+from typing import overload
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: str) -> str: ...
+@overload
+def f(x: bytes, y: bytes) -> bytes: ...
+def f(x, y=None): return x
+f(1.0)
+"#;
+        let (state, handle) = TestEnv::one("main", code).to_state();
+        let errors = state
+            .transaction()
+            .get_errors(&[handle("main")])
+            .collect_errors()
+            .ordinary;
+        assert_eq!(errors.len(), 1);
+        let annotations = errors[0].secondary_annotations();
+        assert_eq!(annotations.len(), 3);
+        assert_eq!(
+            &*annotations[0].label,
+            "Overload 1 [closest match] does not match: Argument `x`: expected `int`, got `float`"
+        );
+        assert_eq!(
+            &*annotations[1].label,
+            "Overload 2 does not match: Argument `x`: expected `str`, got `float`"
+        );
+        assert_eq!(
+            &*annotations[2].label,
+            "Overload 3 does not match: Expected at least 2 arguments, got 1"
+        );
+        assert_eq!(
+            errors[0]
+                .annotation_module(&annotations[1])
+                .code_at(annotations[1].range),
+            "f"
+        );
+    }
+
     /// Integration test: verify that binary operator errors from the type checker
     /// produce secondary annotations labeling both operands with their types.
     #[test]
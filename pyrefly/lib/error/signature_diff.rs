@@ -476,6 +476,35 @@ class B(A):
         assert_eq!(messages[0], expected);
     }
 
+    /// A call matching no overload compares the closest overload with the argument types.
+    #[test]
+    fn test_signature_diff_no_matching_overload() {
+        let messages = error_messages(
+            r#"
+from typing import overload
+
+@overload
+def f(x: int, y: str) -> int: ...
+@overload
+def f(x: str, y: int) -> str: ...
+def f(x, y): return x
+
+f(1.0, "a")
+"#,
+        );
+        assert_eq!(messages.len(), 1, "Expected one error, got {messages:?}");
+        assert!(
+            messages[0].contains("\n  Signature mismatch:\n  expected: (x: int, y: str) -> int\n"),
+            "{}",
+            messages[0]
+        );
+        assert!(
+            messages[0].contains("\n  found:    (x: float, y: str) -> int\n"),
+            "{}",
+            messages[0]
+        );
+    }
+
     /// Callable-style signatures (no `def`, no `: ...` suffix) where the
     /// shorter return type is a prefix of the longer one. Previously panicked
     /// because `diff_ranges` produced a span past the end of the source string.
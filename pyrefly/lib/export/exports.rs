@@ -14,6 +14,7 @@ use pyrefly_graph::calculation::Calculation;
 use pyrefly_python::docstring::Docstring;
 use pyrefly_python::dunder;
 use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
 use pyrefly_python::symbol_kind::SymbolKind;
 use pyrefly_python::sys_info::SysInfo;
 use pyrefly_types::function::Deprecation;
@@ -43,6 +44,10 @@ pub trait LookupExport {
     /// `module_exists_and_record_export_dependency` instead.
     fn module_exists(&self, module: ModuleName) -> FindingOrError<()>;
 
+    /// Find the path `module` resolves to, without loading it. Returns `None` if the import
+    /// can't be resolved.
+    fn module_path(&self, module: ModuleName) -> Option<ModulePath>;
+
     /// Get the wildcard exports for a module. Records a dependency on `module` regardless of if it exists.
     fn get_wildcard(&self, module: ModuleName) -> Option<Arc<SmallSet<Name>>>;
 
@@ -520,6 +525,10 @@ mod tests {
             }
         }
 
+        fn module_path(&self, _module: ModuleName) -> Option<ModulePath> {
+            None
+        }
+
         fn get_deprecated(&self, _module: ModuleName, _name: &Name) -> Option<Deprecation> {
             None
         }
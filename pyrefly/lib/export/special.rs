@@ -86,6 +86,7 @@ pub enum SpecialExport {
     AttrsLegacyAttrib,
     AttrsNextGenField,
     AttrsNothing,
+    ArgparseArgumentParser,
 }
 
 impl SpecialExport {
@@ -163,6 +164,7 @@ impl SpecialExport {
             "attr" | "attrib" | "ib" => Some(Self::AttrsLegacyAttrib),
             "field" => Some(Self::AttrsNextGenField),
             "NOTHING" => Some(Self::AttrsNothing),
+            "ArgumentParser" => Some(Self::ArgparseArgumentParser),
             _ => None,
         }
     }
@@ -252,6 +254,7 @@ impl SpecialExport {
             Self::AttrsLegacyAttrib | Self::AttrsNextGenField | Self::AttrsNothing => {
                 matches!(m.as_str(), "attr" | "attrs")
             }
+            Self::ArgparseArgumentParser => matches!(m.as_str(), "argparse"),
         }
     }
 
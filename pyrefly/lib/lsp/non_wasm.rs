@@ -7,6 +7,7 @@
 
 mod build_system;
 pub mod call_hierarchy;
+pub mod change_signature;
 pub mod code_lens;
 pub mod connection;
 pub mod convert_module_package;
@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The Change Signature refactor. The code action only carries a command with the current
+//! parameter list; the client lets the user edit that list and sends it back through
+//! `workspace/executeCommand`, and the server replies by asking the client to apply the
+//! resulting edit with `workspace/applyEdit`.
+
+use std::collections::HashMap;
+
+use lsp_types::ClientCapabilities;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionKind;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Command;
+use lsp_types::Position;
+use lsp_types::TextDocumentIdentifier;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use pyrefly_build::handle::Handle;
+use ruff_text_size::TextSize;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::lsp::non_wasm::module_helpers::PathRemapper;
+use crate::lsp::non_wasm::module_helpers::module_info_to_uri;
+use crate::state::lsp::ChangeSignatureParameter;
+use crate::state::state::Transaction;
+
+pub const CHANGE_SIGNATURE_COMMAND: &str = "pyrefly.changeSignature";
+
+/// The single argument of the `pyrefly.changeSignature` command. The code action sends the
+/// current parameters; the client sends back the parameters the user asked for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSignatureArguments {
+    pub text_document: TextDocumentIdentifier,
+    /// A position in the header of the function to change.
+    pub position: Position,
+    pub parameters: Vec<ChangeSignatureParameter>,
+}
+
+fn supports_apply_edit(capabilities: &ClientCapabilities) -> bool {
+    capabilities
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.apply_edit)
+        .unwrap_or(false)
+}
+
+pub(crate) fn change_signature_code_action(
+    capabilities: &ClientCapabilities,
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    uri: &Url,
+    lsp_position: Position,
+    position: TextSize,
+) -> Option<CodeActionOrCommand> {
+    if !supports_apply_edit(capabilities) {
+        return None;
+    }
+    let target = transaction.change_signature_target(handle, position)?;
+    let arguments = ChangeSignatureArguments {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        position: lsp_position,
+        parameters: target.parameters,
+    };
+    let title = format!("Change signature of `{}`", target.name);
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.clone(),
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        command: Some(Command {
+            title,
+            command: CHANGE_SIGNATURE_COMMAND.to_owned(),
+            arguments: Some(vec![serde_json::to_value(arguments).ok()?]),
+        }),
+        ..Default::default()
+    }))
+}
+
+/// Computes the workspace edit for a `pyrefly.changeSignature` command, or the reason the
+/// signature can't be changed safely.
+pub(crate) fn change_signature_workspace_edit(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    position: TextSize,
+    parameters: &[ChangeSignatureParameter],
+    path_remapper: Option<&PathRemapper>,
+) -> Result<WorkspaceEdit, String> {
+    let edits = transaction.change_signature_edits(handle, position, parameters)?;
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for (module, range, new_text) in edits {
+        let Some(edit_uri) = module_info_to_uri(&module, path_remapper) else {
+            continue;
+        };
+        changes.entry(edit_uri).or_default().push(TextEdit {
+            range: module.to_lsp_range(range),
+            new_text,
+        });
+    }
+    Ok(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
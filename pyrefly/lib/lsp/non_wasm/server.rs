@@ -31,6 +31,7 @@ use itertools::Itertools;
 use lsp_server::ErrorCode;
 use lsp_server::RequestId;
 use lsp_server::ResponseError;
+use lsp_types::ApplyWorkspaceEditParams;
 use lsp_types::CallHierarchyServerCapability;
 use lsp_types::CodeAction;
 use lsp_types::CodeActionKind;
@@ -69,6 +70,8 @@ use lsp_types::DocumentHighlightKind;
 use lsp_types::DocumentHighlightParams;
 use lsp_types::DocumentSymbolParams;
 use lsp_types::DocumentSymbolResponse;
+use lsp_types::ExecuteCommandOptions;
+use lsp_types::ExecuteCommandParams;
 use lsp_types::FileEvent;
 use lsp_types::FileSystemWatcher;
 use lsp_types::FoldingRange;
@@ -170,6 +173,7 @@ use lsp_types::notification::Initialized;
 use lsp_types::notification::Notification as _;
 use lsp_types::notification::Progress;
 use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::ApplyWorkspaceEdit;
 use lsp_types::request::CallHierarchyIncomingCalls;
 use lsp_types::request::CallHierarchyOutgoingCalls;
 use lsp_types::request::CallHierarchyPrepare;
@@ -179,6 +183,7 @@ use lsp_types::request::Completion;
 use lsp_types::request::DocumentDiagnosticRequest;
 use lsp_types::request::DocumentHighlightRequest;
 use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::ExecuteCommand;
 use lsp_types::request::FoldingRangeRequest;
 use lsp_types::request::GotoDeclaration;
 use lsp_types::request::GotoDefinition;
@@ -284,6 +289,10 @@ use crate::lsp::non_wasm::call_hierarchy::find_function_at_position_in_ast;
 use crate::lsp::non_wasm::call_hierarchy::prepare_call_hierarchy_item;
 use crate::lsp::non_wasm::call_hierarchy::transform_incoming_calls;
 use crate::lsp::non_wasm::call_hierarchy::transform_outgoing_calls;
+use crate::lsp::non_wasm::change_signature::CHANGE_SIGNATURE_COMMAND;
+use crate::lsp::non_wasm::change_signature::ChangeSignatureArguments;
+use crate::lsp::non_wasm::change_signature::change_signature_code_action;
+use crate::lsp::non_wasm::change_signature::change_signature_workspace_edit;
 use crate::lsp::non_wasm::code_lens::runnable_lsp_code_lens;
 use crate::lsp::non_wasm::convert_module_package::convert_module_package_code_actions;
 use crate::lsp::non_wasm::document_symbols::flatten_to_symbol_information;
@@ -343,6 +352,8 @@ use crate::lsp::wasm::provide_type::ProvideType;
 use crate::lsp::wasm::provide_type::ProvideTypeParams;
 use crate::lsp::wasm::provide_type::ProvideTypeResponse;
 use crate::lsp::wasm::provide_type::provide_type;
+use crate::lsp::wasm::type_derivation::TYPE_DERIVATION_COMMAND;
+use crate::lsp::wasm::type_derivation::type_derivation;
 use crate::module::bundled::BundledStub;
 use crate::state::load::Load;
 use crate::state::load::LspFile;
@@ -802,9 +813,11 @@ mod tests {
     use serde_json::json;
 
     use super::SOURCE_FIX_ALL_PYREFLY;
+    use super::SOURCE_ORGANIZE_IMPORTS_PYREFLY;
     use super::client_uses_custom_hover_provider;
     use super::format_diagnostic_message_for_markdown;
     use super::matches_fix_all_kind;
+    use super::matches_organize_imports_kind;
 
     #[test]
     fn test_format_diagnostic_message_for_markdown() {
@@ -866,6 +879,21 @@ mod tests {
         assert!(!matches_fix_all_kind(&CodeActionKind::REFACTOR_EXTRACT));
     }
 
+    #[test]
+    fn test_organize_imports_kind_filter() {
+        assert!(matches_organize_imports_kind(&CodeActionKind::SOURCE));
+        assert!(matches_organize_imports_kind(
+            &CodeActionKind::SOURCE_ORGANIZE_IMPORTS
+        ));
+        assert!(matches_organize_imports_kind(&CodeActionKind::new(
+            SOURCE_ORGANIZE_IMPORTS_PYREFLY,
+        )));
+        assert!(!matches_organize_imports_kind(
+            &CodeActionKind::SOURCE_FIX_ALL
+        ));
+        assert!(!matches_organize_imports_kind(&CodeActionKind::QUICKFIX));
+    }
+
     #[test]
     fn test_custom_hover_provider_requires_explicit_opt_in() {
         let mut params = InitializeParams::default();
@@ -1356,12 +1384,21 @@ pub fn capabilities(
                 CodeActionKind::REFACTOR_INLINE,
                 CodeActionKind::SOURCE_FIX_ALL,
                 CodeActionKind::new(SOURCE_FIX_ALL_PYREFLY),
+                CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                CodeActionKind::new(SOURCE_ORGANIZE_IMPORTS_PYREFLY),
             ]),
             ..Default::default()
         })),
         code_lens_provider: Some(CodeLensOptions {
             resolve_provider: Some(false),
         }),
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![
+                CHANGE_SIGNATURE_COMMAND.to_owned(),
+                TYPE_DERIVATION_COMMAND.to_owned(),
+            ],
+            ..Default::default()
+        }),
         completion_provider: Some(CompletionOptions {
             trigger_characters: Some(vec![".".to_owned(), "'".to_owned(), "\"".to_owned()]),
             resolve_provider: Some(true),
@@ -1481,6 +1518,14 @@ fn matches_fix_all_kind(kind: &CodeActionKind) -> bool {
     kind == &CodeActionKind::SOURCE_FIX_ALL || kind.as_str() == SOURCE_FIX_ALL_PYREFLY
 }
 
+const SOURCE_ORGANIZE_IMPORTS_PYREFLY: &str = "source.organizeImports.pyrefly";
+
+fn matches_organize_imports_kind(kind: &CodeActionKind) -> bool {
+    kind == &CodeActionKind::SOURCE
+        || kind == &CodeActionKind::SOURCE_ORGANIZE_IMPORTS
+        || kind.as_str() == SOURCE_ORGANIZE_IMPORTS_PYREFLY
+}
+
 struct TypeHierarchyTarget {
     def_index: ClassDefIndex,
     module_path: ModulePath,
@@ -2481,6 +2526,20 @@ impl Server {
                             Ok(self.provide_type(&mut transaction, params)),
                         ));
                     }
+                } else if let Some(params) = as_request::<ExecuteCommand>(&x) {
+                    if let Some(params) = self
+                        .extract_request_params_or_send_err_response::<ExecuteCommand>(
+                            params, &x.id,
+                        )
+                    {
+                        let response = match self.execute_command(&transaction, params) {
+                            Ok(result) => new_response(x.id, Ok(result)),
+                            Err(message) => {
+                                Response::new_err(x.id, ErrorCode::InvalidRequest as i32, message)
+                            }
+                        };
+                        self.send_response(response);
+                    }
                 } else if let Some(params) = as_request::<WillRenameFiles>(&x) {
                     if let Some(params) = self
                         .extract_request_params_or_send_err_response::<WillRenameFiles>(
@@ -2934,6 +2993,18 @@ impl Server {
             .collect()
     }
 
+    /// The location of a secondary annotation of a diagnostic. Ranges inside notebooks are
+    /// skipped, since they would need to be attributed to a cell.
+    fn related_location(&self, module: &ModuleInfo, range: TextRange) -> Option<Location> {
+        if module.is_notebook() {
+            return None;
+        }
+        Some(Location {
+            uri: module_info_to_uri(module, self.path_remapper.as_ref())?,
+            range: module.to_lsp_range(range),
+        })
+    }
+
     fn get_diag_if_shown(
         &self,
         e: &Error,
@@ -2976,10 +3047,18 @@ impl Server {
                         {
                             None
                         } else {
-                            Some((PathBuf::from(error_cell_uri.to_string()), e.to_diagnostic()))
+                            Some((
+                                PathBuf::from(error_cell_uri.to_string()),
+                                e.to_diagnostic(|module, range| {
+                                    self.related_location(module, range)
+                                }),
+                            ))
                         }
                     }
-                    LspFile::Source(_) => Some((path.to_path_buf(), e.to_diagnostic())),
+                    LspFile::Source(_) => Some((
+                        path.to_path_buf(),
+                        e.to_diagnostic(|module, range| self.related_location(module, range)),
+                    )),
                 };
             }
 
@@ -2994,7 +3073,10 @@ impl Server {
                 && !config.project_excludes.covers(&path)
                 && type_error_status.is_enabled()
             {
-                return Some((path.to_path_buf(), e.to_diagnostic()));
+                return Some((
+                    path.to_path_buf(),
+                    e.to_diagnostic(|module, range| self.related_location(module, range)),
+                ));
             }
         }
         None
@@ -4583,6 +4665,11 @@ impl Server {
         let allow_quickfix = only_kinds
             .is_none_or(|kinds| kinds.iter().any(|kind| kind == &CodeActionKind::QUICKFIX));
         let allow_fix_all = only_kinds.is_none_or(|kinds| kinds.iter().any(matches_fix_all_kind));
+        // Organizing imports rewrites the whole import block, so only offer it when the
+        // client asks for it (e.g. the "Organize Imports" command or `codeActionsOnSave`)
+        // rather than in every lightbulb menu.
+        let allow_organize_imports =
+            only_kinds.is_some_and(|kinds| kinds.iter().any(matches_organize_imports_kind));
         let allow_refactor = only_kinds.is_none_or(|kinds| {
             kinds
                 .iter()
@@ -4698,6 +4785,36 @@ impl Server {
             }
             record_code_action_telemetry("fix_all", start);
         }
+        if allow_organize_imports {
+            let start = Instant::now();
+            if let Some(edits) = transaction.organize_imports_edits(&handle) {
+                let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+                for (module, edit_range, new_text) in edits {
+                    let Some(lsp_location) = self.to_lsp_location(&TextRangeWithModule {
+                        module,
+                        range: edit_range,
+                    }) else {
+                        continue;
+                    };
+                    changes.entry(lsp_location.uri).or_default().push(TextEdit {
+                        range: lsp_location.range,
+                        new_text,
+                    });
+                }
+                if !changes.is_empty() {
+                    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                        title: "Organize imports".to_owned(),
+                        kind: Some(CodeActionKind::new(SOURCE_ORGANIZE_IMPORTS_PYREFLY)),
+                        edit: Some(WorkspaceEdit {
+                            changes: Some(changes),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }));
+                }
+            }
+            record_code_action_telemetry("organize_imports", start);
+        }
         // Optimization: do not calculate refactors for automated codeactions since they're expensive
         // If we had lazy code actions, we could keep them.
         if let Some(trigger_kind) = params.context.trigger_kind
@@ -4808,6 +4925,10 @@ impl Server {
                 "convert_dict",
                 transaction.convert_dict_code_actions(&handle, range)
             );
+            timed_refactor_action!(
+                "add_missing_isinstance_branches",
+                transaction.add_missing_isinstance_branches_code_actions(&handle, range)
+            );
             timed_refactor_action!(
                 "pytest_fixture_type_annotation",
                 transaction.pytest_fixture_type_annotation_code_actions(
@@ -4836,6 +4957,18 @@ impl Server {
                 actions.push(action);
             }
             record_code_action_telemetry("move_symbol_new_file", start);
+            let start = Instant::now();
+            if let Some(action) = change_signature_code_action(
+                &self.initialize_params.capabilities,
+                transaction,
+                &handle,
+                uri,
+                params.range.start,
+                range.start(),
+            ) {
+                actions.push(action);
+            }
+            record_code_action_telemetry("change_signature", start);
         }
         let start = Instant::now();
         if let Some(action) = safe_delete_file_code_action(
@@ -4850,6 +4983,72 @@ impl Server {
         Ok((!actions.is_empty()).then_some(actions))
     }
 
+    /// Runs a `workspace/executeCommand` request. Change Signature's edit is sent back to the
+    /// client as a `workspace/applyEdit` request; errors explain why the signature can't be
+    /// changed. The type derivation command returns the rendered derivation.
+    fn execute_command(
+        &self,
+        transaction: &Transaction<'_>,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<Value>, String> {
+        if params.command == TYPE_DERIVATION_COMMAND {
+            return self.type_derivation_command(transaction, params);
+        }
+        if params.command != CHANGE_SIGNATURE_COMMAND {
+            return Err(format!("Unknown command: {}", params.command));
+        }
+        let arguments: ChangeSignatureArguments = params
+            .arguments
+            .into_iter()
+            .next()
+            .and_then(|arguments| serde_json::from_value(arguments).ok())
+            .ok_or_else(|| format!("Invalid arguments for `{CHANGE_SIGNATURE_COMMAND}`"))?;
+        let uri = &arguments.text_document.uri;
+        let handle = self
+            .make_handle_if_enabled(uri, Some(ExecuteCommand::METHOD))
+            .map_err(|_| format!("Language services are disabled for {uri}"))?;
+        let module_info = transaction
+            .get_module_info(&handle)
+            .ok_or_else(|| format!("{uri} is not loaded"))?;
+        let position = self.from_lsp_position(uri, &module_info, arguments.position);
+        let edit = change_signature_workspace_edit(
+            transaction,
+            &handle,
+            position,
+            &arguments.parameters,
+            self.path_remapper.as_ref(),
+        )?;
+        self.send_request::<ApplyWorkspaceEdit>(ApplyWorkspaceEditParams {
+            label: Some("Change signature".to_owned()),
+            edit,
+        });
+        Ok(None)
+    }
+
+    fn type_derivation_command(
+        &self,
+        transaction: &Transaction<'_>,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<Value>, String> {
+        let arguments: TextDocumentPositionParams = params
+            .arguments
+            .into_iter()
+            .next()
+            .and_then(|arguments| serde_json::from_value(arguments).ok())
+            .ok_or_else(|| format!("Invalid arguments for `{TYPE_DERIVATION_COMMAND}`"))?;
+        let uri = &arguments.text_document.uri;
+        let handle = self
+            .make_handle_if_enabled(uri, Some(ExecuteCommand::METHOD))
+            .map_err(|_| format!("Language services are disabled for {uri}"))?;
+        let module_info = transaction
+            .get_module_info(&handle)
+            .ok_or_else(|| format!("{uri} is not loaded"))?;
+        let position = self.from_lsp_position(uri, &module_info, arguments.position);
+        let derivation = type_derivation(transaction, &handle, position)
+            .ok_or_else(|| "There is no expression at this position".to_owned())?;
+        Ok(Some(Value::String(derivation.to_string())))
+    }
+
     fn document_highlight(
         &self,
         transaction: &Transaction<'_>,
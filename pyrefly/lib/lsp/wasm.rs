@@ -12,4 +12,5 @@ pub mod notebook;
 pub mod provide_type;
 pub mod semantic_tokens;
 pub mod signature_help;
+pub mod type_derivation;
 pub mod type_source;
@@ -12,6 +12,7 @@ use lsp_types::CompletionItem;
 use lsp_types::CompletionItemKind;
 use lsp_types::CompletionItemLabelDetails;
 use lsp_types::CompletionItemTag;
+use lsp_types::CompletionTextEdit;
 use lsp_types::InsertTextFormat;
 use lsp_types::TextEdit;
 use pyrefly_build::handle::Handle;
@@ -55,6 +56,7 @@ use crate::state::lsp::ImportFormat;
 use crate::state::lsp::MIN_CHARACTERS_TYPED_AUTOIMPORT;
 use crate::state::state::Transaction;
 use crate::types::callable::Param;
+use crate::types::class::Class;
 use crate::types::types::Type;
 
 /// Classification of a completion item's source, used for ranking.
@@ -87,6 +89,10 @@ impl RankedCompletion {
             is_incompatible: false,
         }
     }
+
+    pub(crate) fn label(&self) -> &str {
+        &self.item.label
+    }
 }
 
 /// All completion ranking logic lives here. Assigns `sort_text` to each item
@@ -201,6 +207,49 @@ fn push_kwarg_completion(
     }
 }
 
+/// Escapes `text` for a snippet, where `$`, `}` and `\` are special.
+fn escape_snippet(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '$' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// The loop variable for iterating over values of type `element`: the class name in snake
+/// case for a user-defined class (`http_request` for `HTTPRequest`), otherwise `item`.
+fn loop_variable_name(element: &Type) -> String {
+    let Type::ClassType(cls) = element else {
+        return "item".to_owned();
+    };
+    if cls.class_object().module_name() == ModuleName::builtins() {
+        return "item".to_owned();
+    }
+    let chars = cls.name().chars().collect::<Vec<_>>();
+    let mut name = String::with_capacity(chars.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1);
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next.is_some_and(|n| n.is_ascii_lowercase()))
+            {
+                name.push('_');
+            }
+        }
+        name.extend(c.to_lowercase());
+    }
+    if is_valid_identifier(&name) {
+        name
+    } else {
+        "item".to_owned()
+    }
+}
+
 impl Transaction<'_> {
     /// Adds a common alias auto-import completion (e.g. `np` -> `numpy`).
     /// Returns the module name that was aliased when a completion was added.
@@ -1036,6 +1085,219 @@ impl Transaction<'_> {
         });
     }
 
+    /// Adds postfix completions for `expr.|`, which rewrite the attribute expression into a
+    /// statement or expression built around `expr`: `expr.if` becomes `if expr:`, `expr.for`
+    /// loops over it, and so on. Each is only offered where the type of `expr` and the
+    /// position of the attribute make the result valid.
+    fn add_postfix_completions(
+        &self,
+        handle: &Handle,
+        covering_nodes: &[AnyNodeRef],
+        base_type: &Type,
+        attribute_range: TextRange,
+        identifier: &Identifier,
+        supports_snippets: bool,
+        completions: &mut Vec<RankedCompletion>,
+    ) {
+        // Classes, modules and `super()` are rarely used as values in their own right.
+        if matches!(
+            base_type,
+            Type::ClassDef(_) | Type::Module(_) | Type::SuperInstance(_)
+        ) {
+            return;
+        }
+        let Some(module_info) = self.get_module_info(handle) else {
+            return;
+        };
+        let source = module_info.contents().as_str();
+        let start = attribute_range.start().to_usize();
+        let Some(receiver) = source
+            .get(start..identifier.range.start().to_usize())
+            .and_then(|text| text.trim_end().strip_suffix('.'))
+            .map(str::trim_end)
+        else {
+            return;
+        };
+        if receiver.is_empty() || receiver.contains('\n') {
+            return;
+        }
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &source[line_start..start];
+        let is_statement = indent.chars().all(|c| c == ' ' || c == '\t')
+            && matches!(
+                covering_nodes.get(2),
+                Some(AnyNodeRef::StmtExpr(stmt)) if stmt.range() == attribute_range
+            );
+        // `return` and `await` need an enclosing function, and `await` an `async` one.
+        let enclosing_function = covering_nodes
+            .iter()
+            .find_map(|node| match node {
+                AnyNodeRef::StmtFunctionDef(function_def) => Some(Some(*function_def)),
+                AnyNodeRef::ExprLambda(_) | AnyNodeRef::StmtClassDef(_) => Some(None),
+                _ => None,
+            })
+            .flatten();
+        let exports = self.get_exports(handle);
+        let is_nameable = |cls: &Class| {
+            cls.module_name() == ModuleName::builtins() || exports.contains_key(cls.name())
+        };
+        let is_any = base_type.is_any();
+        let Some((element, is_awaitable, match_patterns)) =
+            self.ad_hoc_solve(handle, "completion_postfix", |solver| {
+                if is_any {
+                    return (None, false, None);
+                }
+                let members = match base_type {
+                    Type::Union(union) => union.members.iter().collect::<Vec<_>>(),
+                    ty => vec![ty],
+                };
+                let mut patterns = Vec::new();
+                let mut exhaustive = true;
+                let mut offers_match = matches!(base_type, Type::Union(_) | Type::Literal(_));
+                for member in members {
+                    let mut member_patterns = Vec::new();
+                    match member {
+                        Type::None => member_patterns.push("None".to_owned()),
+                        Type::Literal(lit) => match &lit.value {
+                            Lit::Enum(lit_enum) if is_nameable(lit_enum.class.class_object()) => {
+                                member_patterns.push(format!(
+                                    "{}.{}",
+                                    lit_enum.class.name(),
+                                    lit_enum.member
+                                ));
+                            }
+                            Lit::Enum(_) => exhaustive = false,
+                            value => member_patterns.push(value.to_string_escaped(false)),
+                        },
+                        Type::ClassType(cls) if cls.is_builtin("bool") => {
+                            offers_match = true;
+                            member_patterns.extend(["True".to_owned(), "False".to_owned()]);
+                        }
+                        Type::ClassType(cls)
+                            if solver.get_metadata_for_class(cls.class_object()).is_enum() =>
+                        {
+                            offers_match = true;
+                            if is_nameable(cls.class_object()) {
+                                for lit in solver.get_enum_members(cls.class_object()) {
+                                    if let Lit::Enum(lit_enum) = lit {
+                                        member_patterns.push(format!(
+                                            "{}.{}",
+                                            cls.name(),
+                                            lit_enum.member
+                                        ));
+                                    }
+                                }
+                            } else {
+                                exhaustive = false;
+                            }
+                        }
+                        Type::ClassType(cls) if is_nameable(cls.class_object()) => {
+                            member_patterns.push(format!("{}()", cls.name()));
+                        }
+                        _ => exhaustive = false,
+                    }
+                    for pattern in member_patterns {
+                        if !patterns.contains(&pattern) {
+                            patterns.push(pattern);
+                        }
+                    }
+                }
+                if !exhaustive {
+                    patterns.push("_".to_owned());
+                }
+                (
+                    solver.unwrap_iterable(base_type),
+                    solver.unwrap_awaitable(base_type).is_some(),
+                    (offers_match && patterns.len() > 1).then_some(patterns),
+                )
+            })
+        else {
+            return;
+        };
+        let escape = |text: &str| {
+            if supports_snippets {
+                escape_snippet(text)
+            } else {
+                text.to_owned()
+            }
+        };
+        let expr = escape(receiver);
+        let placeholder = if supports_snippets { "$0" } else { "pass" };
+        let body_indent = format!("{indent}    ");
+        let range = module_info.to_lsp_range(attribute_range);
+        let mut push = |keyword: &str, detail: String, new_text: String| {
+            completions.push(RankedCompletion::new(CompletionItem {
+                label: keyword.to_owned(),
+                detail: Some(detail),
+                kind: Some(CompletionItemKind::SNIPPET),
+                filter_text: Some(format!("{receiver}.{keyword}")),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit { range, new_text })),
+                insert_text_format: supports_snippets.then_some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            }));
+        };
+        if !base_type.is_none() {
+            if is_statement {
+                push(
+                    "if",
+                    format!("if {receiver}:"),
+                    format!("if {expr}:\n{body_indent}{placeholder}"),
+                );
+            }
+            push("not", format!("not {receiver}"), format!("not {expr}"));
+        }
+        if is_statement && let Some(element) = &element {
+            let variable = loop_variable_name(element);
+            push(
+                "for",
+                format!("for {variable} in {receiver}:"),
+                format!("for {variable} in {expr}:\n{body_indent}{placeholder}"),
+            );
+        }
+        if is_awaitable && enclosing_function.is_some_and(|function_def| function_def.is_async) {
+            push(
+                "await",
+                format!("await {receiver}"),
+                format!("await {expr}"),
+            );
+        }
+        if is_statement && enclosing_function.is_some() {
+            push(
+                "return",
+                format!("return {receiver}"),
+                format!("return {expr}"),
+            );
+        }
+        push("par", format!("({receiver})"), format!("({expr})"));
+        if let Type::Union(union) = base_type {
+            let mut classes = SmallSet::new();
+            for member in &union.members {
+                if let Type::ClassType(cls) = member
+                    && is_nameable(cls.class_object())
+                {
+                    classes.insert(cls.name().clone());
+                }
+            }
+            for cls in classes {
+                push(
+                    "isinstance",
+                    format!("isinstance({receiver}, {cls})"),
+                    format!("isinstance({expr}, {cls})"),
+                );
+            }
+        }
+        if is_statement && let Some(patterns) = match_patterns {
+            let mut text = format!("match {expr}:");
+            for pattern in &patterns {
+                text.push_str(&format!(
+                    "\n{body_indent}case {}:\n{body_indent}    pass",
+                    escape(pattern)
+                ));
+            }
+            push("match", format!("match {receiver}:"), text);
+        }
+    }
+
     /// Core completion implementation returning items and incomplete flag.
     pub(crate) fn completion_sorted_opt_with_incomplete<F>(
         &self,
@@ -1149,14 +1411,33 @@ impl Transaction<'_> {
                 }
             }
             Some(IdentifierWithContext {
-                identifier: _,
-                context: IdentifierContext::Attribute { base_range, .. },
+                identifier,
+                context:
+                    IdentifierContext::Attribute {
+                        base_range,
+                        range,
+                        expr_context,
+                        ..
+                    },
             }) => {
                 let expected_type = self.get_expected_type_at(handle, position);
                 allow_function_call_parens = true;
                 if let Some(answers) = self.get_answers(handle)
                     && let Some(base_type) = answers.get_type_trace(base_range)
                 {
+                    if matches!(expr_context, ExprContext::Load | ExprContext::Invalid)
+                        && let Some(covering_nodes) = covering_nodes.as_deref()
+                    {
+                        self.add_postfix_completions(
+                            handle,
+                            covering_nodes,
+                            &base_type,
+                            range,
+                            &identifier,
+                            supports_snippet_completions,
+                            &mut result,
+                        );
+                    }
                     self.add_attribute_completions_for_type(
                         handle,
                         base_type,
@@ -1215,6 +1496,16 @@ impl Transaction<'_> {
                             );
                         }
                     }
+                    if is_method_def
+                        && let [
+                            _,
+                            AnyNodeRef::StmtFunctionDef(function_def),
+                            AnyNodeRef::StmtClassDef(class_def),
+                            ..,
+                        ] = covering_nodes
+                    {
+                        self.add_override_completions(handle, class_def, function_def, &mut result);
+                    }
                 }
                 self.add_kwargs_completions(handle, position, &mut result);
                 // In `func(foo=1, ba|` the cursor can only be a keyword-argument
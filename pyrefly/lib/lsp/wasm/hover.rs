@@ -15,7 +15,15 @@ use lsp_types::HoverContents;
 use lsp_types::MarkupContent;
 use lsp_types::MarkupKind;
 use lsp_types::Range;
+#[cfg(not(target_arch = "wasm32"))]
+use lsp_types::TextDocumentIdentifier;
+#[cfg(not(target_arch = "wasm32"))]
+use lsp_types::TextDocumentPositionParams;
 use lsp_types::Url;
+#[cfg(not(target_arch = "wasm32"))]
+use percent_encoding::NON_ALPHANUMERIC;
+#[cfg(not(target_arch = "wasm32"))]
+use percent_encoding::utf8_percent_encode;
 use pyrefly_build::handle::Handle;
 use pyrefly_python::ast::Ast;
 use pyrefly_python::docstring::Docstring;
@@ -58,6 +66,8 @@ use crate::lsp::module_helpers::to_real_path;
 use crate::lsp::wasm::signature_help::CallInfo;
 use crate::lsp::wasm::signature_help::is_constructor_call;
 use crate::lsp::wasm::signature_help::override_constructor_return_type;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::lsp::wasm::type_derivation::SHOW_TYPE_DERIVATION_CLIENT_COMMAND;
 use crate::lsp::wasm::type_source::set_display_pos_fragment;
 use crate::lsp::wasm::type_source::type_sources_for_hover;
 use crate::state::lsp::DefinitionMetadata;
@@ -82,6 +92,8 @@ pub struct HoverValue {
     pub docstring: Option<Docstring>,
     pub parameter_doc: Option<(String, String)>,
     pub type_sources: Vec<String>,
+    /// The exceptions that may escape a call to the hovered function.
+    pub raises: Vec<String>,
     pub display: Option<String>,
     pub show_go_to_links: bool,
 }
@@ -131,6 +143,26 @@ impl HoverValue {
         None
     }
 
+    /// A link that runs the client command showing how the hovered type was derived.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn format_type_derivation_link(handle: &Handle, range: Option<Range>) -> Option<String> {
+        let uri = Url::from_file_path(handle.path().as_path()).ok()?;
+        let arguments = serde_json::to_string(&[TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: range?.start,
+        }])
+        .ok()?;
+        Some(format!(
+            "[Explain how this type was derived](command:{SHOW_TYPE_DERIVATION_CLIENT_COMMAND}?{})",
+            utf8_percent_encode(&arguments, NON_ALPHANUMERIC)
+        ))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn format_type_derivation_link(_handle: &Handle, _range: Option<Range>) -> Option<String> {
+        None
+    }
+
     fn resolve_symbol_kind(&self) -> Option<SymbolKind> {
         match self.kind {
             Some(SymbolKind::Attribute) => Some(attribute_symbol_kind_from_type(&self.type_)),
@@ -255,6 +287,20 @@ impl HoverValue {
                     let cleaned = doc.trim().replace('\n', "  \n");
                     format!("{prefix}**Parameter `{}`**\n{}", name, cleaned)
                 });
+        let raises_formatted = if self.raises.is_empty() {
+            String::new()
+        } else {
+            let prefix = if self.docstring.is_some() || self.parameter_doc.is_some() {
+                "\n\n---\n"
+            } else {
+                "\n---\n"
+            };
+            let mut section = format!("{prefix}**Raises**\n");
+            for raised in &self.raises {
+                section.push_str(&format!("- `{raised}`\n"));
+            }
+            section
+        };
         let kind_formatted = self
             .resolve_symbol_kind()
             .map(|kind| format!("{} ", kind.display_for_hover()))
@@ -285,6 +331,13 @@ impl HoverValue {
                 section.push_str(source);
                 section.push('\n');
             }
+            if self.show_go_to_links
+                && let Some(link) = Self::format_type_derivation_link(handle, self.range)
+            {
+                section.push('\n');
+                section.push_str(&link);
+                section.push('\n');
+            }
             section
         };
         let type_display = self.display.clone().unwrap_or_else(|| {
@@ -296,13 +349,14 @@ impl HoverValue {
             contents: HoverContents::Markup(MarkupContent {
                 kind: MarkupKind::Markdown,
                 value: format!(
-                    "```python\n{}{}{}\n```{}{}{}{}",
+                    "```python\n{}{}{}\n```{}{}{}{}{}",
                     kind_formatted,
                     name_formatted,
                     type_display,
                     type_source_formatted,
                     docstring_formatted,
                     parameter_doc_formatted,
+                    raises_formatted,
                     symbol_def_formatted
                 ),
             }),
@@ -1052,6 +1106,7 @@ pub fn get_hover_with_verbosity(
     };
 
     let parameter_doc = resolve_hover_parameter_doc(transaction, handle, position);
+    let raises = transaction.raised_exceptions(handle, &type_);
 
     Some(HoverResult {
         hover: HoverValue {
@@ -1062,6 +1117,7 @@ pub fn get_hover_with_verbosity(
             docstring,
             parameter_doc,
             type_sources: type_sources_for_hover(transaction, handle, position),
+            raises,
             display: type_display,
             show_go_to_links: options.show_go_to_links,
         }
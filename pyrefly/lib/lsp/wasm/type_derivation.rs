@@ -0,0 +1,407 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Explain why an expression has the type pyrefly inferred for it.
+//!
+//! The explanation is a tree: each node is one step of the derivation, and its children
+//! are the steps it was derived from. Names are followed through their flow bindings
+//! (declarations, assignments, narrowing and branch joins), and calls show the overload
+//! that was selected and how the callee's type variables were solved.
+
+use std::fmt;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use pyrefly_build::handle::Handle;
+use pyrefly_graph::index::Idx;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::short_identifier::ShortIdentifier;
+use pyrefly_types::types::Type;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprAttribute;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ExprContext;
+use ruff_python_ast::ExprName;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::small_set::SmallSet;
+
+use crate::alt::answers::Answers;
+use crate::binding::binding::Binding;
+use crate::binding::binding::FirstUse;
+use crate::binding::binding::FunctionParameter;
+use crate::binding::binding::Key;
+use crate::binding::binding::KeyAnnotation;
+use crate::binding::bindings::Bindings;
+use crate::lsp::wasm::type_source::format_code_snippet;
+use crate::state::state::Transaction;
+
+/// The `workspace/executeCommand` command that returns the rendered derivation of the type
+/// at a position. Its only argument is a `TextDocumentPositionParams`.
+pub const TYPE_DERIVATION_COMMAND: &str = "pyrefly.why";
+
+/// The client command linked from hovers, which runs [`TYPE_DERIVATION_COMMAND`] with the
+/// same argument and shows the result.
+pub const SHOW_TYPE_DERIVATION_CLIENT_COMMAND: &str = "pyrefly.showTypeDerivation";
+
+/// How many bindings to follow before giving up, so that long flow chains stay readable.
+const MAX_DEPTH: usize = 24;
+
+/// One step in the derivation of a type, along with the steps it was derived from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDerivation {
+    pub label: String,
+    pub children: Vec<TypeDerivation>,
+}
+
+impl TypeDerivation {
+    fn leaf(label: String) -> Self {
+        Self {
+            label,
+            children: Vec::new(),
+        }
+    }
+
+    fn fmt_children(&self, f: &mut fmt::Formatter<'_>, prefix: &str) -> fmt::Result {
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            let (branch, indent) = if last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            writeln!(f, "{prefix}{branch}{}", child.label)?;
+            child.fmt_children(f, &format!("{prefix}{indent}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TypeDerivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.label)?;
+        self.fmt_children(f, "")
+    }
+}
+
+/// Explain the type of the innermost expression at `position`, which must be in a module
+/// that has been solved with traces enabled (as it is for IDE requests).
+pub fn type_derivation(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    position: TextSize,
+) -> Option<TypeDerivation> {
+    let ast = transaction.get_ast(handle)?;
+    let node = Ast::locate_node(&ast, position)
+        .into_iter()
+        .find(|node| node.as_expr_ref().is_some())?;
+    let mut deriver = Deriver {
+        module: transaction.get_module_info(handle)?,
+        bindings: transaction.get_bindings(handle)?,
+        answers: transaction.get_answers(handle)?,
+        seen: SmallSet::new(),
+        depth: 0,
+    };
+    Some(deriver.explain_root(node))
+}
+
+struct Deriver {
+    module: Module,
+    bindings: Bindings,
+    answers: Arc<Answers>,
+    /// Bindings that already appear in the tree, so joins over the same definition
+    /// (and loops) are only expanded once.
+    seen: SmallSet<Idx<Key>>,
+    depth: usize,
+}
+
+impl Deriver {
+    fn explain_root(&mut self, node: AnyNodeRef) -> TypeDerivation {
+        let snippet = self.snippet(node.range());
+        if let AnyNodeRef::ExprName(name) = node
+            && let Some(idx) = self.name_idx(name)
+        {
+            let ty = self.type_at(idx);
+            return TypeDerivation {
+                label: format!("`{snippet}` has type `{ty}`"),
+                children: vec![self.explain_idx(idx)],
+            };
+        }
+        let ty = self.trace(node.range());
+        TypeDerivation {
+            label: format!("`{snippet}` has type `{ty}`"),
+            children: match node {
+                AnyNodeRef::ExprCall(call) => self.explain_call(call),
+                AnyNodeRef::ExprAttribute(attribute) => vec![self.explain_attribute(attribute)],
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// The steps an expression's type was derived from.
+    fn explain_expr(&mut self, expr: &Expr) -> Vec<TypeDerivation> {
+        match expr {
+            Expr::Name(name) => self
+                .name_idx(name)
+                .map(|idx| vec![self.explain_idx(idx)])
+                .unwrap_or_default(),
+            Expr::Call(call) => self.explain_call(call),
+            Expr::Attribute(attribute) => vec![self.explain_attribute(attribute)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn explain_attribute(&mut self, attribute: &ExprAttribute) -> TypeDerivation {
+        let base = self.snippet(attribute.value.range());
+        let base_ty = self.trace(attribute.value.range());
+        TypeDerivation {
+            label: format!(
+                "attribute `{}` looked up on `{base}` of type `{base_ty}`",
+                attribute.attr.id
+            ),
+            children: self.explain_expr(&attribute.value),
+        }
+    }
+
+    fn explain_call(&mut self, call: &ExprCall) -> Vec<TypeDerivation> {
+        let callee_ty = self.answers.get_type_trace_for_display(call.func.range());
+        let mut steps = vec![TypeDerivation {
+            label: format!(
+                "callee `{}` has type `{}`",
+                self.snippet(call.func.range()),
+                Self::display(callee_ty.as_ref()),
+            ),
+            children: self.explain_expr(&call.func),
+        }];
+
+        if let Some((overloads, Some(chosen))) =
+            self.answers.get_all_overload_trace(call.arguments.range)
+            && overloads.len() > 1
+        {
+            let errors = self
+                .answers
+                .get_overload_errors(call.arguments.range)
+                .unwrap_or_default();
+            let children = overloads
+                .iter()
+                .enumerate()
+                .map(|(i, overload)| {
+                    let signature = Type::Callable(Box::new(overload.clone()));
+                    let errors = errors.get(i).map(Vec::as_slice).unwrap_or_default();
+                    let status = if i == chosen {
+                        "selected"
+                    } else if errors.is_empty() {
+                        "not selected"
+                    } else {
+                        "rejected"
+                    };
+                    TypeDerivation {
+                        label: format!(
+                            "overload {} of {}: `{signature}`, {status}",
+                            i + 1,
+                            overloads.len(),
+                        ),
+                        children: errors
+                            .iter()
+                            .map(|error| TypeDerivation::leaf(error.clone()))
+                            .collect(),
+                    }
+                })
+                .collect();
+            steps.push(TypeDerivation {
+                label: format!("resolved to overload {} of {}", chosen + 1, overloads.len()),
+                children,
+            });
+        }
+        if let Some(solutions) = self
+            .answers
+            .get_call_solutions_for_display(call.arguments.range)
+        {
+            steps.push(TypeDerivation {
+                label: "type variables solved as".to_owned(),
+                children: solutions
+                    .into_iter()
+                    .map(|(q, ty)| TypeDerivation::leaf(format!("`{}` = `{ty}`", q.name())))
+                    .collect(),
+            });
+        }
+        steps
+    }
+
+    /// Explain a flow binding, following it back to where the name was declared or assigned.
+    fn explain_idx(&mut self, idx: Idx<Key>) -> TypeDerivation {
+        let idx = self.skip_forwards(idx);
+        let ty = self.type_at(idx);
+        let range = self.bindings.idx_to_key(idx).range();
+        let pos = self.module.display_pos(range.start());
+        if !self.seen.insert(idx) {
+            return TypeDerivation::leaf(format!("`{ty}` from {pos}, as explained above"));
+        }
+        if self.depth >= MAX_DEPTH {
+            return TypeDerivation::leaf(format!("`{ty}` from {pos}, not explained any further"));
+        }
+        self.depth += 1;
+        let bindings = self.bindings.clone();
+        let derivation = match bindings.get(idx) {
+            Binding::Narrow(prev, op, _) => {
+                let condition = match bindings.idx_to_key(idx) {
+                    Key::Narrow(x) => {
+                        let (name, op_range, _) = x.as_ref();
+                        op.as_python_snippet(name, &|range| {
+                            format_code_snippet(&self.module, range)
+                        })
+                        .or_else(|| format_code_snippet(&self.module, *op_range))
+                    }
+                    _ => None,
+                };
+                let label = match condition {
+                    Some(condition) => format!("narrowed to `{ty}` by `{condition}` at {pos}"),
+                    None => format!("narrowed to `{ty}` at {pos}"),
+                };
+                TypeDerivation {
+                    label,
+                    children: vec![self.explain_idx(*prev)],
+                }
+            }
+            Binding::Phi(_, branches) => TypeDerivation {
+                label: format!("joined to `{ty}` from {} branches at {pos}", branches.len()),
+                children: branches
+                    .iter()
+                    .map(|branch| self.explain_idx(branch.value_key))
+                    .collect(),
+            },
+            Binding::LoopPhi(prior, others) => TypeDerivation {
+                label: format!("joined to `{ty}` across loop iterations at {pos}"),
+                children: std::iter::once(*prior)
+                    .chain(others.iter().copied())
+                    .map(|idx| self.explain_idx(idx))
+                    .collect(),
+            },
+            Binding::NameAssign(assign) => {
+                let value = self.snippet(assign.expr.range());
+                match (&assign.annotation, &assign.first_use) {
+                    (Some((_, annotation)), _) => TypeDerivation {
+                        label: format!("declared as `{}` at {pos}", self.annotation(*annotation)),
+                        children: Vec::new(),
+                    },
+                    (None, FirstUse::UsedBy(use_idx)) => {
+                        let use_range = bindings.idx_to_key(*use_idx).range();
+                        TypeDerivation {
+                            label: format!(
+                                "inferred as `{ty}` from its first use at {}: `{}`",
+                                self.module.display_pos(use_range.start()),
+                                self.snippet(use_range)
+                            ),
+                            children: vec![TypeDerivation {
+                                label: format!("assigned `{value}` at {pos}"),
+                                children: self.explain_expr(&assign.expr),
+                            }],
+                        }
+                    }
+                    (None, _) => TypeDerivation {
+                        label: format!(
+                            "inferred as `{ty}` from the assigned value `{value}` at {pos}"
+                        ),
+                        children: self.explain_expr(&assign.expr),
+                    },
+                }
+            }
+            Binding::AnnotatedType(annotation, _) => TypeDerivation::leaf(format!(
+                "declared as `{}` at {pos}",
+                self.annotation(*annotation)
+            )),
+            Binding::FunctionParameter(parameter) => match parameter.as_ref() {
+                FunctionParameter::Annotated(annotation) => TypeDerivation::leaf(format!(
+                    "parameter declared as `{}` at {pos}",
+                    self.annotation(*annotation)
+                )),
+                FunctionParameter::Unannotated(..) => TypeDerivation::leaf(format!(
+                    "unannotated parameter inferred as `{ty}` at {pos}"
+                )),
+            },
+            Binding::Function { .. } => {
+                TypeDerivation::leaf(format!("`{ty}` defined by the function at {pos}"))
+            }
+            Binding::ClassDef(..) => {
+                TypeDerivation::leaf(format!("`{ty}` defined by the class at {pos}"))
+            }
+            Binding::Import(..) | Binding::Module(..) => {
+                TypeDerivation::leaf(format!("`{ty}` imported at {pos}"))
+            }
+            Binding::IterableValueLoop(_, iterable, _)
+            | Binding::IterableValueComprehension(iterable, _, _) => TypeDerivation {
+                label: format!(
+                    "`{ty}` from iterating over `{}` at {pos}",
+                    self.snippet(iterable.range())
+                ),
+                children: self.explain_expr(iterable),
+            },
+            _ => TypeDerivation::leaf(format!(
+                "bound to `{ty}` at {pos}: `{}`",
+                self.snippet(range)
+            )),
+        };
+        self.depth -= 1;
+        derivation
+    }
+
+    /// Follow bindings that only pass a type along, so every step in the tree does something.
+    fn skip_forwards(&self, mut idx: Idx<Key>) -> Idx<Key> {
+        let mut seen = SmallSet::new();
+        while seen.insert(idx) {
+            match self.bindings.get(idx) {
+                Binding::Forward(next)
+                | Binding::PromoteForward(next)
+                | Binding::ForwardToFirstUse(next)
+                | Binding::PatternCapture(next) => idx = *next,
+                _ => break,
+            }
+        }
+        idx
+    }
+
+    fn name_idx(&self, name: &ExprName) -> Option<Idx<Key>> {
+        let key = match name.ctx {
+            ExprContext::Store => Key::Definition(ShortIdentifier::expr_name(name)),
+            ExprContext::Load | ExprContext::Del | ExprContext::Invalid => {
+                Key::BoundName(ShortIdentifier::expr_name(name))
+            }
+        };
+        self.bindings
+            .is_valid_key(&key)
+            .then(|| self.bindings.key_to_idx(&key))
+    }
+
+    fn type_at(&self, idx: Idx<Key>) -> String {
+        Self::display(self.answers.get_type_at_for_display(idx).as_ref())
+    }
+
+    fn trace(&self, range: TextRange) -> String {
+        Self::display(self.answers.get_type_trace_for_display(range).as_ref())
+    }
+
+    fn annotation(&self, idx: Idx<KeyAnnotation>) -> String {
+        match self.answers.get_idx(idx) {
+            Some(annotation) => annotation.annotation.to_string(),
+            None => "?".to_owned(),
+        }
+    }
+
+    fn display(ty: Option<&Type>) -> String {
+        match ty {
+            Some(ty) => ty.to_string(),
+            None => "?".to_owned(),
+        }
+    }
+
+    fn snippet(&self, range: TextRange) -> String {
+        format_code_snippet(&self.module, range).unwrap_or_default()
+    }
+}
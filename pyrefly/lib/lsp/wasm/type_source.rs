@@ -13,7 +13,9 @@
 use lsp_types::Url;
 #[cfg(target_arch = "wasm32")]
 use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
 use pyrefly_util::lined_buffer::DisplayPos;
+use ruff_text_size::TextRange;
 #[cfg(target_arch = "wasm32")]
 use ruff_text_size::TextSize;
 
@@ -37,6 +39,36 @@ pub fn set_display_pos_fragment(url: &mut Url, pos: DisplayPos) {
     url.set_fragment(Some(&fragment));
 }
 
+/// Render the source at `range` on a single line for display in hover and type
+/// explanations, truncated if it is long. Returns `None` for empty ranges.
+pub fn format_code_snippet(module: &Module, range: TextRange) -> Option<String> {
+    if range.is_empty() {
+        return None;
+    }
+    let snippet = module.code_at(range);
+    let cleaned = snippet
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('`', "'");
+    if cleaned.is_empty() {
+        return None;
+    }
+    const MAX_LEN: usize = 80;
+    let mut truncated = cleaned;
+    if truncated.chars().count() > MAX_LEN {
+        let max_chars = MAX_LEN.saturating_sub(3);
+        let end_byte = truncated
+            .char_indices()
+            .nth(max_chars)
+            .map(|(idx, _)| idx)
+            .unwrap_or_else(|| truncated.len());
+        truncated.truncate(end_byte);
+        truncated.push_str("...");
+    }
+    Some(truncated)
+}
+
 // Type source tracking is only available on non-wasm targets because it requires
 // Url::from_file_path which is not available in wasm builds.
 #[cfg(not(target_arch = "wasm32"))]
@@ -52,6 +84,7 @@ mod impl_ {
     use ruff_text_size::TextSize;
     use starlark_map::small_set::SmallSet;
 
+    use super::format_code_snippet;
     use super::set_display_pos_fragment;
     use crate::binding::binding::Binding;
     use crate::binding::binding::FirstUse;
@@ -70,34 +103,6 @@ mod impl_ {
         format!("[{}]({})", location, url)
     }
 
-    fn format_code_snippet(module: &Module, range: TextRange) -> Option<String> {
-        if range.is_empty() {
-            return None;
-        }
-        let snippet = module.code_at(range);
-        let cleaned = snippet
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join(" ")
-            .replace('`', "'");
-        if cleaned.is_empty() {
-            return None;
-        }
-        const MAX_LEN: usize = 80;
-        let mut truncated = cleaned;
-        if truncated.chars().count() > MAX_LEN {
-            let max_chars = MAX_LEN.saturating_sub(3);
-            let end_byte = truncated
-                .char_indices()
-                .nth(max_chars)
-                .map(|(idx, _)| idx)
-                .unwrap_or_else(|| truncated.len());
-            truncated.truncate(end_byte);
-            truncated.push_str("...");
-        }
-        Some(truncated)
-    }
-
     fn narrow_source_for_key(
         bindings: &Bindings,
         module: &Module,
@@ -6,6 +6,7 @@
  */
 
 pub mod bundled;
+pub mod dependencies;
 pub mod finder;
 pub mod parse;
 pub mod third_party;
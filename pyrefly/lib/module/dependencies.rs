@@ -0,0 +1,626 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Map third-party imports to the distributions that provide them, and compare those
+//! distributions with the dependencies a project declares in its `pyproject.toml`.
+//!
+//! Installed distributions are discovered from the `*.dist-info` directories in the
+//! site package path, and the `RECORD` of each lists the files it installs. An import is
+//! attributed to a distribution by resolving it with the module finder and looking up the
+//! resolved path, so first-party modules and stdlib modules that share a name with an
+//! installed package are never attributed to it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePath;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_util::fs_anyhow;
+use serde::Deserialize;
+use starlark_map::small_map::SmallMap;
+
+use crate::config::config::ConfigFile;
+use crate::config::workspace::normalize_distribution_name;
+use crate::config::workspace::requirement_name;
+
+#[derive(Debug, Deserialize)]
+struct PyProject {
+    project: Option<Project>,
+    #[serde(rename = "dependency-groups", default)]
+    dependency_groups: BTreeMap<String, Vec<toml::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(rename = "optional-dependencies", default)]
+    optional_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// The dependencies declared in the `[project]` table of a `pyproject.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredDependencies {
+    /// The `pyproject.toml` the dependencies were read from.
+    pub pyproject: PathBuf,
+    /// The normalized name of the project itself, which never needs to depend on itself.
+    pub name: Option<String>,
+    /// Normalized distribution names, in declaration order, each mapped to the groups declaring it:
+    /// `None` for `[project.dependencies]`, otherwise the name of an optional dependency or dependency group.
+    pub dependencies: SmallMap<String, Vec<Option<String>>>,
+}
+
+impl DeclaredDependencies {
+    /// Parse the dependencies of a `pyproject.toml`. Returns `None` if it has no `[project]` table,
+    /// e.g. because it only configures tools.
+    pub fn parse(pyproject: &Path, contents: &str) -> anyhow::Result<Option<Self>> {
+        let parsed: PyProject = toml::from_str(contents)?;
+        let Some(project) = parsed.project else {
+            return Ok(None);
+        };
+        let mut dependencies: SmallMap<String, Vec<Option<String>>> = SmallMap::new();
+        let mut declare = |requirement: &str, group: Option<&str>| {
+            if let Some(name) = requirement_name(requirement) {
+                dependencies
+                    .entry(name)
+                    .or_default()
+                    .push(group.map(str::to_owned));
+            }
+        };
+        for requirement in &project.dependencies {
+            declare(requirement, None);
+        }
+        for (group, requirements) in &project.optional_dependencies {
+            for requirement in requirements {
+                declare(requirement, Some(group.as_str()));
+            }
+        }
+        for (group, requirements) in &parsed.dependency_groups {
+            // Entries may also be `{include-group = "..."}` tables, whose requirements
+            // are declared by the group they include.
+            for requirement in requirements.iter().filter_map(|x| x.as_str()) {
+                declare(requirement, Some(group.as_str()));
+            }
+        }
+        Ok(Some(Self {
+            pyproject: pyproject.to_owned(),
+            name: project.name.as_deref().map(normalize_distribution_name),
+            dependencies,
+        }))
+    }
+
+    /// Find the nearest `pyproject.toml` with a `[project]` table in `start` or one of its ancestors.
+    /// Fails if a `pyproject.toml` on the way can't be parsed, rather than skipping to an ancestor's.
+    pub fn find(start: &Path) -> anyhow::Result<Option<Self>> {
+        for dir in start.ancestors() {
+            let pyproject = dir.join("pyproject.toml");
+            let Ok(contents) = fs::read_to_string(&pyproject) else {
+                continue;
+            };
+            let declared = Self::parse(&pyproject, &contents)
+                .with_context(|| format!("Failed to parse `{}`", pyproject.display()))?;
+            if declared.is_some() {
+                return Ok(declared);
+            }
+        }
+        Ok(None)
+    }
+
+    /// The directory containing the `pyproject.toml`.
+    pub fn root(&self) -> &Path {
+        self.pyproject.parent().unwrap_or(&self.pyproject)
+    }
+
+    /// Whether the normalized distribution `name` is declared, or is the project itself.
+    pub fn declares(&self, name: &str) -> bool {
+        self.dependencies.contains_key(name) || self.name.as_deref() == Some(name)
+    }
+}
+
+/// A distribution installed in the site package path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    /// The name as written in the distribution's metadata, e.g. `PyYAML`.
+    pub name: String,
+    /// The normalized name, used to compare against requirements.
+    pub normalized: String,
+}
+
+/// The distributions installed in the site package path, indexed by the module files
+/// listed in their `RECORD` and the directories containing them.
+#[derive(Debug, Default)]
+pub struct InstalledDistributions {
+    owners: SmallMap<PathBuf, Vec<Distribution>>,
+}
+
+impl InstalledDistributions {
+    pub fn new<'a>(site_package_path: impl IntoIterator<Item = &'a PathBuf>) -> Self {
+        let mut res = Self::default();
+        for dir in site_package_path {
+            let Ok(entries) = fs_anyhow::read_dir(dir) else {
+                continue;
+            };
+            let mut dist_infos = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "dist-info"))
+                .collect::<Vec<_>>();
+            // Directory iteration order is platform dependent.
+            dist_infos.sort();
+            for dist_info in dist_infos {
+                res.add_distribution(dir, &dist_info);
+            }
+        }
+        res
+    }
+
+    fn add_distribution(&mut self, site_packages: &Path, dist_info: &Path) {
+        let Some(name) = distribution_name(dist_info) else {
+            return;
+        };
+        let Ok(record) = fs::read_to_string(dist_info.join("RECORD")) else {
+            return;
+        };
+        let distribution = Distribution {
+            normalized: normalize_distribution_name(&name),
+            name,
+        };
+        for relative in record.lines().filter_map(record_module_path) {
+            let path = site_packages.join(relative);
+            for owned in path
+                .ancestors()
+                .take_while(|ancestor| *ancestor != site_packages)
+            {
+                let owners = self.owners.entry(owned.to_owned()).or_default();
+                if owners.contains(&distribution) {
+                    // Every further ancestor was recorded along with this one.
+                    break;
+                }
+                owners.push(distribution.clone());
+            }
+        }
+    }
+
+    /// The distributions installing the file or directory `path`. A directory may be
+    /// shared by several distributions, e.g. a namespace package.
+    pub fn owners(&self, path: &Path) -> &[Distribution] {
+        self.owners.get(path).map_or(&[], |x| x.as_slice())
+    }
+}
+
+/// The name of the distribution described by a `*.dist-info` directory, preferring the
+/// `Name:` field of its `METADATA` over the directory name, which is normalized by some installers.
+fn distribution_name(dist_info: &Path) -> Option<String> {
+    if let Ok(metadata) = fs::read_to_string(dist_info.join("METADATA"))
+        && let Some(name) = metadata
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .find_map(|line| line.strip_prefix("Name:"))
+    {
+        return Some(name.trim().to_owned());
+    }
+    let stem = dist_info.file_stem()?.to_str()?;
+    let name = stem.split_once('-').map_or(stem, |(name, _)| name);
+    Some(name.to_owned())
+}
+
+/// The module file installed by a `RECORD` line of the form `path,hash,size`, relative to
+/// the site packages directory. Metadata, bytecode, and files installed outside the site
+/// packages directory (such as scripts) are skipped.
+fn record_module_path(line: &str) -> Option<PathBuf> {
+    let path = line.split(',').next()?;
+    let (_, ext) = path.rsplit_once('.')?;
+    if !matches!(ext, "py" | "pyi" | "so" | "pyd") {
+        return None;
+    }
+    let components = path.split('/').collect::<Vec<_>>();
+    if components
+        .iter()
+        .any(|x| x.is_empty() || *x == ".." || *x == "__pycache__")
+        || components[0].ends_with(".dist-info")
+        || components[0].ends_with(".data")
+    {
+        return None;
+    }
+    Some(components.into_iter().collect())
+}
+
+/// The declared and installed third-party dependencies of the project a config belongs to.
+#[derive(Debug)]
+pub struct ProjectDependencies {
+    declared: DeclaredDependencies,
+    installed: InstalledDistributions,
+    site_package_path: Vec<PathBuf>,
+}
+
+impl ProjectDependencies {
+    /// Returns `None` if there is no `pyproject.toml` with a `[project]` table at or above the config,
+    /// or the workspace member it checks.
+    pub fn new(config: &ConfigFile) -> anyhow::Result<Option<Self>> {
+        let Some(declared) = Self::find_declared(config)? else {
+            return Ok(None);
+        };
+        let site_package_path = config.site_package_path().cloned().collect::<Vec<_>>();
+        Ok(Some(Self {
+            declared,
+            installed: InstalledDistributions::new(&site_package_path),
+            site_package_path,
+        }))
+    }
+
+    /// The dependencies currently declared by the project `config` belongs to.
+    pub fn find_declared(config: &ConfigFile) -> anyhow::Result<Option<DeclaredDependencies>> {
+        let root = match &config.workspace_member {
+            Some(member) => &member.root,
+            None => match config.source.root() {
+                Some(root) => root,
+                None => return Ok(None),
+            },
+        };
+        DeclaredDependencies::find(root)
+    }
+
+    pub fn declared(&self) -> &DeclaredDependencies {
+        &self.declared
+    }
+
+    /// Whether imports in `path` should be checked: the file belongs to the project,
+    /// rather than to an installed package (which may live in a virtual environment inside the project).
+    pub fn covers(&self, path: &Path) -> bool {
+        path.starts_with(self.declared.root()) && self.site_packages_relative(path).is_none()
+    }
+
+    /// `path` relative to the site packages directory containing it, if any.
+    fn site_packages_relative<'p>(&self, path: &'p Path) -> Option<&'p Path> {
+        self.site_package_path
+            .iter()
+            .find_map(|site_packages| path.strip_prefix(site_packages).ok())
+    }
+
+    /// The distributions providing `module`, which the module finder resolved to `path`.
+    /// Empty unless `path` is in the site package path, or is a stub bundled with Pyrefly
+    /// for a package installed there.
+    pub fn providers(&self, module: ModuleName, path: &ModulePath) -> &[Distribution] {
+        match path.details() {
+            ModulePathDetails::FileSystem(path) | ModulePathDetails::Namespace(path) => {
+                let Some(relative) = self.site_packages_relative(path) else {
+                    return &[];
+                };
+                let is_stubs_package = relative
+                    .iter()
+                    .next()
+                    .and_then(|x| x.to_str())
+                    .is_some_and(|x| x.ends_with("-stubs"));
+                if is_stubs_package {
+                    // Attribute `yaml-stubs` (from `types-PyYAML`) to the runtime package if it is installed.
+                    let runtime = self.runtime_providers(module);
+                    if !runtime.is_empty() {
+                        return runtime;
+                    }
+                }
+                self.installed.owners(path)
+            }
+            ModulePathDetails::BundledTypeshedThirdParty(_)
+            | ModulePathDetails::BundledThirdParty(_) => self.runtime_providers(module),
+            ModulePathDetails::Memory(_) | ModulePathDetails::BundledTypeshed(_) => &[],
+        }
+    }
+
+    /// The distributions installing the runtime module or package for `module` in the site package path.
+    fn runtime_providers(&self, module: ModuleName) -> &[Distribution] {
+        for site_packages in &self.site_package_path {
+            let mut path = site_packages.clone();
+            path.extend(module.components().iter().map(|x| x.as_str()));
+            for candidate in [path.with_extension("py"), path.with_extension("pyi"), path] {
+                let owners = self.installed.owners(&candidate);
+                if !owners.is_empty() {
+                    return owners;
+                }
+            }
+        }
+        &[]
+    }
+
+    /// The distributions providing `module`, if it is third-party and none of them are declared.
+    pub fn undeclared(&self, module: ModuleName, path: &ModulePath) -> Option<&[Distribution]> {
+        let providers = self.providers(module, path);
+        if providers.is_empty()
+            || providers
+                .iter()
+                .any(|dist| self.declared.declares(&dist.normalized))
+        {
+            None
+        } else {
+            Some(providers)
+        }
+    }
+
+    /// Summarize which `[project.dependencies]` are used, given every distribution imported
+    /// by the project, mapped to whether it was only imported under `TYPE_CHECKING`.
+    /// Optional dependencies and dependency groups are left out, since they often hold
+    /// tools such as test runners and linters that are never imported.
+    pub fn usage(&self, imported: &SmallMap<String, bool>) -> DependencyUsage {
+        let mut usage = DependencyUsage {
+            pyproject: self.declared.pyproject.clone(),
+            unused: Vec::new(),
+            type_checking_only: Vec::new(),
+        };
+        let required = self
+            .declared
+            .dependencies
+            .iter()
+            .filter(|(_, groups)| groups.contains(&None))
+            .map(|(name, _)| name);
+        for name in required {
+            match imported.get(name) {
+                None => usage.unused.push(name.clone()),
+                Some(true) => usage.type_checking_only.push(name.clone()),
+                Some(false) => {}
+            }
+        }
+        usage
+    }
+}
+
+/// How the `[project.dependencies]` of a `pyproject.toml` are used by the checked modules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyUsage {
+    pub pyproject: PathBuf,
+    /// Dependencies that are never imported.
+    pub unused: Vec<String>,
+    /// Dependencies that are only imported under `TYPE_CHECKING`.
+    pub type_checking_only: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use dupe::Dupe;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_declared_dependencies() {
+        let contents = r#"
+[project]
+name = "My_Project"
+dependencies = ["requests>=2", "PyYAML"]
+
+[project.optional-dependencies]
+fast = ["orjson"]
+
+[dependency-groups]
+typing = ["types-requests", {include-group = "fast"}]
+"#;
+        let declared = DeclaredDependencies::parse(Path::new("/p/pyproject.toml"), contents)
+            .unwrap()
+            .unwrap();
+        assert_eq!(declared.name.as_deref(), Some("my-project"));
+        assert_eq!(
+            declared.dependencies.keys().collect::<Vec<_>>(),
+            vec!["requests", "pyyaml", "orjson", "types-requests"]
+        );
+        assert_eq!(
+            declared.dependencies.get("orjson"),
+            Some(&vec![Some("fast".to_owned())])
+        );
+        assert!(declared.declares("my-project"));
+        assert!(!declared.declares("numpy"));
+        assert_eq!(
+            DeclaredDependencies::parse(Path::new("pyproject.toml"), "[tool.pyrefly]\n").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_malformed_pyproject() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path();
+        let member = root.join("member");
+        fs::create_dir(&member).unwrap();
+        fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"workspace\"\n",
+        )
+        .unwrap();
+        fs::write(member.join("pyproject.toml"), "[project\n").unwrap();
+        // The member's dependencies are never checked against the workspace's.
+        assert!(DeclaredDependencies::find(&member).is_err());
+        assert_eq!(
+            DeclaredDependencies::find(root)
+                .unwrap()
+                .and_then(|x| x.name),
+            Some("workspace".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_record_module_path() {
+        assert_eq!(
+            record_module_path("yaml/__init__.py,sha256=abc,123"),
+            Some(PathBuf::from("yaml/__init__.py"))
+        );
+        assert_eq!(
+            record_module_path("six.py,sha256=abc,123"),
+            Some(PathBuf::from("six.py"))
+        );
+        assert_eq!(
+            record_module_path("_cffi_backend.cpython-312-x86_64-linux-gnu.so,sha256=abc,1"),
+            Some(PathBuf::from(
+                "_cffi_backend.cpython-312-x86_64-linux-gnu.so"
+            ))
+        );
+        assert_eq!(record_module_path("PyYAML-6.0.dist-info/RECORD,,"), None);
+        assert_eq!(record_module_path("../../bin/tool.py,,"), None);
+        assert_eq!(
+            record_module_path("__pycache__/six.cpython-312.pyc,,"),
+            None
+        );
+        assert_eq!(
+            record_module_path("yaml/__pycache__/x.cpython-312.py,,"),
+            None
+        );
+    }
+
+    fn install(site_packages: &Path, dist_info: &str, name: &str, files: &[&str]) {
+        let dist_info = site_packages.join(dist_info);
+        fs::create_dir(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("METADATA"),
+            format!("Metadata-Version: 2.1\nName: {name}\n\nName: not-a-header\n"),
+        )
+        .unwrap();
+        let mut record = String::new();
+        for file in files {
+            let path = site_packages.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "").unwrap();
+            record.push_str(&format!("{file},sha256=abc,0\n"));
+        }
+        fs::write(dist_info.join("RECORD"), record).unwrap();
+    }
+
+    #[test]
+    fn test_installed_distributions() {
+        let tdir = tempfile::tempdir().unwrap();
+        let site_packages = tdir.path().to_path_buf();
+        install(
+            &site_packages,
+            "PyYAML-6.0.dist-info",
+            "PyYAML",
+            &["yaml/__init__.py", "yaml/constructor.py"],
+        );
+        install(&site_packages, "six-1.16.0.dist-info", "six", &["six.py"]);
+        let installed = InstalledDistributions::new([&site_packages]);
+        let pyyaml = [Distribution {
+            name: "PyYAML".to_owned(),
+            normalized: "pyyaml".to_owned(),
+        }];
+        assert_eq!(
+            installed.owners(&site_packages.join("yaml/constructor.py")),
+            &pyyaml
+        );
+        assert_eq!(installed.owners(&site_packages.join("yaml")), &pyyaml);
+        assert_eq!(
+            installed.owners(&site_packages.join("six.py"))[0].name,
+            "six"
+        );
+        assert!(installed.owners(&site_packages.join("os.py")).is_empty());
+        assert!(installed.owners(&site_packages).is_empty());
+    }
+
+    #[test]
+    fn test_providers_follow_resolved_path() {
+        let tdir = tempfile::tempdir().unwrap();
+        let root = tdir.path();
+        let site_packages = root.join(".venv/lib/python3.12/site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        install(
+            &site_packages,
+            "PyYAML-6.0.dist-info",
+            "PyYAML",
+            &["yaml/__init__.py"],
+        );
+        install(
+            &site_packages,
+            "types_PyYAML-6.0.dist-info",
+            "types-PyYAML",
+            &["yaml-stubs/__init__.pyi"],
+        );
+        install(
+            &site_packages,
+            "protobuf-5.0.dist-info",
+            "protobuf",
+            &["google/protobuf/__init__.py"],
+        );
+        install(
+            &site_packages,
+            "googleapis_common_protos-1.0.dist-info",
+            "googleapis-common-protos",
+            &["google/api/__init__.py"],
+        );
+        // A first-party package with the same name as an installed one.
+        install(
+            &site_packages,
+            "some_dist-1.0.dist-info",
+            "some-dist",
+            &["tests/__init__.py"],
+        );
+        let dependencies = ProjectDependencies {
+            declared: DeclaredDependencies::parse(
+                &root.join("pyproject.toml"),
+                "[project]\nname = \"app\"\ndependencies = [\"protobuf\"]\n",
+            )
+            .unwrap()
+            .unwrap(),
+            installed: InstalledDistributions::new([&site_packages]),
+            site_package_path: vec![site_packages.clone()],
+        };
+        let names = |module: &str, path: ModulePath| {
+            dependencies
+                .providers(ModuleName::from_str(module), &path)
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(
+                "yaml",
+                ModulePath::filesystem(site_packages.join("yaml/__init__.py"))
+            ),
+            vec!["PyYAML"]
+        );
+        assert_eq!(
+            names(
+                "yaml",
+                ModulePath::filesystem(site_packages.join("yaml-stubs/__init__.pyi"))
+            ),
+            vec!["PyYAML"]
+        );
+        assert_eq!(
+            names(
+                "yaml",
+                ModulePath::bundled_typeshed_third_party(PathBuf::from("yaml/__init__.pyi"))
+            ),
+            vec!["PyYAML"]
+        );
+        assert_eq!(
+            names(
+                "tests",
+                ModulePath::filesystem(root.join("tests/__init__.py"))
+            ),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            names(
+                "dataclasses",
+                ModulePath::bundled_typeshed(PathBuf::from("dataclasses.pyi"))
+            ),
+            Vec::<&str>::new()
+        );
+
+        let protobuf = ModulePath::filesystem(site_packages.join("google/protobuf/__init__.py"));
+        assert_eq!(names("google.protobuf", protobuf.dupe()), vec!["protobuf"]);
+        assert!(
+            dependencies
+                .undeclared(ModuleName::from_str("google.protobuf"), &protobuf)
+                .is_none()
+        );
+        let api = ModulePath::filesystem(site_packages.join("google/api/__init__.py"));
+        assert_eq!(
+            dependencies
+                .undeclared(ModuleName::from_str("google.api"), &api)
+                .unwrap()[0]
+                .name,
+            "googleapis-common-protos"
+        );
+    }
+}
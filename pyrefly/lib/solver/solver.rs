@@ -186,7 +186,10 @@ mod tests {
     use super::*;
 
     fn solver_with_answer(answer: Type) -> (Solver, Var) {
-        let solver = Solver::new(false, true, false, false, false, false);
+        let solver = Solver::new(SolverOptions {
+            tensor_shapes: true,
+            ..Default::default()
+        });
         let uniques = UniqueFactory::new();
         let var = Var::new(&uniques);
         solver
@@ -229,7 +232,10 @@ mod tests {
 
     #[test]
     fn sanitize_type_vars_follows_answer_chains_without_rewriting() {
-        let solver = Solver::new(false, true, false, false, false, false);
+        let solver = Solver::new(SolverOptions {
+            tensor_shapes: true,
+            ..Default::default()
+        });
         let uniques = UniqueFactory::new();
         let range = TextRange::new(TextSize::new(1), TextSize::new(3));
         let partial = solver.fresh_partial_contained(&uniques, range);
@@ -692,7 +698,10 @@ mod tests {
         ];
         for (index, (v1_quantified, k1, r1, v2_quantified, k2, r2)) in cases.into_iter().enumerate()
         {
-            let solver = Solver::new(false, true, false, false, false, false);
+            let solver = Solver::new(SolverOptions {
+                tensor_shapes: true,
+                ..Default::default()
+            });
             let uniques = UniqueFactory::new();
             let v1 = Var::new(&uniques);
             let v2 = Var::new(&uniques);
@@ -1073,6 +1082,19 @@ struct VarState {
     error: Option<TypeVarSpecializationError>,
 }
 
+/// The options that control how a `Solver` relates types, set from the config for each module.
+#[derive(Debug, Clone, Default)]
+pub struct SolverOptions {
+    pub infer_with_first_use: bool,
+    pub tensor_shapes: bool,
+    pub strict_callable_subtyping: bool,
+    pub strict_partial_subtyping: bool,
+    pub spec_compliant_overloads: bool,
+    pub legacy_overload_expansion: bool,
+    /// How many failing members to report when a type is not assignable to a protocol.
+    pub max_protocol_mismatches: usize,
+}
+
 #[derive(Debug)]
 pub struct Solver {
     variables: Mutex<Variables>,
@@ -1091,6 +1113,8 @@ pub struct Solver {
     pub strict_partial_subtyping: bool,
     pub spec_compliant_overloads: bool,
     pub legacy_overload_expansion: bool,
+    /// How many failing members to report when a type is not assignable to a protocol.
+    pub max_protocol_mismatches: usize,
 }
 
 impl Display for Solver {
@@ -1143,14 +1167,16 @@ impl SubsetWithSnapshotResult {
 
 impl Solver {
     /// Create a new solver.
-    pub fn new(
-        infer_with_first_use: bool,
-        tensor_shapes: bool,
-        strict_callable_subtyping: bool,
-        strict_partial_subtyping: bool,
-        spec_compliant_overloads: bool,
-        legacy_overload_expansion: bool,
-    ) -> Self {
+    pub fn new(options: SolverOptions) -> Self {
+        let SolverOptions {
+            infer_with_first_use,
+            tensor_shapes,
+            strict_callable_subtyping,
+            strict_partial_subtyping,
+            spec_compliant_overloads,
+            legacy_overload_expansion,
+            max_protocol_mismatches,
+        } = options;
         Self {
             variables: Default::default(),
             instantiation_errors: Default::default(),
@@ -1163,6 +1189,7 @@ impl Solver {
             strict_partial_subtyping,
             spec_compliant_overloads,
             legacy_overload_expansion,
+            max_protocol_mismatches,
         }
     }
 
@@ -2980,10 +3007,10 @@ impl Solver {
         for (range, label) in tcc.annotations {
             builder = builder.with_annotation(range, label);
         }
-        if let Some(detail) = subset_error.to_error_msg() {
-            builder = builder.with_detail(detail);
+        for (module, range, label) in tcc.related_annotations {
+            builder = builder.with_related_annotation(module, range, label);
         }
-        builder
+        builder.with_details(subset_error.to_error_details())
     }
 
     /// Union a list of types together. In the process may cause some variables to be forced.
@@ -3136,6 +3163,25 @@ impl Solver {
         }
     }
 
+    /// Like `is_subset_eq`, but for callers that report the returned error, so a failed
+    /// protocol check lists every failing member (up to `max-protocol-mismatches`) instead
+    /// of stopping at the first.
+    pub fn is_subset_eq_with_reason<'subset, Ans: LookupAnswer>(
+        &self,
+        got: &Type,
+        want: &Type,
+        type_order: TypeOrder<Ans>,
+        call_context: Option<&CallContext<'subset>>,
+    ) -> Result<(), SubsetError> {
+        let mut subset = self.subset(type_order);
+        subset.report_protocol_members = true;
+        if let Some(cc) = call_context {
+            subset.with_active_call_context(cc.clone(), |me| me.is_subset_eq(got, want))
+        } else {
+            subset.is_subset_eq(got, want)
+        }
+    }
+
     pub fn is_consistent<Ans: LookupAnswer>(
         &self,
         got: &Type,
@@ -3168,6 +3214,7 @@ impl Solver {
             subset_cache: SmallMap::new(),
             class_protocol_assumptions: SmallSet::new(),
             coinductive_assumptions_used: false,
+            report_protocol_members: false,
             witness_deferred_vars: SmallMap::new(),
         }
     }
@@ -3362,6 +3409,10 @@ pub enum SubsetError {
     /// Attribute in `got` is incompatible with the same attribute in Protocol `want`
     /// The first element is the name of `want, the second element is `got`, and the third element is the name of the attribute
     IncompatibleAttribute(Box<(Name, Type, Name, AttrSubsetError)>),
+    /// Several members that Protocol `want` requires are missing or incompatible.
+    /// The first element is the name of the protocol, the second holds the reported failures
+    /// (up to `max-protocol-mismatches`), and the third is whether more members failed.
+    ProtocolMembers(Box<(Name, Vec1<SubsetError>, bool)>),
     /// TypedDict subset check failed
     TypedDict(Box<TypedDictSubsetError>),
     /// Errors involving arbitrary unknown fields in open TypedDicts
@@ -3404,6 +3455,7 @@ impl SubsetError {
                 let (protocol, got, attribute, err) = &*inner;
                 Some(err.to_error_msg(&Name::new(format!("{got}")), protocol, attribute))
             }
+            err @ SubsetError::ProtocolMembers(_) => Some(err.to_error_details().join("\n")),
             SubsetError::TypedDict(err) => Some(err.to_error_msg()),
             SubsetError::OpenTypedDict(err) => Some(err.to_error_msg()),
             SubsetError::Shape(err) => Some(err.to_string()),
@@ -3424,6 +3476,46 @@ impl SubsetError {
             SubsetError::Other => None,
         }
     }
+
+    /// Like `to_error_msg`, but split into detail lines, with extra lines for failures that
+    /// benefit from them, such as a signature diff for an incompatible protocol method.
+    pub fn to_error_details(self) -> Vec<String> {
+        match self {
+            SubsetError::IncompatibleAttribute(inner) => {
+                let (protocol, got, attribute, err) = &*inner;
+                let mut details =
+                    vec![err.to_error_msg(&Name::new(format!("{got}")), protocol, attribute)];
+                details.extend(err.signature_diff(attribute).unwrap_or_default());
+                details
+            }
+            SubsetError::ProtocolMembers(inner) => {
+                let (protocol, errors, more) = *inner;
+                let mut details = vec![if more {
+                    format!(
+                        "At least {} members required by protocol `{protocol}` are missing or incompatible:",
+                        errors.len() + 1
+                    )
+                } else {
+                    format!(
+                        "{} members required by protocol `{protocol}` are missing or incompatible:",
+                        errors.len()
+                    )
+                }];
+                for err in errors {
+                    details.extend(
+                        err.to_error_details()
+                            .into_iter()
+                            .map(|line| format!("  {line}")),
+                    );
+                }
+                if more {
+                    details.push("  ...and more".to_owned());
+                }
+                details
+            }
+            err => err.to_error_msg().into_iter().collect(),
+        }
+    }
 }
 
 /// Cached result for a recursive subset check. Used by `Subset::subset_cache`.
@@ -3818,6 +3910,9 @@ pub struct Subset<'solver, 'subset, Ans: LookupAnswer> {
     /// the current computation. Used to avoid caching protocol results in the
     /// persistent cross-call cache when they depend on coinductive assumptions.
     pub coinductive_assumptions_used: bool,
+    /// Whether the result will be reported, in which case a failed protocol check keeps
+    /// going to collect every failing member. Speculative checks stop at the first failure.
+    pub report_protocol_members: bool,
     witness_deferred_vars: SmallMap<u64, SmallSet<Var>>,
 }
 
@@ -45,6 +45,7 @@ use pyrefly_util::owner::Owner;
 use ruff_python_ast::name::Name;
 use ruff_text_size::TextRange;
 use starlark_map::small_map::SmallMap;
+use vec1::Vec1;
 
 use crate::alt::answers::LookupAnswer;
 use crate::alt::callable::CallArg;
@@ -644,8 +645,16 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
         let want = Type::ClassType(protocol.clone());
         let has_no_vars = got.collect_all_vars().is_empty() && want.collect_all_vars().is_empty();
 
-        // Check cross-call protocol cache for types without Vars.
-        if has_no_vars && let Some(result) = self.solver.check_protocol_cache(&got, &want) {
+        // Checking further members after a failure is only worthwhile if the error is reported,
+        // and only side-effect free without Vars.
+        let report_all_members = has_no_vars && self.report_protocol_members;
+
+        // Check cross-call protocol cache for types without Vars. A cached failure may come
+        // from a speculative check that stopped at the first failing member.
+        if has_no_vars
+            && let Some(result) = self.solver.check_protocol_cache(&got, &want)
+            && (result.is_ok() || !report_all_members)
+        {
             return result;
         }
 
@@ -676,7 +685,7 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
         } else {
             None
         };
-        let res = self.is_subset_protocol_inner(got.clone(), protocol);
+        let res = self.is_subset_protocol_inner(got.clone(), protocol, report_all_members);
         // Clean up assumptions
         if let Some(key) = class_check {
             self.class_protocol_assumptions.shift_remove(&key);
@@ -699,10 +708,14 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
         res
     }
 
+    /// Check `got` against each member of `protocol`. If `report_all_members` is set, keep
+    /// checking after the first failing member, so that up to `max_protocol_mismatches` of them
+    /// can be reported together.
     fn is_subset_protocol_inner(
         &mut self,
         got: Type,
         protocol: ClassType,
+        report_all_members: bool,
     ) -> Result<(), SubsetError> {
         // TODO: Remove this once pandas 2.x is no longer supported.
         // This is fixed in pandas 3.0 stubs. Until then, we hard-code that list/tuple satisfy
@@ -724,6 +737,9 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
         let protocol_members = self
             .type_order
             .get_protocol_member_names(protocol.class_object());
+        let max_failures = self.solver.max_protocol_mismatches.max(1);
+        let mut failures = Vec::new();
+        let mut more_failures = false;
         for name in protocol_members {
             let allow_residual_capture = name == dunder::CALL;
             if name == dunder::INIT || name == dunder::NEW {
@@ -738,7 +754,7 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
                 // Class-subscription hook, not an instance member
                 continue;
             }
-            if matches!(
+            let res = if matches!(
                 got,
                 Type::Callable(_) | Type::Function(_) | Type::BoundMethod(_) | Type::Overload(_)
             ) && name == dunder::CALL
@@ -754,9 +770,9 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
                         &got,
                         &want_no_self,
                         allow_residual_capture,
-                    )?;
+                    )
                 } else {
-                    self.is_subset_eq_for_protocol_member(&got, &want, allow_residual_capture)?;
+                    self.is_subset_eq_for_protocol_member(&got, &want, allow_residual_capture)
                 }
             } else {
                 self.type_order.is_protocol_subset_at_attr(
@@ -766,10 +782,30 @@ impl<'solver, 'subset, Ans: LookupAnswer> Subset<'solver, 'subset, Ans> {
                     &mut |got, want| {
                         self.is_subset_eq_for_protocol_member(got, want, allow_residual_capture)
                     },
-                )?;
+                )
+            };
+            if let Err(err) = res {
+                if !report_all_members {
+                    return Err(err);
+                }
+                if failures.len() == max_failures {
+                    more_failures = true;
+                    break;
+                }
+                failures.push(err);
             }
         }
-        Ok(())
+        match Vec1::try_from_vec(failures) {
+            Err(_) => Ok(()),
+            Ok(failures) if failures.len() == 1 && !more_failures => {
+                Err(failures.into_iter().next().unwrap())
+            }
+            Ok(failures) => Err(SubsetError::ProtocolMembers(Box::new((
+                protocol.name().clone(),
+                failures,
+                more_failures,
+            )))),
+        }
     }
 
     fn is_subset_eq_for_protocol_member(
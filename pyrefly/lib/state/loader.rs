@@ -8,6 +8,7 @@
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
 
 use dupe::Dupe;
 use pyrefly_config::error_kind::ErrorKind;
@@ -16,6 +17,7 @@ use pyrefly_python::module_path::ModulePath;
 use pyrefly_python::module_path::ModuleStyle;
 use pyrefly_util::arc_id::ArcId;
 use pyrefly_util::locked_map::LockedMap;
+use tracing::warn;
 use vec1::Vec1;
 use vec1::vec1;
 
@@ -24,6 +26,7 @@ use crate::config::config::ConfigSource;
 use crate::config::config::FallbackSearchPath;
 use crate::config::config::ImportLookupPathPart;
 use crate::error::context::ErrorContext;
+use crate::module::dependencies::ProjectDependencies;
 use crate::module::finder::DirEntryCache;
 use crate::module::finder::find_import;
 use crate::module::finder::find_import_filtered;
@@ -210,6 +213,8 @@ pub struct LoaderFindCache {
     // If a python executable module (excludes .pyi) exists and differs from the imported python module, store it here
     executable_cache: LockedMap<(ModuleName, Option<ModulePath>), Option<ModulePath>>,
     dir_cache: DirEntryCache,
+    /// Computed on first use, since it reads every `*.dist-info` directory in the site package path.
+    project_dependencies: OnceLock<Option<Arc<ProjectDependencies>>>,
 }
 
 impl LoaderFindCache {
@@ -230,9 +235,37 @@ impl LoaderFindCache {
             cache: Default::default(),
             executable_cache: Default::default(),
             dir_cache: DirEntryCache::new(),
+            project_dependencies: OnceLock::new(),
         }
     }
 
+    /// The dependencies declared by the project this config belongs to, and the installed
+    /// distributions they can be checked against.
+    pub fn project_dependencies(&self) -> Option<Arc<ProjectDependencies>> {
+        self.project_dependencies
+            .get_or_init(|| match ProjectDependencies::new(&self.config) {
+                Ok(dependencies) => dependencies.map(Arc::new),
+                Err(e) => {
+                    warn!("Not checking for undeclared dependencies: {e:#}");
+                    None
+                }
+            })
+            .clone()
+    }
+
+    /// Whether the dependencies declared by the project differ from those returned by
+    /// `project_dependencies`, e.g. because its `pyproject.toml` was edited. Always `false`
+    /// if they haven't been read yet.
+    pub fn project_dependencies_changed(&self) -> bool {
+        self.project_dependencies.get().is_some_and(|dependencies| {
+            dependencies.as_ref().map(|x| x.declared())
+                != ProjectDependencies::find_declared(&self.config)
+                    .ok()
+                    .flatten()
+                    .as_ref()
+        })
+    }
+
     pub fn find_import_prefer_executable(
         &self,
         module: ModuleName,
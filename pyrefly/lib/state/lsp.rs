@@ -71,6 +71,7 @@ use crate::alt::attr::AttrDefinition;
 use crate::alt::attr::AttrInfo;
 use crate::binding::binding::Key;
 use crate::config::error_kind::ErrorKind;
+use crate::error::error::ErrorQuickFix;
 use crate::error::suppress::detect_line_ending;
 use crate::export::exports::Export;
 use crate::export::exports::ExportLocation;
@@ -94,10 +95,14 @@ use crate::types::type_var::Restriction;
 use crate::types::types::Type;
 
 mod dict_completions;
+mod exception_flow;
 mod extra_extensions;
+mod override_completions;
 mod pytest;
 mod quick_fixes;
 
+pub(crate) use self::quick_fixes::change_signature::ChangeSignatureParameter;
+pub(crate) use self::quick_fixes::change_signature::ChangeSignatureTarget;
 pub(crate) use self::quick_fixes::move_module::MoveModuleMemberContext;
 pub(crate) use self::quick_fixes::types::LocalRefactorCodeAction;
 
@@ -190,6 +195,18 @@ pub enum ImportFormat {
     Relative,
 }
 
+/// A fix that is safe to apply without review, e.g. by `pyrefly check --fix`.
+#[derive(Clone, Debug)]
+pub struct MachineApplicableFix {
+    /// The kind of the error the fix resolves.
+    pub error_kind: ErrorKind,
+    /// The module and range the error is reported at.
+    pub error_module: Module,
+    pub error_range: TextRange,
+    /// Edits that must be applied together, e.g. a decorator and the import it needs.
+    pub edits: Vec<(Module, TextRange, String)>,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum DisplayTypeErrors {
@@ -3115,7 +3132,7 @@ impl<'a> Transaction<'a> {
         let mut generate_actions = Vec::new();
         let mut other_actions = Vec::new();
         // Actions that carry more than one edit (e.g. the missing-`@override` fix,
-        // which inserts both the decorator and an import).
+        // which inserts both the decorator and an import, or the implement-members fix).
         let mut multi_actions: Vec<(String, Vec<(Module, TextRange, String)>)> = Vec::new();
         let mut other_action_keys: HashSet<(String, TextRange, String)> = HashSet::new();
         for error in errors {
@@ -3269,6 +3286,55 @@ impl<'a> Transaction<'a> {
                         multi_actions.push((title, edits));
                     }
                 }
+                ErrorKind::NonExhaustiveMatch if error_range.contains_range(range) => {
+                    if let Some(action) =
+                        quick_fixes::exhaustive_match::add_missing_cases_code_action(
+                            self,
+                            handle,
+                            &module_info,
+                            &ast,
+                            error_range,
+                        )
+                    {
+                        multi_actions.push(action);
+                    }
+                }
+                ErrorKind::UnclosedResource if error_range.contains_range(range) => {
+                    if let Some(action) = quick_fixes::wrap_in_with::wrap_in_with_code_action(
+                        &module_info,
+                        &ast,
+                        error_range,
+                    ) {
+                        multi_actions.push(action);
+                    }
+                }
+                ErrorKind::BadInstantiation
+                | ErrorKind::BadAssignment
+                | ErrorKind::BadArgumentType
+                | ErrorKind::BadReturn
+                    if error_range.contains_range(range) =>
+                {
+                    if let Some(fix) =
+                        quick_fixes::implement_members::implement_members_code_action(
+                            self, handle, &error,
+                        )
+                    {
+                        let mut edits = fix.edits;
+                        if fix.uses_override
+                            && !quick_fixes::add_override::override_in_scope(&fix.ast)
+                            && let Some(import_edit) = self.override_import_edit(
+                                &fix.handle,
+                                &fix.module,
+                                &fix.ast,
+                                import_format,
+                                custom_thread_pool,
+                            )
+                        {
+                            edits.push(import_edit);
+                        }
+                        multi_actions.push((fix.title, edits));
+                    }
+                }
                 _ => {}
             }
         }
@@ -3279,7 +3345,7 @@ impl<'a> Transaction<'a> {
         // this will be the public/non-deprecated version)
         import_actions.dedup_by(|a, b| a.insert_text == b.insert_text);
 
-        // Every quick-fix producer except the missing-`@override` fix yields a single
+        // Every quick-fix producer except the ones in `multi_actions` yields a single
         // edit; wrap those in a one-element edit list so they share the multi-edit shape
         // that `multi_actions` and the LSP layer expect.
         fn wrap_single(
@@ -3478,6 +3544,159 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Edits that remove unused imports and merge, group and sort the rest, or `None` if the
+    /// imports of `handle` are already organized.
+    pub fn organize_imports_edits(
+        &self,
+        handle: &Handle,
+    ) -> Option<Vec<(Module, TextRange, String)>> {
+        let mut edits = quick_fixes::organize_imports::organize_imports_edits(self, handle)?;
+        for (module, _, text) in &mut edits {
+            let line_ending = detect_line_ending(module.contents().as_str());
+            if line_ending != "\n" {
+                *text = text.replace('\n', line_ending);
+            }
+        }
+        Some(edits)
+    }
+
+    /// Fixes for the errors shown in `handle` that don't need a human to pick between
+    /// alternatives: adding a missing `@override`, removing a redundant cast or type
+    /// conversion, replacing a value with the suggested enum member, and importing an unknown
+    /// name that exactly one module exports. Fixes may overlap; the caller decides which
+    /// ones to apply.
+    pub fn machine_applicable_fixes(
+        &self,
+        handle: &Handle,
+        import_format: ImportFormat,
+    ) -> Vec<MachineApplicableFix> {
+        let (Some(module_info), Some(ast)) = (self.get_module_info(handle), self.get_ast(handle))
+        else {
+            return Vec::new();
+        };
+        let errors = self.get_errors(vec![handle]).collect_errors().ordinary;
+        let mut fixes = Vec::new();
+        for error in errors {
+            let error_range = error.range();
+            let edits = if error
+                .quick_fixes()
+                .iter()
+                .any(|fix| matches!(fix, ErrorQuickFix::ReplaceWithEnumMember { .. }))
+            {
+                quick_fixes::enum_member::replace_with_enum_member_code_action(
+                    &module_info,
+                    &ast,
+                    &error,
+                )
+                .map(|(_, module, range, text)| vec![(module, range, text)])
+            } else {
+                match error.error_kind() {
+                    ErrorKind::MissingOverrideDecorator => {
+                        quick_fixes::add_override::add_override_code_action(
+                            &module_info,
+                            &ast,
+                            error_range,
+                        )
+                        .map(|(_, module, range, text)| {
+                            let mut edits = vec![(module, range, text)];
+                            if !quick_fixes::add_override::override_in_scope(ast.as_ref())
+                                && let Some(import_edit) = self.override_import_edit(
+                                    handle,
+                                    &module_info,
+                                    &ast,
+                                    import_format,
+                                    None,
+                                )
+                            {
+                                edits.push(import_edit);
+                            }
+                            edits
+                        })
+                    }
+                    ErrorKind::RedundantCast => {
+                        quick_fixes::redundant_cast::redundant_cast_code_action(
+                            &module_info,
+                            &ast,
+                            error_range,
+                        )
+                        .map(|(_, module, range, text)| vec![(module, range, text)])
+                    }
+                    ErrorKind::UnnecessaryTypeConversion => {
+                        quick_fixes::unnecessary_type_conversion::unnecessary_type_conversion_code_action(
+                            &module_info,
+                            &ast,
+                            error_range,
+                        )
+                        .map(|(_, module, range, text)| vec![(module, range, text)])
+                    }
+                    ErrorKind::UnknownName => self
+                        .unambiguous_import_edit(
+                            handle,
+                            &ast,
+                            module_info.code_at(error_range),
+                            import_format,
+                        )
+                        .map(|(range, text)| vec![(module_info.dupe(), range, text)]),
+                    _ => None,
+                }
+            };
+            if let Some(mut edits) = edits {
+                let line_ending = detect_line_ending(module_info.contents().as_str());
+                if line_ending != "\n" {
+                    for (_, _, text) in &mut edits {
+                        *text = text.replace('\n', line_ending);
+                    }
+                }
+                fixes.push(MachineApplicableFix {
+                    error_kind: error.error_kind(),
+                    error_module: module_info.dupe(),
+                    error_range,
+                    edits,
+                });
+            }
+        }
+        fixes
+    }
+
+    /// An edit importing `name` into `handle`, if exactly one public, non-deprecated module
+    /// exports it.
+    fn unambiguous_import_edit(
+        &self,
+        handle: &Handle,
+        ast: &ModModule,
+        name: &str,
+        import_format: ImportFormat,
+    ) -> Option<(TextRange, String)> {
+        let candidates = self
+            .search_exports_exact(name, None)
+            .ok()?
+            .into_iter()
+            .filter(|(candidate, _, export)| {
+                candidate.module() != handle.module()
+                    && export.deprecation.is_none()
+                    && !candidate
+                        .module()
+                        .components()
+                        .iter()
+                        .any(|component| component.as_str().starts_with('_'))
+            })
+            .map(|(candidate, import_name, _)| (candidate.module(), (candidate, import_name)))
+            .collect::<SmallMap<_, _>>();
+        if candidates.len() != 1 {
+            return None;
+        }
+        let (_, (candidate, import_name)) = candidates.into_iter().next()?;
+        let edit = insert_import_edit(
+            ast,
+            self.config_finder(),
+            handle.dupe(),
+            candidate,
+            import_name.as_str(),
+            import_format,
+        );
+        Some((edit.range, edit.insert_text))
+    }
+
     pub fn pytest_fixture_type_annotation_code_actions(
         &self,
         handle: &Handle,
@@ -3639,6 +3858,24 @@ impl<'a> Transaction<'a> {
     ) -> Option<Vec<LocalRefactorCodeAction>> {
         quick_fixes::introduce_parameter::introduce_parameter_code_actions(self, handle, selection)
     }
+
+    pub fn change_signature_target(
+        &self,
+        handle: &Handle,
+        position: TextSize,
+    ) -> Option<ChangeSignatureTarget> {
+        quick_fixes::change_signature::change_signature_target(self, handle, position)
+    }
+
+    pub fn change_signature_edits(
+        &self,
+        handle: &Handle,
+        position: TextSize,
+        parameters: &[ChangeSignatureParameter],
+    ) -> Result<Vec<(Module, TextRange, String)>, String> {
+        quick_fixes::change_signature::change_signature_edits(self, handle, position, parameters)
+    }
+
     pub fn convert_star_import_code_actions(
         &self,
         handle: &Handle,
@@ -3655,6 +3892,16 @@ impl<'a> Transaction<'a> {
         quick_fixes::convert_dict::convert_dict_code_actions(self, handle, selection)
     }
 
+    pub fn add_missing_isinstance_branches_code_actions(
+        &self,
+        handle: &Handle,
+        selection: TextRange,
+    ) -> Option<Vec<LocalRefactorCodeAction>> {
+        quick_fixes::exhaustive_match::add_missing_isinstance_branches_code_actions(
+            self, handle, selection,
+        )
+    }
+
     /// Determines whether a module is a third-party package.
     ///
     /// Checks if the module's path is located within any of the configured
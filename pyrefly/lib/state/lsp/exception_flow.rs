@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The exceptions a function may raise, shown on hover. The solver computes these under
+//! `KeyFunctionRaises`, so hover agrees with the `undocumented-raise` check. Nothing is shown
+//! when that check is disabled, since the raise sites aren't recorded then.
+
+use pyrefly_build::handle::Handle;
+
+use crate::state::state::Transaction;
+use crate::types::function::FunctionKind;
+use crate::types::types::Type;
+
+impl<'a> Transaction<'a> {
+    /// The names of the exceptions that may escape a call to the function `ty`.
+    pub fn raised_exceptions(&self, handle: &Handle, ty: &Type) -> Vec<String> {
+        let Some(FunctionKind::Def(func_id)) = ty.to_func_kind() else {
+            return Vec::new();
+        };
+        self.ad_hoc_solve(handle, "raised_exceptions", |solver| {
+            solver
+                .get_function_raises(func_id)
+                .map_or_else(Vec::new, |raises| {
+                    raises.0.iter().map(|cls| cls.name().to_string()).collect()
+                })
+        })
+        .unwrap_or_default()
+    }
+}
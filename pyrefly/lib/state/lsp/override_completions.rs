@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Completions that override an inherited method or property, offered for the name of a `def`
+//! in a class body.
+
+use lsp_types::CompletionItem;
+use lsp_types::CompletionItemKind;
+use lsp_types::InsertTextMode;
+use lsp_types::TextEdit;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::short_identifier::ShortIdentifier;
+use ruff_python_ast::StmtClassDef;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::small_set::SmallSet;
+
+use super::quick_fixes::add_override::override_in_scope;
+use super::quick_fixes::extract_shared::build_from_import_edit;
+use super::quick_fixes::extract_shared::function_has_decorator;
+use super::quick_fixes::extract_shared::line_indent_and_start;
+use super::quick_fixes::implement_members::OverrideStub;
+use super::quick_fixes::implement_members::override_stub;
+use crate::binding::binding::Key;
+use crate::lsp::wasm::completion::RankedCompletion;
+use crate::state::state::Transaction;
+use crate::types::types::Type;
+
+impl<'a> Transaction<'a> {
+    /// Adds a completion for each method and property `class_def` inherits, for the name of
+    /// `function_def` while nothing follows it yet. Each one completes the signature and a body
+    /// that calls the inherited member, and adds `@override` (from Python 3.12) and the imports
+    /// the annotations need. They replace the plain completions for the same names.
+    pub(crate) fn add_override_completions(
+        &self,
+        handle: &Handle,
+        class_def: &StmtClassDef,
+        function_def: &StmtFunctionDef,
+        completions: &mut Vec<RankedCompletion>,
+    ) {
+        let (Some(module_info), Some(ast)) = (self.get_module_info(handle), self.get_ast(handle))
+        else {
+            return;
+        };
+        let source = module_info.contents().as_str();
+        let name_range = function_def.name.range();
+        let rest_of_line = &source[name_range.end().to_usize()..];
+        if !rest_of_line
+            .split('\n')
+            .next()
+            .is_some_and(|rest| rest.trim().is_empty())
+        {
+            return;
+        }
+        let key = Key::Definition(ShortIdentifier::new(&class_def.name));
+        let Some(Type::ClassDef(cls)) = self.get_type(handle, &key) else {
+            return;
+        };
+        let Some((indent, line_start)) =
+            line_indent_and_start(source, function_def.range().start())
+        else {
+            return;
+        };
+        let Some((class_indent, _)) = line_indent_and_start(source, class_def.range().start())
+        else {
+            return;
+        };
+        let unit = indent
+            .strip_prefix(class_indent.as_str())
+            .filter(|unit| !unit.is_empty())
+            .unwrap_or("    ");
+        let uses_override = handle.sys_info().version().at_least(3, 12)
+            && !function_has_decorator(function_def, "override");
+        let module_name = handle.module();
+        let Some(stubs) = self.ad_hoc_solve(handle, "completion_overrides", |solver| {
+            let own_fields = solver.get_class_fields(&cls);
+            let mut seen = SmallSet::new();
+            let mut stubs: Vec<(Name, OverrideStub)> = Vec::new();
+            for ancestor in solver.get_mro_for_class(&cls).ancestors(solver.stdlib) {
+                let ancestor = ancestor.class_object();
+                let Some(fields) = solver.get_class_fields(ancestor) else {
+                    continue;
+                };
+                for name in fields.names() {
+                    if !seen.insert(name.clone())
+                        || Ast::is_mangled_attr(name)
+                        || name.as_str() == "__class__"
+                        || (*name != function_def.name.id
+                            && own_fields.is_some_and(|fields| fields.contains(name)))
+                    {
+                        continue;
+                    }
+                    let Some(field) = solver.get_class_member(&cls, name) else {
+                        continue;
+                    };
+                    if !field.can_have_override_decorator() || field.is_final() {
+                        continue;
+                    }
+                    // There is nothing to delegate to for an abstract or protocol member.
+                    let call_super = !field.is_abstract() && !ancestor.is_protocol();
+                    if let Some(stub) = override_stub(
+                        solver.stdlib,
+                        module_name,
+                        uses_override,
+                        name,
+                        &solver.for_display(field.ty()),
+                        field.is_property(),
+                        call_super,
+                    ) {
+                        stubs.push((name.clone(), stub));
+                    }
+                }
+            }
+            stubs
+        }) else {
+            return;
+        };
+        let add_override_import = uses_override && !override_in_scope(&ast);
+        let def_keyword = source[..name_range.start().to_usize()]
+            .rfind("def")
+            .and_then(|position| TextSize::try_from(position).ok());
+        let mut names = SmallSet::new();
+        let mut items = Vec::new();
+        for (name, mut stub) in stubs {
+            let mut edits = Vec::new();
+            if !stub.decorators.is_empty() {
+                edits.push(TextEdit {
+                    range: module_info.to_lsp_range(TextRange::empty(line_start)),
+                    new_text: stub
+                        .decorators
+                        .iter()
+                        .map(|decorator| format!("{indent}{decorator}\n"))
+                        .collect(),
+                });
+            }
+            if stub.is_async
+                && !function_def.is_async
+                && let Some(def_keyword) = def_keyword
+            {
+                edits.push(TextEdit {
+                    range: module_info.to_lsp_range(TextRange::empty(def_keyword)),
+                    new_text: "async ".to_owned(),
+                });
+            }
+            if add_override_import {
+                stub.imports
+                    .entry("typing".to_owned())
+                    .or_default()
+                    .insert("override".to_owned());
+            }
+            for (import_module, import_names) in &stub.imports {
+                let import_names = import_names.iter().map(String::as_str).collect::<Vec<_>>();
+                if let Some((_, range, new_text)) =
+                    build_from_import_edit(&module_info, &ast, import_module, &import_names)
+                {
+                    edits.push(TextEdit {
+                        range: module_info.to_lsp_range(range),
+                        new_text,
+                    });
+                }
+            }
+            let keyword = if stub.is_async { "async def" } else { "def" };
+            let signature = stub.header.strip_suffix(':').unwrap_or(&stub.header);
+            names.insert(name.to_string());
+            items.push(RankedCompletion::new(CompletionItem {
+                label: name.to_string(),
+                detail: Some(format!("{keyword} {signature}")),
+                kind: Some(CompletionItemKind::METHOD),
+                insert_text: Some(format!("{}\n{indent}{unit}{}", stub.header, stub.body)),
+                insert_text_mode: Some(InsertTextMode::AS_IS),
+                additional_text_edits: (!edits.is_empty()).then_some(edits),
+                ..Default::default()
+            }));
+        }
+        completions.retain(|completion| !names.contains(completion.label()));
+        completions.extend(items);
+    }
+}
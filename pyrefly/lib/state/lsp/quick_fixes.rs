@@ -6,15 +6,18 @@
  */
 
 pub(crate) mod add_override;
+pub(crate) mod change_signature;
 pub(crate) mod convert_dict;
 pub(crate) mod convert_star_import;
 pub(crate) mod enum_member;
+pub(crate) mod exhaustive_match;
 pub(crate) mod extract_field;
 pub(crate) mod extract_function;
-mod extract_shared;
+pub(crate) mod extract_shared;
 pub(crate) mod extract_superclass;
 pub(crate) mod extract_variable;
 pub(crate) mod generate_code;
+pub(crate) mod implement_members;
 pub(crate) mod inline_method;
 pub(crate) mod inline_parameter;
 pub(crate) mod inline_variable;
@@ -22,9 +25,11 @@ pub(crate) mod introduce_parameter;
 pub(crate) mod invert_boolean;
 pub(crate) mod move_members;
 pub(crate) mod move_module;
+pub(crate) mod organize_imports;
 pub(crate) mod pyrefly_ignore;
 pub(crate) mod pytest_fixture;
 pub(crate) mod redundant_cast;
 pub(crate) mod safe_delete;
 pub(crate) mod types;
 pub(crate) mod unnecessary_type_conversion;
+pub(crate) mod wrap_in_with;
@@ -0,0 +1,1364 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::keywords::is_valid_identifier;
+use pyrefly_python::module::Module;
+use pyrefly_util::visit::Visit;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::ArgOrKeyword;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ExprCall;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Parameter;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtClassDef;
+use ruff_python_ast::StmtFunctionDef;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use serde::Deserialize;
+use serde::Serialize;
+use vec1::Vec1;
+
+use super::extract_shared::first_parameter_name;
+use super::extract_shared::function_has_decorator;
+use crate::alt::callable::CallArg;
+use crate::alt::callable::CallKeyword;
+use crate::alt::expr::TypeOrExpr;
+use crate::state::lsp::FindDefinitionItemWithDocstring;
+use crate::state::lsp::FindPreference;
+use crate::state::lsp::ReferenceOptions;
+use crate::state::lsp::Transaction;
+use crate::types::types::Type;
+
+/// How a parameter can be passed once the signature has been changed.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSignatureParameterKind {
+    PositionalOnly,
+    #[default]
+    PositionalOrKeyword,
+    KeywordOnly,
+}
+
+/// One parameter of the signature requested by a Change Signature refactor. The requested
+/// parameters are listed in their new order; existing parameters that are left out are removed.
+/// The receiver of a method (`self`/`cls`) is never listed and always stays first; `*args` and
+/// `**kwargs` are never listed either and keep their place.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSignatureParameter {
+    pub name: String,
+    /// The existing parameter this one replaces, or `None` to add a new parameter.
+    #[serde(default)]
+    pub original_name: Option<String>,
+    #[serde(default)]
+    pub kind: ChangeSignatureParameterKind,
+    /// Source text of the annotation.
+    #[serde(default)]
+    pub annotation: Option<String>,
+    /// Source text of the default value.
+    #[serde(default)]
+    pub default_value: Option<String>,
+    /// Source text passed for a new parameter at existing call sites. When absent, call sites
+    /// rely on `default_value`.
+    #[serde(default)]
+    pub call_site_value: Option<String>,
+}
+
+/// A function whose signature can be changed, along with its current parameters in the form
+/// a Change Signature request expects them.
+#[derive(Clone, Debug)]
+pub struct ChangeSignatureTarget {
+    pub name: String,
+    pub parameters: Vec<ChangeSignatureParameter>,
+}
+
+/// A function (or method) whose definition and call sites are rewritten.
+struct SignatureOwner<'a> {
+    function_def: &'a StmtFunctionDef,
+    class_name: Option<String>,
+    /// The implicit first parameter of an instance or class method.
+    receiver: Option<String>,
+}
+
+impl<'a> SignatureOwner<'a> {
+    fn new(function_def: &'a StmtFunctionDef, class_def: Option<&StmtClassDef>) -> Self {
+        let is_staticmethod = function_has_decorator(function_def, "staticmethod");
+        let receiver = if class_def.is_some() && !is_staticmethod {
+            first_parameter_name(&function_def.parameters)
+        } else {
+            None
+        };
+        Self {
+            function_def,
+            class_name: class_def.map(|class_def| class_def.name.id.to_string()),
+            receiver,
+        }
+    }
+
+    fn display_name(&self) -> String {
+        match &self.class_name {
+            Some(class_name) => format!("{class_name}.{}", self.function_def.name.id),
+            None => self.function_def.name.id.to_string(),
+        }
+    }
+
+    /// The declared parameters, minus the receiver, `*args` and `**kwargs`.
+    fn existing_parameters(&self, module: &Module) -> Vec<ExistingParameter> {
+        let parameters = &self.function_def.parameters;
+        let mut existing = Vec::new();
+        for (kind, params) in [
+            (
+                ChangeSignatureParameterKind::PositionalOnly,
+                &parameters.posonlyargs,
+            ),
+            (
+                ChangeSignatureParameterKind::PositionalOrKeyword,
+                &parameters.args,
+            ),
+            (
+                ChangeSignatureParameterKind::KeywordOnly,
+                &parameters.kwonlyargs,
+            ),
+        ] {
+            for param in params {
+                existing.push(ExistingParameter {
+                    name: param.name().id.to_string(),
+                    kind,
+                    annotation: param
+                        .annotation()
+                        .map(|annotation| module.code_at(annotation.range()).to_owned()),
+                    default_value: param
+                        .default()
+                        .map(|default| module.code_at(default.range()).to_owned()),
+                });
+            }
+        }
+        if self.receiver.is_some() && !existing.is_empty() {
+            existing.remove(0);
+        }
+        existing
+    }
+
+    /// The names of the `*args` and `**kwargs` parameters.
+    fn variadic_names(&self) -> (Option<&str>, Option<&str>) {
+        let parameters = &self.function_def.parameters;
+        (
+            parameters
+                .vararg
+                .as_ref()
+                .map(|param| param.name.id.as_str()),
+            parameters
+                .kwarg
+                .as_ref()
+                .map(|param| param.name.id.as_str()),
+        )
+    }
+
+    /// The `*args` and `**kwargs` parameters as written, which a change of signature keeps.
+    fn variadic_texts(&self, module: &Module) -> (Option<String>, Option<String>) {
+        let render = |prefix: &str, param: &Parameter| match &param.annotation {
+            Some(annotation) => format!(
+                "{prefix}{}: {}",
+                param.name.id,
+                module.code_at(annotation.range())
+            ),
+            None => format!("{prefix}{}", param.name.id),
+        };
+        let parameters = &self.function_def.parameters;
+        (
+            parameters.vararg.as_deref().map(|param| render("*", param)),
+            parameters.kwarg.as_deref().map(|param| render("**", param)),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ExistingParameter {
+    name: String,
+    kind: ChangeSignatureParameterKind,
+    annotation: Option<String>,
+    default_value: Option<String>,
+}
+
+/// An argument of a call being rewritten, as written in the source.
+struct CallArgument {
+    /// The whole argument, including any `name=`, `*` or `**`.
+    range: TextRange,
+    /// The name in `name=value`.
+    keyword: Option<TextRange>,
+    /// The value in `name=value`, or the whole argument otherwise.
+    value: TextRange,
+    binding: ArgumentBinding,
+}
+
+impl CallArgument {
+    fn in_keyword_section(&self) -> bool {
+        self.keyword.is_some() || matches!(self.binding, ArgumentBinding::UnpackedKeywords)
+    }
+}
+
+/// What an argument binds to, according to the type checker's matching of the call.
+enum ArgumentBinding {
+    /// The explicit receiver in `Class.method(obj, ...)`.
+    Receiver,
+    /// An existing parameter, other than `*args` and `**kwargs`.
+    Parameter(String),
+    /// A value collected by `*args` or `**kwargs`.
+    Collected,
+    /// `*xs`, filling the listed positional parameters in order, and `*args` too when `spills`
+    /// is set.
+    UnpackedPositional { fills: Vec<String>, spills: bool },
+    /// `**xs`.
+    UnpackedKeywords,
+}
+
+/// An argument of the rewritten call, passed as `name=value` when `keyword` is set.
+#[derive(Clone)]
+struct NewArgument {
+    source: ArgumentSource,
+    keyword: Option<String>,
+}
+
+#[derive(Clone)]
+enum ArgumentSource {
+    /// The argument at this index of the original call.
+    Existing(usize),
+    /// The value passed for a new parameter.
+    Added(String),
+}
+
+impl NewArgument {
+    fn existing_index(&self) -> Option<usize> {
+        match self.source {
+            ArgumentSource::Existing(index) => Some(index),
+            ArgumentSource::Added(_) => None,
+        }
+    }
+}
+
+/// A function to rewrite, with everything needed to rewrite it and its calls.
+struct RewriteTarget<'a> {
+    owner: SignatureOwner<'a>,
+    module: Module,
+    definition: FindDefinitionItemWithDocstring,
+    existing: Vec<ExistingParameter>,
+}
+
+/// Returns the function whose header contains `position`, if its signature can be changed.
+pub(crate) fn change_signature_target(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    position: TextSize,
+) -> Option<ChangeSignatureTarget> {
+    let module_info = transaction.get_module_info(handle)?;
+    let ast = transaction.get_ast(handle)?;
+    let owner = find_owner_at(ast.as_ref(), position)?;
+    let existing = owner.existing_parameters(&module_info);
+    Some(ChangeSignatureTarget {
+        name: owner.function_def.name.id.to_string(),
+        parameters: existing
+            .into_iter()
+            .map(|param| ChangeSignatureParameter {
+                name: param.name.clone(),
+                original_name: Some(param.name),
+                kind: param.kind,
+                annotation: param.annotation,
+                default_value: param.default_value,
+                call_site_value: None,
+            })
+            .collect(),
+    })
+}
+
+/// Builds the edits that give the function whose header contains `position` the requested
+/// parameter list. Every call site found through find-references is rewritten, as are the
+/// overrides of a method and their call sites. Returns a user-facing message when the change
+/// can't be applied safely.
+pub(crate) fn change_signature_edits(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    position: TextSize,
+    requested: &[ChangeSignatureParameter],
+) -> Result<Vec<(Module, TextRange, String)>, String> {
+    let not_found = || "No function definition found at the cursor".to_owned();
+    let module_info = transaction.get_module_info(handle).ok_or_else(not_found)?;
+    let ast = transaction.get_ast(handle).ok_or_else(not_found)?;
+    let owner = find_owner_at(ast.as_ref(), position).ok_or_else(not_found)?;
+    let existing = owner.existing_parameters(&module_info);
+    validate_request(&owner, &existing, requested)?;
+    let definition =
+        owner_definition(transaction, handle, &module_info, &owner).ok_or_else(|| {
+            format!(
+                "Cannot find the definition of `{}`",
+                owner.function_def.name.id
+            )
+        })?;
+
+    let override_asts = override_asts(transaction, &definition);
+    let mut targets = vec![RewriteTarget {
+        owner,
+        module: module_info.dupe(),
+        definition,
+        existing,
+    }];
+    for (override_handle, override_module, override_ast, name_range) in &override_asts {
+        let Some(override_owner) = find_owner_by_name_range(override_ast.as_ref(), *name_range)
+        else {
+            continue;
+        };
+        let override_existing = override_owner.existing_parameters(override_module);
+        let same_names = override_existing.len() == targets[0].existing.len()
+            && override_existing
+                .iter()
+                .zip(&targets[0].existing)
+                .all(|(a, b)| a.name == b.name);
+        if !same_names {
+            return Err(format!(
+                "Cannot update override `{}` because its parameters differ from `{}`",
+                override_owner.display_name(),
+                targets[0].owner.display_name()
+            ));
+        }
+        let Some(override_definition) = owner_definition(
+            transaction,
+            override_handle,
+            override_module,
+            &override_owner,
+        ) else {
+            continue;
+        };
+        targets.push(RewriteTarget {
+            owner: override_owner,
+            module: override_module.dupe(),
+            definition: override_definition,
+            existing: override_existing,
+        });
+    }
+
+    let base_existing = targets[0].existing.clone();
+    let mut edits = Vec::new();
+    for module_handle in transaction.handles() {
+        let Some(module) = transaction.get_module_info(&module_handle) else {
+            continue;
+        };
+        let mut calls = collect_calls(transaction, &module_handle, &targets).unwrap_or_default();
+        let signatures = targets
+            .iter()
+            .filter(|target| target.module.path() == module.path())
+            .collect::<Vec<_>>();
+        if calls.is_empty() && signatures.is_empty() {
+            continue;
+        }
+        let mut module_edits = Vec::new();
+        for target in &signatures {
+            module_edits.extend(renamed_parameter_references(
+                transaction,
+                &module_handle,
+                target,
+                requested,
+            ));
+        }
+        // Rewrite inner calls first so that calls passed as arguments keep their rewrites.
+        calls.sort_by_key(|(call, _)| call.range().len());
+        for (call, target) in calls {
+            for (range, text) in rewrite_call(
+                transaction,
+                &module_handle,
+                &module,
+                &call,
+                target,
+                requested,
+                &module_edits,
+            )? {
+                push_edit(&mut module_edits, range, text);
+            }
+        }
+        for target in signatures {
+            let text = signature_text(
+                target.owner.receiver.as_deref(),
+                target.owner.variadic_texts(&target.module),
+                &base_existing,
+                &target.existing,
+                requested,
+            );
+            push_edit(
+                &mut module_edits,
+                target.owner.function_def.parameters.range(),
+                text,
+            );
+        }
+        edits.extend(
+            merge_insertions(module_edits)
+                .into_iter()
+                .map(|(range, text)| (module.dupe(), range, text)),
+        );
+    }
+    Ok(edits)
+}
+
+/// Finds the innermost function whose header (decorators through parameters) contains
+/// `position`.
+fn find_owner_at(ast: &ModModule, position: TextSize) -> Option<SignatureOwner<'_>> {
+    let covering_nodes = Ast::locate_node(ast, position);
+    for (idx, node) in covering_nodes.iter().enumerate() {
+        if let AnyNodeRef::StmtFunctionDef(function_def) = node {
+            let header = TextRange::new(
+                function_def.range().start(),
+                function_def.parameters.range().end(),
+            );
+            if !header.contains_inclusive(position) {
+                return None;
+            }
+            let class_def = match covering_nodes.get(idx + 1) {
+                Some(AnyNodeRef::StmtClassDef(class_def)) => Some(*class_def),
+                _ => None,
+            };
+            return Some(SignatureOwner::new(function_def, class_def));
+        }
+    }
+    None
+}
+
+/// Finds the method whose name is at `name_range`, searching class bodies at any depth.
+fn find_owner_by_name_range(ast: &ModModule, name_range: TextRange) -> Option<SignatureOwner<'_>> {
+    fn search<'a>(
+        body: &'a [Stmt],
+        class_def: Option<&'a StmtClassDef>,
+        name_range: TextRange,
+    ) -> Option<SignatureOwner<'a>> {
+        for stmt in body {
+            match stmt {
+                Stmt::FunctionDef(function_def) if function_def.name.range == name_range => {
+                    return Some(SignatureOwner::new(function_def, class_def));
+                }
+                Stmt::ClassDef(nested) if nested.range().contains_range(name_range) => {
+                    return search(&nested.body, Some(nested), name_range);
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+    search(&ast.body, None, name_range)
+}
+
+fn owner_definition(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    module: &Module,
+    owner: &SignatureOwner<'_>,
+) -> Option<FindDefinitionItemWithDocstring> {
+    let name_range = owner.function_def.name.range;
+    transaction
+        .find_definition(handle, name_range.start(), FindPreference::default())
+        .map(Vec1::into_vec)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|def| {
+            def.module.path() == module.path() && def.definition_range.contains_range(name_range)
+        })
+}
+
+/// Returns the methods that override `definition`, according to the override graph recorded
+/// in each module's index.
+fn override_asts(
+    transaction: &Transaction<'_>,
+    definition: &FindDefinitionItemWithDocstring,
+) -> Vec<(Handle, Module, Arc<ModModule>, TextRange)> {
+    let mut overrides = Vec::new();
+    for module_handle in transaction.handles() {
+        let Some(index) = transaction
+            .get_solutions(&module_handle)
+            .and_then(|solutions| solutions.get_index())
+        else {
+            continue;
+        };
+        let child_ranges = index
+            .lock()
+            .parent_methods_map
+            .iter()
+            .filter(|(_, parents)| {
+                parents.iter().any(|(path, range)| {
+                    path == definition.module.path() && *range == definition.definition_range
+                })
+            })
+            .map(|(child_range, _)| *child_range)
+            .collect::<Vec<_>>();
+        if child_ranges.is_empty() {
+            continue;
+        }
+        let (Some(module), Some(ast)) = (
+            transaction.get_module_info(&module_handle),
+            transaction.get_ast(&module_handle),
+        ) else {
+            continue;
+        };
+        for child_range in child_ranges {
+            overrides.push((module_handle.dupe(), module.dupe(), ast.dupe(), child_range));
+        }
+    }
+    overrides
+}
+
+fn validate_request(
+    owner: &SignatureOwner<'_>,
+    existing: &[ExistingParameter],
+    requested: &[ChangeSignatureParameter],
+) -> Result<(), String> {
+    let mut names = HashSet::new();
+    let (vararg, kwarg) = owner.variadic_names();
+    names.extend(
+        owner
+            .receiver
+            .as_deref()
+            .into_iter()
+            .chain(vararg)
+            .chain(kwarg),
+    );
+    let mut originals = HashSet::new();
+    let mut previous_kind = ChangeSignatureParameterKind::PositionalOnly;
+    let mut previous_default: Option<&str> = None;
+    for param in requested {
+        if !is_valid_identifier(&param.name) {
+            return Err(format!("`{}` is not a valid parameter name", param.name));
+        }
+        if !names.insert(param.name.as_str()) {
+            return Err(format!("Duplicate parameter `{}`", param.name));
+        }
+        match &param.original_name {
+            Some(original) => {
+                if !existing.iter().any(|existing| &existing.name == original) {
+                    return Err(format!(
+                        "`{}` has no parameter named `{original}`",
+                        owner.display_name()
+                    ));
+                }
+                if !originals.insert(original.as_str()) {
+                    return Err(format!("Parameter `{original}` is listed more than once"));
+                }
+            }
+            None if param.default_value.is_none() && param.call_site_value.is_none() => {
+                return Err(format!(
+                    "New parameter `{}` needs a default value or a value to pass at existing call sites",
+                    param.name
+                ));
+            }
+            None => {}
+        }
+        if param.kind < previous_kind {
+            return Err(format!(
+                "Parameter `{}` is out of order: positional-only parameters must come first and keyword-only parameters last",
+                param.name
+            ));
+        }
+        previous_kind = param.kind;
+        if param.kind != ChangeSignatureParameterKind::KeywordOnly {
+            if let Some(previous) = previous_default
+                && param.default_value.is_none()
+            {
+                return Err(format!(
+                    "Parameter `{}` without a default follows parameter `{previous}` with a default",
+                    param.name
+                ));
+            }
+            if param.default_value.is_some() {
+                previous_default = Some(&param.name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Renders the new parameter list, parentheses included. Annotations and defaults of existing
+/// parameters are only replaced when the request changes them relative to `base`, so overrides
+/// keep their own. `*args` goes between the positional and keyword-only parameters and
+/// `**kwargs` last.
+fn signature_text<'p>(
+    receiver: Option<&str>,
+    (mut vararg, kwarg): (Option<String>, Option<String>),
+    base: &'p [ExistingParameter],
+    own: &'p [ExistingParameter],
+    requested: &[ChangeSignatureParameter],
+) -> String {
+    let mut parts = receiver.map(str::to_owned).into_iter().collect::<Vec<_>>();
+    let mut previous_kind = None;
+    for param in requested {
+        if previous_kind == Some(ChangeSignatureParameterKind::PositionalOnly)
+            && param.kind != ChangeSignatureParameterKind::PositionalOnly
+        {
+            parts.push("/".to_owned());
+        }
+        if param.kind == ChangeSignatureParameterKind::KeywordOnly
+            && previous_kind != Some(ChangeSignatureParameterKind::KeywordOnly)
+        {
+            parts.push(vararg.take().unwrap_or_else(|| "*".to_owned()));
+        }
+        previous_kind = Some(param.kind);
+        let find = |params: &'p [ExistingParameter]| {
+            params
+                .iter()
+                .find(|existing| param.original_name.as_ref() == Some(&existing.name))
+        };
+        let (base_param, own_param) = (find(base), find(own));
+        let annotation = pick_value(
+            &param.annotation,
+            base_param.map(|existing| &existing.annotation),
+            own_param.map(|existing| &existing.annotation),
+        );
+        let default_value = pick_value(
+            &param.default_value,
+            base_param.map(|existing| &existing.default_value),
+            own_param.map(|existing| &existing.default_value),
+        );
+        parts.push(match (annotation, default_value) {
+            (Some(annotation), Some(default)) => {
+                format!("{}: {annotation} = {default}", param.name)
+            }
+            (Some(annotation), None) => format!("{}: {annotation}", param.name),
+            (None, Some(default)) => format!("{}={default}", param.name),
+            (None, None) => param.name.clone(),
+        });
+    }
+    if previous_kind == Some(ChangeSignatureParameterKind::PositionalOnly) {
+        parts.push("/".to_owned());
+    }
+    parts.extend(vararg);
+    parts.extend(kwarg);
+    format!("({})", parts.join(", "))
+}
+
+/// Picks the annotation or default to write for a parameter. A method override keeps its own
+/// value for an existing parameter unless the request changes the value in the base method.
+fn pick_value(
+    requested: &Option<String>,
+    base: Option<&Option<String>>,
+    own: Option<&Option<String>>,
+) -> Option<String> {
+    match (base, own) {
+        (Some(base), Some(own)) if base == requested => own.clone(),
+        _ => requested.clone(),
+    }
+}
+
+/// Finds the uses of renamed parameters in the body of `target`, paired with their new names.
+fn renamed_parameter_references(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    target: &RewriteTarget<'_>,
+    requested: &[ChangeSignatureParameter],
+) -> Vec<(TextRange, String)> {
+    let parameters = &target.owner.function_def.parameters;
+    let mut edits = Vec::new();
+    for param in requested {
+        let Some(original) = &param.original_name else {
+            continue;
+        };
+        if *original == param.name {
+            continue;
+        }
+        let Some(declared) = parameters
+            .iter_non_variadic_params()
+            .find(|declared| declared.name().id.as_str() == original)
+        else {
+            continue;
+        };
+        for range in transaction.find_local_references(
+            handle,
+            declared.name().range.start(),
+            ReferenceOptions::textual_only(false),
+        ) {
+            if !parameters.range().contains_range(range) {
+                edits.push((range, param.name.clone()));
+            }
+        }
+    }
+    edits
+}
+
+/// Collects the calls in `handle` whose callee refers to one of `targets`.
+fn collect_calls<'t, 'a>(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    targets: &'t [RewriteTarget<'a>],
+) -> Option<Vec<(ExprCall, &'t RewriteTarget<'a>)>> {
+    let mut callee_ends: HashMap<TextSize, &'t RewriteTarget<'a>> = HashMap::new();
+    for target in targets {
+        let Some(refs) = transaction.local_references_from_definition(
+            handle,
+            target.definition.metadata.clone(),
+            target.definition.definition_range,
+            &target.definition.module,
+            ReferenceOptions::textual_only(true),
+        ) else {
+            continue;
+        };
+        for range in refs {
+            callee_ends.insert(range.end(), target);
+        }
+    }
+    if callee_ends.is_empty() {
+        return Some(Vec::new());
+    }
+    let ast = transaction.get_ast(handle)?;
+    let mut calls = Vec::new();
+    ast.as_ref().visit(&mut |expr| {
+        if let Expr::Call(call) = expr
+            && let Some(target) = callee_ends.get(&call.func.range().end())
+        {
+            calls.push((call.clone(), *target));
+        }
+    });
+    Some(calls)
+}
+
+/// Rewrites the arguments of `call` for the requested signature. Only arguments that are added,
+/// removed, moved or switched between positional and keyword form are edited, so the others
+/// keep their text, comments and layout.
+fn rewrite_call(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    module: &Module,
+    call: &ExprCall,
+    target: &RewriteTarget<'_>,
+    requested: &[ChangeSignatureParameter],
+    edits: &[(TextRange, String)],
+) -> Result<Vec<(TextRange, String)>, String> {
+    let refuse = |reason: String| {
+        format!(
+            "Cannot rewrite the call to `{}` at {}:{} because {reason}",
+            target.owner.display_name(),
+            module.name(),
+            module.display_pos(call.range().start())
+        )
+    };
+    let arguments = call_arguments(transaction, handle, call, target).map_err(refuse)?;
+    let new = new_arguments(module, &arguments, target, requested).map_err(refuse)?;
+    Ok(argument_edits(module, call, &arguments, &new, edits))
+}
+
+/// Maps the range of each argument of `call` to the parameter it binds to, using the type
+/// checker's matching of arguments to parameters. Arguments collected by `*args` or `**kwargs`
+/// map to that parameter, and `*xs` maps to the last parameter it fills.
+fn argument_parameters(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    call: &ExprCall,
+) -> Option<HashMap<TextRange, Option<Name>>> {
+    let callee = transaction.get_type_trace(handle, call.func.range())?;
+    let type_of = |expr: &Expr| {
+        transaction
+            .get_type_trace(handle, expr.range())
+            .unwrap_or_else(Type::any_implicit)
+    };
+    let arg_types = call
+        .arguments
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Expr::Starred(starred) => type_of(&starred.value),
+            _ => type_of(arg),
+        })
+        .collect::<Vec<_>>();
+    let keyword_types = call
+        .arguments
+        .keywords
+        .iter()
+        .map(|keyword| type_of(&keyword.value))
+        .collect::<Vec<_>>();
+    let argmap =
+        transaction.ad_hoc_solve(handle, "change_signature_match_arguments", |solver| {
+            let args = call
+                .arguments
+                .args
+                .iter()
+                .zip(&arg_types)
+                .map(|(arg, ty)| match arg {
+                    Expr::Starred(starred) => {
+                        CallArg::Star(TypeOrExpr::Type(ty, starred.value.range()), arg.range())
+                    }
+                    _ => CallArg::ty(ty, arg.range()),
+                })
+                .collect::<Vec<_>>();
+            let keywords = call
+                .arguments
+                .keywords
+                .iter()
+                .zip(&keyword_types)
+                .map(|(keyword, ty)| CallKeyword {
+                    range: keyword.range,
+                    arg: keyword.arg.as_ref(),
+                    value: TypeOrExpr::Type(ty, keyword.value.range()),
+                })
+                .collect::<Vec<_>>();
+            solver.match_call_arguments(callee, &args, &keywords, call.arguments.range())
+        })??;
+    Some(
+        argmap
+            .range_to_param
+            .into_iter()
+            .map(|(range, param)| (range, param.name))
+            .collect(),
+    )
+}
+
+/// Lists the arguments of `call` in source order, along with what each one binds to.
+fn call_arguments(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    call: &ExprCall,
+    target: &RewriteTarget<'_>,
+) -> Result<Vec<CallArgument>, String> {
+    let bindings = argument_parameters(transaction, handle, call)
+        .ok_or_else(|| "its arguments can't be matched to the parameters".to_owned())?;
+    let (vararg, kwarg) = target.owner.variadic_names();
+    let positional = target
+        .existing
+        .iter()
+        .filter(|param| param.kind != ChangeSignatureParameterKind::KeywordOnly)
+        .map(|param| param.name.as_str())
+        .collect::<Vec<_>>();
+    let bound_to = |range: TextRange| {
+        bindings
+            .get(&range)
+            .and_then(|name| name.as_ref())
+            .map(|name| name.as_str())
+    };
+    let mut next_positional = 0;
+    let mut seen = HashSet::new();
+    let mut arguments = Vec::new();
+    for argument in call.arguments.iter_source_order() {
+        let argument = match argument {
+            ArgOrKeyword::Arg(Expr::Starred(starred)) => {
+                let start = next_positional;
+                let (end, spills) = match bound_to(starred.range) {
+                    Some(name) if vararg == Some(name) => (positional.len(), true),
+                    Some(name) => match positional.iter().position(|param| *param == name) {
+                        Some(index) => (index + 1, false),
+                        None => return Err(format!("it unpacks arguments into `{name}`")),
+                    },
+                    None => (start, false),
+                };
+                next_positional = end.max(start);
+                CallArgument {
+                    range: starred.range,
+                    keyword: None,
+                    value: starred.range,
+                    binding: ArgumentBinding::UnpackedPositional {
+                        fills: positional[start..next_positional]
+                            .iter()
+                            .map(|param| (*param).to_owned())
+                            .collect(),
+                        spills,
+                    },
+                }
+            }
+            ArgOrKeyword::Arg(arg) => {
+                let binding = match bound_to(arg.range()) {
+                    Some(name) if target.owner.receiver.as_deref() == Some(name) => {
+                        ArgumentBinding::Receiver
+                    }
+                    Some(name) if vararg == Some(name) => ArgumentBinding::Collected,
+                    Some(name) => match positional.iter().position(|param| *param == name) {
+                        Some(index) => {
+                            next_positional = index + 1;
+                            ArgumentBinding::Parameter(name.to_owned())
+                        }
+                        None => {
+                            return Err("it passes too many positional arguments".to_owned());
+                        }
+                    },
+                    None => return Err("it passes too many positional arguments".to_owned()),
+                };
+                CallArgument {
+                    range: arg.range(),
+                    keyword: None,
+                    value: arg.range(),
+                    binding,
+                }
+            }
+            ArgOrKeyword::Keyword(keyword) => match &keyword.arg {
+                None => CallArgument {
+                    range: keyword.range,
+                    keyword: None,
+                    value: keyword.range,
+                    binding: ArgumentBinding::UnpackedKeywords,
+                },
+                Some(arg) => {
+                    let binding = match bound_to(keyword.range) {
+                        Some(name) if kwarg == Some(name) => ArgumentBinding::Collected,
+                        Some(name) if target.existing.iter().any(|param| param.name == name) => {
+                            ArgumentBinding::Parameter(name.to_owned())
+                        }
+                        _ => return Err(format!("it passes unknown keyword `{}`", arg.id)),
+                    };
+                    CallArgument {
+                        range: keyword.range,
+                        keyword: Some(arg.range),
+                        value: keyword.value.range(),
+                        binding,
+                    }
+                }
+            },
+        };
+        if let ArgumentBinding::Parameter(name) = &argument.binding
+            && !seen.insert(name.clone())
+        {
+            return Err(format!("it passes `{name}` more than once"));
+        }
+        arguments.push(argument);
+    }
+    Ok(arguments)
+}
+
+/// Lays out the arguments of the rewritten call: positional arguments in parameter order,
+/// then keyword arguments. Arguments already passed by keyword keep their relative order.
+fn new_arguments(
+    module: &Module,
+    arguments: &[CallArgument],
+    target: &RewriteTarget<'_>,
+    requested: &[ChangeSignatureParameter],
+) -> Result<Vec<NewArgument>, String> {
+    let existing = |index: usize, keyword: Option<String>| NewArgument {
+        source: ArgumentSource::Existing(index),
+        keyword,
+    };
+    let text = |index: usize| module.code_at(arguments[index].range);
+    let mut positional = Vec::new();
+    let mut keywords = Vec::new();
+    let mut collected_keywords = Vec::new();
+    let mut collected_positional = Vec::new();
+    let mut bound = HashMap::new();
+    // Each `*xs` that fills parameters, keyed by the first parameter it fills.
+    let mut unpacked = HashMap::new();
+    for (index, argument) in arguments.iter().enumerate() {
+        match &argument.binding {
+            ArgumentBinding::Receiver => positional.push(existing(index, None)),
+            ArgumentBinding::Parameter(name) => {
+                bound.insert(name.as_str(), index);
+            }
+            ArgumentBinding::Collected => match argument.keyword {
+                Some(keyword) => collected_keywords
+                    .push(existing(index, Some(module.code_at(keyword).to_owned()))),
+                None => collected_positional.push(index),
+            },
+            ArgumentBinding::UnpackedPositional { fills, spills } => match fills.first() {
+                Some(first) => {
+                    unpacked.insert(first.as_str(), (index, fills, *spills));
+                }
+                None => collected_positional.push(index),
+            },
+            ArgumentBinding::UnpackedKeywords => collected_keywords.push(existing(index, None)),
+        }
+    }
+
+    // Once an argument is omitted or passed by keyword, later ones must be passed by keyword.
+    let mut keywords_only = false;
+    // Whether a positional parameter is no longer passed positionally, so values collected by
+    // `*args` would fill it instead.
+    let mut positional_gap = false;
+    let mut placed = HashSet::new();
+    let mut i = 0;
+    while let Some(param) = requested.get(i) {
+        let original = param.original_name.as_deref();
+        if let Some(&(index, fills, spills)) = original.and_then(|original| unpacked.get(original))
+        {
+            let run = requested.get(i..i + fills.len()).unwrap_or_default();
+            let keeps_fills = !keywords_only
+                && run.len() == fills.len()
+                && run.iter().zip(fills).all(|(param, fill)| {
+                    param.original_name.as_ref() == Some(fill)
+                        && param.kind != ChangeSignatureParameterKind::KeywordOnly
+                });
+            if !keeps_fills
+                || (spills
+                    && requested[i + fills.len()..]
+                        .iter()
+                        .any(|param| param.kind != ChangeSignatureParameterKind::KeywordOnly))
+            {
+                return Err(format!(
+                    "`{}` would no longer fill the same parameters",
+                    text(index)
+                ));
+            }
+            placed.insert(index);
+            positional.push(existing(index, None));
+            i += fills.len();
+            continue;
+        }
+        i += 1;
+        let value = match original {
+            Some(original) => bound.get(original).map(|&index| {
+                (
+                    ArgumentSource::Existing(index),
+                    arguments[index].keyword.is_some(),
+                )
+            }),
+            None => param
+                .call_site_value
+                .clone()
+                .map(|text| (ArgumentSource::Added(text), false)),
+        };
+        let Some((source, by_keyword)) = value else {
+            keywords_only = true;
+            positional_gap |= param.kind != ChangeSignatureParameterKind::KeywordOnly;
+            continue;
+        };
+        let use_keyword = param.kind == ChangeSignatureParameterKind::KeywordOnly
+            || keywords_only
+            || (by_keyword && param.kind != ChangeSignatureParameterKind::PositionalOnly);
+        if use_keyword && param.kind == ChangeSignatureParameterKind::PositionalOnly {
+            return Err(format!(
+                "positional-only parameter `{}` would follow an omitted argument",
+                param.name
+            ));
+        }
+        if use_keyword {
+            keywords_only = true;
+            positional_gap |= param.kind != ChangeSignatureParameterKind::KeywordOnly;
+            keywords.push(NewArgument {
+                source,
+                keyword: Some(param.name.clone()),
+            });
+        } else {
+            positional.push(NewArgument {
+                source,
+                keyword: None,
+            });
+        }
+    }
+    if let Some((index, ..)) = unpacked
+        .values()
+        .filter(|(index, ..)| !placed.contains(index))
+        .min_by_key(|(index, ..)| *index)
+    {
+        return Err(format!(
+            "`{}` would no longer fill the same parameters",
+            text(*index)
+        ));
+    }
+    if let Some(&index) = collected_positional.first()
+        && positional_gap
+    {
+        return Err(format!(
+            "positional argument `{}` would follow an omitted argument",
+            text(index)
+        ));
+    }
+    positional.extend(
+        collected_positional
+            .into_iter()
+            .map(|index| existing(index, None)),
+    );
+    if let Some(index) = arguments
+        .iter()
+        .position(|argument| matches!(argument.binding, ArgumentBinding::UnpackedKeywords))
+    {
+        // `**xs` may pass any parameter this call doesn't pass explicitly, by its current name.
+        let unchanged = |name: &str| {
+            requested
+                .iter()
+                .any(|param| param.name == name && param.original_name.as_deref() == Some(name))
+        };
+        if let Some(param) = target.existing.iter().find(|param| {
+            param.kind != ChangeSignatureParameterKind::PositionalOnly
+                && !bound.contains_key(param.name.as_str())
+                && !unchanged(&param.name)
+        }) {
+            return Err(format!(
+                "`{}` may pass `{}` by keyword",
+                text(index),
+                param.name
+            ));
+        }
+    }
+
+    keywords.extend(collected_keywords);
+    // Put the arguments that were already passed by keyword back in their original order, so a
+    // call isn't reshuffled just because the parameters were.
+    let slots = keywords
+        .iter()
+        .enumerate()
+        .filter(|(_, argument)| {
+            argument
+                .existing_index()
+                .is_some_and(|index| arguments[index].in_keyword_section())
+        })
+        .map(|(slot, _)| slot)
+        .collect::<Vec<_>>();
+    let mut reordered = slots
+        .iter()
+        .map(|&slot| keywords[slot].clone())
+        .collect::<Vec<_>>();
+    reordered.sort_by_key(NewArgument::existing_index);
+    for (slot, argument) in slots.into_iter().zip(reordered) {
+        keywords[slot] = argument;
+    }
+    positional.extend(keywords);
+    Ok(positional)
+}
+
+/// Builds the edits that turn the arguments of `call` into `new`. The longest run of arguments
+/// that keep their relative order stays in place; the others are deleted and, if still passed,
+/// reinserted next to their new neighbours along with the added ones. Inserted arguments follow
+/// the layout of the call, one per line when it puts its arguments on separate lines.
+fn argument_edits(
+    module: &Module,
+    call: &ExprCall,
+    arguments: &[CallArgument],
+    new: &[NewArgument],
+    edits: &[(TextRange, String)],
+) -> Vec<(TextRange, String)> {
+    let kept = new
+        .iter()
+        .filter_map(NewArgument::existing_index)
+        .collect::<Vec<_>>();
+    let anchors = longest_increasing_subsequence(&kept)
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let anchor = |argument: &NewArgument| {
+        argument
+            .existing_index()
+            .filter(|index| anchors.contains(index))
+    };
+    let render = |argument: &NewArgument| {
+        let value = match &argument.source {
+            ArgumentSource::Existing(index) => {
+                text_with_edits(module, arguments[*index].value, edits)
+            }
+            ArgumentSource::Added(text) => text.clone(),
+        };
+        match &argument.keyword {
+            Some(name) => format!("{name}={value}"),
+            None => value,
+        }
+    };
+    let open = call.arguments.range().start() + TextSize::from(1);
+    let close = call.arguments.range().end() - TextSize::from(1);
+    let indent = argument_indent(module, open, arguments);
+    let separator = match &indent {
+        Some(indent) => format!(",\n{indent}"),
+        None => ", ".to_owned(),
+    };
+    let mut result = Vec::new();
+
+    // Arguments that don't stay in place go before the next argument that does, or after the
+    // last one.
+    let mut pending = Vec::new();
+    let mut last_anchor = None;
+    for argument in new {
+        match anchor(argument) {
+            Some(index) => {
+                if !pending.is_empty() {
+                    result.push((
+                        TextRange::empty(arguments[index].range.start()),
+                        pending
+                            .drain(..)
+                            .map(|text| format!("{text}{separator}"))
+                            .collect::<String>(),
+                    ));
+                }
+                last_anchor = Some(index);
+            }
+            None => pending.push(render(argument)),
+        }
+    }
+    if !pending.is_empty() {
+        result.push(match last_anchor {
+            Some(index) => {
+                let end = arguments[index].range.end();
+                let next = arguments
+                    .get(index + 1)
+                    .map_or(close, |next| next.range.start());
+                let tail = module.code_at(TextRange::new(end, next));
+                match (&indent, tail.find('\n')) {
+                    // Add whole lines after the line of the last argument when it ends with a comma.
+                    (Some(indent), Some(newline)) if tail.trim_start().starts_with(',') => (
+                        TextRange::empty(end + TextSize::of(&tail[..=newline])),
+                        pending
+                            .iter()
+                            .map(|text| format!("{indent}{text},\n"))
+                            .collect(),
+                    ),
+                    _ => (
+                        TextRange::empty(end),
+                        pending
+                            .iter()
+                            .map(|text| format!("{separator}{text}"))
+                            .collect(),
+                    ),
+                }
+            }
+            None => (
+                TextRange::empty(arguments.first().map_or(open, |first| first.range.start())),
+                pending.join(&separator),
+            ),
+        });
+    }
+
+    for argument in new {
+        let Some(index) = anchor(argument) else {
+            continue;
+        };
+        let original = &arguments[index];
+        match (original.keyword, &argument.keyword) {
+            (Some(range), Some(name)) if module.code_at(range) != name.as_str() => {
+                result.push((range, name.clone()));
+            }
+            (Some(_), None) => result.push((
+                TextRange::new(original.range.start(), original.value.start()),
+                String::new(),
+            )),
+            (None, Some(name)) => {
+                result.push((TextRange::empty(original.range.start()), format!("{name}=")));
+            }
+            _ => {}
+        }
+    }
+
+    let mut start = 0;
+    while start < arguments.len() {
+        if anchors.contains(&start) {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end + 1 < arguments.len() && !anchors.contains(&(end + 1)) {
+            end += 1;
+        }
+        let range = if let Some(next) = arguments.get(end + 1) {
+            TextRange::new(arguments[start].range.start(), next.range.start())
+        } else if let Some(previous) = start.checked_sub(1).map(|index| &arguments[index]) {
+            let before = TextRange::new(previous.range.end(), arguments[start].range.start());
+            let after = TextRange::new(arguments[end].range.end(), close);
+            let (before_text, after_text) = (module.code_at(before), module.code_at(after));
+            // Drop whole lines, keeping the comma and comment that follow the previous argument.
+            match (before_text.rfind('\n'), after_text.find('\n')) {
+                (Some(line_start), Some(line_end)) => TextRange::new(
+                    before.start() + TextSize::of(&before_text[..=line_start]),
+                    after.start() + TextSize::of(&after_text[..=line_end]),
+                ),
+                _ => TextRange::new(previous.range.end(), arguments[end].range.end()),
+            }
+        } else if new.is_empty() {
+            TextRange::new(open, close)
+        } else {
+            TextRange::new(arguments[start].range.start(), arguments[end].range.end())
+        };
+        result.push((range, String::new()));
+        start = end + 1;
+    }
+    merge_insertions(result)
+}
+
+/// The indentation of the arguments of a call that puts them on separate lines.
+fn argument_indent(module: &Module, open: TextSize, arguments: &[CallArgument]) -> Option<String> {
+    let mut previous_end = open;
+    for argument in arguments {
+        let gap = module.code_at(TextRange::new(previous_end, argument.range.start()));
+        if let Some(newline) = gap.rfind('\n') {
+            let indent = &gap[newline + 1..];
+            return indent
+                .chars()
+                .all(char::is_whitespace)
+                .then(|| indent.to_owned());
+        }
+        previous_end = argument.range.end();
+    }
+    None
+}
+
+/// Returns a longest strictly increasing subsequence of `values`.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    let mut lengths = vec![1; values.len()];
+    let mut previous = vec![None; values.len()];
+    for (i, value) in values.iter().enumerate() {
+        for (j, earlier) in values[..i].iter().enumerate() {
+            if earlier < value && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+    let mut subsequence = Vec::new();
+    let mut next = (0..values.len()).max_by_key(|&i| lengths[i]);
+    while let Some(i) = next {
+        subsequence.push(values[i]);
+        next = previous[i];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+/// Sorts `edits` by position and folds each insertion into the edit that starts at the same
+/// offset, inserted text first, so no two edits start at the same offset.
+fn merge_insertions(mut edits: Vec<(TextRange, String)>) -> Vec<(TextRange, String)> {
+    edits.sort_by_key(|(range, _)| edit_order(*range));
+    let mut merged: Vec<(TextRange, String)> = Vec::new();
+    for (range, text) in edits {
+        match merged.last_mut() {
+            Some((last_range, last_text))
+                if last_range.is_empty() && last_range.start() == range.start() =>
+            {
+                *last_range = range;
+                last_text.push_str(&text);
+            }
+            _ => merged.push((range, text)),
+        }
+    }
+    merged
+}
+
+/// Orders edits by position, putting an insertion before an edit that starts at the same offset.
+fn edit_order(range: TextRange) -> (TextSize, bool) {
+    (range.start(), !range.is_empty())
+}
+
+/// Returns the source text at `range` with the already-built `edits` inside it applied, so an
+/// argument that is itself a rewritten call keeps its rewrite.
+fn text_with_edits(module: &Module, range: TextRange, edits: &[(TextRange, String)]) -> String {
+    let mut inner = edits
+        .iter()
+        .filter(|(edit_range, _)| range.contains_range(*edit_range))
+        .collect::<Vec<_>>();
+    inner.sort_by_key(|(edit_range, _)| edit_order(*edit_range));
+    let mut text = String::new();
+    let mut position = range.start();
+    for (edit_range, edit_text) in inner {
+        text.push_str(module.code_at(TextRange::new(position, edit_range.start())));
+        text.push_str(edit_text);
+        position = edit_range.end();
+    }
+    text.push_str(module.code_at(TextRange::new(position, range.end())));
+    text
+}
+
+/// Adds an edit, dropping the edits it subsumes.
+fn push_edit(edits: &mut Vec<(TextRange, String)>, range: TextRange, text: String) {
+    edits.retain(|(edit_range, _)| !range.contains_range(*edit_range));
+    edits.push((range, text));
+}
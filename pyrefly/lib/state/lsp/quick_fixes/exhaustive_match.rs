@@ -0,0 +1,455 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Fixes that add the branches a `match` statement, or an `if`/`elif` chain of `isinstance`
+//! checks, is missing for the type of its subject.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use dupe::Dupe;
+use lsp_types::CodeActionKind;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtIf;
+use ruff_python_ast::StmtMatch;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::Hashed;
+
+use super::extract_shared::build_from_import_edit;
+use super::extract_shared::line_end_position;
+use super::extract_shared::line_indent_and_start;
+use super::types::LocalRefactorCodeAction;
+use crate::alt::answers_solver::AnswersSolver;
+use crate::binding::binding::Binding;
+use crate::binding::binding::BindingExpect;
+use crate::binding::binding::ExhaustivenessKind;
+use crate::binding::binding::Key;
+use crate::binding::binding::KeyExpect;
+use crate::state::lsp::Transaction;
+use crate::state::state::TransactionHandle;
+use crate::types::class::Class;
+use crate::types::literal::Lit;
+use crate::types::tuple::Tuple;
+use crate::types::types::Type;
+
+/// A `case` a `match` statement is missing.
+enum MissingCase {
+    /// A pattern that needs no names in scope, like `None`, `True` or `"a"`.
+    Literal(String),
+    /// A member of an enum, matched as `Enum.MEMBER`.
+    Member(Class, Name),
+    /// An instance of a class, matched as `Class(a, b)` with a capture for each of its
+    /// `__match_args__`.
+    Class(Class, Vec<Name>),
+}
+
+/// A branch an `isinstance` chain is missing.
+enum MissingBranch {
+    None,
+    Class(Class),
+}
+
+/// Names classes in the inserted code, importing the ones that are not in scope.
+struct ClassNamer {
+    module_name: ModuleName,
+    in_scope: BTreeSet<Name>,
+    imports: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ClassNamer {
+    fn new(transaction: &Transaction<'_>, handle: &Handle) -> Self {
+        Self {
+            module_name: handle.module(),
+            in_scope: transaction.get_exports(handle).keys().cloned().collect(),
+            imports: BTreeMap::new(),
+        }
+    }
+
+    fn name(&mut self, cls: &Class) -> String {
+        if cls.module_name() != self.module_name
+            && cls.module_name() != ModuleName::builtins()
+            && !self.in_scope.contains(cls.name())
+        {
+            self.imports
+                .entry(cls.module_name().to_string())
+                .or_default()
+                .insert(cls.name().to_string());
+        }
+        cls.name().to_string()
+    }
+
+    fn import_edits(
+        &self,
+        module_info: &Module,
+        ast: &ModModule,
+    ) -> Vec<(Module, TextRange, String)> {
+        self.imports
+            .iter()
+            .filter_map(|(module, names)| {
+                let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+                build_from_import_edit(module_info, ast, module, &names)
+            })
+            .collect()
+    }
+}
+
+/// Builds the quick fix for a `non-exhaustive-match` error, which adds a `case` for each
+/// enum member, literal and class the `match` statement does not handle.
+pub(crate) fn add_missing_cases_code_action(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    module_info: &Module,
+    ast: &ModModule,
+    error_range: TextRange,
+) -> Option<(String, Vec<(Module, TextRange, String)>)> {
+    let stmt_match = find_match_stmt(ast, error_range)?;
+    let missing = transaction.ad_hoc_solve(handle, "quickfix_missing_cases", |solver| {
+        let bindings = solver.bindings();
+        let key = KeyExpect::MatchExhaustiveness(stmt_match.range());
+        let idx = bindings.key_to_idx_hashed_opt(Hashed::new(&key))?;
+        let BindingExpect::MatchExhaustiveness {
+            subject_idx,
+            narrowing_subject,
+            narrow_ops_for_fall_through,
+            subject_range,
+            ..
+        } = bindings.get(idx)
+        else {
+            return None;
+        };
+        let (_, remaining) = solver.match_fall_through_type(
+            subject_idx,
+            narrowing_subject.as_ref(),
+            narrow_ops_for_fall_through,
+            subject_range,
+        )?;
+        let mut missing = Vec::new();
+        missing_cases(&solver, &remaining, &mut missing)?;
+        // Unions are sorted, so put enum members back in the order they are declared in.
+        missing.sort_by_cached_key(|case| match case {
+            MissingCase::Member(cls, member) => solver
+                .get_enum_members(cls)
+                .iter()
+                .position(|lit| matches!(lit, Lit::Enum(lit_enum) if &lit_enum.member == member)),
+            _ => None,
+        });
+        Some(missing)
+    })??;
+    if missing.is_empty() {
+        return None;
+    }
+
+    let source = module_info.contents().as_str();
+    let last_case = stmt_match.cases.last()?;
+    let (match_indent, _) = line_indent_and_start(source, stmt_match.range().start())?;
+    let (case_indent, _) = line_indent_and_start(source, last_case.range().start())?;
+    let body_indent = body_indent(source, &case_indent, &match_indent, &last_case.body)?;
+    let mut namer = ClassNamer::new(transaction, handle);
+    let mut text = String::new();
+    for case in missing {
+        let pattern = match case {
+            MissingCase::Literal(pattern) => pattern,
+            MissingCase::Member(cls, member) => format!("{}.{member}", namer.name(&cls)),
+            MissingCase::Class(cls, captures) => {
+                format!("{}({})", namer.name(&cls), captures.join(", "))
+            }
+        };
+        text.push_str(&format!(
+            "{case_indent}case {pattern}:\n{body_indent}pass\n"
+        ));
+    }
+    let mut edits = vec![insertion_edit(
+        module_info,
+        source,
+        stmt_match.range().end(),
+        text,
+    )];
+    edits.extend(namer.import_edits(module_info, ast));
+    Some(("Add missing `case` arms".to_owned(), edits))
+}
+
+/// Builds a refactor for an `if`/`elif` chain whose conditions are all `isinstance` checks
+/// on the same variable, which adds an `elif` for each type the chain does not handle and an
+/// `else` that calls `assert_never`.
+pub(crate) fn add_missing_isinstance_branches_code_actions(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    selection: TextRange,
+) -> Option<Vec<LocalRefactorCodeAction>> {
+    let module_info = transaction.get_module_info(handle)?;
+    let ast = transaction.get_ast(handle)?;
+    let stmt_if = find_if_stmt(&ast, selection.start())?;
+    let subject = isinstance_chain_subject(stmt_if)?;
+    let missing = transaction.ad_hoc_solve(handle, "refactor_missing_isinstance", |solver| {
+        let bindings = solver.bindings();
+        let key = Key::Exhaustive(ExhaustivenessKind::IfElif, stmt_if.range());
+        let idx = bindings.key_to_idx_hashed_opt(Hashed::new(&key))?;
+        let Binding::Exhaustive(exhaustive) = bindings.get(idx) else {
+            return None;
+        };
+        let [(subject_idx, op, narrow_range)] = exhaustive.narrow_entries.as_slice() else {
+            return None;
+        };
+        let remaining = solver.exhaustive_fall_through_type(*subject_idx, op, *narrow_range);
+        let mut missing = Vec::new();
+        missing_branches(&solver, &remaining, &mut missing)?;
+        Some(missing)
+    })??;
+    if missing.is_empty() {
+        return None;
+    }
+
+    let source = module_info.contents().as_str();
+    let (indent, _) = line_indent_and_start(source, stmt_if.range().start())?;
+    let body_indent = body_indent(source, &indent, &indent, &stmt_if.body)?;
+    let mut namer = ClassNamer::new(transaction, handle);
+    let mut text = String::new();
+    for branch in missing {
+        let test = match branch {
+            MissingBranch::None => format!("{subject} is None"),
+            MissingBranch::Class(cls) => format!("isinstance({subject}, {})", namer.name(&cls)),
+        };
+        text.push_str(&format!("{indent}elif {test}:\n{body_indent}pass\n"));
+    }
+    text.push_str(&format!(
+        "{indent}else:\n{body_indent}assert_never({subject})\n"
+    ));
+    let typing = if handle.sys_info().version().at_least(3, 11) {
+        "typing"
+    } else {
+        "typing_extensions"
+    };
+    namer
+        .imports
+        .entry(typing.to_owned())
+        .or_default()
+        .insert("assert_never".to_owned());
+    let mut edits = vec![insertion_edit(
+        &module_info,
+        source,
+        stmt_if.range().end(),
+        text,
+    )];
+    edits.extend(namer.import_edits(&module_info, &ast));
+    Some(vec![LocalRefactorCodeAction {
+        title: "Add missing `isinstance` branches".to_owned(),
+        edits,
+        kind: CodeActionKind::REFACTOR_REWRITE,
+    }])
+}
+
+/// The innermost `match` statement whose subject covers `range`.
+fn find_match_stmt(ast: &ModModule, range: TextRange) -> Option<&StmtMatch> {
+    Ast::locate_node(ast, range.start())
+        .into_iter()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtMatch(stmt_match)
+                if stmt_match.subject.range().contains_range(range) =>
+            {
+                Some(stmt_match)
+            }
+            _ => None,
+        })
+}
+
+/// The innermost `if` statement with `position` in the condition of its `if` or one of its
+/// `elif` clauses.
+fn find_if_stmt(ast: &ModModule, position: TextSize) -> Option<&StmtIf> {
+    Ast::locate_node(ast, position)
+        .into_iter()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtIf(stmt_if)
+                if TextRange::new(stmt_if.range().start(), stmt_if.test.range().end())
+                    .contains_range(TextRange::empty(position))
+                    || stmt_if.elif_else_clauses.iter().any(|clause| {
+                        clause.test.as_ref().is_some_and(|test| {
+                            TextRange::new(clause.range().start(), test.range().end())
+                                .contains_range(TextRange::empty(position))
+                        })
+                    }) =>
+            {
+                Some(stmt_if)
+            }
+            _ => None,
+        })
+}
+
+/// The name every condition of an `if`/`elif` chain without an `else` checks with
+/// `isinstance`.
+fn isinstance_chain_subject(stmt_if: &StmtIf) -> Option<&Name> {
+    let mut subject = None;
+    for test in std::iter::once(Some(&*stmt_if.test)).chain(
+        stmt_if
+            .elif_else_clauses
+            .iter()
+            .map(|clause| clause.test.as_ref()),
+    ) {
+        let Some(Expr::Call(call)) = test else {
+            return None;
+        };
+        let (Expr::Name(func), [Expr::Name(checked), _]) = (&*call.func, &*call.arguments.args)
+        else {
+            return None;
+        };
+        if func.id.as_str() != "isinstance"
+            || !call.arguments.keywords.is_empty()
+            || subject.is_some_and(|subject| subject != &checked.id)
+        {
+            return None;
+        }
+        subject = Some(&checked.id);
+    }
+    subject
+}
+
+/// The indentation of the statements in `body`, whose header is indented by `header_indent`
+/// inside a statement indented by `outer_indent`.
+fn body_indent(
+    source: &str,
+    header_indent: &str,
+    outer_indent: &str,
+    body: &[Stmt],
+) -> Option<String> {
+    let (first_line_indent, _) = line_indent_and_start(source, body.first()?.range().start())?;
+    if first_line_indent.len() > header_indent.len() {
+        return Some(first_line_indent);
+    }
+    // The body is on the same line as its header.
+    let unit = header_indent
+        .strip_prefix(outer_indent)
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or("    ");
+    Some(format!("{header_indent}{unit}"))
+}
+
+/// Inserts `text`, a sequence of whole lines, after the line containing `end`.
+fn insertion_edit(
+    module_info: &Module,
+    source: &str,
+    end: TextSize,
+    text: String,
+) -> (Module, TextRange, String) {
+    let position = line_end_position(source, end);
+    let text = if source[..position.to_usize()].ends_with('\n') {
+        text
+    } else {
+        format!("\n{text}")
+    };
+    (module_info.dupe(), TextRange::empty(position), text)
+}
+
+/// Adds the cases that match the values of `ty`, or returns `None` if some of them can't be
+/// matched by a simple pattern.
+fn missing_cases(
+    solver: &AnswersSolver<TransactionHandle<'_>>,
+    ty: &Type,
+    missing: &mut Vec<MissingCase>,
+) -> Option<()> {
+    match ty {
+        Type::None => missing.push(MissingCase::Literal("None".to_owned())),
+        Type::Literal(lit) => match &lit.value {
+            Lit::Enum(lit_enum) => missing.push(MissingCase::Member(
+                lit_enum.class.class_object().dupe(),
+                lit_enum.member.clone(),
+            )),
+            value => missing.push(MissingCase::Literal(value.to_string_escaped(false))),
+        },
+        Type::ClassType(cls) if cls.is_builtin("bool") => {
+            missing.push(MissingCase::Literal("True".to_owned()));
+            missing.push(MissingCase::Literal("False".to_owned()));
+        }
+        Type::ClassType(cls) if solver.get_metadata_for_class(cls.class_object()).is_enum() => {
+            for lit in solver.get_enum_members(cls.class_object()) {
+                if let Lit::Enum(lit_enum) = lit {
+                    missing.push(MissingCase::Member(
+                        cls.class_object().dupe(),
+                        lit_enum.member.clone(),
+                    ));
+                }
+            }
+        }
+        Type::ClassType(cls) => missing.push(MissingCase::Class(
+            cls.class_object().dupe(),
+            match_args(solver, cls.class_object()).unwrap_or_default(),
+        )),
+        Type::Union(union) => {
+            for member in &union.members {
+                missing_cases(solver, member, missing)?;
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// The names in the `__match_args__` of `cls` (set for dataclasses and named tuples), which
+/// class patterns bind positionally.
+fn match_args(solver: &AnswersSolver<TransactionHandle<'_>>, cls: &Class) -> Option<Vec<Name>> {
+    let field = solver.get_class_member(cls, &Name::new_static("__match_args__"))?;
+    let Type::Tuple(Tuple::Concrete(elements)) = field.ty() else {
+        return None;
+    };
+    elements
+        .iter()
+        .map(|element| match element {
+            Type::Literal(lit) => match &lit.value {
+                Lit::Str(name) => Some(Name::new(name.as_str())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Adds the `isinstance` branches that cover `ty`, or returns `None` if it has a part that
+/// can't be checked with `isinstance`.
+fn missing_branches(
+    solver: &AnswersSolver<TransactionHandle<'_>>,
+    ty: &Type,
+    missing: &mut Vec<MissingBranch>,
+) -> Option<()> {
+    let branch = match ty {
+        Type::None => MissingBranch::None,
+        Type::Literal(lit) => match &lit.value {
+            Lit::Enum(lit_enum) => MissingBranch::Class(lit_enum.class.class_object().dupe()),
+            value => MissingBranch::Class(
+                value
+                    .general_class_type(solver.stdlib)
+                    .class_object()
+                    .dupe(),
+            ),
+        },
+        Type::ClassType(cls) => MissingBranch::Class(cls.class_object().dupe()),
+        Type::Union(union) => {
+            for member in &union.members {
+                missing_branches(solver, member, missing)?;
+            }
+            return Some(());
+        }
+        _ => return None,
+    };
+    // Literals of the same class, e.g. the members of an enum, share a branch.
+    let duplicate = missing.iter().any(|existing| match (existing, &branch) {
+        (MissingBranch::None, MissingBranch::None) => true,
+        (MissingBranch::Class(existing), MissingBranch::Class(cls)) => existing == cls,
+        _ => false,
+    });
+    if !duplicate {
+        missing.push(branch);
+    }
+    Some(())
+}
@@ -415,7 +415,7 @@ pub(super) fn import_insertion_point(ast: &ModModule) -> TextSize {
     }
 }
 
-pub(super) fn build_from_import_edit(
+pub(crate) fn build_from_import_edit(
     module_info: &Module,
     ast: &ModModule,
     module_name: &str,
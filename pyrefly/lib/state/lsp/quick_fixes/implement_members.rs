@@ -0,0 +1,701 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The "Implement members" quick fix. It generates stubs for the abstract methods and
+//! properties a class inherits without overriding, and for the members of a protocol the
+//! class is used as but doesn't define. The same stubs back the completions that override an
+//! inherited member after `def` in a class body.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::ast::Ast;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use ruff_python_ast::AnyNodeRef;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::StmtClassDef;
+use ruff_python_ast::helpers::is_docstring_stmt;
+use ruff_python_ast::name::Name;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+
+use super::extract_shared::build_from_import_edit;
+use super::extract_shared::line_end_position;
+use super::extract_shared::line_indent_and_start;
+use crate::config::error_kind::ErrorKind;
+use crate::error::error::Error;
+use crate::error::error::ErrorQuickFix;
+use crate::state::lsp::Transaction;
+use crate::types::callable::DefaultValue;
+use crate::types::callable::Param;
+use crate::types::callable::Params;
+use crate::types::callable::Required;
+use crate::types::class::Class;
+use crate::types::class::ClassType;
+use crate::types::function::FuncFlags;
+use crate::types::function::Function;
+use crate::types::literal::Lit;
+use crate::types::quantified::QuantifiedKind;
+use crate::types::stdlib::Stdlib;
+use crate::types::type_output::AnnotationPart;
+use crate::types::types::AnyStyle;
+use crate::types::types::Forallable;
+use crate::types::types::Overload;
+use crate::types::types::OverloadType;
+use crate::types::types::TParams;
+use crate::types::types::Type;
+
+/// A member the class has to define. Its type is already expressed in terms of the class: the
+/// type arguments the class passes to its bases (or the ones the protocol was used with) are
+/// substituted.
+struct MissingMember {
+    name: Name,
+    ty: Type,
+    is_property: bool,
+}
+
+/// An "Implement members" quick fix. The stubs may land in a different module than the error,
+/// so the fix carries the module it edits; the caller adds the `override` import to it when
+/// `uses_override` is set.
+pub(crate) struct ImplementMembersFix {
+    pub title: String,
+    pub handle: Handle,
+    pub module: Module,
+    pub ast: Arc<ModModule>,
+    pub edits: Vec<(Module, TextRange, String)>,
+    pub uses_override: bool,
+}
+
+/// Builds the quick fix for an error the solver marked as fixable by implementing members: a
+/// `bad-instantiation` error on a class with unimplemented abstract members, or an assignment,
+/// argument or return error caused by a class missing members of a protocol.
+pub(crate) fn implement_members_code_action(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    error: &Error,
+) -> Option<ImplementMembersFix> {
+    let (cls, missing, title) = error.quick_fixes().iter().find_map(|fix| match fix {
+        ErrorQuickFix::ImplementAbstractMembers { class } => {
+            abstract_members(transaction, handle, class)
+        }
+        ErrorQuickFix::ImplementProtocolMembers { class, protocol } => {
+            protocol_members(transaction, handle, class, protocol)
+        }
+        _ => None,
+    })?;
+    if missing.is_empty() {
+        return None;
+    }
+    let target_handle = if cls.module_path() == handle.path() {
+        handle.dupe()
+    } else {
+        transaction
+            .handles()
+            .into_iter()
+            .find(|candidate| candidate.path() == cls.module_path())?
+    };
+    let module = transaction.get_module_info(&target_handle)?;
+    let target_ast = transaction.get_ast(&target_handle)?;
+    let class_def = find_class_def(&target_ast, cls.range())?;
+    let source = module.contents().as_str();
+
+    let (class_indent, _) = line_indent_and_start(source, class_def.range().start())?;
+    let first = class_def.body.first()?;
+    let header_end = class_def.name.range().end().to_usize();
+    let body_on_own_line = source[header_end..first.range().start().to_usize()].contains('\n');
+    let indent = if body_on_own_line {
+        line_indent_and_start(source, first.range().start())?.0
+    } else {
+        format!("{class_indent}    ")
+    };
+    let unit = indent
+        .strip_prefix(class_indent.as_str())
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or("    ")
+        .to_owned();
+
+    let uses_override = transaction
+        .get_config(&target_handle)
+        .is_some_and(|config| {
+            config
+                .errors(target_handle.path().as_path())
+                .severity(ErrorKind::MissingOverrideDecorator)
+                .is_enabled()
+        });
+    let stdlib = transaction.get_stdlib(&target_handle);
+    let mut renderer = StubRenderer {
+        stdlib: &stdlib,
+        module_name: target_handle.module(),
+        unit: &unit,
+        uses_override,
+        imports: BTreeMap::new(),
+    };
+    let mut attributes = Vec::new();
+    let mut methods = Vec::new();
+    for member in &missing {
+        match renderer.member(member) {
+            Stub::Attribute(line) => attributes.push(line),
+            Stub::Method(lines) => methods.push(lines.join(&format!("\n{indent}"))),
+        }
+    }
+    let mut blocks = Vec::new();
+    if !attributes.is_empty() {
+        blocks.push(attributes.join(&format!("\n{indent}")));
+    }
+    blocks.extend(methods);
+    let stubs = blocks.join(&format!("\n\n{indent}"));
+
+    let placeholder = match class_def.body.as_slice() {
+        [only] if is_placeholder(only) => Some(only),
+        [docstring, last] if is_docstring_stmt(docstring) && is_placeholder(last) => Some(last),
+        _ => None,
+    };
+    let mut edits = Vec::new();
+    match placeholder {
+        Some(placeholder) if body_on_own_line => {
+            edits.push((module.dupe(), placeholder.range(), stubs));
+        }
+        Some(placeholder) => {
+            // `class C(Base): ...`: move the body onto its own lines.
+            let colon = source[..placeholder.range().start().to_usize()].rfind(':')?;
+            let start = TextSize::try_from(colon + 1).ok()?;
+            edits.push((
+                module.dupe(),
+                TextRange::new(start, placeholder.range().end()),
+                format!("\n{indent}{stubs}"),
+            ));
+        }
+        None => {
+            let last = class_def.body.last()?;
+            let position = line_end_position(source, last.range().end());
+            let separator = if source[..position.to_usize()].ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            edits.push((
+                module.dupe(),
+                TextRange::empty(position),
+                format!("{separator}\n{indent}{stubs}\n"),
+            ));
+        }
+    }
+    for (import_module, names) in &renderer.imports {
+        let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+        if let Some(edit) = build_from_import_edit(&module, &target_ast, import_module, &names) {
+            edits.push(edit);
+        }
+    }
+    Some(ImplementMembersFix {
+        title,
+        handle: target_handle,
+        module,
+        ast: target_ast,
+        edits,
+        uses_override,
+    })
+}
+
+fn abstract_members(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    cls: &Class,
+) -> Option<(Class, Vec<MissingMember>, String)> {
+    let missing = transaction.ad_hoc_solve(handle, "implement_abstract_members", |solver| {
+        solver
+            .get_abstract_members_for_class(cls)
+            .unimplemented_abstract_methods()
+            .iter()
+            .filter_map(|name| {
+                let field = solver.get_class_member(cls, name)?;
+                Some(MissingMember {
+                    name: name.clone(),
+                    ty: solver.for_display(field.ty()),
+                    is_property: field.is_property(),
+                })
+            })
+            .collect::<Vec<_>>()
+    })?;
+    let title = format!("Implement abstract members of `{}`", cls.name());
+    Some((cls.dupe(), missing, title))
+}
+
+fn protocol_members(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+    cls: &Class,
+    protocol: &ClassType,
+) -> Option<(Class, Vec<MissingMember>, String)> {
+    // The subset check may stop at the first missing member; we implement all of them.
+    let missing = transaction.ad_hoc_solve(handle, "implement_protocol_members", |solver| {
+        let metadata = solver.get_metadata_for_class(protocol.class_object());
+        let members = metadata.protocol_metadata()?.members.clone();
+        Some(
+            members
+                .iter()
+                .filter(|name| solver.get_class_member(cls, name).is_none())
+                .filter_map(|name| {
+                    let field = solver.get_class_member(protocol.class_object(), name)?;
+                    Some(MissingMember {
+                        name: name.clone(),
+                        ty: solver.for_display(protocol.targs().substitute_into(field.ty())),
+                        is_property: field.is_property(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+    })??;
+    let title = format!(
+        "Implement members of `{}` in `{}`",
+        protocol.name(),
+        cls.name()
+    );
+    Some((cls.dupe(), missing, title))
+}
+
+fn find_class_def(ast: &ModModule, name_range: TextRange) -> Option<&StmtClassDef> {
+    Ast::locate_node(ast, name_range.start())
+        .into_iter()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtClassDef(class_def) if class_def.name.range() == name_range => {
+                Some(class_def)
+            }
+            _ => None,
+        })
+}
+
+fn is_placeholder(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Pass(_) => true,
+        Stmt::Expr(x) => matches!(*x.value, Expr::EllipsisLiteral(_)),
+        _ => false,
+    }
+}
+
+/// An override of an inherited method or property, split up to complete a `def` being typed in
+/// a class body: the decorators go above the `def` line and the header replaces the name.
+pub(crate) struct OverrideStub {
+    /// Decorator lines, outermost first.
+    pub decorators: Vec<String>,
+    pub is_async: bool,
+    /// The `def` line after the keyword, including the colon.
+    pub header: String,
+    /// The only statement of the body.
+    pub body: String,
+    /// Names the stub refers to that have to be imported, keyed by module.
+    pub imports: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Renders an override of the member `name` of type `ty`, as seen from the overriding class in
+/// `module_name`. When `call_super` is set the body delegates to the inherited member through
+/// `super()`; otherwise (e.g. for an abstract method) it raises `NotImplementedError`.
+pub(crate) fn override_stub(
+    stdlib: &Stdlib,
+    module_name: ModuleName,
+    uses_override: bool,
+    name: &Name,
+    ty: &Type,
+    is_property: bool,
+    call_super: bool,
+) -> Option<OverrideStub> {
+    let mut renderer = StubRenderer {
+        stdlib,
+        module_name,
+        unit: "",
+        uses_override: false,
+        imports: BTreeMap::new(),
+    };
+    let name = name.as_str();
+    let mut decorators = Vec::new();
+    if uses_override {
+        decorators.push("@override".to_owned());
+    }
+    let (is_async, header, body) = if let Type::Overload(overload) = ty {
+        // A single implementation that accepts every overload signature.
+        let flags = &overload.metadata.flags;
+        decorators.extend(method_kind_decorator(flags));
+        let receiver = if flags.is_staticmethod {
+            None
+        } else {
+            overload
+                .signatures
+                .iter()
+                .find_map(|signature| match signature {
+                    OverloadType::Function(func) => receiver_name(func),
+                    OverloadType::Forall(forall) => receiver_name(&forall.body),
+                })
+        };
+        let receiver = receiver.map_or_else(String::new, |receiver| format!("{receiver}, "));
+        let body = if !call_super || flags.is_staticmethod {
+            "raise NotImplementedError".to_owned()
+        } else if flags.is_async {
+            format!("return await super().{name}(*args, **kwargs)")
+        } else {
+            format!("return super().{name}(*args, **kwargs)")
+        };
+        let header = format!("{name}({receiver}*args, **kwargs):");
+        (flags.is_async, header, body)
+    } else {
+        let getter;
+        let ty = if is_property {
+            getter = ty
+                .is_property_setter_with_getter()
+                .unwrap_or_else(|| ty.clone());
+            decorators.push("@property".to_owned());
+            &getter
+        } else {
+            ty
+        };
+        let (tparams, func) = function_parts(ty)?;
+        let flags = &func.metadata.flags;
+        decorators.extend(method_kind_decorator(flags));
+        let (is_async, ret) = awaited_return(func);
+        let header = format!("{}:", renderer.signature(name, tparams, func));
+        let body = if !call_super || flags.is_staticmethod {
+            "raise NotImplementedError".to_owned()
+        } else if is_property {
+            format!("return super().{name}")
+        } else {
+            let arguments = match &func.signature.params {
+                Params::List(params) => forwarded_arguments(params.items(), true),
+                _ => "*args, **kwargs".to_owned(),
+            };
+            let call = if is_async {
+                format!("await super().{name}({arguments})")
+            } else {
+                format!("super().{name}({arguments})")
+            };
+            if matches!(ret, Type::None) {
+                call
+            } else {
+                format!("return {call}")
+            }
+        };
+        (is_async, header, body)
+    };
+    Some(OverrideStub {
+        decorators,
+        is_async,
+        header,
+        body,
+        imports: renderer.imports,
+    })
+}
+
+enum Stub {
+    /// A single annotated declaration, e.g. `name: str`.
+    Attribute(String),
+    /// The lines of a method, with the body indented by one level.
+    Method(Vec<String>),
+}
+
+struct StubRenderer<'a> {
+    stdlib: &'a Stdlib,
+    module_name: ModuleName,
+    unit: &'a str,
+    uses_override: bool,
+    /// Names each stub refers to that have to be imported, keyed by module.
+    imports: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl StubRenderer<'_> {
+    fn member(&mut self, member: &MissingMember) -> Stub {
+        let name = member.name.as_str();
+        if member.is_property {
+            let getter = member
+                .ty
+                .is_property_setter_with_getter()
+                .unwrap_or_else(|| member.ty.clone());
+            if let Some(lines) = self.method(name, &getter, Some("property")) {
+                return Stub::Method(lines);
+            }
+        }
+        if let Type::Overload(overload) = &member.ty {
+            return Stub::Method(self.overload(name, overload));
+        }
+        match self.method(name, &member.ty, None) {
+            Some(lines) => Stub::Method(lines),
+            None => Stub::Attribute(format!("{name}: {}", self.annotation(&member.ty))),
+        }
+    }
+
+    fn method(&mut self, name: &str, ty: &Type, decorator: Option<&str>) -> Option<Vec<String>> {
+        let (tparams, func) = function_parts(ty)?;
+        let mut lines = Vec::new();
+        if self.uses_override {
+            lines.push("@override".to_owned());
+        }
+        if let Some(decorator) = decorator {
+            lines.push(format!("@{decorator}"));
+        }
+        lines.extend(method_kind_decorator(&func.metadata.flags));
+        lines.push(format!("{}:", self.header(name, tparams, func)));
+        lines.push(format!("{}raise NotImplementedError", self.unit));
+        Some(lines)
+    }
+
+    /// Repeats each overload signature and adds an implementation that accepts all of them.
+    fn overload(&mut self, name: &str, overload: &Overload) -> Vec<String> {
+        self.imports
+            .entry(ModuleName::typing().as_str().to_owned())
+            .or_default()
+            .insert("overload".to_owned());
+        let flags = &overload.metadata.flags;
+        let mut lines = Vec::new();
+        let mut receiver = None;
+        for signature in overload.signatures.iter() {
+            let (tparams, func) = match signature {
+                OverloadType::Function(func) => (None, func),
+                OverloadType::Forall(forall) => (Some(&*forall.tparams), &forall.body),
+            };
+            if receiver.is_none() && !flags.is_staticmethod {
+                receiver = receiver_name(func);
+            }
+            lines.push("@overload".to_owned());
+            lines.extend(method_kind_decorator(flags));
+            lines.push(format!("{}: ...", self.header(name, tparams, func)));
+        }
+        if self.uses_override {
+            lines.push("@override".to_owned());
+        }
+        lines.extend(method_kind_decorator(flags));
+        let receiver = receiver.map_or_else(String::new, |receiver| format!("{receiver}, "));
+        let keyword = if flags.is_async { "async def" } else { "def" };
+        lines.push(format!("{keyword} {name}({receiver}*args, **kwargs):"));
+        lines.push(format!("{}raise NotImplementedError", self.unit));
+        lines
+    }
+
+    fn header(&mut self, name: &str, tparams: Option<&TParams>, func: &Function) -> String {
+        let keyword = if awaited_return(func).0 {
+            "async def"
+        } else {
+            "def"
+        };
+        format!("{keyword} {}", self.signature(name, tparams, func))
+    }
+
+    /// The part of the `def` line after the keyword, without the trailing colon.
+    fn signature(&mut self, name: &str, tparams: Option<&TParams>, func: &Function) -> String {
+        let flags = &func.metadata.flags;
+        let ret = awaited_return(func).1;
+        let type_params = tparams.map_or_else(String::new, render_type_params);
+        let params = match &func.signature.params {
+            Params::List(params) => self.params(params.items(), !flags.is_staticmethod),
+            _ => {
+                let receiver = (!flags.is_staticmethod)
+                    .then(|| receiver_name(func))
+                    .flatten()
+                    .map_or_else(String::new, |receiver| format!("{receiver}, "));
+                format!("{receiver}*args, **kwargs")
+            }
+        };
+        let returns = match ret {
+            Type::Any(AnyStyle::Implicit) => String::new(),
+            ret => format!(" -> {}", self.annotation(ret)),
+        };
+        format!("{name}{type_params}({params}){returns}")
+    }
+
+    fn params(&mut self, params: &[Param], has_receiver: bool) -> String {
+        let mut rendered = Vec::new();
+        let mut in_positional_only = false;
+        let mut seen_star = false;
+        for (i, param) in params.iter().enumerate() {
+            let positional_only = matches!(param, Param::PosOnly(..));
+            if in_positional_only && !positional_only {
+                rendered.push("/".to_owned());
+            }
+            in_positional_only = positional_only;
+            match param {
+                Param::PosOnly(Some(name), _, _) | Param::Pos(name, _, _)
+                    if i == 0 && has_receiver =>
+                {
+                    // The receiver stays unannotated, like in a hand-written method.
+                    rendered.push(name.to_string());
+                }
+                Param::PosOnly(name, ty, required) => {
+                    let name = name
+                        .as_ref()
+                        .map_or_else(|| format!("_{i}"), |name| name.to_string());
+                    rendered.push(self.param(&name, ty, required));
+                }
+                Param::Pos(name, ty, required) => {
+                    rendered.push(self.param(name.as_str(), ty, required));
+                }
+                Param::Varargs(name, ty) => {
+                    seen_star = true;
+                    let name = name.as_ref().map_or("args", |name| name.as_str());
+                    rendered.push(format!("*{}", self.param(name, ty, &Required::Required)));
+                }
+                Param::KwOnly(name, ty, required) => {
+                    if !seen_star {
+                        seen_star = true;
+                        rendered.push("*".to_owned());
+                    }
+                    rendered.push(self.param(name.as_str(), ty, required));
+                }
+                Param::Kwargs(name, ty) => {
+                    let name = name.as_ref().map_or("kwargs", |name| name.as_str());
+                    rendered.push(format!("**{}", self.param(name, ty, &Required::Required)));
+                }
+            }
+        }
+        if in_positional_only {
+            rendered.push("/".to_owned());
+        }
+        rendered.join(", ")
+    }
+
+    fn param(&mut self, name: &str, ty: &Type, required: &Required) -> String {
+        let annotation = match ty {
+            Type::Any(AnyStyle::Implicit) => None,
+            ty => Some(self.annotation(ty)),
+        };
+        let default = match required {
+            Required::Required => None,
+            Required::Optional(default) => Some(default_text(default.as_ref())),
+        };
+        match (annotation, default) {
+            (Some(annotation), Some(default)) => format!("{name}: {annotation} = {default}"),
+            (Some(annotation), None) => format!("{name}: {annotation}"),
+            (None, Some(default)) => format!("{name}={default}"),
+            (None, None) => name.to_owned(),
+        }
+    }
+
+    /// Renders `ty` as an annotation, recording the imports it needs in the target module.
+    fn annotation(&mut self, ty: &Type) -> String {
+        ty.get_annotation_parts(Some(self.stdlib))
+            .into_iter()
+            .map(|part| match part {
+                AnnotationPart::Text(text) => text,
+                AnnotationPart::Reference { module, name } => {
+                    if module != self.module_name && module != ModuleName::builtins() {
+                        // A nested class is imported through its outermost class.
+                        let imported = name.split('.').next().unwrap_or(&name).to_owned();
+                        self.imports
+                            .entry(module.as_str().to_owned())
+                            .or_default()
+                            .insert(imported);
+                    }
+                    name
+                }
+            })
+            .collect()
+    }
+}
+
+/// The type parameters and function of a method type, if it is a single function.
+fn function_parts(ty: &Type) -> Option<(Option<&TParams>, &Function)> {
+    match ty {
+        Type::Function(func) => Some((None, &**func)),
+        Type::Forall(forall) => match &forall.body {
+            Forallable::Function(func) => Some((Some(&*forall.tparams), func)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `func` is written as an `async def`, and the type its body returns: for an
+/// `async def`, the result of awaiting the coroutine.
+fn awaited_return(func: &Function) -> (bool, &Type) {
+    let ret = &func.signature.ret;
+    if func.metadata.flags.is_async
+        && let Type::ClassType(coroutine) = ret
+        && coroutine.has_qname("typing", "Coroutine")
+        && let Some(awaited) = coroutine.targs().as_slice().get(2)
+    {
+        (true, awaited)
+    } else {
+        (false, ret)
+    }
+}
+
+fn method_kind_decorator(flags: &FuncFlags) -> Option<String> {
+    if flags.is_staticmethod {
+        Some("@staticmethod".to_owned())
+    } else if flags.is_classmethod {
+        Some("@classmethod".to_owned())
+    } else {
+        None
+    }
+}
+
+fn receiver_name(func: &Function) -> Option<String> {
+    let Params::List(params) = &func.signature.params else {
+        return None;
+    };
+    match params.items().first()? {
+        Param::PosOnly(Some(name), _, _) | Param::Pos(name, _, _) => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+/// The arguments that pass on each parameter rendered by `StubRenderer::params`.
+fn forwarded_arguments(params: &[Param], has_receiver: bool) -> String {
+    params
+        .iter()
+        .enumerate()
+        .filter_map(|(i, param)| match param {
+            Param::PosOnly(Some(_), _, _) | Param::Pos(..) if i == 0 && has_receiver => None,
+            Param::PosOnly(name, _, _) => Some(
+                name.as_ref()
+                    .map_or_else(|| format!("_{i}"), |name| name.to_string()),
+            ),
+            Param::Pos(name, _, _) => Some(name.to_string()),
+            Param::Varargs(name, _) => Some(format!(
+                "*{}",
+                name.as_ref().map_or("args", |name| name.as_str())
+            )),
+            Param::KwOnly(name, _, _) => Some(format!("{name}={name}")),
+            Param::Kwargs(name, _) => Some(format!(
+                "**{}",
+                name.as_ref().map_or("kwargs", |name| name.as_str())
+            )),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_type_params(tparams: &TParams) -> String {
+    if tparams.is_empty() {
+        return String::new();
+    }
+    let rendered = tparams
+        .iter()
+        .map(|tparam| match tparam.kind() {
+            QuantifiedKind::TypeVarTuple => format!("*{}", tparam.name()),
+            QuantifiedKind::ParamSpec => format!("**{}", tparam.name()),
+            QuantifiedKind::TypeVar | QuantifiedKind::IntVar => tparam.name().to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", rendered.join(", "))
+}
+
+fn default_text(default: Option<&DefaultValue>) -> String {
+    match default {
+        Some(DefaultValue {
+            display: Some(text),
+            ..
+        }) => text.clone(),
+        Some(DefaultValue {
+            ty: Type::Literal(lit),
+            ..
+        }) if !matches!(lit.value, Lit::Enum(_)) => format!("{}", lit.value),
+        Some(DefaultValue { ty: Type::None, .. }) => "None".to_owned(),
+        _ => "...".to_owned(),
+    }
+}
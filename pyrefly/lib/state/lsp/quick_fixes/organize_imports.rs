@@ -0,0 +1,532 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The Organize Imports source action.
+//!
+//! Each run of consecutive import statements is rewritten on its own, so imports never move
+//! past other code, into or out of an `if TYPE_CHECKING:` block. Within a run, unused imports
+//! are removed, duplicate imports are merged, and the rest are grouped into sections (future,
+//! standard library, third party, first party, local) and sorted the way isort's `black`
+//! profile does with `combine_as_imports` set.
+
+use std::cmp::Reverse;
+
+use dupe::Dupe;
+use pyrefly_build::handle::Handle;
+use pyrefly_python::module::Module;
+use pyrefly_python::module_name::ModuleName;
+use pyrefly_python::module_path::ModulePathDetails;
+use pyrefly_python::sys_info::SysInfo;
+use ruff_python_ast::Alias;
+use ruff_python_ast::Stmt;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+use ruff_text_size::TextSize;
+use starlark_map::small_map::SmallMap;
+
+use crate::state::lsp::Transaction;
+
+/// Statements longer than this are wrapped, one name per line.
+const LINE_LENGTH: usize = 88;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Future,
+    Stdlib,
+    ThirdParty,
+    FirstParty,
+    LocalFolder,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ImportedName {
+    name: String,
+    asname: Option<String>,
+}
+
+impl ImportedName {
+    fn new(alias: &Alias) -> Self {
+        Self {
+            name: alias.name.id.to_string(),
+            asname: alias.asname.as_ref().map(|asname| asname.id.to_string()),
+        }
+    }
+
+    fn render(&self) -> String {
+        match &self.asname {
+            Some(asname) => format!("{} as {asname}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ImportKind {
+    /// `import name [as asname]`
+    Module(ImportedName),
+    /// `from module import names`, where `module` includes any leading dots.
+    From {
+        module: String,
+        level: u32,
+        names: Vec<ImportedName>,
+    },
+}
+
+/// One import statement as it will be printed, with the comments that travel with it.
+#[derive(Debug)]
+struct Import {
+    section: Section,
+    kind: ImportKind,
+    leading_comments: Vec<String>,
+    trailing_comments: Vec<String>,
+}
+
+impl Import {
+    /// Straight imports sort before `from` imports, as isort does when
+    /// `force_sort_within_sections` is off.
+    fn sort_key(&self) -> (Section, bool, Reverse<u32>, String, String) {
+        match &self.kind {
+            ImportKind::Module(name) => (
+                self.section,
+                false,
+                Reverse(0),
+                name.name.to_lowercase(),
+                name.render(),
+            ),
+            ImportKind::From { module, level, .. } => (
+                self.section,
+                true,
+                // Relative imports go from the furthest parent to the closest.
+                Reverse(*level),
+                module.to_lowercase(),
+                module.clone(),
+            ),
+        }
+    }
+
+    fn render(&self, indent: &str, out: &mut Vec<String>) {
+        for comment in &self.leading_comments {
+            out.push(format!("{indent}{comment}"));
+        }
+        let comments = if self.trailing_comments.is_empty() {
+            String::new()
+        } else {
+            format!("  {}", self.trailing_comments.join("  "))
+        };
+        match &self.kind {
+            ImportKind::Module(name) => {
+                out.push(format!("{indent}import {}{comments}", name.render()));
+            }
+            ImportKind::From { module, names, .. } => {
+                let names = names.iter().map(ImportedName::render).collect::<Vec<_>>();
+                let line = format!(
+                    "{indent}from {module} import {}{comments}",
+                    names.join(", ")
+                );
+                if line.len() <= LINE_LENGTH || names.len() == 1 && comments.is_empty() {
+                    out.push(line);
+                } else {
+                    out.push(format!("{indent}from {module} import ({comments}"));
+                    for name in names {
+                        out.push(format!("{indent}    {name},"));
+                    }
+                    out.push(format!("{indent})"));
+                }
+            }
+        }
+    }
+}
+
+/// isort's `order_by_type`: constants, then classes, then everything else.
+fn name_sort_key(name: &ImportedName) -> (u8, String, String) {
+    let name_str = name.name.as_str();
+    let category = if name_str.len() > 1
+        && name_str.chars().any(char::is_uppercase)
+        && !name_str.chars().any(char::is_lowercase)
+    {
+        0
+    } else if name_str.starts_with(char::is_uppercase) {
+        1
+    } else {
+        2
+    };
+    (category, name.name.to_lowercase(), name.render())
+}
+
+fn is_organizable(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Import(_) => true,
+        // Star imports stay where they are, since moving them can change which names win.
+        Stmt::ImportFrom(x) => !x.names.iter().any(|alias| alias.name.as_str() == "*"),
+        _ => false,
+    }
+}
+
+struct Context<'a, 'b> {
+    transaction: &'a Transaction<'b>,
+    handle: &'a Handle,
+    module_info: &'a Module,
+    unused: Vec<TextRange>,
+    sections: SmallMap<ModuleName, Section>,
+}
+
+impl Context<'_, '_> {
+    fn section(&mut self, module: ModuleName) -> Section {
+        if module == ModuleName::future() {
+            return Section::Future;
+        }
+        if let Some(section) = self.sections.get(&module) {
+            return *section;
+        }
+        let section = self.resolve_section(module);
+        self.sections.insert(module, section);
+        section
+    }
+
+    /// Classifies a module by where the import finder actually resolves it, rather than by
+    /// a list of known names. Anything found on disk outside of site-packages comes from the
+    /// search path, the import root or a source database, so it belongs to the project.
+    fn resolve_section(&self, module: ModuleName) -> Section {
+        let Some(found) = self
+            .transaction
+            .import_handle(self.handle, module, None)
+            .finding()
+        else {
+            return Section::ThirdParty;
+        };
+        let path = match found.path().details() {
+            ModulePathDetails::BundledTypeshed(_) => return Section::Stdlib,
+            ModulePathDetails::BundledTypeshedThirdParty(_)
+            | ModulePathDetails::BundledThirdParty(_) => return Section::ThirdParty,
+            ModulePathDetails::FileSystem(path)
+            | ModulePathDetails::Namespace(path)
+            | ModulePathDetails::Memory(path) => path,
+        };
+        let Some(config) = self.transaction.get_config(self.handle) else {
+            return Section::FirstParty;
+        };
+        if config
+            .typeshed_stdlib_path()
+            .is_some_and(|stdlib| path.starts_with(stdlib))
+        {
+            Section::Stdlib
+        } else if config
+            .site_package_path()
+            .any(|root| path.starts_with(root))
+        {
+            Section::ThirdParty
+        } else {
+            Section::FirstParty
+        }
+    }
+
+    fn is_unused(&self, alias: &Alias) -> bool {
+        self.unused
+            .iter()
+            .any(|range| alias.range().contains_range(*range))
+    }
+}
+
+fn line_start(source: &str, offset: TextSize) -> usize {
+    source[..offset.to_usize()].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// The end of the line containing `offset`, before any line terminator.
+fn line_end(source: &str, offset: TextSize) -> usize {
+    let offset = offset.to_usize();
+    let end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+    if end > offset && source[..end].ends_with('\r') {
+        end - 1
+    } else {
+        end
+    }
+}
+
+/// The text after `stmt` on its last line, if it is only a comment.
+fn trailing_comment(source: &str, stmt: &Stmt) -> Result<Option<String>, ()> {
+    let rest = source[stmt.range().end().to_usize()..line_end(source, stmt.range().end())].trim();
+    if rest.is_empty() {
+        Ok(None)
+    } else if rest.starts_with('#') {
+        Ok(Some(rest.to_owned()))
+    } else {
+        Err(())
+    }
+}
+
+/// Rewrites one run of import statements, or returns `None` if it can't be rewritten safely
+/// or is already organized. `whole_body` is set when the run is the entire body of a block,
+/// which must not end up empty.
+fn organize_block(
+    ctx: &mut Context<'_, '_>,
+    block: &[&Stmt],
+    whole_body: bool,
+) -> Option<(TextRange, String)> {
+    let module_info = ctx.module_info;
+    let source = module_info.contents().as_str();
+    let first = block.first()?;
+    let last = block.last()?;
+    let start = line_start(source, first.range().start());
+    let indent = &source[start..first.range().start().to_usize()];
+    if !indent.trim().is_empty() {
+        return None;
+    }
+
+    let mut imports = Vec::new();
+    let mut pending_comments = Vec::new();
+    let mut previous_end = None;
+    for stmt in block {
+        // Comments inside a parenthesized import have nowhere to go once it is reflowed.
+        if source[stmt.range()].contains('#') {
+            return None;
+        }
+        if let Some(previous_end) = previous_end {
+            let between = &source[previous_end..line_start(source, stmt.range().start())];
+            for line in between.lines().map(str::trim) {
+                if line.starts_with('#') {
+                    pending_comments.push(line.to_owned());
+                }
+            }
+            if !source[line_start(source, stmt.range().start())..stmt.range().start().to_usize()]
+                .trim()
+                .is_empty()
+            {
+                return None;
+            }
+        }
+        let trailing = trailing_comment(source, stmt).ok()?;
+        previous_end = Some(line_end(source, stmt.range().end()));
+
+        let mut produced = Vec::new();
+        match stmt {
+            Stmt::Import(x) => {
+                for alias in &x.names {
+                    if ctx.is_unused(alias) {
+                        continue;
+                    }
+                    produced.push(Import {
+                        section: ctx.section(ModuleName::from_name(&alias.name.id)),
+                        kind: ImportKind::Module(ImportedName::new(alias)),
+                        leading_comments: Vec::new(),
+                        trailing_comments: Vec::new(),
+                    });
+                }
+            }
+            Stmt::ImportFrom(x) => {
+                let names = x
+                    .names
+                    .iter()
+                    .filter(|alias| !ctx.is_unused(alias))
+                    .map(ImportedName::new)
+                    .collect::<Vec<_>>();
+                if !names.is_empty() {
+                    let dots = ".".repeat(x.level as usize);
+                    let module = match &x.module {
+                        Some(module) => format!("{dots}{}", module.id),
+                        None => dots,
+                    };
+                    let section = match &x.module {
+                        Some(module) if x.level == 0 => {
+                            ctx.section(ModuleName::from_name(&module.id))
+                        }
+                        _ => Section::LocalFolder,
+                    };
+                    produced.push(Import {
+                        section,
+                        kind: ImportKind::From {
+                            module,
+                            level: x.level,
+                            names,
+                        },
+                        leading_comments: Vec::new(),
+                        trailing_comments: Vec::new(),
+                    });
+                }
+            }
+            _ => return None,
+        }
+        // Comments stay with the first import that survives, even if their own import was
+        // removed as unused.
+        if let Some(import) = produced.first_mut() {
+            import.leading_comments = std::mem::take(&mut pending_comments);
+            import.trailing_comments.extend(trailing);
+        } else {
+            pending_comments.extend(trailing);
+        }
+        imports.extend(produced);
+    }
+    let end = line_end(source, last.range().end());
+
+    if imports.is_empty() && whole_body {
+        // Removing every import would leave an empty block; keep the imports, just sorted.
+        let unused = std::mem::take(&mut ctx.unused);
+        let result = organize_block(ctx, block, false);
+        ctx.unused = unused;
+        return result;
+    }
+
+    let merged = merge_imports(imports);
+    let mut lines = Vec::new();
+    let mut previous_section = None;
+    for import in &merged {
+        if previous_section.is_some_and(|section| section != import.section) {
+            lines.push(String::new());
+        }
+        previous_section = Some(import.section);
+        import.render(indent, &mut lines);
+    }
+    for comment in pending_comments {
+        lines.push(format!("{indent}{comment}"));
+    }
+
+    let (range, replacement) = if lines.is_empty() {
+        // Drop the lines entirely, including the final newline.
+        let terminator = if source[end..].starts_with("\r\n") {
+            2
+        } else {
+            usize::from(end < source.len())
+        };
+        let end = end + terminator;
+        (
+            TextRange::new(TextSize::new(start as u32), TextSize::new(end as u32)),
+            String::new(),
+        )
+    } else {
+        let mut replacement = lines.join("\n");
+        replacement.replace_range(..indent.len(), "");
+        (
+            TextRange::new(first.range().start(), TextSize::new(end as u32)),
+            replacement,
+        )
+    };
+    if source[range] == replacement {
+        None
+    } else {
+        Some((range, replacement))
+    }
+}
+
+/// Merges duplicate imports and sorts them into their final order.
+fn merge_imports(imports: Vec<Import>) -> Vec<Import> {
+    let mut merged: Vec<Import> = Vec::new();
+    for import in imports {
+        let existing = merged.iter_mut().find(|existing| {
+            existing.section == import.section
+                && match (&existing.kind, &import.kind) {
+                    (ImportKind::Module(a), ImportKind::Module(b)) => a == b,
+                    (ImportKind::From { module: a, .. }, ImportKind::From { module: b, .. }) => {
+                        a == b
+                    }
+                    _ => false,
+                }
+        });
+        let Some(existing) = existing else {
+            merged.push(import);
+            continue;
+        };
+        if let (ImportKind::From { names, .. }, ImportKind::From { names: new, .. }) =
+            (&mut existing.kind, import.kind)
+        {
+            for name in new {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        existing.leading_comments.extend(import.leading_comments);
+        existing.trailing_comments.extend(import.trailing_comments);
+    }
+    for import in &mut merged {
+        if let ImportKind::From { names, .. } = &mut import.kind {
+            names.sort_by_key(name_sort_key);
+        }
+    }
+    merged.sort_by_cached_key(Import::sort_key);
+    merged
+}
+
+fn organize_body(
+    ctx: &mut Context<'_, '_>,
+    body: &[Stmt],
+    nested: bool,
+    edits: &mut Vec<(TextRange, String)>,
+) {
+    let mut i = 0;
+    while i < body.len() {
+        if is_organizable(&body[i]) {
+            let start = i;
+            while i < body.len() && is_organizable(&body[i]) {
+                i += 1;
+            }
+            // A run that shares a line with the code around it can't be replaced line-wise.
+            let module_info = ctx.module_info;
+            let source = module_info.contents().as_str();
+            let shares_line = |a: &Stmt, b: &Stmt| {
+                !source[a.range().end().to_usize()..b.range().start().to_usize()].contains('\n')
+            };
+            if (start > 0 && shares_line(&body[start - 1], &body[start]))
+                || (i < body.len() && shares_line(&body[i - 1], &body[i]))
+                || body[start..i].windows(2).any(|w| shares_line(&w[0], &w[1]))
+            {
+                continue;
+            }
+            let block = body[start..i].iter().collect::<Vec<_>>();
+            let whole_body = nested && start == 0 && i == body.len();
+            edits.extend(organize_block(ctx, &block, whole_body));
+        } else {
+            if let Stmt::If(x) = &body[i]
+                && SysInfo::is_type_checking_guard(&x.test)
+            {
+                organize_body(ctx, &x.body, true, edits);
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Edits that organize the imports of `handle`, or `None` if they are already organized.
+pub(crate) fn organize_imports_edits(
+    transaction: &Transaction<'_>,
+    handle: &Handle,
+) -> Option<Vec<(Module, TextRange, String)>> {
+    let module_info = transaction.get_module_info(handle)?;
+    if module_info.is_notebook() {
+        return None;
+    }
+    let ast = transaction.get_ast(handle)?;
+    let bindings = transaction.get_bindings(handle)?;
+    // Imports in an `__init__.py` are usually there to be re-exported.
+    let unused = if module_info.path().is_init() {
+        Vec::new()
+    } else {
+        bindings
+            .unused_imports()
+            .iter()
+            .map(|unused| unused.range)
+            .collect()
+    };
+    let mut ctx = Context {
+        transaction,
+        handle,
+        module_info: &module_info,
+        unused,
+        sections: SmallMap::new(),
+    };
+    let mut edits = Vec::new();
+    organize_body(&mut ctx, &ast.body, false, &mut edits);
+    if edits.is_empty() {
+        return None;
+    }
+    Some(
+        edits
+            .into_iter()
+            .map(|(range, text)| (module_info.dupe(), range, text))
+            .collect(),
+    )
+}
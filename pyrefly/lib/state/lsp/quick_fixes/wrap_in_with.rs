@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The fix for an `unclosed-resource` error, which turns `f = open(path)` into
+//! `with open(path) as f:` and moves the statements that use `f` into the `with` block.
+
+use dupe::Dupe;
+use pyrefly_python::module::Module;
+use ruff_python_ast::Expr;
+use ruff_python_ast::ModModule;
+use ruff_python_ast::Stmt;
+use ruff_python_ast::name::Name;
+use ruff_python_ast::visitor::Visitor;
+use ruff_python_ast::visitor::walk_body;
+use ruff_python_ast::visitor::walk_expr;
+use ruff_text_size::Ranged;
+use ruff_text_size::TextRange;
+
+use super::extract_shared::line_end_position;
+use super::extract_shared::line_indent_and_start;
+use super::extract_shared::reindent_block;
+use super::extract_shared::statement_removal_range;
+
+/// Finds the block containing the assignment whose value is at `value_range`, along with the
+/// index of the assignment in it.
+struct FindAssign<'a> {
+    value_range: TextRange,
+    found: Option<(&'a [Stmt], usize)>,
+}
+
+impl<'a> Visitor<'a> for FindAssign<'a> {
+    fn visit_body(&mut self, body: &'a [Stmt]) {
+        if self.found.is_some() {
+            return;
+        }
+        if let Some(i) = body.iter().position(|stmt| match stmt {
+            Stmt::Assign(x) => x.value.range() == self.value_range,
+            _ => false,
+        }) {
+            self.found = Some((body, i));
+            return;
+        }
+        walk_body(self, body);
+    }
+}
+
+/// Whether a statement reads or writes a name.
+struct UsesName<'a> {
+    name: &'a Name,
+    found: bool,
+}
+
+impl<'a, 'b> Visitor<'b> for UsesName<'a> {
+    fn visit_expr(&mut self, expr: &'b Expr) {
+        if let Expr::Name(x) = expr
+            && &x.id == self.name
+        {
+            self.found = true;
+        }
+        walk_expr(self, expr);
+    }
+}
+
+fn uses_name(stmt: &Stmt, name: &Name) -> bool {
+    let mut visitor = UsesName { name, found: false };
+    visitor.visit_stmt(stmt);
+    visitor.found
+}
+
+/// Whether `stmt` is `name.close()`, which the `with` makes redundant.
+fn is_close_of(stmt: &Stmt, name: &Name) -> bool {
+    if let Stmt::Expr(x) = stmt
+        && let Expr::Call(call) = &*x.value
+        && call.arguments.is_empty()
+        && let Expr::Attribute(attr) = &*call.func
+        && attr.attr.id.as_str() == "close"
+        && let Expr::Name(x) = &*attr.value
+    {
+        &x.id == name
+    } else {
+        false
+    }
+}
+
+/// Builds the quick fix for an `unclosed-resource` error at `error_range`, the range of the
+/// call creating the resource. The `with` block runs through the last statement of the same
+/// block that uses the resource, and an explicit `.close()` of it is dropped.
+pub(crate) fn wrap_in_with_code_action(
+    module_info: &Module,
+    ast: &ModModule,
+    error_range: TextRange,
+) -> Option<(String, Vec<(Module, TextRange, String)>)> {
+    let mut finder = FindAssign {
+        value_range: error_range,
+        found: None,
+    };
+    finder.visit_body(&ast.body);
+    let (body, i) = finder.found?;
+    let Stmt::Assign(assign) = &body[i] else {
+        return None;
+    };
+    let [Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    let name = &target.id;
+    let source = module_info.contents().as_str();
+    let (indent, start) = line_indent_and_start(source, assign.range().start())?;
+    let last = (i + 1..body.len())
+        .rev()
+        .find(|&j| uses_name(&body[j], name))
+        .unwrap_or(i);
+    let end = line_end_position(source, body[last].range().end());
+
+    let mut moved = String::new();
+    if last > i {
+        let mut position = line_end_position(source, assign.range().end());
+        for stmt in &body[i + 1..=last] {
+            if is_close_of(stmt, name) {
+                let removal = statement_removal_range(source, stmt)?;
+                moved.push_str(&source[position.to_usize()..removal.start().to_usize()]);
+                position = removal.end();
+            }
+        }
+        moved.push_str(&source[position.to_usize()..end.to_usize()]);
+    }
+    let body_indent = format!("{indent}    ");
+    let mut moved = reindent_block(&moved, &indent, &body_indent);
+    if moved.trim().is_empty() {
+        moved = format!("{body_indent}pass\n");
+    } else if !moved.ends_with('\n') {
+        moved.push('\n');
+    }
+    let text = format!(
+        "{indent}with {} as {name}:\n{moved}",
+        module_info.code_at(assign.value.range()),
+    );
+    Some((
+        "Wrap in `with` block".to_owned(),
+        vec![(module_info.dupe(), TextRange::new(start, end), text)],
+    ))
+}
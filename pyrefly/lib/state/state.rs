@@ -94,6 +94,8 @@ use crate::binding::binding::KeyClassMro;
 use crate::binding::binding::KeyClassSubscriptSymmetry;
 use crate::binding::binding::KeyClassSynthesizedFields;
 use crate::binding::binding::KeyExport;
+use crate::binding::binding::KeyFunctionLocalRaises;
+use crate::binding::binding::KeyFunctionRaises;
 use crate::binding::binding::KeyTParams;
 use crate::binding::binding::KeyVariance;
 use crate::binding::binding::Keyed;
@@ -115,6 +117,8 @@ use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
 use crate::export::special::SpecialExport;
 use crate::module::bundled::BundledStub;
+use crate::module::dependencies::DependencyUsage;
+use crate::module::dependencies::ProjectDependencies;
 use crate::module::finder::find_import_prefixes;
 use crate::module::typeshed::BundledTypeshedStdlib;
 use crate::module::typeshed::custom_typeshed_stdlib_config;
@@ -144,6 +148,7 @@ use crate::types::class::Class;
 use crate::types::class::ClassDefIndex;
 use crate::types::class::ClassFields;
 use crate::types::function::Deprecation;
+use crate::types::function::FuncDefIndex;
 use crate::types::stdlib::Stdlib;
 use crate::types::types::TParams;
 use crate::types::types::Type;
@@ -177,6 +182,8 @@ pub struct ModuleDeps {
     pub classes: SmallSet<ClassDefIndex>,
     /// Which type aliases do we depend on?
     pub type_aliases: SmallSet<TypeAliasIndex>,
+    /// Which functions' raised exceptions do we depend on?
+    pub functions: SmallSet<FuncDefIndex>,
     /// Do we depend on module-level Django reverse relation metadata?
     pub django_relations: bool,
 }
@@ -250,7 +257,8 @@ impl ModuleChanges {
             AnyExportedKey::KeyExport(k) => {
                 self.0.names.entry(k.0).or_default();
             }
-            // Classes, type aliases, and django relations don't distinguish between existence and change.
+            // Classes, type aliases, functions and django relations don't distinguish between
+            // existence and change.
             _ => self.add_key(key),
         }
     }
@@ -268,7 +276,7 @@ impl ModuleChanges {
     /// Check if two change sets overlap, for cycle detection.
     ///
     /// This is symmetric: both sides are change sets. Two changes overlap if
-    /// they affect the same name/class/type_alias. An existence change (default
+    /// they affect the same name/class/type_alias/function. An existence change (default
     /// NameDep) overlaps with any change on the same name, since it's strictly
     /// more impactful than a type/metadata-only change.
     pub fn overlaps(&self, other: &ModuleChanges) -> bool {
@@ -297,6 +305,14 @@ impl ModuleChanges {
         if self.0.classes.iter().any(|c| other.0.classes.contains(c)) {
             return true;
         }
+        if self
+            .0
+            .functions
+            .iter()
+            .any(|f| other.0.functions.contains(f))
+        {
+            return true;
+        }
         self.0
             .type_aliases
             .iter()
@@ -315,6 +331,10 @@ impl ModuleDeps {
             AnyExportedKey::KeyTypeAlias(k) => {
                 self.type_aliases.insert(k.0);
             }
+            AnyExportedKey::KeyFunctionRaises(KeyFunctionRaises(f))
+            | AnyExportedKey::KeyFunctionLocalRaises(KeyFunctionLocalRaises(f)) => {
+                self.functions.insert(f);
+            }
             AnyExportedKey::KeyDjangoRelations(_) => {
                 self.django_relations = true;
             }
@@ -379,6 +399,7 @@ impl ModuleDeps {
         }
         self.classes.extend(other.classes);
         self.type_aliases.extend(other.type_aliases);
+        self.functions.extend(other.functions);
         self.wildcard |= other.wildcard;
         self.django_relations |= other.django_relations;
     }
@@ -388,6 +409,7 @@ impl ModuleDeps {
             && !self.wildcard
             && self.classes.is_empty()
             && self.type_aliases.is_empty()
+            && self.functions.is_empty()
             && !self.django_relations
     }
 
@@ -426,6 +448,13 @@ impl ModuleDeps {
         if self.classes.iter().any(|c| changed.0.classes.contains(c)) {
             return true;
         }
+        if self
+            .functions
+            .iter()
+            .any(|f| changed.0.functions.contains(f))
+        {
+            return true;
+        }
         self.type_aliases
             .iter()
             .any(|t| changed.0.type_aliases.contains(t))
@@ -1471,8 +1500,12 @@ impl<'a> Transaction<'a> {
                     .spec_compliant_overloads(module_data.handle.path().as_path()),
                 legacy_overload_expansion: config
                     .legacy_overload_expansion(module_data.handle.path().as_path()),
+                max_protocol_mismatches: config
+                    .max_protocol_mismatches(module_data.handle.path().as_path()),
+                lint_config: config.lint_config(module_data.handle.path().as_path()),
                 treat_all_caps_as_final: config
                     .treat_all_caps_as_final(module_data.handle.path().as_path()),
+                project_dependencies: self.project_dependencies(&config, &module_data.handle),
                 recursion_limit_config: config.recursion_limit_config(),
                 pysa_context,
                 cinderx_enabled: self.data.cinderx_reporter.is_some(),
@@ -1920,6 +1953,58 @@ impl<'a> Transaction<'a> {
             .is_some()
     }
 
+    /// The dependencies of the project `handle` belongs to, if `undeclared-dependency` is enabled for it.
+    fn project_dependencies(
+        &self,
+        config: &ArcId<ConfigFile>,
+        handle: &Handle,
+    ) -> Option<Arc<ProjectDependencies>> {
+        let path = handle.path().as_path();
+        if !config
+            .errors(path)
+            .severity(ErrorKind::UndeclaredDependency)
+            .is_enabled()
+        {
+            return None;
+        }
+        self.get_cached_loader(config)
+            .project_dependencies()
+            .filter(|dependencies| dependencies.covers(path))
+    }
+
+    /// How `handles` use the dependencies declared by the projects they belong to, for each
+    /// project with `undeclared-dependency` enabled. Must be called after `handles` are checked.
+    pub fn dependency_usage(&self, handles: &[Handle]) -> Vec<DependencyUsage> {
+        let mut projects: SmallMap<PathBuf, (Arc<ProjectDependencies>, SmallMap<String, bool>)> =
+            SmallMap::new();
+        for handle in handles {
+            let Some(config) = self.get_config(handle) else {
+                continue;
+            };
+            let Some(dependencies) = self.project_dependencies(&config, handle) else {
+                continue;
+            };
+            let (_, imported) = projects
+                .entry(dependencies.declared().pyproject.clone())
+                .or_insert_with(|| (dependencies.dupe(), SmallMap::new()));
+            let Some(solutions) = self.get_solutions(handle) else {
+                continue;
+            };
+            for (name, type_checking_only) in solutions.metadata().imported_distributions() {
+                match imported.get_mut(name) {
+                    Some(only) => *only &= *type_checking_only,
+                    None => {
+                        imported.insert(name.clone(), *type_checking_only);
+                    }
+                }
+            }
+        }
+        projects
+            .into_values()
+            .map(|(dependencies, imported)| dependencies.usage(&imported))
+            .collect()
+    }
+
     pub fn get_stdlib(&self, handle: &Handle) -> Arc<Stdlib> {
         if self.data.stdlib.len() == 1 {
             // Since we know our one must exist, we can shortcut
@@ -2342,6 +2427,19 @@ impl<'a> Transaction<'a> {
                 }
             }
         }
+        drop(dirty_set);
+
+        // Loaders read the project dependencies once, so configs that are unchanged still need
+        // fresh loaders if the dependencies their project declares have changed.
+        let stale = self
+            .data
+            .updated_loaders
+            .iter_unordered()
+            .chain(self.readable.loaders.iter())
+            .filter(|(_, loader)| loader.project_dependencies_changed())
+            .map(|(config, _)| config.dupe())
+            .collect::<SmallSet<_>>();
+        self.invalidate_find_for_configs(stale);
     }
 
     /// Called if the `find` portion of loading might have changed for specific configs,
@@ -2484,7 +2582,10 @@ impl<'a> Transaction<'a> {
                     .spec_compliant_overloads(m.handle.path().as_path()),
                 legacy_overload_expansion: config
                     .legacy_overload_expansion(m.handle.path().as_path()),
+                max_protocol_mismatches: config.max_protocol_mismatches(m.handle.path().as_path()),
+                lint_config: config.lint_config(m.handle.path().as_path()),
                 treat_all_caps_as_final: config.treat_all_caps_as_final(m.handle.path().as_path()),
+                project_dependencies: self.project_dependencies(&config, &m.handle),
                 recursion_limit_config: config.recursion_limit_config(),
                 pysa_context: None,
                 cinderx_enabled: false,
@@ -2884,6 +2985,12 @@ impl<'a> LookupExport for TransactionHandle<'a> {
             })
     }
 
+    fn module_path(&self, module: ModuleName) -> Option<ModulePath> {
+        self.get_module(module, None, ModuleDep::Exists)
+            .finding()
+            .map(|module_data| module_data.handle.path().dupe())
+    }
+
     fn is_submodule_imported_implicitly(&self, module: ModuleName, name: &Name) -> bool {
         self.with_exports(
             module,
@@ -29,11 +29,14 @@ use crate::alt::answers::Solutions;
 use crate::binding::bindings::Bindings;
 use crate::config::base::InferReturnTypes;
 use crate::config::base::RecursionLimitConfig;
+use crate::config::config::LintConfig;
 use crate::error::style::ErrorStyle;
 use crate::export::exports::Exports;
 use crate::export::exports::LookupExport;
+use crate::module::dependencies::ProjectDependencies;
 use crate::module::parse::module_parse;
 use crate::solver::solver::Solver;
+use crate::solver::solver::SolverOptions;
 use crate::state::load::Load;
 use crate::state::memory::MemoryFilesLookup;
 use crate::state::require::Require;
@@ -64,7 +67,11 @@ pub struct Context<'a, Lookup> {
     pub strict_partial_subtyping: bool,
     pub spec_compliant_overloads: bool,
     pub legacy_overload_expansion: bool,
+    pub max_protocol_mismatches: usize,
+    pub lint_config: LintConfig,
     pub treat_all_caps_as_final: bool,
+    /// Set when imports of this module should be checked against the project's declared dependencies.
+    pub project_dependencies: Option<Arc<ProjectDependencies>>,
     pub recursion_limit_config: Option<RecursionLimitConfig>,
     /// Pysa context for building PysaSolutions during the Solutions step.
     pub pysa_context: Option<PysaContext<'a>>,
@@ -486,14 +493,15 @@ impl Step {
         ast: Arc<ModModule>,
         exports: Arc<Exports>,
     ) -> Arc<(Bindings, Arc<Answers>)> {
-        let solver = Solver::new(
-            ctx.infer_with_first_use,
-            ctx.tensor_shapes,
-            ctx.strict_callable_subtyping,
-            ctx.strict_partial_subtyping,
-            ctx.spec_compliant_overloads,
-            ctx.legacy_overload_expansion,
-        );
+        let solver = Solver::new(SolverOptions {
+            infer_with_first_use: ctx.infer_with_first_use,
+            tensor_shapes: ctx.tensor_shapes,
+            strict_callable_subtyping: ctx.strict_callable_subtyping,
+            strict_partial_subtyping: ctx.strict_partial_subtyping,
+            spec_compliant_overloads: ctx.spec_compliant_overloads,
+            legacy_overload_expansion: ctx.legacy_overload_expansion,
+            max_protocol_mismatches: ctx.max_protocol_mismatches,
+        });
         let enable_index = ctx.require.keep_index();
         let enable_trace =
             ctx.require.keep_answers_trace() || ctx.pysa_context.is_some() || ctx.cinderx_enabled;
@@ -510,6 +518,8 @@ impl Step {
             ctx.require.keep_index(),
             ctx.infer_return_types,
             ctx.treat_all_caps_as_final,
+            ctx.project_dependencies.as_deref(),
+            ctx.lint_config.dupe(),
         );
         let answers = Answers::new(&bindings, solver, enable_index, enable_trace);
         Arc::new((bindings, Arc::new(answers)))
@@ -0,0 +1,184 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::testcase;
+
+testcase!(
+    test_argparse_namespace_fields,
+    r#"
+import argparse
+from typing import assert_type
+
+parser = argparse.ArgumentParser()
+parser.add_argument("path")
+parser.add_argument("--count", type=int, default=1)
+parser.add_argument("--limit", type=int)
+parser.add_argument("-v", "--verbose", action="store_true")
+parser.add_argument("--dry-run", action="store_false")
+parser.add_argument("--tag", action="append")
+parser.add_argument("files", nargs="*", type=float)
+parser.add_argument("--level", action="count")
+args = parser.parse_args()
+assert_type(args.path, str)
+assert_type(args.count, int)
+assert_type(args.limit, int | None)
+assert_type(args.verbose, bool)
+assert_type(args.dry_run, bool)
+assert_type(args.tag, list[str] | None)
+assert_type(args.files, list[float])
+assert_type(args.level, int | None)
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_missing_attribute,
+    r#"
+import argparse
+
+parser = argparse.ArgumentParser()
+parser.add_argument("--name", required=True)
+args = parser.parse_args()
+args.name
+args.nmae  # E: Object of class `Namespace` has no attribute `nmae`
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_dest_and_set_defaults,
+    r#"
+import argparse
+from typing import assert_type
+
+parser = argparse.ArgumentParser()
+parser.add_argument("--out", dest="output", default="-")
+parser.add_argument("--quiet", action="store_const", const=0, dest="verbosity")
+parser.set_defaults(verbosity=1)
+args = parser.parse_args()
+assert_type(args.output, str)
+assert_type(args.verbosity, int)
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_groups_and_subparsers,
+    r#"
+import argparse
+from typing import Any, assert_type
+
+parser = argparse.ArgumentParser()
+group = parser.add_mutually_exclusive_group()
+group.add_argument("--fast", action="store_true")
+subparsers = parser.add_subparsers(dest="command", required=True)
+run = subparsers.add_parser("run")
+run.add_argument("--jobs", type=int, default=4)
+args = parser.parse_args()
+assert_type(args.fast, bool)
+assert_type(args.command, str)
+# Only set when the `run` subcommand is chosen.
+assert_type(args.jobs, Any)
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_subcommand_destinations,
+    r#"
+import argparse
+from typing import Any, assert_type
+
+parser = argparse.ArgumentParser()
+parser.add_argument("--verbose", action="store_true")
+subparsers = parser.add_subparsers(dest="command")
+subparsers.add_parser("build").add_argument("--target", default="all")
+clean = subparsers.add_parser("clean")
+clean.add_argument("--verbose", action="count")
+clean.set_defaults(handler=print)
+args = parser.parse_args()
+assert_type(args.command, str | None)
+assert_type(args.verbose, bool | int | None)
+assert_type(args.target, Any)
+assert_type(args.handler, Any)
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_without_subcommands_is_closed,
+    r#"
+import argparse
+
+parser = argparse.ArgumentParser()
+subparsers = parser.add_subparsers(dest="command")
+parser.add_argument("--name")
+args = parser.parse_args()
+args.nmae  # E: Object of class `Namespace` has no attribute `nmae`
+    "#,
+);
+
+testcase!(
+    test_argparse_namespace_is_namespace,
+    r#"
+import argparse
+
+def main(args: argparse.Namespace) -> None: ...
+
+parser = argparse.ArgumentParser()
+parser.add_argument("--flag", action="store_true")
+main(parser.parse_args())
+    "#,
+);
+
+testcase!(
+    test_argparse_escaped_parser,
+    r#"
+import argparse
+from typing import Any, assert_type
+
+def add_common(parser: argparse.ArgumentParser) -> None: ...
+
+parser = argparse.ArgumentParser()
+parser.add_argument("--name")
+add_common(parser)
+args = parser.parse_args()
+assert_type(args, argparse.Namespace)
+assert_type(args.anything, Any)
+    "#,
+);
+
+testcase!(
+    test_argparse_computed_option,
+    r#"
+import argparse
+from typing import assert_type
+
+def flag() -> str: ...
+
+parser = argparse.ArgumentParser()
+parser.add_argument(flag())
+assert_type(parser.parse_args(), argparse.Namespace)
+    "#,
+);
+
+testcase!(
+    test_argparse_several_parsers,
+    r#"
+from argparse import ArgumentParser
+from argparse import ArgumentParser as Parser
+import argparse as ap
+from typing import assert_type
+
+first = ArgumentParser()
+first.add_argument("--count", type=int, default=1)
+assert_type(first.parse_args().count, int)
+
+second = Parser()
+second.add_argument("--name", default="")
+assert_type(second.parse_args().name, str)
+
+third = ap.ArgumentParser()
+third.add_argument("--flag", action="store_true")
+assert_type(third.parse_args().flag, bool)
+    "#,
+);
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+fn env_banned_lib(name: &str, required_keywords: &[&str]) -> TestEnv {
+    let mut env =
+        TestEnv::new().with_banned_api(name, Some("Ask the platform team"), required_keywords);
+    env.add(
+        "lib",
+        r#"
+def old() -> None: ...
+def new() -> None: ...
+def fetch(url: str, timeout: float | None = None) -> None: ...
+class Legacy: ...
+class Client:
+    def send(self) -> None: ...
+"#,
+    );
+    env
+}
+
+testcase!(
+    test_banned_function,
+    env_banned_lib("lib.old", &[]),
+    r#"
+import lib
+from lib import old  # E: `lib.old` is banned
+lib.old()  # E: `lib.old` is banned
+old()  # E: `lib.old` is banned
+f = old  # E: `lib.old` is banned
+lib.new()
+"#,
+);
+
+fn env_banned_reexport() -> TestEnv {
+    let mut env = env_banned_lib("pkg.old", &[]);
+    env.add(
+        "pkg",
+        "from lib import old as old  # E: `pkg.old` is banned",
+    );
+    env
+}
+
+testcase!(
+    test_banned_function_through_reexport,
+    env_banned_reexport(),
+    r#"
+import lib
+import pkg
+pkg.old()  # E: `pkg.old` is banned
+lib.old()  # E: `pkg.old` is banned
+"#,
+);
+
+testcase!(
+    test_banned_class,
+    env_banned_lib("lib.Legacy", &[]),
+    r#"
+import lib
+from lib import Legacy  # E: `lib.Legacy` is banned
+x = Legacy()  # E: `lib.Legacy` is banned
+y = lib.Legacy  # E: `lib.Legacy` is banned
+"#,
+);
+
+testcase!(
+    test_banned_method_through_instances_and_subclasses,
+    env_banned_lib("lib.Client.send", &[]),
+    r#"
+from lib import Client
+class MyClient(Client):
+    pass
+Client().send()  # E: `lib.Client.send` is banned
+MyClient().send()  # E: `lib.Client.send` is banned
+f = MyClient.send  # E: `lib.Client.send` is banned
+"#,
+);
+
+testcase!(
+    test_banned_api_required_keywords,
+    env_banned_lib("lib.fetch", &["timeout"]),
+    r#"
+import lib
+from lib import fetch
+lib.fetch("a")  # E: `lib.fetch` must be called with keyword argument `timeout`
+fetch("a")  # E: `lib.fetch` must be called with keyword argument `timeout`
+lib.fetch("a", timeout=1)
+f = lib.fetch
+def g(**kwargs: float) -> None:
+    lib.fetch("a", **kwargs)
+"#,
+);
+
+testcase!(
+    test_banned_stdlib_reexport,
+    TestEnv::new().with_banned_api("pickle.loads", None, &[]),
+    r#"
+import pickle
+from pickle import loads  # E: `pickle.loads` is banned
+pickle.loads(b"")  # E: `pickle.loads` is banned
+loads(b"")  # E: `pickle.loads` is banned
+pickle.dumps(1)
+"#,
+);
+
+testcase!(
+    test_banned_module,
+    TestEnv::new().with_banned_api("pickle", None, &[]),
+    r#"
+import pickle  # E: `pickle` is banned
+import pickle as p  # E: `pickle` is banned
+from pickle import dumps  # E: `pickle` is banned
+pickle.dumps(1)  # E: `pickle` is banned
+"#,
+);
+
+testcase!(
+    test_banned_package_submodule_import,
+    TestEnv::new().with_banned_api("xml", None, &[]),
+    r#"
+import xml.etree.ElementTree  # E: `xml` is banned
+from xml.etree import ElementTree  # E: `xml` is banned
+import json
+"#,
+);
@@ -0,0 +1,77 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+testcase!(
+    test_blocking_call_in_async,
+    TestEnv::new().enable_blocking_call_in_async_error(),
+    r#"
+import subprocess
+import time
+from time import sleep
+
+async def handler() -> None:
+    time.sleep(1)  # E: `time.sleep` blocks the event loop when called in an async function
+    sleep(1)  # E: `time.sleep` blocks the event loop
+    subprocess.run(["ls"])  # E: `subprocess.run` blocks the event loop
+    with open("data.txt") as f:  # E: `builtins.open` blocks the event loop
+        pass
+
+def sync_handler() -> None:
+    time.sleep(1)
+    subprocess.run(["ls"])
+"#,
+);
+
+testcase!(
+    test_blocking_call_offloaded_from_async,
+    TestEnv::new().enable_blocking_call_in_async_error(),
+    r#"
+import asyncio
+import time
+
+async def handler() -> None:
+    await asyncio.sleep(1)
+    await asyncio.to_thread(time.sleep, 1)
+    await asyncio.get_running_loop().run_in_executor(None, lambda: time.sleep(1))
+    def work() -> None:
+        time.sleep(1)
+    await asyncio.to_thread(work)
+"#,
+);
+
+testcase!(
+    test_extra_blocking_call_through_subclass,
+    TestEnv::new()
+        .enable_blocking_call_in_async_error()
+        .with_extra_blocking_call("main.Database.query"),
+    r#"
+class Database:
+    def query(self) -> None: ...
+
+class Replica(Database):
+    pass
+
+async def handler(db: Replica) -> None:
+    db.query()  # E: `main.Database.query` blocks the event loop
+
+def sync_handler(db: Replica) -> None:
+    db.query()
+"#,
+);
+
+testcase!(
+    test_blocking_call_in_async_off_by_default,
+    r#"
+import time
+
+async def handler() -> None:
+    time.sleep(1)
+"#,
+);
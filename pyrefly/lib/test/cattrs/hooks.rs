@@ -0,0 +1,70 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::cattrs_testcase;
+
+cattrs_testcase!(
+    test_structure,
+    r#"
+from typing import assert_type
+from dataclasses import dataclass
+from cattrs import Converter
+
+@dataclass
+class User:
+    name: str
+
+converter = Converter()
+assert_type(converter.structure({"name": "a"}, User), User)
+    "#,
+);
+
+cattrs_testcase!(
+    test_register_hooks,
+    r#"
+from datetime import datetime
+from typing import Any
+from cattrs import Converter
+
+def structure_datetime(value: Any, cl: type[datetime]) -> datetime: ...
+def unstructure_datetime(value: datetime) -> str: ...
+def structure_wrong(value: Any, cl: type[datetime]) -> str: ...
+def unstructure_wrong(value: int) -> str: ...
+
+converter = Converter()
+converter.register_structure_hook(datetime, structure_datetime)
+converter.register_unstructure_hook(datetime, unstructure_datetime)
+converter.register_unstructure_hook(datetime, lambda d: d.isoformat())
+converter.register_structure_hook(datetime, structure_wrong)  # E: Argument `(value: Any, cl: type[datetime]) -> str` is not assignable to parameter `func`
+converter.register_unstructure_hook(datetime, unstructure_wrong)  # E: Argument `(value: int) -> str` is not assignable to parameter `func`
+    "#,
+);
+
+cattrs_testcase!(
+    test_register_hooks_keywords_and_generics,
+    r#"
+from typing import Any, Generic, TypeVar
+from cattrs import Converter
+
+T = TypeVar("T")
+
+class Box(Generic[T]):
+    item: T
+
+def structure_box(value: Any, cl: type[Box[int]]) -> Box[int]: ...
+def unstructure_box(value: Box[int]) -> dict[str, Any]: ...
+def unstructure_str(value: str) -> str: ...
+
+converter = Converter()
+converter.register_structure_hook(Box, structure_box)
+converter.register_structure_hook(Box[int], structure_box)
+converter.register_unstructure_hook(Box[int], unstructure_box)
+converter.register_unstructure_hook(cl=Box[int], func=unstructure_box)
+converter.register_unstructure_hook(Box, func=unstructure_str)  # E: Argument `(value: str) -> str` is not assignable to parameter `func`
+converter.register_unstructure_hook(cl=Box[int], func=unstructure_str)  # E: Argument `(value: str) -> str` is not assignable to parameter `func`
+    "#,
+);
@@ -0,0 +1,8 @@
+"""
+Minimal cattrs stubs for testing.
+"""
+
+from cattrs.converters import BaseConverter as BaseConverter
+from cattrs.converters import Converter as Converter
+
+__all__ = ["BaseConverter", "Converter"]
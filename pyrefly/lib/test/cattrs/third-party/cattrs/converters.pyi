@@ -0,0 +1,18 @@
+"""
+Minimal cattrs stubs for testing.
+"""
+
+from typing import Any, Callable, TypeVar
+
+T = TypeVar("T")
+
+class BaseConverter:
+    def __init__(self) -> None: ...
+    def structure(self, obj: Any, cl: type[T]) -> T: ...
+    def unstructure(self, obj: Any, unstructure_as: Any = None) -> Any: ...
+    def register_structure_hook(
+        self, cl: Any, func: Callable[[Any, Any], Any]
+    ) -> None: ...
+    def register_unstructure_hook(self, cl: Any, func: Callable[[Any], Any]) -> None: ...
+
+class Converter(BaseConverter): ...
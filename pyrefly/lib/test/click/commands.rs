@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::click_testcase;
+
+click_testcase!(
+    test_option_names,
+    r#"
+import click
+
+@click.command()
+@click.option("--count", type=int, default=1)
+@click.option("-n", "--dry-run", is_flag=True)
+@click.option("--shout/--no-shout")
+@click.option("-o", "--out", "output")
+@click.argument("input-file")
+def cli(count: int, dry_run: bool, shout: bool, output: str | None, input_file: str) -> None: ...
+    "#,
+);
+
+click_testcase!(
+    test_option_not_a_parameter,
+    r#"
+import click
+
+@click.command()
+@click.option("--verbose", is_flag=True)  # E: `--verbose` is passed as `verbose`, which is not a parameter of `cli`
+def cli(
+    quiet: bool,  # E: Parameter `quiet` of command `cli` is not declared by any click option or argument
+) -> None: ...
+    "#,
+);
+
+click_testcase!(
+    test_unmodeled_decorators,
+    r#"
+from typing import Any, Callable
+import click
+
+class State: ...
+
+pass_state = click.make_pass_decorator(State)
+
+def common_options(f: Callable[..., Any]) -> Callable[..., Any]:
+    return click.option("--debug", is_flag=True)(f)
+
+@click.command()
+@click.version_option("1.0")
+@click.help_option("-h", "--help")
+@click.option("--name")
+def versioned(
+    name: str | None,
+    quiet: bool,  # E: Parameter `quiet` of command `versioned` is not declared by any click option or argument
+) -> None: ...
+
+@click.command()
+@click.password_option()
+def login(password: str) -> None: ...
+
+@click.command()
+@pass_state
+def show(state: State) -> None: ...
+
+@click.command()
+@common_options
+@click.option("--name")
+def bundled(name: str | None, debug: bool) -> None: ...
+    "#,
+);
+
+click_testcase!(
+    test_option_types,
+    r#"
+import click
+
+@click.command()
+@click.option("--count", type=int, default=1)  # E: `--count` provides `int`, which is not assignable to parameter `count` with type `str`
+@click.option("--ratio", type=float)  # E: `--ratio` provides `float | None`, which is not assignable to parameter `ratio` with type `float`
+@click.option("--name", default="x")
+@click.option("--tag", multiple=True)
+@click.option("--level", type=click.Choice(["a", "b"]), required=True)
+@click.option("--verbose", "-v", count=True)
+@click.argument("paths", nargs=-1, type=click.Path())
+def cli(
+    count: str,
+    ratio: float,
+    name: str,
+    tag: tuple[str, ...],
+    level: str,
+    verbose: int,
+    paths: tuple[str, ...],
+) -> None: ...
+    "#,
+);
+
+click_testcase!(
+    test_group_commands,
+    r#"
+from typing import assert_type
+import click
+
+@click.group()
+@click.pass_context
+def cli(ctx: click.Context) -> None: ...
+
+assert_type(cli, click.Group)
+
+@cli.command()
+@click.option("--name")
+def hello(name: str | None) -> None: ...
+
+@cli.command()
+@click.option("--name")  # E: `--name` is passed as `name`
+def bye(nmae: str | None = None) -> None: ...
+
+assert_type(hello, click.Command)
+    "#,
+);
+
+click_testcase!(
+    test_typer_defaults,
+    r#"
+import typer
+
+app = typer.Typer()
+
+@app.command()
+def main(
+    name: str = typer.Argument("world"),
+    count: int = typer.Option(1, "--count", "-c"),
+    dry_run: bool = typer.Option(..., "--dry-run"),
+    ratio: float = typer.Option("high"),  # E: Default `Literal['high']` is not assignable to parameter `ratio` with type `float`
+) -> None: ...
+    "#,
+);
@@ -0,0 +1,22 @@
+"""
+Minimal click stubs for testing.
+"""
+
+from click.core import Command as Command
+from click.core import Context as Context
+from click.core import Group as Group
+from click.decorators import argument as argument
+from click.decorators import command as command
+from click.decorators import confirmation_option as confirmation_option
+from click.decorators import group as group
+from click.decorators import help_option as help_option
+from click.decorators import make_pass_decorator as make_pass_decorator
+from click.decorators import option as option
+from click.decorators import pass_context as pass_context
+from click.decorators import pass_obj as pass_obj
+from click.decorators import password_option as password_option
+from click.decorators import version_option as version_option
+from click.types import Choice as Choice
+from click.types import FloatRange as FloatRange
+from click.types import IntRange as IntRange
+from click.types import Path as Path
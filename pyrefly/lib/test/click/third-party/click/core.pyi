@@ -0,0 +1,28 @@
+"""
+Minimal click stubs for testing.
+"""
+
+from typing import Any, Callable, TypeVar, overload
+
+_AnyCallable = Callable[..., Any]
+
+class Context:
+    obj: Any
+
+class Command:
+    name: str | None
+    callback: Callable[..., Any] | None
+    def __call__(self, *args: Any, **kwargs: Any) -> Any: ...
+    def main(self, args: list[str] | None = None) -> Any: ...
+
+class Group(Command):
+    commands: dict[str, Command]
+    def add_command(self, cmd: Command, name: str | None = None) -> None: ...
+    @overload
+    def command(self, __func: Callable[..., Any]) -> Command: ...
+    @overload
+    def command(self, *args: Any, **kwargs: Any) -> Callable[[Callable[..., Any]], Command]: ...
+    @overload
+    def group(self, __func: Callable[..., Any]) -> Group: ...
+    @overload
+    def group(self, *args: Any, **kwargs: Any) -> Callable[[Callable[..., Any]], Group]: ...
@@ -0,0 +1,29 @@
+"""
+Minimal click stubs for testing.
+"""
+
+from typing import Any, Callable, TypeVar, overload
+
+from click.core import Command, Group
+
+_AnyCallable = Callable[..., Any]
+FC = TypeVar("FC", bound=Callable[..., Any] | Command)
+F = TypeVar("F", bound=Callable[..., Any])
+
+def pass_context(f: F) -> F: ...
+def pass_obj(f: F) -> F: ...
+def make_pass_decorator(object_type: type[Any], ensure: bool = False) -> Callable[[F], F]: ...
+@overload
+def command(name: _AnyCallable) -> Command: ...
+@overload
+def command(name: str | None = None, **attrs: Any) -> Callable[[_AnyCallable], Command]: ...
+@overload
+def group(name: _AnyCallable) -> Group: ...
+@overload
+def group(name: str | None = None, **attrs: Any) -> Callable[[_AnyCallable], Group]: ...
+def argument(*param_decls: str, **attrs: Any) -> Callable[[FC], FC]: ...
+def option(*param_decls: str, **attrs: Any) -> Callable[[FC], FC]: ...
+def confirmation_option(*param_decls: str, **kwargs: Any) -> Callable[[FC], FC]: ...
+def password_option(*param_decls: str, **kwargs: Any) -> Callable[[FC], FC]: ...
+def version_option(version: str | None = None, *param_decls: str, **kwargs: Any) -> Callable[[FC], FC]: ...
+def help_option(*param_decls: str, **kwargs: Any) -> Callable[[FC], FC]: ...
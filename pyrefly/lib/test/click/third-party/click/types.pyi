@@ -0,0 +1,19 @@
+"""
+Minimal click stubs for testing.
+"""
+
+from typing import Any, Sequence
+
+class ParamType: ...
+
+class Choice(ParamType):
+    def __init__(self, choices: Sequence[str], case_sensitive: bool = True) -> None: ...
+
+class IntRange(ParamType):
+    def __init__(self, min: int | None = None, max: int | None = None) -> None: ...
+
+class FloatRange(ParamType):
+    def __init__(self, min: float | None = None, max: float | None = None) -> None: ...
+
+class Path(ParamType):
+    def __init__(self, exists: bool = False, path_type: Any = None) -> None: ...
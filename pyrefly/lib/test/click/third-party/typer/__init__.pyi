@@ -0,0 +1,7 @@
+"""
+Minimal typer stubs for testing.
+"""
+
+from typer.main import Typer as Typer
+from typer.params import Argument as Argument
+from typer.params import Option as Option
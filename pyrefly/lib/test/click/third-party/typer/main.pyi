@@ -0,0 +1,14 @@
+"""
+Minimal typer stubs for testing.
+"""
+
+from typing import Any, Callable, TypeVar
+
+CommandFunctionType = TypeVar("CommandFunctionType", bound=Callable[..., Any])
+
+class Typer:
+    def __init__(self) -> None: ...
+    def command(
+        self, name: str | None = None
+    ) -> Callable[[CommandFunctionType], CommandFunctionType]: ...
+    def __call__(self, *args: Any, **kwargs: Any) -> Any: ...
@@ -0,0 +1,8 @@
+"""
+Minimal typer stubs for testing.
+"""
+
+from typing import Any
+
+def Option(default: Any = ..., *param_decls: str, help: str | None = None) -> Any: ...
+def Argument(default: Any = ..., *, help: str | None = None) -> Any: ...
@@ -0,0 +1,39 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+
+pub fn click_env() -> TestEnv {
+    let path = std::env::var("CLICK_TEST_PATH").expect("CLICK_TEST_PATH must be set");
+    TestEnv::new_with_site_package_paths(&[&path])
+}
+
+#[macro_export]
+macro_rules! click_testcase {
+    (bug = $explanation:literal, $name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::click::util::click_env(),
+                $contents,
+                file!(),
+                line!(),
+            )
+        }
+    };
+    ($name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::click::util::click_env(),
+                $contents,
+                file!(),
+                line!() - 1,
+            )
+        }
+    };
+}
@@ -0,0 +1,230 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+testcase!(
+    test_unreachable_except,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+def f() -> None:
+    try:
+        pass
+    except LookupError:
+        pass
+    except KeyError:  # E: `except KeyError` is unreachable because an earlier `except` clause catches `LookupError`
+        pass
+    except (IndexError, ValueError):
+        pass
+    except ValueError as e:  # E: `except ValueError` is unreachable because an earlier `except` clause catches `ValueError`
+        pass
+    except Exception:
+        pass
+"#,
+);
+
+testcase!(
+    test_reachable_except,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+def f() -> None:
+    try:
+        pass
+    except KeyError:
+        pass
+    except LookupError:
+        pass
+    except (ValueError, OSError):
+        pass
+    except ExceptionGroup:
+        pass
+    except:
+        pass
+"#,
+);
+
+testcase!(
+    test_undocumented_raise,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+class ParseError(ValueError): ...
+
+def parse(x: str) -> int:
+    """Parse a number.
+
+    Raises:
+        ValueError: If `x` is not a number.
+    """
+    if not x:
+        raise ParseError()
+    if x == "?":
+        raise KeyError(x)  # E: `KeyError` is raised but not listed in the `Raises` section of the docstring
+    if x == "!":
+        raise TypeError  # E: `TypeError` is raised but not listed in the `Raises` section
+    return int(x)
+
+def parse_numpy(x: str) -> int:
+    """Parse a number.
+
+    Raises
+    ------
+    builtins.KeyError
+        If `x` is empty.
+    """
+    try:
+        if x == "?":
+            raise LookupError(x)
+    except LookupError:
+        raise KeyError(x)
+    return int(x)
+
+def undocumented(x: str) -> int:
+    """Parse a number."""
+    raise RuntimeError(x)
+"#,
+);
+
+testcase!(
+    test_undocumented_raise_caught,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+def f(x: int) -> int:
+    """Do something.
+
+    :raises ValueError: on bad input
+    """
+    try:
+        raise KeyError(x)
+    except LookupError:
+        pass
+    try:
+        raise OSError(x)
+    except:
+        pass
+    try:
+        raise TypeError(x)  # E: `TypeError` is raised but not listed
+    except KeyError:
+        pass
+    else:
+        raise IndexError(x)  # E: `IndexError` is raised but not listed
+    def helper() -> None:
+        raise RuntimeError(x)
+    raise ValueError(x)
+"#,
+);
+
+testcase!(
+    test_undocumented_raise_from_call,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+class ConfigError(Exception): ...
+
+def read(path: str) -> str:
+    if not path:
+        raise ValueError(path)
+    return path
+
+def parse(path: str) -> str:
+    return read(path)
+
+def load(path: str) -> str:
+    """Load a config.
+
+    Raises:
+        ConfigError: If the config is invalid.
+    """
+    try:
+        text = parse(path)
+    except ValueError:
+        raise ConfigError(path)
+    if text == "?":
+        return parse(text)  # E: `ValueError` may be raised by this call but is not listed in the `Raises` section of the docstring
+    return text
+
+class Loader:
+    def check(self, x: int) -> None:
+        if x < 0:
+            raise ValueError(x)
+
+    def load(self, x: int) -> None:
+        """Load something.
+
+        Raises:
+            KeyError: Never.
+        """
+        self.check(x)  # E: `ValueError` may be raised by this call
+"#,
+);
+
+testcase!(
+    test_undocumented_raise_recursive_call,
+    TestEnv::new().enable_exception_flow_errors(),
+    r#"
+def walk(n: int) -> int:
+    """Walk down.
+
+    Raises:
+        ValueError: If `n` is negative.
+    """
+    if n < 0:
+        raise ValueError(n)
+    return walk(n - 1) + bounce(n)
+
+def bounce(n: int) -> int:
+    return walk(n - 2)
+"#,
+);
+
+fn env_raises_across_modules() -> TestEnv {
+    let mut env = TestEnv::new().enable_exception_flow_errors();
+    env.add(
+        "first",
+        r#"
+def raise_key() -> None:
+    raise KeyError()
+"#,
+    );
+    env.add(
+        "second",
+        r#"
+from first import raise_key
+
+def call_first() -> None:
+    raise_key()
+
+def raise_index() -> None:
+    raise IndexError()
+
+def both() -> None:
+    call_first()
+    raise_index()
+"#,
+    );
+    env
+}
+
+// We follow calls into other modules only one level deep, so the `KeyError` raised in `first`
+// is not seen through `second`.
+testcase!(
+    test_undocumented_raise_across_modules,
+    env_raises_across_modules(),
+    r#"
+from first import raise_key
+from second import both, call_first
+
+def f() -> None:
+    """Do something.
+
+    Raises:
+        RuntimeError: Never.
+    """
+    raise_key()  # E: `KeyError` may be raised by this call
+    call_first()
+    both()  # E: `IndexError` may be raised by this call
+"#,
+);
@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::fastapi_testcase;
+
+fastapi_testcase!(
+    test_depends_return_type,
+    r#"
+from typing import Annotated, AsyncIterator, Iterator
+from fastapi import Depends, FastAPI
+
+class Session: ...
+class User: ...
+
+def get_session() -> Iterator[Session]: ...
+async def get_user() -> User: ...
+async def get_user_stream() -> AsyncIterator[User]: ...
+
+app = FastAPI()
+
+@app.get("/ok")
+def ok(
+    user: Annotated[User, Depends(get_user)],
+    streamed: Annotated[User, Depends(get_user_stream)],
+    session: Session = Depends(get_session),
+) -> None: ...
+
+@app.get("/bad")
+def bad(
+    user: Annotated[Session, Depends(get_user)],  # E: Dependency provides `User`, which is not assignable to parameter `user` with type `Session`
+    session: User = Depends(get_session),  # E: Dependency provides `Session`, which is not assignable to parameter `session` with type `User`
+) -> None: ...
+    "#,
+);
+
+fastapi_testcase!(
+    test_depends_in_dependency,
+    r#"
+from fastapi import Depends
+
+def get_token() -> str: ...
+
+def get_user_id(token: int = Depends(get_token)) -> int:  # E: Dependency provides `str`
+    return token
+    "#,
+);
+
+fastapi_testcase!(
+    test_parameter_defaults,
+    r#"
+from fastapi import Body, FastAPI, Path, Query
+
+DEFAULT_LIMIT = 10
+
+app = FastAPI()
+
+@app.get("/items/{item_id}")
+def read_items(
+    item_id: int = Path(...),
+    q: str | None = Query(None),
+    limit: int = Query(DEFAULT_LIMIT),
+    offset: int = Query(default="0"),  # E: Default `Literal['0']` is not assignable to parameter `offset` with type `int`
+    name: str = Body(3),  # E: Default `Literal[3]` is not assignable to parameter `name` with type `str`
+) -> None: ...
+    "#,
+);
+
+fastapi_testcase!(
+    test_route_path_parameters,
+    r#"
+from fastapi import APIRouter, Depends, FastAPI
+
+app = FastAPI()
+router = APIRouter()
+
+@app.get("/items/{item_id}")
+def read_item(item_id: int) -> None: ...
+
+@app.get("/items/{item_id}/tags/{tag}")  # E: Path parameter `tag` in `/items/{item_id}/tags/{tag}` is not a parameter of `read_tag`
+def read_tag(item_id: int) -> None: ...
+
+@router.get("/files/{file_path:path}")
+def read_file(file_path: str) -> None: ...
+
+def get_item(item_id: int) -> str: ...
+
+@app.get("/via-dependency/{item_id}")
+def via_dependency(item: str = Depends(get_item)) -> None: ...
+    "#,
+);
+
+fastapi_testcase!(
+    test_response_model,
+    r#"
+from pydantic import BaseModel
+from fastapi import FastAPI
+
+class UserIn(BaseModel):
+    name: str
+    password: str
+
+class UserOut(BaseModel):
+    name: str
+
+class Item(BaseModel):
+    title: str
+
+app = FastAPI()
+
+@app.post("/users", response_model=UserOut)
+def create_user(user: UserIn) -> UserIn:
+    return user
+
+@app.get("/items", response_model=list[Item])
+async def list_items() -> list[Item]: ...
+
+@app.get("/item", response_model=Item)  # E: Returned `UserOut` does not provide field `title` required by `response_model` `Item`
+def get_item() -> UserOut: ...
+
+@app.get("/count", response_model=int)  # E: Returned `str` is not compatible with `response_model` `int`
+def count() -> str: ...
+    "#,
+);
@@ -0,0 +1,13 @@
+"""
+Minimal FastAPI stubs for testing.
+"""
+
+from fastapi.applications import FastAPI as FastAPI
+from fastapi.param_functions import Body as Body
+from fastapi.param_functions import Depends as Depends
+from fastapi.param_functions import Path as Path
+from fastapi.param_functions import Query as Query
+from fastapi.param_functions import Security as Security
+from fastapi.routing import APIRouter as APIRouter
+
+__all__ = ["APIRouter", "Body", "Depends", "FastAPI", "Path", "Query", "Security"]
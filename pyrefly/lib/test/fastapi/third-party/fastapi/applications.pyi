@@ -0,0 +1,34 @@
+"""
+Minimal FastAPI stubs for testing.
+"""
+
+from typing import Any, Callable, Sequence, TypeVar
+
+from fastapi.routing import APIRouter
+
+DecoratedCallable = TypeVar("DecoratedCallable", bound=Callable[..., Any])
+
+class FastAPI:
+    router: APIRouter
+    def __init__(self, *, dependencies: Sequence[Any] | None = None) -> None: ...
+    def include_router(self, router: APIRouter, *, prefix: str = "") -> None: ...
+    def api_route(
+        self,
+        path: str,
+        *,
+        response_model: Any = None,
+        dependencies: Sequence[Any] | None = None,
+        methods: list[str] | None = None,
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def get(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def post(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def put(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def delete(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
@@ -0,0 +1,16 @@
+"""
+Minimal FastAPI stubs for testing.
+"""
+
+from typing import Any, Callable
+
+def Path(default: Any = ..., *, alias: str | None = None) -> Any: ...
+def Query(default: Any = ..., *, alias: str | None = None) -> Any: ...
+def Body(default: Any = ..., *, embed: bool = False) -> Any: ...
+def Depends(dependency: Callable[..., Any] | None = None, *, use_cache: bool = True) -> Any: ...
+def Security(
+    dependency: Callable[..., Any] | None = None,
+    *,
+    scopes: list[str] | None = None,
+    use_cache: bool = True,
+) -> Any: ...
@@ -0,0 +1,30 @@
+"""
+Minimal FastAPI stubs for testing.
+"""
+
+from typing import Any, Callable, Sequence, TypeVar
+
+DecoratedCallable = TypeVar("DecoratedCallable", bound=Callable[..., Any])
+
+class APIRouter:
+    def __init__(self, *, prefix: str = "", dependencies: Sequence[Any] | None = None) -> None: ...
+    def api_route(
+        self,
+        path: str,
+        *,
+        response_model: Any = None,
+        dependencies: Sequence[Any] | None = None,
+        methods: list[str] | None = None,
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def get(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def post(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def put(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
+    def delete(
+        self, path: str, *, response_model: Any = None, dependencies: Sequence[Any] | None = None
+    ) -> Callable[[DecoratedCallable], DecoratedCallable]: ...
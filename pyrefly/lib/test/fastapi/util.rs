@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+
+pub fn fastapi_env() -> TestEnv {
+    let pydantic_path =
+        std::env::var("PYDANTIC_TEST_PATH").expect("PYDANTIC_TEST_PATH must be set");
+    let fastapi_path = std::env::var("FASTAPI_TEST_PATH").expect("FASTAPI_TEST_PATH must be set");
+    TestEnv::new_with_site_package_paths(&[&pydantic_path, &fastapi_path])
+}
+
+#[macro_export]
+macro_rules! fastapi_testcase {
+    (bug = $explanation:literal, $name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::fastapi::util::fastapi_env(),
+                $contents,
+                file!(),
+                line!(),
+            )
+        }
+    };
+    ($name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::fastapi::util::fastapi_env(),
+                $contents,
+                file!(),
+                line!() - 1,
+            )
+        }
+    };
+}
@@ -6,6 +6,7 @@
  */
 
 use pyrefly_build::handle::Handle;
+use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_python::module_name::ModuleName;
 use pyrefly_python::module_path::ModulePath;
 use pyrefly_util::fs_anyhow;
@@ -670,6 +671,88 @@ fn test_import_fail_to_load() {
     assert_eq!(err.path().as_path().file_name(), Some("foo.py".as_ref()));
 }
 
+#[test]
+fn test_undeclared_dependency() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+    fs_anyhow::write(
+        &root.join("pyproject.toml"),
+        "[project]\nname = \"app\"\ndependencies = [\"requests\", \"attrs\", \"rich\"]\n\n\
+        [dependency-groups]\ndev = [\"pytest\"]\n",
+    )
+    .unwrap();
+    let site_packages = root.join("site-packages");
+    for (dist_info, file) in [
+        ("requests-2.32.0", "requests/__init__.py"),
+        ("PyYAML-6.0", "yaml/__init__.py"),
+        ("attrs-23.0", "attr/__init__.py"),
+        ("six-1.16.0", "six/__init__.py"),
+        ("rich-13.0", "rich/__init__.py"),
+        // A backport shadowed by the standard library, so `import dataclasses` never uses it.
+        ("dataclasses-0.6", "dataclasses.py"),
+    ] {
+        let dist_info = site_packages.join(format!("{dist_info}.dist-info"));
+        fs_anyhow::create_dir_all(&dist_info).unwrap();
+        fs_anyhow::write(&dist_info.join("RECORD"), format!("{file},,\n")).unwrap();
+        let path = site_packages.join(file);
+        fs_anyhow::create_dir_all(path.parent().unwrap()).unwrap();
+        fs_anyhow::write(&path, "x = 1").unwrap();
+    }
+    let main = root.join("main.py");
+    fs_anyhow::write(
+        &main,
+        r#"
+from typing import TYPE_CHECKING
+import requests
+import yaml
+from six import x
+import dataclasses
+if TYPE_CHECKING:
+    import attr
+    import six
+"#,
+    )
+    .unwrap();
+    let mut env = TestEnv::new()
+        .with_site_package_paths(vec![site_packages])
+        .with_config_path(root.join("pyrefly.toml"))
+        .enable_undeclared_dependency_error();
+    env.add_real_path("main", main);
+    let handles = [Handle::new(
+        ModuleName::from_str("main"),
+        ModulePath::filesystem(root.join("main.py")),
+        env.sys_info(),
+    )];
+    let (state, _) = env.to_state();
+    let transaction = state.transaction();
+    let errors = transaction.get_errors(&handles).collect_errors().ordinary;
+    let messages = errors
+        .iter()
+        .filter(|e| e.error_kind() == ErrorKind::UndeclaredDependency)
+        .map(|e| (e.display_range().to_string(), e.msg_header().to_owned()))
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    assert!(messages[0].0.starts_with("4:"), "{messages:?}");
+    assert_eq!(
+        messages[0].1,
+        "`yaml` is provided by `PyYAML`, which is not declared as a dependency in `pyproject.toml`"
+    );
+    assert!(messages[1].0.starts_with("5:"), "{messages:?}");
+    assert!(messages[1].1.starts_with("`six` is provided by `six`"));
+    // Undeclared imports under `TYPE_CHECKING` are reported with their own explanation.
+    assert!(messages[2].0.starts_with("9:"), "{messages:?}");
+    assert!(
+        messages[2]
+            .1
+            .contains("only imported under `TYPE_CHECKING`")
+    );
+    let usage = transaction.dependency_usage(&handles);
+    assert_eq!(usage.len(), 1);
+    // `pytest` is never imported either, but only `[project.dependencies]` are summarized.
+    assert_eq!(usage[0].unused, vec!["rich".to_owned()]);
+    assert_eq!(usage[0].type_checking_only, vec!["attrs".to_owned()]);
+}
+
 testcase!(
     test_import_os,
     r#"
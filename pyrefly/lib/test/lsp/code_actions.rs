@@ -9,11 +9,13 @@ use std::fs;
 
 use pretty_assertions::assert_eq;
 use pyrefly_build::handle::Handle;
+use pyrefly_config::error_kind::ErrorKind;
 use pyrefly_python::module::Module;
 use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
 use crate::module::module_info::ModuleInfo;
+use crate::state::lsp::ChangeSignatureParameter;
 use crate::state::lsp::ImportFormat;
 use crate::state::lsp::LocalRefactorCodeAction;
 use crate::state::require::Require;
@@ -1340,6 +1342,184 @@ fn redundant_cast_fix_all() {
     );
 }
 
+#[test]
+fn machine_applicable_fixes_apply_together() {
+    let (handles, state) = mk_multi_file_state(
+        &[(
+            "main",
+            "from typing import cast\nx: int = 0\nx = cast(int, x)\ndef f(y: str) -> str:\n    return str(y)\n",
+        )],
+        Require::Exports,
+        false,
+    );
+    let handle = handles.get("main").unwrap();
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let fixes = transaction.machine_applicable_fixes(handle, ImportFormat::Absolute);
+    assert_eq!(
+        fixes.iter().map(|fix| fix.error_kind).collect::<Vec<_>>(),
+        vec![
+            ErrorKind::RedundantCast,
+            ErrorKind::UnnecessaryTypeConversion
+        ]
+    );
+    // Each fix records where its error is, so `check --fix` can tell whether it went away.
+    assert!(
+        fixes[0]
+            .error_module
+            .code_at(fixes[0].error_range)
+            .starts_with("cast")
+    );
+    assert!(
+        fixes[1]
+            .error_module
+            .code_at(fixes[1].error_range)
+            .starts_with("str")
+    );
+    let edits = fixes
+        .into_iter()
+        .flat_map(|fix| fix.edits)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        "from typing import cast\nx: int = 0\nx = x\ndef f(y: str) -> str:\n    return y\n",
+        apply_refactor_edits_for_module(&module_info, &edits)
+    );
+}
+
+#[test]
+fn machine_applicable_fixes_only_import_unambiguous_names() {
+    let (handles, state) = mk_multi_file_state(
+        &[
+            ("main", "unique_helper()\nshared_helper()\n"),
+            (
+                "lib",
+                "def unique_helper() -> None: ...\ndef shared_helper() -> None: ...\n",
+            ),
+            ("other", "def shared_helper() -> None: ...\n"),
+        ],
+        Require::Exports,
+        false,
+    );
+    let handle = handles.get("main").unwrap();
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let fixes = transaction.machine_applicable_fixes(handle, ImportFormat::Absolute);
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(
+        "from lib import unique_helper\nunique_helper()\nshared_helper()\n",
+        apply_refactor_edits_for_module(&module_info, &fixes[0].edits)
+    );
+}
+
+/// Applies the Organize Imports edits to `main`, or returns `None` if none are offered.
+fn organize_imports(env: TestEnv) -> Option<String> {
+    let (state, handle_for_module) = env.to_state();
+    let handle = handle_for_module("main");
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(&handle).unwrap();
+    let edits = transaction.organize_imports_edits(&handle)?;
+    Some(apply_refactor_edits_for_module(&module_info, &edits))
+}
+
+#[test]
+fn organize_imports_groups_sorts_and_removes_unused() {
+    let mut env = TestEnv::new();
+    env.add("lib", "def helper() -> None: ...\n");
+    env.add(
+        "main",
+        r#"import sys
+from typing import List
+import os
+from lib import helper
+from typing import Dict
+import json
+
+print(os, sys, helper, Dict, List)
+"#,
+    );
+    assert_eq!(
+        organize_imports(env).unwrap(),
+        r#"import os
+import sys
+from typing import Dict, List
+
+from lib import helper
+
+print(os, sys, helper, Dict, List)
+"#
+    );
+}
+
+#[test]
+fn organize_imports_keeps_comments_and_type_checking_blocks() {
+    let mut env = TestEnv::new();
+    env.add("lib", "class A: ...\nclass B: ...\n");
+    env.add(
+        "main",
+        r#"from __future__ import annotations
+
+# local helpers
+from lib import B
+import os  # keep
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from lib import A
+    import collections
+
+print(os, B, TYPE_CHECKING)
+
+def f(x: A, y: collections.OrderedDict[int, int]) -> None: ...
+"#,
+    );
+    assert_eq!(
+        organize_imports(env).unwrap(),
+        r#"from __future__ import annotations
+
+import os  # keep
+from typing import TYPE_CHECKING
+
+# local helpers
+from lib import B
+
+if TYPE_CHECKING:
+    import collections
+
+    from lib import A
+
+print(os, B, TYPE_CHECKING)
+
+def f(x: A, y: collections.OrderedDict[int, int]) -> None: ...
+"#
+    );
+}
+
+#[test]
+fn organize_imports_resolves_site_packages_as_third_party() {
+    let mut env = TestEnv::new_with_site_package_paths(&["/site-packages"]);
+    env.add_with_path("numpy", "/site-packages/numpy/__init__.py", "");
+    env.add("lib", "");
+    env.add(
+        "main",
+        "import lib\nimport numpy\nimport os\n\nprint(lib, numpy, os)\n",
+    );
+    assert_eq!(
+        organize_imports(env).unwrap(),
+        "import os\n\nimport numpy\n\nimport lib\n\nprint(lib, numpy, os)\n"
+    );
+}
+
+#[test]
+fn organize_imports_leaves_organized_imports_alone() {
+    let env = TestEnv::one(
+        "main",
+        "import os\nfrom typing import (\n    TYPE_CHECKING,  # why\n)\n\nprint(os, TYPE_CHECKING)\n",
+    );
+    assert_eq!(organize_imports(env), None);
+    let env = TestEnv::one("main", "import os\nimport sys\n\nprint(os, sys)\n");
+    assert_eq!(organize_imports(env), None);
+}
+
 #[test]
 fn unnecessary_str_call_quickfix() {
     let report = get_batched_lsp_operations_report_allow_error(
@@ -4932,6 +5112,313 @@ class Child(Base):
     assert!(apply_first_safe_delete_action(code).is_none());
 }
 
+/// Applies a Change Signature request to the function at the cursor in `target_module` and
+/// returns the updated source of every module, or the reason the refactor was refused.
+fn apply_change_signature(
+    modules: &[(&'static str, &str)],
+    target_module: &'static str,
+    parameters: serde_json::Value,
+) -> Result<Vec<String>, String> {
+    let (handles, state) = mk_multi_file_state_assert_no_errors(modules, Require::Everything);
+    let transaction = state.transaction();
+    let target_code = modules
+        .iter()
+        .find(|(name, _)| *name == target_module)
+        .unwrap()
+        .1;
+    let position = cursor_selection(target_code).start();
+    let parameters: Vec<ChangeSignatureParameter> = serde_json::from_value(parameters).unwrap();
+    let edits = transaction.change_signature_edits(
+        handles.get(target_module).unwrap(),
+        position,
+        &parameters,
+    )?;
+    Ok(modules
+        .iter()
+        .map(|(name, _)| {
+            let module_info = transaction
+                .get_module_info(handles.get(name).unwrap())
+                .unwrap();
+            apply_refactor_edits_for_module(&module_info, &edits)
+        })
+        .collect())
+}
+
+#[test]
+fn change_signature_target_lists_parameters_without_receiver() {
+    let code = r#"
+class Greeter:
+    def greet(self, name: str, /, punctuation: str = "!", *, loud: bool = False) -> str:
+#       ^
+        return name + punctuation
+"#;
+    let (handles, state) =
+        mk_multi_file_state_assert_no_errors(&[("main", code)], Require::Everything);
+    let transaction = state.transaction();
+    let target = transaction
+        .change_signature_target(handles.get("main").unwrap(), cursor_selection(code).start())
+        .expect("expected a change signature target");
+    assert_eq!("greet", target.name);
+    assert_eq!(
+        serde_json::json!([
+            {"name": "name", "originalName": "name", "kind": "positionalOnly", "annotation": "str", "defaultValue": null, "callSiteValue": null},
+            {"name": "punctuation", "originalName": "punctuation", "kind": "positionalOrKeyword", "annotation": "str", "defaultValue": "\"!\"", "callSiteValue": null},
+            {"name": "loud", "originalName": "loud", "kind": "keywordOnly", "annotation": "bool", "defaultValue": "False", "callSiteValue": null},
+        ]),
+        serde_json::to_value(&target.parameters).unwrap()
+    );
+}
+
+#[test]
+fn change_signature_rewrites_calls_across_modules() {
+    let main = r#"
+def area(width: int, height: int, scale: int = 1) -> int:
+#   ^
+    return width * height * scale
+
+area(2, 3)
+area(2, height=3, scale=4)
+"#;
+    let other = r#"
+from main import area
+
+area(5, 6, 7)
+area(area(1, 2), 3)
+"#;
+    let updated = apply_change_signature(
+        &[("main", main), ("other", other)],
+        "main",
+        serde_json::json!([
+            {"name": "h", "originalName": "height", "annotation": "int"},
+            {"name": "width", "originalName": "width", "annotation": "int"},
+            {"name": "scale", "originalName": "scale", "kind": "keywordOnly", "annotation": "int", "defaultValue": "1"},
+            {"name": "unit", "kind": "keywordOnly", "annotation": "str", "defaultValue": "\"px\""},
+        ]),
+    )
+    .expect("expected change signature edits");
+    let expected_main = r#"
+def area(h: int, width: int, *, scale: int = 1, unit: str = "px") -> int:
+#   ^
+    return width * h * scale
+
+area(3, 2)
+area(h=3, width=2, scale=4)
+"#;
+    let expected_other = r#"
+from main import area
+
+area(6, 5, scale=7)
+area(3, area(2, 1))
+"#;
+    assert_eq!(vec![expected_main, expected_other], updated);
+}
+
+#[test]
+fn change_signature_updates_overrides() {
+    let code = r#"
+class Shape:
+    def scale(self, factor: float, origin: tuple[float, float]) -> None:
+#       ^
+        pass
+
+class Circle(Shape):
+    def scale(self, factor: float, origin: tuple[float, float] = (0.0, 0.0)) -> None:
+        print(factor, origin)
+
+def grow(shape: Shape, circle: Circle) -> None:
+    shape.scale(2.0, (1.0, 1.0))
+    circle.scale(3.0)
+    Shape.scale(shape, 2.0, (0.0, 0.0))
+"#;
+    let updated = apply_change_signature(
+        &[("main", code)],
+        "main",
+        serde_json::json!([
+            {"name": "ratio", "originalName": "factor", "annotation": "float"},
+            {"name": "origin", "originalName": "origin", "kind": "keywordOnly", "annotation": "tuple[float, float]"},
+        ]),
+    )
+    .expect("expected change signature edits");
+    let expected = r#"
+class Shape:
+    def scale(self, ratio: float, *, origin: tuple[float, float]) -> None:
+#       ^
+        pass
+
+class Circle(Shape):
+    def scale(self, ratio: float, *, origin: tuple[float, float] = (0.0, 0.0)) -> None:
+        print(ratio, origin)
+
+def grow(shape: Shape, circle: Circle) -> None:
+    shape.scale(2.0, origin=(1.0, 1.0))
+    circle.scale(3.0)
+    Shape.scale(shape, 2.0, origin=(0.0, 0.0))
+"#;
+    assert_eq!(vec![expected], updated);
+}
+
+#[test]
+fn change_signature_keeps_call_layout() {
+    let code = r#"
+def send(host: str, port: int, timeout: float = 1.0) -> None:
+#   ^
+    pass
+
+send(
+    "localhost",  # the host
+    8080,
+    timeout=2.0,  # seconds
+)
+send("example.org", 80)
+send(
+    "example.org",
+    443,
+)
+"#;
+    let updated = apply_change_signature(
+        &[("main", code)],
+        "main",
+        serde_json::json!([
+            {"name": "host", "originalName": "host", "annotation": "str"},
+            {"name": "port", "originalName": "port", "annotation": "int"},
+            {"name": "retries", "annotation": "int", "defaultValue": "0", "callSiteValue": "3"},
+            {"name": "deadline", "originalName": "timeout", "annotation": "float", "defaultValue": "1.0"},
+        ]),
+    )
+    .expect("expected change signature edits");
+    let expected = r#"
+def send(host: str, port: int, retries: int = 0, deadline: float = 1.0) -> None:
+#   ^
+    pass
+
+send(
+    "localhost",  # the host
+    8080,
+    3,
+    deadline=2.0,  # seconds
+)
+send("example.org", 80, 3)
+send(
+    "example.org",
+    443,
+    3,
+)
+"#;
+    assert_eq!(vec![expected], updated);
+}
+
+#[test]
+fn change_signature_keeps_variadic_parameters() {
+    let code = r#"
+def log(level: int, *messages: str, sep: str = " ", **extra: object) -> None:
+#   ^
+    pass
+
+log(1, "a", "b", sep=",", user="me")
+"#;
+    let updated = apply_change_signature(
+        &[("main", code)],
+        "main",
+        serde_json::json!([
+            {"name": "level", "originalName": "level", "annotation": "int"},
+            {"name": "prefix", "annotation": "str", "callSiteValue": "\"> \""},
+            {"name": "separator", "originalName": "sep", "kind": "keywordOnly", "annotation": "str", "defaultValue": "\" \""},
+        ]),
+    )
+    .expect("expected change signature edits");
+    let expected = r#"
+def log(level: int, prefix: str, *messages: str, separator: str = " ", **extra: object) -> None:
+#   ^
+    pass
+
+log(1, "> ", "a", "b", separator=",", user="me")
+"#;
+    assert_eq!(vec![expected], updated);
+}
+
+#[test]
+fn change_signature_keeps_unpacked_arguments() {
+    let code = r#"
+def move(x: int, y: int, z: int = 0) -> None:
+#   ^
+    pass
+
+def go(point: tuple[int, int]) -> None:
+    move(*point, z=1)
+"#;
+    let updated = apply_change_signature(
+        &[("main", code)],
+        "main",
+        serde_json::json!([
+            {"name": "x", "originalName": "x", "annotation": "int"},
+            {"name": "y", "originalName": "y", "annotation": "int"},
+            {"name": "depth", "originalName": "z", "annotation": "int", "defaultValue": "0"},
+            {"name": "scale", "kind": "keywordOnly", "annotation": "int", "callSiteValue": "2"},
+        ]),
+    )
+    .expect("expected change signature edits");
+    let expected = r#"
+def move(x: int, y: int, depth: int = 0, *, scale: int) -> None:
+#   ^
+    pass
+
+def go(point: tuple[int, int]) -> None:
+    move(*point, depth=1, scale=2)
+"#;
+    assert_eq!(vec![expected], updated);
+}
+
+#[test]
+fn change_signature_refuses_breaking_unpacked_arguments() {
+    let code = r#"
+def greet(name: str, punctuation: str) -> str:
+#   ^
+    return name + punctuation
+
+def shout(*args: str) -> str:
+    return greet(*args)
+
+def whisper(**kwargs: str) -> str:
+    return greet("hi", **kwargs)
+"#;
+    let swapped = serde_json::json!([
+        {"name": "punctuation", "originalName": "punctuation", "annotation": "str"},
+        {"name": "name", "originalName": "name", "annotation": "str"},
+    ]);
+    let error = apply_change_signature(&[("main", code)], "main", swapped)
+        .expect_err("expected the refactor to be refused");
+    assert!(
+        error.starts_with("Cannot rewrite the call to `greet` at main:")
+            && error.ends_with("because `*args` would no longer fill the same parameters"),
+        "unexpected message: {error}"
+    );
+
+    let renamed = serde_json::json!([
+        {"name": "name", "originalName": "name", "annotation": "str"},
+        {"name": "suffix", "originalName": "punctuation", "annotation": "str"},
+    ]);
+    let error = apply_change_signature(&[("main", code)], "main", renamed)
+        .expect_err("expected the refactor to be refused");
+    assert!(
+        error.starts_with("Cannot rewrite the call to `greet` at main:")
+            && error.ends_with("because `**kwargs` may pass `punctuation` by keyword"),
+        "unexpected message: {error}"
+    );
+
+    let missing_value = serde_json::json!([
+        {"name": "name", "originalName": "name", "annotation": "str"},
+        {"name": "punctuation", "originalName": "punctuation", "annotation": "str"},
+        {"name": "suffix", "annotation": "str"},
+    ]);
+    assert_eq!(
+        Err(
+            "New parameter `suffix` needs a default value or a value to pass at existing call sites"
+                .to_owned()
+        ),
+        apply_change_signature(&[("main", code)], "main", missing_value)
+    );
+}
+
 #[test]
 fn pytest_fixture_type_annotation_code_actions() {
     let conftest = r#"
@@ -5256,3 +5743,423 @@ class Derived(Base):
 ";
     assert_eq!(expected, after);
 }
+
+/// Applies the "Implement ..." quick fix offered at the first occurrence of `trigger` in
+/// `code`, or returns `None` if the fix is not offered.
+fn apply_implement_members_quickfix(
+    code: &str,
+    trigger: &str,
+    enable_missing_override: bool,
+) -> Option<String> {
+    let mut env = TestEnv::new();
+    env.add("main", code);
+    let env = if enable_missing_override {
+        env.enable_missing_override_decorator_error()
+    } else {
+        env
+    };
+    let (state, handle_for_module) = env.to_state();
+    let handle = handle_for_module("main");
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(&handle).unwrap();
+    let position = TextSize::try_from(code.find(trigger).unwrap() + trigger.len()).unwrap();
+    let (_, edits) = transaction
+        .local_quickfix_code_actions_sorted(
+            &handle,
+            TextRange::new(position, position),
+            ImportFormat::Absolute,
+            None,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(title, _)| title.starts_with("Implement "))?;
+    Some(apply_refactor_edits_for_module(&module_info, &edits))
+}
+
+#[test]
+fn quickfix_implement_abstract_members_substitutes_generics() {
+    let code = "\
+from abc import ABC, abstractmethod
+
+class Base[T](ABC):
+    @abstractmethod
+    def get(self, key: str, default: T | None = None) -> T: ...
+
+    @property
+    @abstractmethod
+    def size(self) -> int: ...
+
+class Impl(Base[int]):
+    pass
+
+Impl()
+";
+    let after = apply_implement_members_quickfix(code, "\nImpl(", false)
+        .expect("expected implement members quick fix");
+    let expected = "\
+from abc import ABC, abstractmethod
+
+class Base[T](ABC):
+    @abstractmethod
+    def get(self, key: str, default: T | None = None) -> T: ...
+
+    @property
+    @abstractmethod
+    def size(self) -> int: ...
+
+class Impl(Base[int]):
+    def get(self, key: str, default: int | None = None) -> int:
+        raise NotImplementedError
+
+    @property
+    def size(self) -> int:
+        raise NotImplementedError
+
+Impl()
+";
+    assert_eq!(expected, after);
+}
+
+#[test]
+fn quickfix_implement_abstract_members_adds_override_when_enabled() {
+    let code = "\
+from abc import ABC, abstractmethod
+
+class Base(ABC):
+    @abstractmethod
+    def run(self) -> None: ...
+
+class Impl(Base): ...
+
+Impl()
+";
+    let after = apply_implement_members_quickfix(code, "\nImpl(", true)
+        .expect("expected implement members quick fix");
+    assert!(
+        after.contains(
+            "\
+class Impl(Base):
+    @override
+    def run(self) -> None:
+        raise NotImplementedError
+"
+        ),
+        "expected an `@override` stub, got:\n{after}"
+    );
+    assert!(
+        after.contains("from typing import override\n"),
+        "expected the `override` import, got:\n{after}"
+    );
+}
+
+#[test]
+fn quickfix_implement_protocol_members() {
+    let code = "\
+from typing import Protocol
+
+class Greeter(Protocol):
+    name: str
+    def greet(self, other: str) -> str: ...
+
+class English:
+    pass
+
+g: Greeter = English()
+";
+    let after = apply_implement_members_quickfix(code, "English(", false)
+        .expect("expected implement members quick fix");
+    let expected = "\
+from typing import Protocol
+
+class Greeter(Protocol):
+    name: str
+    def greet(self, other: str) -> str: ...
+
+class English:
+    name: str
+
+    def greet(self, other: str) -> str:
+        raise NotImplementedError
+
+g: Greeter = English()
+";
+    assert_eq!(expected, after);
+}
+
+#[test]
+fn quickfix_implement_protocol_members_from_other_module() {
+    let lib = "\
+from typing import Protocol
+
+class Source[T](Protocol):
+    def read(self) -> T: ...
+    def close(self) -> None: ...
+
+def consume(source: Source[bytes]) -> None: ...
+";
+    let code = "\
+import lib
+
+class File:
+    pass
+
+lib.consume(File())
+";
+    let (handles, state) =
+        mk_multi_file_state(&[("lib", lib), ("main", code)], Require::Everything, false);
+    let handle = handles.get("main").unwrap();
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let position = TextSize::try_from(code.find("File(").unwrap()).unwrap();
+    let (title, edits) = transaction
+        .local_quickfix_code_actions_sorted(
+            handle,
+            TextRange::new(position, position),
+            ImportFormat::Absolute,
+            None,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(title, _)| title.starts_with("Implement "))
+        .expect("expected implement members quick fix");
+    assert_eq!("Implement members of `Source` in `File`", title);
+    let after = apply_refactor_edits_for_module(&module_info, &edits);
+    let expected = "\
+import lib
+
+class File:
+    def read(self) -> bytes:
+        raise NotImplementedError
+
+    def close(self) -> None:
+        raise NotImplementedError
+
+lib.consume(File())
+";
+    assert_eq!(expected, after);
+}
+
+fn apply_missing_cases_quickfix(files: &[(&'static str, &str)], trigger: &str) -> Option<String> {
+    let (handles, state) = mk_multi_file_state(files, Require::Everything, false);
+    let handle = handles.get("main").unwrap();
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let code = module_info.contents().as_str();
+    let position = TextSize::try_from(code.find(trigger).unwrap()).unwrap();
+    let (_, edits) = transaction
+        .local_quickfix_code_actions_sorted(
+            handle,
+            TextRange::new(position, position),
+            ImportFormat::Absolute,
+            None,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(title, _)| title == "Add missing `case` arms")?;
+    Some(apply_refactor_edits_for_module(&module_info, &edits))
+}
+
+fn apply_missing_isinstance_branches_action(code: &str, trigger: &str) -> Option<String> {
+    let (handles, state) =
+        mk_multi_file_state_assert_no_errors(&[("main", code)], Require::Everything);
+    let handle = handles.get("main").unwrap();
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(handle).unwrap();
+    let position = TextSize::try_from(code.find(trigger).unwrap()).unwrap();
+    let action = transaction
+        .add_missing_isinstance_branches_code_actions(handle, TextRange::new(position, position))?
+        .into_iter()
+        .next()?;
+    Some(apply_refactor_edits_for_module(&module_info, &action.edits))
+}
+
+#[test]
+fn quickfix_add_missing_enum_cases() {
+    let code = r#"from enum import Enum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+def describe(color: Color) -> None:
+    match color:
+        case Color.RED:
+            print("red")
+"#;
+    let after = apply_missing_cases_quickfix(&[("main", code)], "color:\n")
+        .expect("expected add missing cases quick fix");
+    let expected = r#"from enum import Enum
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+
+def describe(color: Color) -> None:
+    match color:
+        case Color.RED:
+            print("red")
+        case Color.GREEN:
+            pass
+        case Color.BLUE:
+            pass
+"#;
+    assert_eq!(expected, after);
+}
+
+#[test]
+fn quickfix_add_missing_class_and_literal_cases() {
+    let shapes = r#"from dataclasses import dataclass
+from typing import final
+
+@final
+@dataclass
+class Circle:
+    radius: float
+
+@final
+@dataclass
+class Rect:
+    width: float
+    height: float
+"#;
+    let code = r#"from typing import Literal
+import shapes
+
+def area(shape: shapes.Circle | shapes.Rect | Literal["empty"] | None) -> None:
+    match shape:
+        case shapes.Circle(radius):
+            print(radius)
+"#;
+    let after = apply_missing_cases_quickfix(&[("main", code), ("shapes", shapes)], "shape:\n")
+        .expect("expected add missing cases quick fix");
+    for case in [
+        "        case Rect(width, height):\n            pass\n",
+        "        case \"empty\":\n            pass\n",
+        "        case None:\n            pass\n",
+    ] {
+        assert!(after.contains(case), "expected `{case}`, got:\n{after}");
+    }
+    assert!(
+        after.starts_with("from shapes import Rect\n"),
+        "expected an import of `Rect`, got:\n{after}"
+    );
+    assert!(
+        !after.contains("case Circle"),
+        "expected `Circle` to be handled already, got:\n{after}"
+    );
+}
+
+#[test]
+fn refactor_add_missing_isinstance_branches() {
+    let code = r#"def handle(value: int | str | bytes | None) -> None:
+    if isinstance(value, int):
+        print(value)
+    elif isinstance(value, str):
+        print(value)
+"#;
+    let after = apply_missing_isinstance_branches_action(code, "isinstance(value, int)")
+        .expect("expected add missing isinstance branches action");
+    for branch in [
+        "    elif isinstance(value, bytes):\n        pass\n",
+        "    elif value is None:\n        pass\n",
+    ] {
+        assert!(after.contains(branch), "expected `{branch}`, got:\n{after}");
+    }
+    assert!(
+        after.ends_with("    else:\n        assert_never(value)\n"),
+        "expected a final `assert_never`, got:\n{after}"
+    );
+    assert!(
+        after.starts_with("from typing import assert_never\n"),
+        "expected an import of `assert_never`, got:\n{after}"
+    );
+}
+
+#[test]
+fn refactor_add_missing_isinstance_branches_requires_isinstance_chain() {
+    let with_else = r#"def handle(value: int | str) -> None:
+    if isinstance(value, int):
+        print(value)
+    else:
+        print(value)
+"#;
+    assert!(
+        apply_missing_isinstance_branches_action(with_else, "isinstance(value, int)").is_none()
+    );
+    let mixed = r#"def handle(value: int | str, flag: bool) -> None:
+    if isinstance(value, int):
+        print(value)
+    elif flag:
+        print(value)
+"#;
+    assert!(apply_missing_isinstance_branches_action(mixed, "isinstance(value, int)").is_none());
+}
+
+fn apply_wrap_in_with_quickfix(code: &str, trigger: &str) -> Option<String> {
+    let mut env = TestEnv::new().enable_unclosed_resource_error();
+    env.add("main", code);
+    let (state, handle_for_module) = env.to_state();
+    let handle = handle_for_module("main");
+    let transaction = state.transaction();
+    let module_info = transaction.get_module_info(&handle).unwrap();
+    let position = TextSize::try_from(code.find(trigger).unwrap()).unwrap();
+    let (_, edits) = transaction
+        .local_quickfix_code_actions_sorted(
+            &handle,
+            TextRange::new(position, position),
+            ImportFormat::Absolute,
+            None,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(title, _)| title == "Wrap in `with` block")?;
+    Some(apply_refactor_edits_for_module(&module_info, &edits))
+}
+
+#[test]
+fn quickfix_wrap_unclosed_resource_in_with() {
+    let code = r#"def read(path: str) -> str:
+    f = open(path)
+    # Read everything at once.
+    data = f.read()
+    if not data:
+        return ""
+    f.close()
+    print(data)
+    return data
+"#;
+    let after =
+        apply_wrap_in_with_quickfix(code, "open(path)").expect("expected wrap in with quick fix");
+    let expected = r#"def read(path: str) -> str:
+    with open(path) as f:
+        # Read everything at once.
+        data = f.read()
+        if not data:
+            return ""
+    print(data)
+    return data
+"#;
+    assert_eq!(expected, after);
+}
+
+#[test]
+fn quickfix_wrap_unused_resource_in_with() {
+    let code = r#"import sqlite3
+
+def connect() -> None:
+    conn = sqlite3.connect(":memory:")
+    print("done")
+"#;
+    let after = apply_wrap_in_with_quickfix(code, "sqlite3.connect")
+        .expect("expected wrap in with quick fix");
+    let expected = r#"import sqlite3
+
+def connect() -> None:
+    with sqlite3.connect(":memory:") as conn:
+        pass
+    print("done")
+"#;
+    assert_eq!(expected, after);
+}
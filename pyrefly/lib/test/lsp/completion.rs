@@ -8,6 +8,7 @@
 use lsp_types::CompletionItem;
 use lsp_types::CompletionItemKind;
 use lsp_types::CompletionItemTag;
+use lsp_types::CompletionTextEdit;
 use pretty_assertions::assert_eq;
 use pyrefly_build::handle::Handle;
 use pyrefly_python::sys_info::PythonVersion;
@@ -44,6 +45,7 @@ fn strip_ansi(input: &str) -> String {
 
 #[derive(Default)]
 struct ResultsFilter {
+    /// Include keywords and postfix snippets.
     include_keywords: bool,
     include_builtins: bool,
 }
@@ -94,6 +96,324 @@ class Foo:
     assert_eq!(get_value_suggestions, 1);
 }
 
+fn override_completion(code: &str, label: &str, version: PythonVersion) -> CompletionItem {
+    let mut env = TestEnv::new_with_version(version).with_default_require_level(Require::Exports);
+    env.add(
+        "lib",
+        r#"
+from abc import ABC, abstractmethod
+
+class Item: ...
+
+class Base(ABC):
+    def fetch(self, item: Item, /, *args: int, retries: int = 3) -> Item:
+        return item
+    def reset(self) -> None: ...
+    async def load(self, key: str) -> bytes:
+        return b""
+    @property
+    def size(self) -> int:
+        return 0
+    @abstractmethod
+    def run(self) -> int: ...
+"#,
+    );
+    env.add("main", code);
+    let (state, handle_for) = env.to_state();
+    let handle = handle_for("main");
+    let position = extract_cursors_for_test(code)[0];
+    let completions =
+        state
+            .transaction()
+            .completion(&handle, position, ImportFormat::Absolute, true, None);
+    let matching = completions
+        .iter()
+        .filter(|item| item.label == label)
+        .collect::<Vec<_>>();
+    assert_eq!(matching.len(), 1, "expected one `{label}` completion");
+    matching[0].clone()
+}
+
+fn additional_edit_texts(item: &CompletionItem) -> Vec<&str> {
+    item.additional_text_edits
+        .iter()
+        .flatten()
+        .map(|edit| edit.new_text.as_str())
+        .collect()
+}
+
+#[test]
+fn completion_override_method_in_class_body() {
+    let code = r#"
+from lib import Base
+
+class Child(Base):
+    def fe
+#         ^
+"#;
+    let item = override_completion(code, "fetch", PythonVersion::new(3, 12, 0));
+    assert_eq!(
+        item.insert_text.as_deref(),
+        Some(
+            "fetch(self, item: Item, /, *args: int, retries: int = 3) -> Item:\n        return super().fetch(item, *args, retries=retries)"
+        )
+    );
+    assert_eq!(
+        additional_edit_texts(&item),
+        vec![
+            "    @override\n",
+            "from lib import Item\n",
+            "from typing import override\n"
+        ]
+    );
+}
+
+#[test]
+fn completion_override_without_override_decorator_before_3_12() {
+    let code = r#"
+from lib import Base
+
+class Child(Base):
+    def re
+#         ^
+"#;
+    let item = override_completion(code, "reset", PythonVersion::new(3, 11, 0));
+    assert_eq!(
+        item.insert_text.as_deref(),
+        Some("reset(self) -> None:\n        super().reset()")
+    );
+    assert!(item.additional_text_edits.is_none());
+}
+
+#[test]
+fn completion_override_async_method_and_property() {
+    let code = r#"
+from typing import override
+from lib import Base
+
+class Child(Base):
+    def lo
+#         ^
+"#;
+    let load = override_completion(code, "load", PythonVersion::new(3, 12, 0));
+    assert_eq!(
+        load.insert_text.as_deref(),
+        Some("load(self, key: str) -> bytes:\n        return await super().load(key)")
+    );
+    assert_eq!(
+        additional_edit_texts(&load),
+        vec!["    @override\n", "async "]
+    );
+    let code = code.replace("def lo", "def si");
+    let size = override_completion(&code, "size", PythonVersion::new(3, 12, 0));
+    assert_eq!(
+        size.insert_text.as_deref(),
+        Some("size(self) -> int:\n        return super().size")
+    );
+    assert_eq!(
+        additional_edit_texts(&size),
+        vec!["    @override\n    @property\n"]
+    );
+}
+
+#[test]
+fn completion_override_abstract_method_raises() {
+    let code = r#"
+from lib import Base
+
+class Child(Base):
+    def ru
+#         ^
+"#;
+    let item = override_completion(code, "run", PythonVersion::new(3, 11, 0));
+    assert_eq!(
+        item.insert_text.as_deref(),
+        Some("run(self) -> int:\n        raise NotImplementedError")
+    );
+}
+
+#[test]
+fn completion_override_skips_members_defined_in_class() {
+    let code = r#"
+class Base:
+    def reset(self) -> None: ...
+    def restore(self) -> None: ...
+
+class Child(Base):
+    def reset(self) -> None: ...
+    def re
+#         ^
+"#;
+    let (handles, state) = mk_multi_file_state(&[("main", code)], Require::Exports, false);
+    let handle = handles.get("main").unwrap();
+    let position = extract_cursors_for_test(code)[0];
+    let completions =
+        state
+            .transaction()
+            .completion(handle, position, ImportFormat::Absolute, true, None);
+    assert!(
+        completions
+            .iter()
+            .filter(|item| item.label == "reset")
+            .all(|item| item.insert_text.is_none()),
+        "`reset` is already overridden: {completions:?}"
+    );
+    assert!(
+        completions
+            .iter()
+            .any(|item| item.label == "restore" && item.insert_text.is_some()),
+        "missing `restore` override: {completions:?}"
+    );
+}
+
+/// The postfix completions at each cursor in `code`, as sorted `(label, new text)` pairs.
+fn postfix_completions(code: &str) -> Vec<Vec<(String, String)>> {
+    let (handles, state) = mk_multi_file_state(&[("main", code)], Require::Exports, false);
+    let handle = handles.get("main").unwrap();
+    let txn = state.transaction();
+    extract_cursors_for_test(code)
+        .into_iter()
+        .map(|position| {
+            let mut snippets = txn
+                .completion(handle, position, ImportFormat::Absolute, true, None)
+                .into_iter()
+                .filter(|item| item.kind == Some(CompletionItemKind::SNIPPET))
+                .map(|item| match item.text_edit {
+                    Some(CompletionTextEdit::Edit(edit)) => (item.label, edit.new_text),
+                    other => panic!("expected a text edit for `{}`, got {other:?}", item.label),
+                })
+                .collect::<Vec<_>>();
+            snippets.sort();
+            snippets
+        })
+        .collect()
+}
+
+fn snippets(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(label, text)| ((*label).to_owned(), (*text).to_owned()))
+        .collect()
+}
+
+#[test]
+fn postfix_completion_statement_templates() {
+    let code = r#"
+class Job: ...
+
+def run(jobs: list[Job]) -> None:
+    jobs.
+#        ^
+"#;
+    assert_eq!(
+        postfix_completions(code),
+        vec![snippets(&[
+            ("for", "for job in jobs:\n        pass"),
+            ("if", "if jobs:\n        pass"),
+            ("not", "not jobs"),
+            ("par", "(jobs)"),
+            ("return", "return jobs"),
+        ])]
+    );
+}
+
+#[test]
+fn postfix_completion_in_expression() {
+    let code = r#"
+def show(value: int | str | None) -> None:
+    print(value.)
+#               ^
+"#;
+    assert_eq!(
+        postfix_completions(code),
+        vec![snippets(&[
+            ("isinstance", "isinstance(value, int)"),
+            ("isinstance", "isinstance(value, str)"),
+            ("not", "not value"),
+            ("par", "(value)"),
+        ])]
+    );
+}
+
+#[test]
+fn postfix_completion_match_union() {
+    let code = r#"
+def show(value: int | str | None) -> None:
+    value.ma
+#          ^
+"#;
+    let results = postfix_completions(code).remove(0);
+    assert!(
+        results.contains(&(
+            "match".to_owned(),
+            "match value:\n        case int():\n            pass\n        case str():\n            pass\n        case None:\n            pass"
+                .to_owned()
+        )),
+        "{results:?}"
+    );
+}
+
+#[test]
+fn postfix_completion_match_enum_and_literal() {
+    let code = r#"
+from enum import Enum
+from typing import Literal
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+
+def paint(color: Color, mode: Literal["fast", "slow"]) -> None:
+    color.ma
+#          ^
+    mode.ma
+#         ^
+"#;
+    let results = postfix_completions(code);
+    assert!(
+        results[0].contains(&(
+            "match".to_owned(),
+            "match color:\n        case Color.RED:\n            pass\n        case Color.GREEN:\n            pass"
+                .to_owned()
+        )),
+        "{results:?}"
+    );
+    assert!(
+        results[1].contains(&(
+            "match".to_owned(),
+            "match mode:\n        case \"fast\":\n            pass\n        case \"slow\":\n            pass"
+                .to_owned()
+        )),
+        "{results:?}"
+    );
+}
+
+#[test]
+fn postfix_completion_await_only_in_async_function() {
+    let code = r#"
+async def fetch() -> int: ...
+
+async def main() -> None:
+    task = fetch()
+    task.aw
+#         ^
+
+def sync() -> None:
+    task = fetch()
+    task.aw
+#         ^
+"#;
+    let results = postfix_completions(code);
+    assert!(
+        results[0].contains(&("await".to_owned(), "await task".to_owned())),
+        "{results:?}"
+    );
+    assert!(
+        !results[1].iter().any(|(label, _)| label == "await"),
+        "{results:?}"
+    );
+}
+
 fn get_default_test_report() -> impl Fn(&State, &Handle, TextSize) -> String {
     get_test_report(ResultsFilter::default(), ImportFormat::Absolute)
 }
@@ -123,7 +443,11 @@ fn get_test_report(
             } else {
                 false
             };
-            if (filter.include_keywords || kind != Some(CompletionItemKind::KEYWORD))
+            if (filter.include_keywords
+                || !matches!(
+                    kind,
+                    Some(CompletionItemKind::KEYWORD | CompletionItemKind::SNIPPET)
+                ))
                 && (filter.include_builtins || data != Some(serde_json::json!("builtin")))
             {
                 report.push_str("\n- (");
@@ -624,6 +624,10 @@ def f(x: int | None) -> None:
         report_with_links.contains("builtins.pyi"),
         "Expected hover links to include builtins.pyi, got: {report_with_links}"
     );
+    assert!(
+        report_with_links.contains("](command:pyrefly.showTypeDerivation?"),
+        "Expected hover to link to the type derivation, got: {report_with_links}"
+    );
 }
 
 #[test]
@@ -2468,3 +2472,71 @@ method
     assert_sphinx_resolved_as_code(&report, "py-meth", "test");
     assert_sphinx_resolved_as_code(&report, "c-func", "other");
 }
+
+/// Hover over each cursor in `code`, with `undocumented-raise` enabled or not.
+fn get_raises_report(code: &str, exception_flow_errors: bool) -> String {
+    let mut env = if exception_flow_errors {
+        TestEnv::new().enable_exception_flow_errors()
+    } else {
+        TestEnv::new()
+    };
+    env.add("main", code);
+    let (state, handle_for_name) = env.to_state();
+    let handle = handle_for_name("main");
+    extract_cursors_for_test(code)
+        .into_iter()
+        .map(|position| get_test_report(&state, &handle, position))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const RAISES_CODE: &str = r#"
+class ConfigError(Exception): ...
+
+def read(path: str) -> str:
+    if not path:
+        raise ValueError(path)
+    try:
+        raise KeyError(path)
+    except LookupError:
+        pass
+    return path
+
+def load(path: str) -> str:
+    if path == "-":
+        raise ConfigError
+    try:
+        return read(path)
+    except ValueError:
+        return ""
+
+def load_all(path: str) -> str:
+    return read(path) + load(path)
+
+load
+#^
+load_all
+#^
+"#;
+
+#[test]
+fn hover_shows_raised_exceptions() {
+    let report = get_raises_report(RAISES_CODE, true);
+    assert!(
+        report.contains("**Raises**\n- `ConfigError`\n"),
+        "got: {report}"
+    );
+    assert!(
+        report.contains("**Raises**\n- `ValueError`\n- `ConfigError`\n"),
+        "got: {report}"
+    );
+    // `load` catches the `ValueError` from `read`, and `read` catches its own `KeyError`.
+    assert_eq!(report.matches("`ValueError`").count(), 1, "got: {report}");
+    assert!(!report.contains("`KeyError`"), "got: {report}");
+}
+
+#[test]
+fn hover_omits_raised_exceptions_when_check_disabled() {
+    let report = get_raises_report(RAISES_CODE, false);
+    assert!(!report.contains("**Raises**"), "got: {report}");
+}
@@ -40,11 +40,14 @@ fn test_initialize_basic() {
             "definitionProvider": true,
             "typeDefinitionProvider": true,
             "codeActionProvider": {
-                "codeActionKinds": ["quickfix", "refactor.extract", "refactor.rewrite", "refactor.delete", "refactor.move", "refactor.inline", "source.fixAll", "source.fixAll.pyrefly"]
+                "codeActionKinds": ["quickfix", "refactor.extract", "refactor.rewrite", "refactor.delete", "refactor.move", "refactor.inline", "source.fixAll", "source.fixAll.pyrefly", "source.organizeImports", "source.organizeImports.pyrefly"]
             },
             "codeLensProvider": {
                 "resolveProvider": false,
             },
+            "executeCommandProvider": {
+                "commands": ["pyrefly.changeSignature", "pyrefly.why"]
+            },
             "completionProvider": {
                 "resolveProvider": true,
                 "triggerCharacters": [".", "'", "\""]
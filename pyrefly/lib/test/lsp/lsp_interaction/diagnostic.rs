@@ -5,6 +5,8 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
+
 use lsp_server::RequestId;
 use lsp_types::DocumentDiagnosticReportResult;
 use lsp_types::PublishDiagnosticsParams;
@@ -29,6 +31,21 @@ use serde_json::json;
 
 use crate::test::lsp::lsp_interaction::util::get_test_files_root;
 
+/// The related information of a `bad-assignment` to `x: int` on `line` of `file`, which
+/// points at the declared type.
+fn declared_type_related_information(root: &Path, file: &str, line: u32) -> Value {
+    json!([{
+        "location": {
+            "uri": Url::from_file_path(root.join(file)).unwrap().to_string(),
+            "range": {
+                "start": {"line": line, "character": 3},
+                "end": {"line": line, "character": 6}
+            }
+        },
+        "message": "declared type"
+    }])
+}
+
 fn require_markdown_initialize(interaction: &LspInteraction) {
     let settings = InitializeSettings {
         configuration: Some(None),
@@ -729,6 +746,11 @@ fn test_error_documentation_links() {
                         "end": {"character": 11, "line": 9},
                         "start": {"character": 9, "line": 9}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        "error_docs_test.py",
+                        9,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 },
@@ -1161,6 +1183,11 @@ fn test_shows_stdlib_type_errors_with_force_on() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        stdlib_filepath,
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -1217,6 +1244,11 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        "filtering_stdlib_errors/usr/local/lib/python3.12/stdlib_file.py",
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -1250,6 +1282,11 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        "filtering_stdlib_errors/usr/local/lib/python3.8/stdlib_file.py",
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -1277,6 +1314,11 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        "filtering_stdlib_errors/usr/lib/python3.12/stdlib_file.py",
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -1310,6 +1352,11 @@ fn test_shows_stdlib_errors_for_multiple_versions_and_paths_with_force_on() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        "filtering_stdlib_errors/usr/lib64/python3.12/stdlib_file.py",
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -1403,6 +1450,11 @@ fn test_shows_stdlib_errors_when_explicitly_included_in_project_includes() {
                         "end": {"character": 12, "line": 5},
                         "start": {"character": 9, "line": 5}
                     },
+                    "relatedInformation": declared_type_related_information(
+                        test_files_root.path(),
+                        stdlib_filepath,
+                        5,
+                    ),
                     "severity": 1,
                     "source": "Pyrefly"
                 }
@@ -9,18 +9,23 @@
 
 mod abstract_methods;
 mod annotation;
+mod argparse;
 mod assign;
 mod attribute_narrow;
 mod attributes;
 mod attrs;
+mod banned_api;
+mod blocking_call;
 mod callable;
 mod callable_residuals;
 mod calls;
+mod cattrs;
 mod cinderx;
 mod class_keywords;
 mod class_overrides;
 mod class_subtyping;
 mod class_super;
+mod click;
 mod constructors;
 mod contextual;
 mod cycles;
@@ -32,7 +37,9 @@ mod descriptors;
 mod dict;
 mod django;
 mod enums;
+mod exception_flow;
 mod factory_boy;
+mod fastapi;
 mod flow_branching;
 mod flow_looping;
 mod functools;
@@ -48,6 +55,7 @@ mod literal;
 mod lsp;
 mod marshmallow;
 mod mro;
+mod msgspec;
 mod named_tuple;
 mod narrow;
 mod natural;
@@ -75,6 +83,7 @@ mod shape_dsl;
 mod simple;
 mod sklearn;
 mod slots;
+mod sqlmodel;
 mod state;
 mod subscript_narrow;
 mod suppression;
@@ -86,6 +95,7 @@ mod type_var_tuple;
 mod typed_dict;
 mod typeform;
 mod typing_self;
+mod unclosed_resource;
 mod unittest_mock_patch;
 mod unnecessary_comparison;
 mod unnecessary_type_conversion;
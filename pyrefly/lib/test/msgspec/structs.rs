@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::msgspec_testcase;
+
+msgspec_testcase!(
+    test_struct_init,
+    r#"
+import msgspec
+
+class User(msgspec.Struct):
+    name: str
+    age: int = 0
+
+User("alice")
+User("alice", 3)
+User(name="alice", age="3")  # E: Argument `Literal['3']` is not assignable to parameter `age` with type `int`
+    "#,
+);
+
+msgspec_testcase!(
+    test_struct_kw_only_and_frozen,
+    r#"
+import msgspec
+
+class Point(msgspec.Struct, kw_only=True, frozen=True):
+    x: int
+    y: int
+
+p = Point(x=1, y=2)
+Point(1, 2)  # E: Expected 0 positional arguments
+p.x = 3  # E: Cannot set field `x`
+    "#,
+);
+
+msgspec_testcase!(
+    test_struct_fields,
+    r#"
+from typing import assert_type, Literal
+import msgspec
+
+class Base(msgspec.Struct):
+    a: int
+
+class Child(Base):
+    b: str
+
+assert_type(Child.__struct_fields__, tuple[Literal['a'], Literal['b']])
+    "#,
+);
+
+msgspec_testcase!(
+    test_struct_options,
+    r#"
+import msgspec
+
+class Tagged(msgspec.Struct, tag=True, tag_field="kind", rename="camel", omit_defaults=True, array_like=False):
+    field_one: int
+
+class Clash(msgspec.Struct, tag=True, tag_field="kind"):  # E: `tag_field` `kind` conflicts with an existing field of that name
+    kind: str
+
+class ArrayLike(msgspec.Struct, tag=True, tag_field="kind", array_like=True):
+    kind: str
+
+class BadRename(msgspec.Struct, rename="snake"):  # E: Invalid value for `rename`
+    x: int
+
+class Typo(msgspec.Struct, frozn=True):  # E: Unexpected keyword `frozn`
+    x: int
+
+class Unhashable(msgspec.Struct, cache_hash=True):  # E: `cache_hash=True` requires `frozen=True`
+    x: int
+
+class Frozen(msgspec.Struct, frozen=True):
+    x: int
+
+class Hashed(Frozen, cache_hash=True):
+    y: int
+    "#,
+);
+
+msgspec_testcase!(
+    test_struct_encode_fields,
+    r#"
+from typing import assert_type, Literal
+import msgspec
+
+class Camel(msgspec.Struct, rename="camel"):
+    field_one: int
+    _private_two: int
+
+class Pascal(msgspec.Struct, rename="pascal"):
+    field_one: int
+
+class Kebab(msgspec.Struct, rename="kebab"):
+    field_one: int
+
+class Upper(Camel, rename="upper"):
+    three: int
+
+class Inherited(Camel):
+    three: int
+
+def custom(name: str) -> str:
+    return name
+
+class Custom(msgspec.Struct, rename=custom):
+    field_one: int
+
+assert_type(Camel.__struct_encode_fields__, tuple[Literal['fieldOne'], Literal['_privateTwo']])
+assert_type(Pascal.__struct_encode_fields__, tuple[Literal['FieldOne']])
+assert_type(Kebab.__struct_encode_fields__, tuple[Literal['field-one']])
+assert_type(Upper.__struct_encode_fields__, tuple[Literal['FIELD_ONE'], Literal['_PRIVATE_TWO'], Literal['THREE']])
+assert_type(Inherited.__struct_encode_fields__, tuple[Literal['fieldOne'], Literal['_privateTwo'], Literal['three']])
+assert_type(Custom.__struct_encode_fields__, tuple[str, ...])
+    "#,
+);
+
+msgspec_testcase!(
+    test_struct_tag,
+    r#"
+from typing import assert_type, Literal
+import msgspec
+
+class Untagged(msgspec.Struct):
+    x: int
+
+class Base(msgspec.Struct, tag=True):
+    x: int
+
+class Child(Base):
+    y: int
+
+class Named(msgspec.Struct, tag="named", tag_field="kind"):
+    x: int
+
+class Numbered(msgspec.Struct, tag=3):
+    x: int
+
+class FieldOnly(msgspec.Struct, tag_field="kind"):
+    x: int
+
+assert_type(Untagged.__struct_tag__, None)
+assert_type(Untagged.__struct_tag_field__, None)
+assert_type(Base.__struct_tag__, Literal['Base'])
+assert_type(Base.__struct_tag_field__, Literal['type'])
+assert_type(Child.__struct_tag__, Literal['Child'])
+assert_type(Named.__struct_tag__, Literal['named'])
+assert_type(Named.__struct_tag_field__, Literal['kind'])
+assert_type(Numbered.__struct_tag__, Literal[3])
+assert_type(FieldOnly.__struct_tag__, Literal['FieldOnly'])
+assert_type(FieldOnly.__struct_tag_field__, Literal['kind'])
+    "#,
+);
+
+msgspec_testcase!(
+    test_decode,
+    r#"
+from typing import assert_type
+import msgspec
+import msgspec.json
+
+class User(msgspec.Struct):
+    name: str
+
+assert_type(msgspec.json.decode(b"{}", type=User), User)
+assert_type(msgspec.convert({}, User), User)
+    "#,
+);
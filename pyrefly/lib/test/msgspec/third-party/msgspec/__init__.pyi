@@ -0,0 +1,52 @@
+"""
+Minimal msgspec stubs for testing.
+"""
+
+from typing import Any, Callable, ClassVar, Literal, Mapping, TypeVar, overload
+
+from typing_extensions import dataclass_transform
+
+T = TypeVar("T")
+
+class _NoDefault: ...
+
+NODEFAULT: _NoDefault
+
+@overload
+def field(*, default: T, name: str | None = None) -> T: ...
+@overload
+def field(*, default_factory: Callable[[], T], name: str | None = None) -> T: ...
+@overload
+def field(*, name: str | None = None) -> Any: ...
+
+class StructMeta(type): ...
+
+@dataclass_transform(field_specifiers=(field,))
+class Struct(metaclass=StructMeta):
+    __struct_fields__: ClassVar[tuple[str, ...]]
+    __struct_encode_fields__: ClassVar[tuple[str, ...]]
+    __struct_tag_field__: ClassVar[str | None]
+    __struct_tag__: ClassVar[str | int | None]
+    def __init_subclass__(
+        cls,
+        tag: None | bool | str | int | Callable[[str], str | int] = None,
+        tag_field: None | str = None,
+        rename: None
+        | Literal["lower", "upper", "camel", "pascal", "kebab"]
+        | Callable[[str], str | None]
+        | Mapping[str, str] = None,
+        omit_defaults: bool = False,
+        forbid_unknown_fields: bool = False,
+        frozen: bool = False,
+        eq: bool = True,
+        order: bool = False,
+        kw_only: bool = False,
+        repr_omit_defaults: bool = False,
+        array_like: bool = False,
+        gc: bool = True,
+        weakref: bool = False,
+        dict: bool = False,
+        cache_hash: bool = False,
+    ) -> None: ...
+
+def convert(obj: Any, type: type[T], *, strict: bool = True) -> T: ...
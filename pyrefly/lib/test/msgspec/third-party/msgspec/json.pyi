@@ -0,0 +1,13 @@
+"""
+Minimal msgspec stubs for testing.
+"""
+
+from typing import Any, TypeVar, overload
+
+T = TypeVar("T")
+
+def encode(obj: Any) -> bytes: ...
+@overload
+def decode(buf: bytes | str) -> Any: ...
+@overload
+def decode(buf: bytes | str, *, type: type[T], strict: bool = True) -> T: ...
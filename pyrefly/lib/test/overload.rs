@@ -2519,3 +2519,81 @@ if TYPE_CHECKING:
     def f(a: str): ...
     "#,
 );
+
+testcase!(
+    test_overload_error_explains_each_overload,
+    r#"
+from typing import overload
+
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: str) -> str: ...
+@overload
+def f(x: bytes, y: bytes) -> bytes: ...
+def f(x, y=None): return x
+
+f(1.0)  # E: Why each overload failed:\n    1 [closest match] | Argument `x`: expected `int`, got `float`\n    2                 | Argument `x`: expected `str`, got `float`\n    3                 | Expected at least 2 arguments, got 1
+    "#,
+);
+
+testcase!(
+    test_overload_error_explains_arity_mismatches,
+    r#"
+from typing import overload
+
+@overload
+def f(x: int) -> int: ...
+@overload
+def f(x: int, y: int) -> int: ...
+def f(x, y=None): return x
+
+f(1, 2, 3)  # E: Why each overload failed:\n    1                 | Expected at most 1 argument, got 3\n    2 [closest match] | Expected at most 2 arguments, got 3
+    "#,
+);
+
+testcase!(
+    test_overload_error_counts_extra_mismatches,
+    r#"
+from typing import overload
+
+@overload
+def f(x: int, y: int) -> int: ...
+@overload
+def f(x: str, y: float) -> str: ...
+def f(x, y): return x
+
+f(1.0, 1.0)  # E: Why each overload failed:\n    1                 | Argument `x`: expected `int`, got `float` (+1 more)\n    2 [closest match] | Argument `x`: expected `str`, got `float`
+    "#,
+);
+
+testcase!(
+    test_overload_error_shows_closest_signature_diff,
+    r#"
+from typing import overload
+
+@overload
+def f(x: int, y: str) -> int: ...
+@overload
+def f(x: str, y: int) -> str: ...
+def f(x, y): return x
+
+f(1.0, "a")  # E: Signature mismatch:\n  expected: (x: int, y: str) -> int
+    "#,
+);
+
+testcase!(
+    test_overload_error_signature_diff_omits_self,
+    r#"
+from typing import overload
+
+class C:
+    @overload
+    def m(self, x: int) -> int: ...
+    @overload
+    def m(self, x: str) -> str: ...
+    def m(self, x): return x
+
+C().m(1.0)  # E: expected: (x: int) -> int
+    "#,
+);
@@ -2034,3 +2034,50 @@ class A(Protocol):
             pass
     "#,
 );
+
+testcase!(
+    test_protocol_mismatch_lists_every_member,
+    r#"
+from typing import Protocol
+class P(Protocol):
+    x: int
+    def f(self, a: int) -> int: ...
+    def g(self) -> None: ...
+class C:
+    x: str
+    def f(self, a: str) -> int: ...
+def take(p: P) -> None: ...
+take(C())  # E: 3 members required by protocol `P` are missing or incompatible:
+"#,
+);
+
+testcase!(
+    test_protocol_mismatch_respects_limit,
+    TestEnv::new().with_max_protocol_mismatches(1),
+    r#"
+from typing import Protocol
+class P(Protocol):
+    x: int
+    def f(self, a: int) -> int: ...
+    def g(self) -> None: ...
+class C:
+    x: str
+    def f(self, a: str) -> int: ...
+def take(p: P) -> None: ...
+take(C())  # E: ...and more
+"#,
+);
+
+testcase!(
+    test_protocol_mismatch_single_member_unchanged,
+    r#"
+from typing import Protocol
+class P(Protocol):
+    x: int
+    y: int
+class C:
+    x: int
+def take(p: P) -> None: ...
+take(C())  # E: Protocol `P` requires attribute `y`
+"#,
+);
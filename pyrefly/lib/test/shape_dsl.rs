@@ -177,6 +177,28 @@ def tc_identity[Shape: IntTuple, DType](x: tcarray[Shape, DType]) -> tcarray[Sha
     env
 }
 
+fn shaped_array_env_with_jax() -> TestEnv {
+    let mut env = shaped_array_env();
+    env.add_with_path(
+        "jax",
+        "jax/__init__.pyi",
+        r#"
+from collections.abc import Callable
+from typing import Any
+from shape_extensions import IntTuple, IntVar, shaped_array
+
+@shaped_array(shape="Shape")
+class Array[Shape: IntTuple]:
+    shape: Shape
+
+def jit[F: Callable[..., Any]](fun: F, /, *, static_argnums: int | tuple[int, ...] | None = None) -> F: ...
+def vmap(fun: Callable[..., Any], in_axes: Any = 0, out_axes: Any = 0) -> Callable[..., Any]: ...
+def ones[N: IntVar, M: IntVar](shape: IntTuple[N, M]) -> Array[[N, M]]: ...
+"#,
+    );
+    env
+}
+
 fn shape_dsl_base_env() -> TestEnv {
     shaped_array_env()
 }
@@ -3727,6 +3749,88 @@ def f(x: np.tcarray[[2, 3], int]) -> None:
 "#,
 );
 
+testcase!(
+    test_jax_vmap_batches_arguments_and_result,
+    shaped_array_env_with_jax(),
+    r#"
+import jax
+from jax import Array
+from shape_extensions import IntVar, assert_shape
+
+def add_bias(x: Array[[3]], b: Array[[3]]) -> Array[[3]]: ...
+def scale[N: IntVar](x: Array[[N]]) -> Array[[N]]: ...
+
+@jax.vmap
+def normalize(x: Array[[3]]) -> Array[[3]]: ...
+
+def f(xs: Array[[8, 3]], ys: Array[[3, 8]], b: Array[[3]]) -> None:
+    assert_shape(jax.vmap(add_bias)(xs, xs), (8, 3))
+    assert_shape(normalize(xs), (8, 3))
+    assert_shape(jax.vmap(add_bias, in_axes=(0, None))(xs, b), (8, 3))
+    assert_shape(jax.vmap(add_bias, in_axes=(1, None))(ys, b), (8, 3))
+    assert_shape(jax.vmap(add_bias, in_axes=(0, None), out_axes=-1)(xs, b), (3, 8))
+    assert_shape(jax.vmap(scale)(xs), (8, 3))
+    assert_shape(jax.vmap(jax.vmap(scale))(jax.ones((2, 8))), (2, 8))
+"#,
+);
+
+testcase!(
+    test_jax_vmap_non_array_is_not_batched,
+    shaped_array_env_with_jax(),
+    r#"
+import jax
+from jax import Array
+from typing import Any, Callable, assert_type
+from shape_extensions import assert_shape
+
+def total(x: Array[[3]]) -> float: ...
+def scale(x: Array[[3]], factor: float) -> Array[[3]]: ...
+def unannotated(x) -> Array[[3]]: ...
+
+def f(xs: Array[[8, 3]]) -> None:
+    assert_type(jax.vmap(total), Callable[..., Any])
+    assert_type(jax.vmap(scale), Callable[..., Any])
+    assert_shape(jax.vmap(scale, in_axes=(0, None))(xs, 2.0), (8, 3))
+    assert_shape(jax.vmap(unannotated)(xs), (8, 3))
+"#,
+);
+
+testcase!(
+    test_jax_vmap_rejects_bad_axes,
+    shaped_array_env_with_jax(),
+    r#"
+import jax
+from jax import Array
+
+def add_bias(x: Array[[3]], b: Array[[3]]) -> Array[[3]]: ...
+
+jax.vmap(add_bias, in_axes=(0, 0, 0))  # E: `in_axes` has 3 entries, but the mapped function takes 2 positional parameters
+jax.vmap(add_bias, in_axes=(2, None))  # E: Axis `2` in `in_axes` is out of bounds
+jax.vmap(add_bias, out_axes=-3)  # E: Axis `-3` in `out_axes` is out of bounds
+"#,
+);
+
+testcase!(
+    test_jax_jit_and_partial_preserve_shape_signatures,
+    shaped_array_env_with_jax(),
+    r#"
+import functools
+import jax
+from jax import Array
+from shape_extensions import IntVar, assert_shape
+
+@jax.jit
+def double[N: IntVar](x: Array[[N]]) -> Array[[N]]: ...
+
+@functools.partial(jax.jit, static_argnums=(1,))
+def repeat[N: IntVar](x: Array[[N]], times: int) -> Array[[N]]: ...
+
+def f(x: Array[[5]]) -> None:
+    assert_shape(double(x), (5,))
+    assert_shape(repeat(x, 2), (5,))
+"#,
+);
+
 fn shape_dsl_env() -> TestEnv {
     let mut env = shape_dsl_base_env();
     env.add_with_path(
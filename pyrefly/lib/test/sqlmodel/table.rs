@@ -0,0 +1,59 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::sqlmodel_testcase;
+
+sqlmodel_testcase!(
+    test_table_model_columns,
+    r#"
+from typing import assert_type
+from sqlalchemy.orm import InstrumentedAttribute
+from sqlmodel import Field, SQLModel, select
+
+class Hero(SQLModel, table=True):
+    id: int | None = Field(default=None, primary_key=True)
+    name: str
+    age: int | None = None
+
+assert_type(Hero.name, InstrumentedAttribute[str])
+assert_type(Hero.age, InstrumentedAttribute[int | None])
+select(Hero).where(Hero.age > 30)
+
+hero = Hero(name="Deadpond")
+assert_type(hero.name, str)
+assert_type(hero.age, int | None)
+    "#,
+);
+
+sqlmodel_testcase!(
+    test_plain_model_is_not_a_table,
+    r#"
+from typing import assert_type
+from sqlmodel import SQLModel
+
+class HeroBase(SQLModel):
+    name: str
+
+assert_type(HeroBase.name, str)
+    "#,
+);
+
+sqlmodel_testcase!(
+    test_table_model_init_is_not_coerced,
+    r#"
+from sqlmodel import SQLModel
+
+class HeroCreate(SQLModel):
+    age: int
+
+class Hero(SQLModel, table=True):
+    age: int
+
+HeroCreate(age="3")
+Hero(age="3")  # E: Argument `Literal['3']` is not assignable to parameter `age` with type `int`
+    "#,
+);
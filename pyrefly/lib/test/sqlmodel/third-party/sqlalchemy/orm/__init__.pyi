@@ -0,0 +1,19 @@
+"""
+Minimal SQLAlchemy stubs for testing.
+"""
+
+from typing import Any, Generic, TypeVar
+
+T = TypeVar("T")
+
+class ColumnElement(Generic[T]): ...
+
+class InstrumentedAttribute(Generic[T]):
+    def __eq__(self, other: Any) -> ColumnElement[bool]: ...  # type: ignore[override]
+    def __ne__(self, other: Any) -> ColumnElement[bool]: ...  # type: ignore[override]
+    def __lt__(self, other: Any) -> ColumnElement[bool]: ...
+    def __le__(self, other: Any) -> ColumnElement[bool]: ...
+    def __gt__(self, other: Any) -> ColumnElement[bool]: ...
+    def __ge__(self, other: Any) -> ColumnElement[bool]: ...
+    def in_(self, other: Any) -> ColumnElement[bool]: ...
+    def is_(self, other: Any) -> ColumnElement[bool]: ...
@@ -0,0 +1,9 @@
+"""
+Minimal SQLModel stubs for testing.
+"""
+
+from sqlmodel.main import Field as Field
+from sqlmodel.main import SQLModel as SQLModel
+from sqlmodel.sql.expression import select as select
+
+__all__ = ["Field", "SQLModel", "select"]
@@ -0,0 +1,25 @@
+"""
+Minimal SQLModel stubs for testing.
+"""
+
+from typing import Any
+
+from pydantic import BaseModel
+from pydantic._internal._model_construction import ModelMetaclass
+from typing_extensions import dataclass_transform
+
+def Field(
+    default: Any = ...,
+    *,
+    default_factory: Any = None,
+    primary_key: bool = False,
+    foreign_key: str | None = None,
+    index: bool = False,
+    nullable: bool | None = None,
+) -> Any: ...
+
+@dataclass_transform(kw_only_default=True, field_specifiers=(Field,))
+class SQLModelMetaclass(ModelMetaclass): ...
+
+class SQLModel(BaseModel, metaclass=SQLModelMetaclass):
+    def __init_subclass__(cls, table: bool = False, **kwargs: Any) -> None: ...
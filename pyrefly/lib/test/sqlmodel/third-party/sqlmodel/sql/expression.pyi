@@ -0,0 +1,12 @@
+"""
+Minimal SQLModel stubs for testing.
+"""
+
+from typing import Any, Generic, TypeVar
+
+T = TypeVar("T")
+
+class Select(Generic[T]):
+    def where(self, *whereclause: Any) -> Select[T]: ...
+
+def select(entity: type[T]) -> Select[T]: ...
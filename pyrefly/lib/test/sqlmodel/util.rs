@@ -0,0 +1,42 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+
+pub fn sqlmodel_env() -> TestEnv {
+    let pydantic_path =
+        std::env::var("PYDANTIC_TEST_PATH").expect("PYDANTIC_TEST_PATH must be set");
+    let sqlmodel_path =
+        std::env::var("SQLMODEL_TEST_PATH").expect("SQLMODEL_TEST_PATH must be set");
+    TestEnv::new_with_site_package_paths(&[&pydantic_path, &sqlmodel_path])
+}
+
+#[macro_export]
+macro_rules! sqlmodel_testcase {
+    (bug = $explanation:literal, $name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::sqlmodel::util::sqlmodel_env(),
+                $contents,
+                file!(),
+                line!(),
+            )
+        }
+    };
+    ($name:ident, $contents:literal,) => {
+        #[test]
+        fn $name() -> anyhow::Result<()> {
+            $crate::test::util::testcase_for_macro(
+                $crate::test::sqlmodel::util::sqlmodel_env(),
+                $contents,
+                file!(),
+                line!() - 1,
+            )
+        }
+    };
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::test::util::TestEnv;
+use crate::testcase;
+
+testcase!(
+    test_unclosed_resource,
+    TestEnv::new().enable_unclosed_resource_error(),
+    r#"
+import sqlite3
+from typing import IO
+
+def read(path: str) -> str:
+    f = open(path)  # E: `f` holds a `TextIOWrapper` that may not be closed before the function returns
+    return f.read()
+
+def query() -> None:
+    conn = sqlite3.connect(":memory:")  # E: `conn` holds a `Connection` that may not be closed
+    conn.execute("select 1")
+
+def write(path: str, data: bytes) -> None:
+    f = open(path, "wb")  # E: `f` holds a `BufferedWriter`
+    f.write(data)
+    f = open(path + ".bak", "wb")
+    f.close()
+
+def collect(files: list[IO[str]], path: str) -> None:
+    f = open(path)  # E: `f` holds a `TextIOWrapper` that may not be closed
+    files.append(f)
+"#,
+);
+
+testcase!(
+    test_unclosed_resource_some_paths,
+    TestEnv::new().enable_unclosed_resource_error(),
+    r#"
+def read(path: str, check: bool) -> str:
+    f = open(path)  # E: `f` holds a `TextIOWrapper` that may not be closed
+    data = f.read()
+    if check and not data:
+        return ""
+    f.close()
+    return data
+
+def close_sometimes(path: str, done: bool) -> None:
+    f = open(path)  # E: `f` holds a `TextIOWrapper` that may not be closed
+    if done:
+        f.close()
+
+def close_or_raise(path: str, done: bool) -> None:
+    f = open(path)
+    if done:
+        f.close()
+    else:
+        raise ValueError(path)
+"#,
+);
+
+testcase!(
+    test_closed_resource,
+    TestEnv::new().enable_unclosed_resource_error(),
+    r#"
+from contextlib import ExitStack, closing
+from typing import IO, Iterator
+
+def with_stmt(path: str) -> str:
+    f = open(path)
+    with f:
+        return f.read()
+
+def with_closing(path: str) -> str:
+    f = open(path)
+    with closing(f):
+        return f.read()
+
+def explicit_close(path: str) -> str:
+    f = open(path)
+    data = f.read()
+    f.close()
+    return data
+
+def closed_in_finally(path: str) -> str:
+    f = open(path)
+    try:
+        if not path:
+            return ""
+        return f.read()
+    finally:
+        f.close()
+
+def returned(path: str) -> IO[str]:
+    f = open(path)
+    return f
+
+def yielded(path: str) -> Iterator[IO[str]]:
+    f = open(path)
+    yield f
+
+def aliased(path: str) -> None:
+    f = open(path)
+    g = f
+    g.close()
+
+def handed_off(stack: ExitStack, path: str) -> None:
+    f = open(path)
+    stack.enter_context(f)
+
+def stored(files: list[IO[str]], paths: dict[str, IO[str]], path: str) -> None:
+    f = open(path)
+    paths[path] = f
+
+class Reader:
+    def __init__(self, path: str) -> None:
+        f = open(path)
+        self.f = f
+        g = open(path)
+        self.files: list[IO[str]] = []
+        self.files.append(g)
+
+f = open("module-level")
+"#,
+);
+
+testcase!(
+    test_in_memory_resource,
+    TestEnv::new().enable_unclosed_resource_error(),
+    r#"
+import io
+import threading
+
+def buffer() -> str:
+    s = io.StringIO()
+    s.write("x")
+    b = io.BytesIO()
+    return s.getvalue()
+
+def lock() -> None:
+    l = threading.Lock()  # E: `l` holds a
+    l.acquire()
+"#,
+);
+
+testcase!(
+    test_extra_closeable_type,
+    TestEnv::new()
+        .enable_unclosed_resource_error()
+        .with_extra_closeable_type("main.Pool"),
+    r#"
+class Pool:
+    def close(self) -> None: ...
+
+class PooledConnection(Pool): ...
+
+class Other: ...
+
+def f() -> None:
+    p = Pool()  # E: `p` holds a `Pool` that may not be closed
+    c = PooledConnection()  # E: `c` holds a `PooledConnection`
+    o = Other()
+    q = Pool()
+    q.close()
+"#,
+);
+
+testcase!(
+    test_unclosed_resource_off_by_default,
+    r#"
+def read(path: str) -> str:
+    f = open(path)
+    return f.read()
+"#,
+);
@@ -40,9 +40,12 @@ use ruff_text_size::TextRange;
 use ruff_text_size::TextSize;
 
 use crate::binding::binding::KeyExport;
+use crate::config::banned_api::BannedApi;
+use crate::config::banned_api::BannedApis;
 use crate::config::base::InferReturnTypes;
 use crate::config::base::UntypedDefBehavior;
 use crate::config::config::ConfigFile;
+use crate::config::config::ConfigSource;
 use crate::config::finder::ConfigFinder;
 use crate::error::error::print_errors;
 use crate::module::finder::DirEntryCache;
@@ -111,6 +114,13 @@ pub struct TestEnv {
     explicit_any_error: bool,
     implicit_any_error: bool,
     unannotated_return_error: bool,
+    undeclared_dependency_error: bool,
+    blocking_call_in_async_error: bool,
+    exception_flow_errors: bool,
+    extra_blocking_calls: Vec<String>,
+    unclosed_resource_error: bool,
+    extra_closeable_types: Vec<String>,
+    config_path: Option<PathBuf>,
     implicit_any_parameter_error: bool,
     implicit_any_attribute_error: bool,
     implicit_bool_error: bool,
@@ -133,6 +143,8 @@ pub struct TestEnv {
     spec_compliant_overloads: bool,
     legacy_overload_expansion: bool,
     treat_all_caps_as_final: bool,
+    max_protocol_mismatches: Option<usize>,
+    banned_apis: Vec<BannedApi>,
     no_any_return_error: bool,
     no_any_return_explicit_error: bool,
     no_any_return_implicit_error: bool,
@@ -166,6 +178,13 @@ impl TestEnv {
             explicit_any_error: false,
             implicit_any_error: false,
             unannotated_return_error: false,
+            undeclared_dependency_error: false,
+            blocking_call_in_async_error: false,
+            exception_flow_errors: false,
+            extra_blocking_calls: Vec::new(),
+            unclosed_resource_error: false,
+            extra_closeable_types: Vec::new(),
+            config_path: None,
             implicit_any_parameter_error: false,
             implicit_any_attribute_error: false,
             implicit_bool_error: false,
@@ -188,6 +207,8 @@ impl TestEnv {
             spec_compliant_overloads: false,
             legacy_overload_expansion: false,
             treat_all_caps_as_final: false,
+            max_protocol_mismatches: None,
+            banned_apis: Vec::new(),
             no_any_return_error: false,
             no_any_return_explicit_error: false,
             no_any_return_implicit_error: false,
@@ -329,6 +350,42 @@ impl TestEnv {
         self
     }
 
+    pub fn enable_undeclared_dependency_error(mut self) -> Self {
+        self.undeclared_dependency_error = true;
+        self
+    }
+
+    pub fn enable_blocking_call_in_async_error(mut self) -> Self {
+        self.blocking_call_in_async_error = true;
+        self
+    }
+
+    pub fn enable_exception_flow_errors(mut self) -> Self {
+        self.exception_flow_errors = true;
+        self
+    }
+
+    pub fn with_extra_blocking_call(mut self, name: &str) -> Self {
+        self.extra_blocking_calls.push(name.to_owned());
+        self
+    }
+
+    pub fn enable_unclosed_resource_error(mut self) -> Self {
+        self.unclosed_resource_error = true;
+        self
+    }
+
+    pub fn with_extra_closeable_type(mut self, name: &str) -> Self {
+        self.extra_closeable_types.push(name.to_owned());
+        self
+    }
+
+    /// Treat the config as loaded from `path`, for checks that read files next to the config.
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
     pub fn enable_implicit_any_parameter_error(mut self) -> Self {
         self.implicit_any_parameter_error = true;
         self
@@ -419,6 +476,25 @@ impl TestEnv {
         self
     }
 
+    pub fn with_max_protocol_mismatches(mut self, max_protocol_mismatches: usize) -> Self {
+        self.max_protocol_mismatches = Some(max_protocol_mismatches);
+        self
+    }
+
+    pub fn with_banned_api(
+        mut self,
+        name: &str,
+        message: Option<&str>,
+        required_keywords: &[&str],
+    ) -> Self {
+        self.banned_apis.push(BannedApi {
+            name: name.to_owned(),
+            message: message.map(str::to_owned),
+            required_keywords: required_keywords.iter().map(|k| (*k).to_owned()).collect(),
+        });
+        self
+    }
+
     pub fn enable_treat_all_caps_as_final(mut self) -> Self {
         self.treat_all_caps_as_final = true;
         self
@@ -564,6 +640,13 @@ impl TestEnv {
         config.root.spec_compliant_overloads = Some(self.spec_compliant_overloads);
         config.root.legacy_overload_expansion = Some(self.legacy_overload_expansion);
         config.root.treat_all_caps_as_final = Some(self.treat_all_caps_as_final);
+        config.root.max_protocol_mismatches = self.max_protocol_mismatches;
+        config.banned_apis = BannedApis::new(self.banned_apis.clone());
+        config.extra_blocking_calls = self.extra_blocking_calls.clone();
+        config.extra_closeable_types = self.extra_closeable_types.clone();
+        if let Some(path) = &self.config_path {
+            config.source = ConfigSource::File(path.clone());
+        }
         if config.root.errors.is_none() {
             config.root.errors = Some(ErrorDisplayConfig::new(HashMap::new()));
         };
@@ -589,6 +672,19 @@ impl TestEnv {
         if self.unannotated_return_error {
             errors.set_error_severity(ErrorKind::UnannotatedReturn, Severity::Error);
         }
+        if self.undeclared_dependency_error {
+            errors.set_error_severity(ErrorKind::UndeclaredDependency, Severity::Error);
+        }
+        if self.blocking_call_in_async_error {
+            errors.set_error_severity(ErrorKind::BlockingCallInAsync, Severity::Error);
+        }
+        if self.unclosed_resource_error {
+            errors.set_error_severity(ErrorKind::UnclosedResource, Severity::Error);
+        }
+        if self.exception_flow_errors {
+            errors.set_error_severity(ErrorKind::UndocumentedRaise, Severity::Error);
+            errors.set_error_severity(ErrorKind::UnreachableExcept, Severity::Error);
+        }
         if self.implicit_any_parameter_error {
             errors.set_error_severity(ErrorKind::ImplicitAnyParameter, Severity::Error);
         }
@@ -168,6 +168,10 @@ NEGATIVE_TEST_CASES: list[tuple[str, str]] = [
         "build_system_custom_without_command",
         '[build-system]\ntype = "custom"',
     ),
+    (
+        "build_system_bazel_with_isolation_dir",
+        '[build-system]\ntype = "bazel"\nisolation-dir = "iso"',
+    ),
     (
         "build_system_custom_with_isolation_dir",
         '[build-system]\ntype = "custom"\ncommand = ["query"]\nisolation-dir = "iso"',
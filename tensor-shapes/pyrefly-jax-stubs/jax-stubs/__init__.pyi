@@ -0,0 +1,118 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from collections.abc import Callable, Sequence
+from typing import Any, overload
+
+import shape_extensions
+from jax._shapes import reduce_ir
+from shape_extensions import broadcast, Int, IntTuple, IntVar, uses_shape_dsl
+
+from . import lax as lax, numpy as numpy
+
+type _Shape = IntTuple
+type _AnyShape = tuple[Any, ...]
+type _Axis = int | tuple[int, ...] | None
+
+# JAX arrays carry their dtype at runtime only, so `Array` is generic in its shape alone.
+@shape_extensions.shaped_array(shape="Shape")
+class Array[Shape: _Shape = _AnyShape]:
+    shape: Shape
+    dtype: Any
+    @property
+    def ndim(self) -> int: ...
+    @overload
+    def __len__[N: IntVar](self: Array[[N]]) -> Int[N]: ...
+    @overload
+    def __len__[N: IntVar, M: IntVar](self: Array[[N, M]]) -> Int[N]: ...
+    # Only 2-D transpose is modeled.
+    @property
+    def T[N: IntVar, P: IntVar](self: Array[[N, P]]) -> Array[[P, N]]: ...
+    @overload
+    def __add__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __add__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __radd__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __radd__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __sub__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __sub__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __rsub__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __rsub__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __mul__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __mul__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __rmul__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __rmul__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __truediv__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __truediv__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    @overload
+    def __rtruediv__(self, other: int | float) -> Array[Shape]: ...
+    @overload
+    def __rtruediv__[OtherShape: _Shape](
+        self, other: Array[OtherShape]
+    ) -> Array[broadcast(Shape, OtherShape)]: ...
+    def __pow__(self, other: int | float) -> Array[Shape]: ...
+    def __neg__(self) -> Array[Shape]: ...
+    # Operator dunders can't use the DSL-backed `jnp.matmul` rule yet, so only 2-D is modeled.
+    def __matmul__[N: IntVar, M: IntVar, P: IntVar](
+        self: Array[[N, M]],
+        other: Array[[M, P]],
+    ) -> Array[[N, P]]: ...
+    @uses_shape_dsl(reduce_ir)
+    def sum(self, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+    @uses_shape_dsl(reduce_ir)
+    def mean(self, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+    @uses_shape_dsl(reduce_ir)
+    def max(self, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+    @uses_shape_dsl(reduce_ir)
+    def min(self, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+
+# `jit` traces and compiles `fun` without changing its signature, so shape-typed signatures
+# (including generic ones) pass through unchanged, as does `functools.partial(jit, ...)`.
+def jit[F: Callable[..., Any]](
+    fun: F,
+    /,
+    *,
+    static_argnums: int | Sequence[int] | None = None,
+    static_argnames: str | Sequence[str] | None = None,
+    donate_argnums: int | Sequence[int] | None = None,
+    donate_argnames: str | Sequence[str] | None = None,
+) -> F: ...
+
+# Pyrefly computes the batched signature of `fun` from `in_axes` and `out_axes`; this
+# signature is only the fallback for axes it doesn't model, such as pytree prefixes.
+def vmap(
+    fun: Callable[..., Any],
+    in_axes: int | None | Sequence[Any] = 0,
+    out_axes: Any = 0,
+    axis_name: Any = None,
+    axis_size: int | None = None,
+    spmd_axis_name: Any = None,
+) -> Callable[..., Any]: ...
@@ -0,0 +1,31 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from jax import Array
+from jax._shapes import matmul_2d_ir, same_shape_ir
+from shape_extensions import IntTuple, uses_shape_dsl
+
+type _Shape = IntTuple
+
+# Unlike `jax.numpy`, `lax` operations don't broadcast: operands must have equal shapes.
+@uses_shape_dsl(same_shape_ir)
+def add(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(same_shape_ir)
+def sub(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(same_shape_ir)
+def mul(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(same_shape_ir)
+def div(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(same_shape_ir)
+def max(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(same_shape_ir)
+def min(x: Array, y: Array, /) -> Array: ...
+def exp[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def log[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def tanh[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def neg[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def stop_gradient[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+@uses_shape_dsl(matmul_2d_ir)
+def dot(lhs: Array, rhs: Array, /) -> Array: ...
@@ -0,0 +1,94 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from typing import Any, Literal, overload
+
+from jax import Array
+from jax._shapes import binary_ufunc_ir, matmul_2d_ir, reduce_ir
+from shape_extensions import Int, IntTuple, IntVar, uses_shape_dsl
+
+type _Shape = IntTuple
+type _Axis = int | tuple[int, ...] | None
+
+ndarray = Array
+
+class float32: ...
+class float64: ...
+class int32: ...
+class bool_: ...
+
+def asarray[Shape: _Shape](a: Array[Shape], dtype: Any = None) -> Array[Shape]: ...
+
+# TODO: Replace these finite-rank constructor overloads with a generic tuple-shape
+# overload once carrier shapes flow through downstream operations.
+@overload
+def zeros[N: IntVar](shape: Int[N], dtype: Any = None) -> Array[[N]]: ...
+@overload
+def zeros[N: IntVar](shape: IntTuple[N], dtype: Any = None) -> Array[[N]]: ...
+@overload
+def zeros[N: IntVar, M: IntVar](
+    shape: IntTuple[N, M], dtype: Any = None
+) -> Array[[N, M]]: ...
+@overload
+def zeros[N: IntVar, M: IntVar, P: IntVar](
+    shape: IntTuple[N, M, P], dtype: Any = None
+) -> Array[[N, M, P]]: ...
+@overload
+def ones[N: IntVar](shape: Int[N], dtype: Any = None) -> Array[[N]]: ...
+@overload
+def ones[N: IntVar](shape: IntTuple[N], dtype: Any = None) -> Array[[N]]: ...
+@overload
+def ones[N: IntVar, M: IntVar](
+    shape: IntTuple[N, M], dtype: Any = None
+) -> Array[[N, M]]: ...
+@overload
+def ones[N: IntVar, M: IntVar, P: IntVar](
+    shape: IntTuple[N, M, P], dtype: Any = None
+) -> Array[[N, M, P]]: ...
+def zeros_like[Shape: _Shape](a: Array[Shape], dtype: Any = None) -> Array[Shape]: ...
+def ones_like[Shape: _Shape](a: Array[Shape], dtype: Any = None) -> Array[Shape]: ...
+def arange[N: IntVar](stop: Int[N], /) -> Array[[N]]: ...
+def eye[N: IntVar](N: Int[N], dtype: Any = None) -> Array[[N, N]]: ...
+def abs[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def exp[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def log[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def sqrt[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def sin[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def cos[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def tanh[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def negative[Shape: _Shape](x: Array[Shape], /) -> Array[Shape]: ...
+def clip[Shape: _Shape](
+    x: Array[Shape], min: int | float | None = None, max: int | float | None = None
+) -> Array[Shape]: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def add(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def subtract(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def multiply(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def divide(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def maximum(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(binary_ufunc_ir)
+def minimum(x: Array, y: Array, /) -> Array: ...
+@uses_shape_dsl(reduce_ir)
+def sum(a: Array, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+@uses_shape_dsl(reduce_ir)
+def mean(a: Array, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+@uses_shape_dsl(reduce_ir)
+def max(a: Array, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+@uses_shape_dsl(reduce_ir)
+def min(a: Array, axis: _Axis = None, *, keepdims: bool = False) -> Array: ...
+@uses_shape_dsl(matmul_2d_ir)
+def matmul(a: Array, b: Array, /) -> Array: ...
+@uses_shape_dsl(matmul_2d_ir)
+def dot(a: Array, b: Array, /) -> Array: ...
+# Only 2-D transpose is modeled.
+def transpose[N: IntVar, M: IntVar](a: Array[[N, M]], axes: None = None) -> Array[[M, N]]: ...
+@overload
+def expand_dims[N: IntVar](a: Array[[N]], axis: Literal[0, -2]) -> Array[[1, N]]: ...
+@overload
+def expand_dims[N: IntVar](a: Array[[N]], axis: Literal[1, -1]) -> Array[[N, 1]]: ...
@@ -0,0 +1,19 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+import sys
+from pathlib import Path
+
+from suites import SUITES
+
+# The runner is shared by the stub packages in `tensor-shapes`.
+sys.path.insert(0, str(Path(__file__).resolve().parent.parent))
+from stub_suites import main  # noqa: E402
+
+
+if __name__ == "__main__":
+    sys.exit(main("jax", SUITES, Path(__file__).resolve().parent))
@@ -0,0 +1,15 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+from pathlib import Path
+
+
+SUITES = {
+    "lax": (Path("test/test_lax.py"),),
+    "numpy": (Path("test/test_numpy.py"),),
+    "transforms": (Path("test/test_transforms.py"),),
+}
@@ -0,0 +1,40 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+import jax.numpy as jnp
+from jax import lax
+from shape_extensions import assert_shape
+
+
+def test_elementwise_operations() -> None:
+    x = jnp.ones((3, 4))
+    y = jnp.zeros((3, 4))
+
+    assert_shape(lax.add(x, y), (3, 4))
+    assert_shape(lax.mul(x, y), (3, 4))
+    assert_shape(lax.max(x, y), (3, 4))
+    assert_shape(lax.tanh(x), (3, 4))
+    assert_shape(lax.stop_gradient(x), (3, 4))
+
+
+def test_operands_do_not_broadcast() -> None:
+    x = jnp.ones((3, 4))
+    row = jnp.ones((4,))
+
+    assert_shape(lax.add(x, x), (3, 4))
+    try:
+        lax.add(x, row)  # E: lax operands must have equal ranks
+    except TypeError:
+        pass
+    try:
+        lax.sub(x, jnp.ones((3, 5)))  # E: lax operands must have equal shapes
+    except TypeError:
+        pass
+
+
+def test_dot() -> None:
+    assert_shape(lax.dot(jnp.ones((3, 4)), jnp.ones((4, 2))), (3, 2))
@@ -0,0 +1,70 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+import jax.numpy as jnp
+from shape_extensions import assert_shape
+
+
+def test_creation() -> None:
+    assert_shape(jnp.zeros(3), (3,))
+    assert_shape(jnp.ones((3, 4)), (3, 4))
+    assert_shape(jnp.zeros((2, 3, 4)), (2, 3, 4))
+    assert_shape(jnp.arange(5), (5,))
+    assert_shape(jnp.eye(4), (4, 4))
+    assert_shape(jnp.zeros_like(jnp.ones((2, 5))), (2, 5))
+
+
+def test_elementwise_preserves_shape() -> None:
+    x = jnp.ones((3, 4))
+
+    assert_shape(jnp.exp(x), (3, 4))
+    assert_shape(jnp.tanh(x), (3, 4))
+    assert_shape(jnp.clip(x, 0.0, 1.0), (3, 4))
+    assert_shape(-x * 2.0, (3, 4))
+
+
+def test_binary_operations_broadcast() -> None:
+    x = jnp.ones((3, 4))
+    row = jnp.ones((4,))
+    column = jnp.ones((3, 1))
+
+    assert_shape(x + row, (3, 4))
+    assert_shape(x * column, (3, 4))
+    assert_shape(jnp.maximum(x, row), (3, 4))
+    assert_shape(jnp.add(column, row), (3, 4))
+
+
+def test_binary_operation_rejects_incompatible_shapes() -> None:
+    x = jnp.ones((3, 4))
+    y = jnp.ones((5,))
+
+    assert_shape(jnp.add(x, jnp.ones((4,))), (3, 4))
+    try:
+        jnp.add(x, y)  # E: operands could not be broadcast together
+    except (TypeError, ValueError):
+        pass
+
+
+def test_reductions() -> None:
+    x = jnp.ones((3, 4))
+
+    assert_shape(jnp.sum(x), ())
+    assert_shape(jnp.mean(x, axis=0), (4,))
+    assert_shape(jnp.max(x, axis=1, keepdims=True), (3, 1))
+    assert_shape(x.sum(axis=-1), (3,))
+
+
+def test_matmul_and_transpose() -> None:
+    a = jnp.ones((3, 4))
+    b = jnp.ones((4, 5))
+
+    assert_shape(jnp.matmul(a, b), (3, 5))
+    assert_shape(jnp.dot(a, b), (3, 5))
+    assert_shape(a @ b, (3, 5))
+    assert_shape(a.T, (4, 3))
+    assert_shape(jnp.transpose(b), (5, 4))
+    assert_shape(jnp.expand_dims(jnp.ones((4,)), 0), (1, 4))
@@ -0,0 +1,87 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+import functools
+
+import jax
+import jax.numpy as jnp
+from jax import Array
+from shape_extensions import assert_shape, IntVar
+
+
+def add_bias(x: Array[[3]], b: Array[[3]]) -> Array[[3]]:
+    return x + b
+
+
+def scale[N: IntVar](x: Array[[N]]) -> Array[[N]]:
+    return x * 2.0
+
+
+@jax.vmap
+def normalize(x: Array[[3]]) -> Array[[3]]:
+    return x / x.sum()
+
+
+@jax.jit
+def double[N: IntVar](x: Array[[N]]) -> Array[[N]]:
+    return x * 2.0
+
+
+@functools.partial(jax.jit, static_argnums=(1,))
+def repeat_scale[N: IntVar](x: Array[[N]], factor: int) -> Array[[N]]:
+    return x * factor
+
+
+def test_vmap_maps_leading_axis() -> None:
+    xs = jnp.ones((8, 3))
+
+    assert_shape(jax.vmap(add_bias)(xs, xs), (8, 3))
+    assert_shape(normalize(xs), (8, 3))
+
+
+def test_vmap_in_axes() -> None:
+    b = jnp.ones((3,))
+
+    assert_shape(jax.vmap(add_bias, in_axes=(0, None))(jnp.ones((8, 3)), b), (8, 3))
+    assert_shape(jax.vmap(add_bias, in_axes=(1, None))(jnp.ones((3, 8)), b), (8, 3))
+    assert_shape(jax.vmap(add_bias, in_axes=-1)(jnp.ones((3, 8)), jnp.ones((3, 8))), (8, 3))
+
+
+def test_vmap_out_axes() -> None:
+    xs = jnp.ones((8, 3))
+    b = jnp.ones((3,))
+
+    assert_shape(jax.vmap(add_bias, in_axes=(0, None), out_axes=1)(xs, b), (3, 8))
+    assert_shape(jax.vmap(add_bias, (0, None), -1)(xs, b), (3, 8))
+
+
+def test_vmap_generic_function() -> None:
+    assert_shape(jax.vmap(scale)(jnp.ones((8, 5))), (8, 5))
+    assert_shape(jax.vmap(jax.vmap(scale))(jnp.ones((2, 8, 5))), (2, 8, 5))
+
+
+def test_vmap_rejects_bad_axes() -> None:
+    xs = jnp.ones((8, 3))
+
+    assert_shape(jax.vmap(add_bias)(xs, xs), (8, 3))
+    try:
+        jax.vmap(add_bias, in_axes=(0, 0, 0))  # E: `in_axes` has 3 entries
+    except ValueError:
+        pass
+    try:
+        jax.vmap(add_bias, in_axes=(2, None))  # E: Axis `2` in `in_axes` is out of bounds
+    except ValueError:
+        pass
+
+
+def test_jit_preserves_signature() -> None:
+    x = jnp.ones((5,))
+
+    assert_shape(jax.jit(add_bias)(jnp.ones((3,)), jnp.ones((3,))), (3,))
+    assert_shape(double(x), (5,))
+    assert_shape(repeat_scale(x, 3), (5,))
+    assert_shape(jax.jit(jax.vmap(scale))(jnp.ones((4, 5))), (4, 5))
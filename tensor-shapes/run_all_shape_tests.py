@@ -124,6 +124,14 @@ def run_cargo_static_corpus(nocapture: bool) -> None:
             *extra_args,
         ]
     )
+    print_step("jax static tensor-shape corpus")
+    run(
+        [
+            sys.executable,
+            "tensor-shapes/pyrefly-jax-stubs/run_pyrefly.py",
+            *extra_args,
+        ]
+    )
 
 
 def run_cargo_runtime_tests() -> None:
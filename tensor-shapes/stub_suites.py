@@ -0,0 +1,162 @@
+# Copyright (c) Meta Platforms, Inc. and affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+from __future__ import annotations
+
+import argparse
+import os
+import subprocess
+import sys
+from collections.abc import Mapping, Sequence
+from pathlib import Path
+
+
+def default_pyrefly(repo_root: Path) -> Path:
+    target_dir = (
+        Path(os.environ["CARGO_TARGET_DIR"])
+        if "CARGO_TARGET_DIR" in os.environ
+        else repo_root / "target"
+    )
+    executable = "pyrefly.exe" if os.name == "nt" else "pyrefly"
+    return target_dir / "debug" / executable
+
+
+def run_check(
+    *,
+    pyrefly_command: list[str],
+    stubs_root: Path,
+    stubs_search_path: Path,
+    shape_extension_root: Path,
+    files: Sequence[Path],
+    suite: str,
+    nocapture: bool,
+) -> int:
+    check_files = [file.as_posix() for file in files]
+    command = [
+        *pyrefly_command,
+        "check",
+        "--config",
+        os.devnull,
+        "--python-version",
+        "3.13",
+        "--expectations",
+        "--search-path",
+        str(stubs_search_path),
+        "--search-path",
+        str(shape_extension_root),
+    ]
+    command.extend(check_files)
+
+    if nocapture:
+        print("+ " + " ".join(command), flush=True)
+        return subprocess.run(command, cwd=stubs_root).returncode
+
+    result = subprocess.run(
+        command,
+        cwd=stubs_root,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        text=True,
+    )
+    if result.returncode != 0:
+        print("+ " + " ".join(command), flush=True)
+        print(result.stdout, end="")
+        print(result.stderr, end="", file=sys.stderr)
+    else:
+        print(f"PASS {suite} ({len(check_files)} files)", flush=True)
+    return result.returncode
+
+
+def main(library: str, suites: Mapping[str, Sequence[Path]], stubs_root: Path) -> int:
+    """Check the `suites` of the `pyrefly-{library}-stubs` package at `stubs_root`."""
+    repo_root_default = stubs_root.parent.parent
+    tensor_shapes_root_default = (
+        Path(os.environ["TENSOR_SHAPES_ROOT"])
+        if "TENSOR_SHAPES_ROOT" in os.environ
+        else repo_root_default / "tensor-shapes"
+    )
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--pyrefly",
+        type=Path,
+        default=Path(os.environ["PYREFLY"])
+        if "PYREFLY" in os.environ
+        else default_pyrefly(repo_root_default),
+    )
+    parser.add_argument(
+        "--buck",
+        action="store_true",
+        help="run Pyrefly through the internal Buck target instead of a local binary",
+    )
+    parser.add_argument(f"--{library}-root", type=Path, default=stubs_root)
+    parser.add_argument(
+        "--tensor-shapes-root",
+        type=Path,
+        default=tensor_shapes_root_default,
+    )
+    parser.add_argument(
+        f"--{library}-stubs-root",
+        type=Path,
+        default=None,
+    )
+    parser.add_argument(
+        "--shape-extension-root",
+        type=Path,
+        default=None,
+    )
+    parser.add_argument(
+        "--suite",
+        choices=("all",) + tuple(suites),
+        action="append",
+        default=[],
+    )
+    parser.add_argument(
+        "--nocapture",
+        action="store_true",
+        help="stream Pyrefly output instead of printing it only on failure",
+    )
+    args = parser.parse_args()
+
+    library_root = getattr(args, f"{library}_root")
+    library_stubs_root = getattr(args, f"{library}_stubs_root")
+    if library_stubs_root is None:
+        moved_stubs_root = args.tensor_shapes_root / f"pyrefly-{library}-stubs"
+        library_stubs_root = (
+            moved_stubs_root if moved_stubs_root.exists() else args.tensor_shapes_root
+        )
+    if args.shape_extension_root is None:
+        moved_shape_extension_root = (
+            args.tensor_shapes_root / "pyrefly-shape-extensions"
+        )
+        shape_extension_root = (
+            moved_shape_extension_root
+            if moved_shape_extension_root.exists()
+            else args.tensor_shapes_root
+        )
+    else:
+        shape_extension_root = args.shape_extension_root
+    selected = args.suite or ["all"]
+    if "all" in selected:
+        selected = list(suites)
+    pyrefly_command = (
+        ["buck2", "run", "fbcode//pyrefly:pyrefly", "--"]
+        if args.buck
+        else [str(args.pyrefly.resolve())]
+    )
+
+    for suite in selected:
+        result = run_check(
+            pyrefly_command=pyrefly_command,
+            stubs_root=library_root.resolve(),
+            stubs_search_path=library_stubs_root.resolve(),
+            shape_extension_root=shape_extension_root.resolve(),
+            files=suites[suite],
+            suite=suite,
+            nocapture=args.nocapture,
+        )
+        if result != 0:
+            return result
+    return 0
+
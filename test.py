@@ -197,6 +197,7 @@ class CargoExecutor(Executor):
         # The runners resolve the debug pyrefly themselves, so we don't pass `--pyrefly`.
         run([sys.executable, "tensor-shapes/pyrefly-torch-stubs/run_pyrefly.py"])
         run([sys.executable, "tensor-shapes/pyrefly-numpy-stubs/run_pyrefly.py"])
+        run([sys.executable, "tensor-shapes/pyrefly-jax-stubs/run_pyrefly.py"])
 
     def conformance(self) -> None:
         cargo_target_dir = os.environ.get("CARGO_TARGET_DIR", "target")